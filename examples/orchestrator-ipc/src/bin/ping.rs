@@ -16,7 +16,8 @@ async fn main() {
         actors: [
             PingActor,
             veecle_ipc::Output::<Ping>: (&connector).into(),
-            veecle_ipc::Input::<Pong>: &connector,
+            veecle_ipc::Input::<Pong>: (&connector).into(),
+            veecle_ipc::Heartbeat: &connector,
         ],
     }
     .await;
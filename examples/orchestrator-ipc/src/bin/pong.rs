@@ -15,8 +15,9 @@ async fn main() {
     veecle_os::runtime::execute! {
         actors: [
             PongActor,
-            veecle_ipc::Input::<Ping>: &connector,
+            veecle_ipc::Input::<Ping>: (&connector).into(),
             veecle_ipc::Output::<Pong>: (&connector).into(),
+            veecle_ipc::Heartbeat: &connector,
         ],
     }
     .await;
@@ -5,8 +5,8 @@ extern crate proc_macro;
 use darling::ast::NestedMeta;
 use darling::{Error, FromMeta};
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{ItemFn, parse_macro_input};
+use quote::{quote, quote_spanned};
+use syn::{ItemFn, LitStr, parse_macro_input};
 
 /// Attribute macro that sets up SOME/IP integration tests against an external test service.
 ///
@@ -16,15 +16,37 @@ use syn::{ItemFn, parse_macro_input};
 ///
 /// Each test service instance runs on a distinct network configuration, enabling parallel test execution.
 ///
+/// The test service only runs on Linux, on other platforms the test is generated as an ignored test with an empty body
+/// so it is reported as skipped. Its `#[ignore]` and `#[should_panic]` attributes are not applied to that stub.
+///
+/// With `instances = N`, `N` test services are launched and injected as a `&[TestService; N]` instead, e.g. to test
+/// the interaction between multiple nodes. All of them are terminated once the test concludes.
+///
+/// ```ignore
+/// #[test_with_test_service(instances = 2)]
+/// fn two_nodes([first, second]: &[TestService; 2]) {
+///     // ...
+/// }
+/// ```
+///
 /// ## Configuration
 ///
 /// - `logging_level` - Verbosity level of the internal logging. Allowed values: `fatal`, `error`, `warning`, `info`,
-///   `debug`, `trace`. Default: `info`.
+///   `debug`, `trace`. Default: `info`. Other values fail to compile, see `LoggingLevel::from_name`.
 /// - `timeout_ms` - The test execution timeout in milliseconds. Default: `240000`.
+/// - `instances` - The number of test services to launch, at least `1`. Default: a single test service injected as
+///   `&TestService`.
 #[proc_macro_attribute]
 pub fn test_with_test_service(meta: TokenStream, item: TokenStream) -> TokenStream {
     let function = parse_macro_input!(item as ItemFn);
     let function_attributes = &function.attrs;
+    // The stub has an empty body and is always ignored, so the attributes controlling how the test runs are dropped
+    // instead of duplicating or contradicting the generated ones.
+    let stub_attributes = function.attrs.iter().filter(|attribute| {
+        !["ignore", "should_panic"]
+            .iter()
+            .any(|name| attribute.path().is_ident(name))
+    });
     let function_arguments = &function.sig.inputs;
     let function_name = &function.sig.ident;
     let function_visibility = &function.vis;
@@ -38,36 +60,71 @@ pub fn test_with_test_service(meta: TokenStream, item: TokenStream) -> TokenStre
         Ok(macro_arguments) => macro_arguments,
         Err(error) => return TokenStream::from(error.write_errors()),
     };
-    let logging_level = macro_arguments
-        .logging_level
-        .unwrap_or(String::from("info"));
+    // The level is parsed in a constant so typos are reported at compile time, the macro itself can't depend on
+    // `someip-test-service` as that links the native test service.
+    let logging_level = match macro_arguments.logging_level {
+        Some(logging_level) => quote_spanned! { logging_level.span() =>
+            match someip_test_service::LoggingLevel::from_name(#logging_level) {
+                Some(logging_level) => logging_level,
+                None => panic!(concat!(
+                    "invalid logging level `",
+                    #logging_level,
+                    "`, expected one of `fatal`, `error`, `warning`, `info`, `debug`, `trace`",
+                )),
+            }
+        },
+        None => quote! { someip_test_service::LoggingLevel::Info },
+    };
     let timeout_ms = macro_arguments.timeout_ms.unwrap_or(240000);
 
+    let test_services = match macro_arguments.instances {
+        Some(0) => {
+            return TokenStream::from(
+                Error::custom("`instances` must be at least 1").write_errors(),
+            );
+        }
+        // Dropping the already launched test services if launching one of them panics is handled by `from_fn`.
+        Some(instances) => quote! {
+            let test_services: [someip_test_service::TestService; #instances] =
+                core::array::from_fn(|_| {
+                    let config = someip_test_service::Config::builder()
+                        .logging_level(LOGGING_LEVEL)
+                        .build();
+                    someip_test_service::TestService::new(&config)
+                });
+        },
+        None => quote! {
+            let config = someip_test_service::Config::builder()
+                .logging_level(LOGGING_LEVEL)
+                .build();
+            let test_services = someip_test_service::TestService::new(&config);
+        },
+    };
+
     let generated_test = quote! {
         #(#function_attributes)*
         #[test]
         #[cfg(target_os = "linux")]
         #[ntest_timeout::timeout(#timeout_ms)]
         #function_visibility fn #function_name() {
-            let mut config = someip_test_service::Config::default();
-            match #logging_level {
-                "fatal" => { config.logging_level = someip_test_service::LoggingLevel::Fatal; },
-                "error" => { config.logging_level = someip_test_service::LoggingLevel::Error; },
-                "warning" => { config.logging_level = someip_test_service::LoggingLevel::Warning; },
-                "info" => { config.logging_level = someip_test_service::LoggingLevel::Info; },
-                "debug" => { config.logging_level = someip_test_service::LoggingLevel::Debug; },
-                "trace" => { config.logging_level = someip_test_service::LoggingLevel::Trace; },
-                _ => panic!("invalid log level provided!"),
-            }
-            let test_service = someip_test_service::TestService::new(&config);
+            const LOGGING_LEVEL: someip_test_service::LoggingLevel = #logging_level;
+
+            #test_services
 
             let closure = |#function_arguments| { #function_block };
-            let result = std::panic::catch_unwind(move || { closure(&test_service) });
+            let result = std::panic::catch_unwind(move || { closure(&test_services) });
 
             if let Err(panic) = result {
                 std::panic::resume_unwind(panic);
             }
         }
+
+        // The test service only runs on Linux, report the test as ignored instead of omitting it elsewhere.
+        #(#stub_attributes)*
+        #[test]
+        #[cfg(not(target_os = "linux"))]
+        #[ignore = "requires Linux to run the SOME/IP test service"]
+        #function_visibility fn #function_name() {}
     };
 
     generated_test.into()
@@ -75,6 +132,7 @@ pub fn test_with_test_service(meta: TokenStream, item: TokenStream) -> TokenStre
 
 #[derive(Debug, FromMeta)]
 struct MacroArguments {
-    logging_level: Option<String>,
+    logging_level: Option<LitStr>,
     timeout_ms: Option<u32>,
+    instances: Option<usize>,
 }
@@ -40,3 +40,29 @@ fn smoke_test(test_service: &TestService) {
         ]
     );
 }
+
+#[test_with_test_service(instances = 2)]
+fn multiple_instances(test_services: &[TestService; 2]) {
+    let request = &[
+        0x04, 0xD2, // Service ID: 1234 (0x04D2).
+        0x01, 0xA7, // Method ID: 0x0423.
+        0x00, 0x00, 0x00,
+        0x09, // Length: 9 bytes (8 bytes header after length + 1 byte payload).
+        0x00, 0x01, 0x00, 0x01, // Request ID: client id (0x0001) and session id (0x0001).
+        0x01, // Protocol Version: 1.
+        0x00, // Interface Version: 1.
+        0x00, // Message Type: 0 (Request).
+        0x00, // Return Code: 0.
+        0x12, // Single int8.
+    ];
+
+    for test_service in test_services {
+        let mut response = [0u8; 17];
+        test_service
+            .send_and_receive(request, &mut response)
+            .unwrap();
+
+        assert_eq!(response[14], 0x80, "message type should be a response");
+        assert_eq!(response[16], 0x12, "payload should be echoed");
+    }
+}
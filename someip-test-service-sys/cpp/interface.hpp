@@ -48,6 +48,25 @@ void launch(void);
  *
  */
 void terminate(void);
+
+/**
+ * Callback receiving a method call recorded by the test service.
+ *
+ * `method` is the name of the called method (e.g. `test_int8`) and `payload` a textual representation
+ * of its input parameter (e.g. `18`), both are null-terminated and only valid during the callback.
+ */
+typedef void (*received_call_callback)(void *context, const char *method, const char *payload);
+
+/**
+ * Calls `callback` with every method call received by the test service, in the order they were received.
+ *
+ * `context` is passed to `callback` unchanged.
+ *
+ * ## Thread Safety
+ *
+ * This function is thread-safe, method calls received while it runs are reported by the next call.
+ */
+void for_each_received_call(received_call_callback callback, void *context);
 }
 
 #endif // _INTERFACE_HPP
@@ -1,5 +1,6 @@
 #include <mutex>
 
+#include "../../interface.hpp"
 #include "manager.hpp"
 
 std::mutex test_service_manager_mutex;
@@ -16,4 +17,10 @@ void terminate(void) {
     auto &manager = test_service::Manager::instance();
     manager.terminate_test_service();
 }
+
+void for_each_received_call(received_call_callback callback, void *context) {
+    test_service::CallRecorder::instance().for_each([&](const auto &call) {
+        callback(context, call.method.c_str(), call.payload.c_str());
+    });
+}
 }
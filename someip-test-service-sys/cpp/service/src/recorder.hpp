@@ -0,0 +1,110 @@
+/**
+ * Records the method calls received by the test service
+ * so tests can query them.
+ */
+#ifndef _SRC_RECORDER_HPP
+#define _SRC_RECORDER_HPP
+
+#include <cstdint>
+#include <limits>
+#include <mutex>
+#include <sstream>
+#include <string>
+#include <type_traits>
+#include <vector>
+
+#include <v0/test/TestService.hpp>
+
+namespace test_service {
+
+/**
+ * A method call received by the test service.
+ */
+struct ReceivedCall {
+    std::string method;
+    std::string payload;
+};
+
+/**
+ * Thread-safe list of all method calls received by the test service.
+ */
+class CallRecorder {
+  public:
+    static CallRecorder &instance() {
+        static CallRecorder recorder;
+        return recorder;
+    }
+
+    CallRecorder(const CallRecorder &) = delete;
+    CallRecorder &operator=(const CallRecorder &) = delete;
+
+    void record(std::string method, std::string payload) {
+        const std::lock_guard<std::mutex> lock(mutex);
+        calls.push_back(ReceivedCall{.method = std::move(method), .payload = std::move(payload)});
+    }
+
+    template <typename F> void for_each(F &&f) const {
+        const std::lock_guard<std::mutex> lock(mutex);
+        for (const auto &call : calls) {
+            f(call);
+        }
+    }
+
+  private:
+    CallRecorder() = default;
+
+    mutable std::mutex mutex;
+    std::vector<ReceivedCall> calls;
+};
+
+/**
+ * Converts a method parameter to its textual payload representation,
+ * e.g. `18`, `"text"`, `[1, 2]` or `{"text", true, ...}` for structs.
+ */
+template <typename T> std::string to_payload(const T &value) {
+    std::ostringstream stream;
+    if constexpr (std::is_same_v<T, bool>) {
+        stream << (value ? "true" : "false");
+    } else if constexpr (std::is_integral_v<T>) {
+        // Widen to avoid printing `int8_t` and `uint8_t` as characters.
+        stream << static_cast<std::conditional_t<std::is_signed_v<T>, int64_t, uint64_t>>(value);
+    } else if constexpr (std::is_floating_point_v<T>) {
+        stream.precision(std::numeric_limits<T>::max_digits10);
+        stream << value;
+    } else {
+        stream << value;
+    }
+    return stream.str();
+}
+
+inline std::string to_payload(const std::string &value) { return "\"" + value + "\""; }
+
+template <typename T> std::string to_payload(const std::vector<T> &values) {
+    std::string payload = "[";
+    for (size_t index = 0; index < values.size(); ++index) {
+        if (index > 0) {
+            payload += ", ";
+        }
+        payload += to_payload(values[index]);
+    }
+    return payload + "]";
+}
+
+inline std::string to_payload(const v0::test::TestService::AllPrimitiveDataTypes &value) {
+    return "{" + to_payload(value.getParam_1()) + ", " + to_payload(value.getParam_2()) + ", " +
+           to_payload(value.getParam_3()) + ", " + to_payload(value.getParam_4()) + ", " +
+           to_payload(value.getParam_5()) + ", " + to_payload(value.getParam_6()) + ", " +
+           to_payload(value.getParam_7()) + ", " + to_payload(value.getParam_8()) + ", " +
+           to_payload(value.getParam_9()) + ", " + to_payload(value.getParam_10()) + ", " +
+           to_payload(value.getParam_11()) + ", " + to_payload(value.getParam_12()) + "}";
+}
+
+/**
+ * Records a call of the current method with the given parameter.
+ * Intended to be placed at the start of the function.
+ */
+#define RECORD_CALL(param) CallRecorder::instance().record(__func__, to_payload(param))
+
+} // namespace test_service
+
+#endif // _SRC_RECORDER_HPP
@@ -8,6 +8,7 @@
 #include <CommonAPI/Logger.hpp>
 #include <v0/test/TestServiceStubDefault.hpp>
 
+#include "recorder.hpp"
 #include "utils.hpp"
 
 namespace test_service {
@@ -16,7 +17,7 @@ namespace test_service {
  * Provides implementation for virtual methods of
  * the service generated out from Franca IDL files.
  *
- * All methods echo input back to the sender.
+ * All methods record the call and echo input back to the sender.
  */
 class TestServiceStubImpl : public v0::test::TestServiceStubDefault {
     using AllPrimitiveDataTypes = v0::test::TestService::AllPrimitiveDataTypes;
@@ -25,55 +26,66 @@ class TestServiceStubImpl : public v0::test::TestServiceStubDefault {
   public:
     void test_bool(const std::shared_ptr<ClientId> _client, bool _flag, test_boolReply_t _reply) override {
         LOG_FUNCTION_CALL();
+        RECORD_CALL(_flag);
         _reply(_flag);
     }
 
     void test_int8(const std::shared_ptr<ClientId> _client, int8_t _param, test_int8Reply_t _reply) override {
         LOG_FUNCTION_CALL();
+        RECORD_CALL(_param);
         _reply(_param);
     }
 
     void test_int16(const std::shared_ptr<ClientId> _client, int16_t _param, test_int16Reply_t _reply) {
         LOG_FUNCTION_CALL();
+        RECORD_CALL(_param);
         _reply(_param);
     }
 
     void test_int32(const std::shared_ptr<ClientId> _client, int32_t _param, test_int32Reply_t _reply) override {
         LOG_FUNCTION_CALL();
+        RECORD_CALL(_param);
         _reply(_param);
     }
     void test_int64(const std::shared_ptr<ClientId> _client, int64_t _param, test_int64Reply_t _reply) override {
         LOG_FUNCTION_CALL();
+        RECORD_CALL(_param);
         _reply(_param);
     }
 
     void test_uint8(const std::shared_ptr<ClientId> _client, uint8_t _param, test_uint8Reply_t _reply) override {
         LOG_FUNCTION_CALL();
+        RECORD_CALL(_param);
         _reply(_param);
     }
 
     void test_uint16(const std::shared_ptr<ClientId> _client, uint16_t _param, test_uint16Reply_t _reply) override {
         LOG_FUNCTION_CALL();
+        RECORD_CALL(_param);
         _reply(_param);
     }
 
     void test_uint32(const std::shared_ptr<ClientId> _client, uint32_t _param, test_uint32Reply_t _reply) override {
         LOG_FUNCTION_CALL();
+        RECORD_CALL(_param);
         _reply(_param);
     }
 
     void test_uint64(const std::shared_ptr<ClientId> _client, uint64_t _param, test_uint64Reply_t _reply) override {
         LOG_FUNCTION_CALL();
+        RECORD_CALL(_param);
         _reply(_param);
     }
 
     void test_double(const std::shared_ptr<ClientId> _client, double _param, test_doubleReply_t _reply) override {
         LOG_FUNCTION_CALL();
+        RECORD_CALL(_param);
         _reply(_param);
     }
 
     void test_float(const std::shared_ptr<ClientId> _client, float _param, test_floatReply_t _reply) override {
         LOG_FUNCTION_CALL();
+        RECORD_CALL(_param);
         _reply(_param);
     }
 
@@ -81,6 +93,7 @@ class TestServiceStubImpl : public v0::test::TestServiceStubDefault {
         const std::shared_ptr<ClientId> _client, AllPrimitiveDataTypes _request, test_structReply_t _reply
     ) override {
         LOG_FUNCTION_CALL();
+        RECORD_CALL(_request);
         _reply(_request);
     }
 
@@ -90,6 +103,7 @@ class TestServiceStubImpl : public v0::test::TestServiceStubDefault {
         test_utf16le_dynamic_length_stringReply_t _reply
     ) override {
         LOG_FUNCTION_CALL();
+        RECORD_CALL(_param);
         _reply(_param);
     }
 
@@ -99,6 +113,7 @@ class TestServiceStubImpl : public v0::test::TestServiceStubDefault {
         test_utf16be_dynamic_length_stringReply_t _reply
     ) override {
         LOG_FUNCTION_CALL();
+        RECORD_CALL(_param);
         _reply(_param);
     }
 
@@ -108,6 +123,7 @@ class TestServiceStubImpl : public v0::test::TestServiceStubDefault {
         test_utf8_dynamic_length_stringReply_t _reply
     ) override {
         LOG_FUNCTION_CALL();
+        RECORD_CALL(_param);
         _reply(_param);
     }
 
@@ -117,6 +133,7 @@ class TestServiceStubImpl : public v0::test::TestServiceStubDefault {
         test_utf16le_fixed_length_stringReply_t _reply
     ) override {
         LOG_FUNCTION_CALL();
+        RECORD_CALL(_param);
         _reply(_param);
     }
 
@@ -126,6 +143,7 @@ class TestServiceStubImpl : public v0::test::TestServiceStubDefault {
         test_utf16be_fixed_length_stringReply_t _reply
     ) override {
         LOG_FUNCTION_CALL();
+        RECORD_CALL(_param);
         _reply(_param);
     }
 
@@ -135,11 +153,13 @@ class TestServiceStubImpl : public v0::test::TestServiceStubDefault {
         test_utf8_fixed_length_stringReply_t _reply
     ) override {
         LOG_FUNCTION_CALL();
+        RECORD_CALL(_param);
         _reply(_param);
     }
 
     void test_fire_and_forget_uint64(const std::shared_ptr<CommonAPI::ClientId> _client, uint64_t _param) override {
         LOG_FUNCTION_CALL();
+        RECORD_CALL(_param);
         LOG(_param);
     }
 
@@ -149,6 +169,7 @@ class TestServiceStubImpl : public v0::test::TestServiceStubDefault {
         test_fixed_length_arrayReply_t _reply
     ) override {
         LOG_FUNCTION_CALL();
+        RECORD_CALL(_param);
         _reply(_param);
     }
 
@@ -158,6 +179,7 @@ class TestServiceStubImpl : public v0::test::TestServiceStubDefault {
         test_dynamic_length_1_byte_arrayReply_t _reply
     ) override {
         LOG_FUNCTION_CALL();
+        RECORD_CALL(_param);
         _reply(_param);
     }
 
@@ -167,6 +189,7 @@ class TestServiceStubImpl : public v0::test::TestServiceStubDefault {
         test_dynamic_length_2_bytes_arrayReply_t _reply
     ) override {
         LOG_FUNCTION_CALL();
+        RECORD_CALL(_param);
         _reply(_param);
     }
 
@@ -176,6 +199,7 @@ class TestServiceStubImpl : public v0::test::TestServiceStubDefault {
         test_dynamic_length_4_bytes_arrayReply_t _reply
     ) override {
         LOG_FUNCTION_CALL();
+        RECORD_CALL(_param);
         _reply(_param);
     }
 };
@@ -7,4 +7,8 @@ extern "C" {
 void launch(void) {}
 
 void terminate(void) {}
+
+typedef void (*received_call_callback)(void *context, const char *method, const char *payload);
+
+void for_each_received_call(received_call_callback callback, void *context) {}
 }
@@ -1,8 +1,31 @@
 //! Types to configure the test service.
 
-use strum::EnumString;
+use std::str::FromStr;
 
 /// Configuration for the test service.
+///
+/// Create it with [`Config::default`] or, to pick settings explicitly, with [`Config::builder`].
+///
+/// # Parallel execution
+///
+/// Test services running at the same time on the same host must not share [`Self::unicast_port`], and, if
+/// [`Self::service_discovery`] is enabled, must not share the pair of [`Self::multicast_address`] and
+/// [`Self::multicast_port`] either.
+/// The other settings do not affect whether test services can run in parallel.
+///
+/// # Examples
+///
+/// ```
+/// use someip_test_service::{Config, LoggingLevel};
+///
+/// let config = Config::builder()
+///     .logging_level(LoggingLevel::Debug)
+///     .port_base(40000)
+///     .build();
+///
+/// assert_eq!(config.unicast_port, 40000);
+/// assert_eq!(config.multicast_port, 40001);
+/// ```
 #[derive(Clone, Debug)]
 pub struct Config {
     /// Unicast address for sending RPC requests.
@@ -61,20 +84,115 @@ impl Default for Config {
     ///
     /// - When it is not possible to obtain a random free UDP port of the `127.0.0.1`.
     fn default() -> Self {
-        let unicast_address = String::from("127.0.0.1");
-        let unicast_port = get_free_udp_port(&unicast_address);
-        Self {
+        ConfigBuilder::default().build()
+    }
+}
+
+impl Config {
+    /// Returns a [`ConfigBuilder`] starting from the [defaults](Config::default).
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::default()
+    }
+}
+
+/// Builder for a [`Config`], see [`Config::builder`].
+///
+/// Settings that are not set keep their [defaults](Config::default).
+/// Unlike with [`Config::default`], a random free UDP port is only picked in [`Self::build`] and only if no unicast port
+/// was set.
+#[derive(Clone, Debug, Default)]
+pub struct ConfigBuilder {
+    unicast_address: Option<String>,
+    unicast_port: Option<u16>,
+    service_discovery: Option<bool>,
+    multicast_address: Option<String>,
+    multicast_port: Option<u16>,
+    logging_level: Option<LoggingLevel>,
+}
+
+impl ConfigBuilder {
+    /// Sets [`Config::unicast_address`].
+    pub fn unicast_address(mut self, unicast_address: impl Into<String>) -> Self {
+        self.unicast_address = Some(unicast_address.into());
+        self
+    }
+
+    /// Sets [`Config::unicast_port`].
+    ///
+    /// Must be unique among test services running in parallel.
+    pub fn unicast_port(mut self, unicast_port: u16) -> Self {
+        self.unicast_port = Some(unicast_port);
+        self
+    }
+
+    /// Sets [`Config::service_discovery`].
+    pub fn service_discovery(mut self, service_discovery: bool) -> Self {
+        self.service_discovery = Some(service_discovery);
+        self
+    }
+
+    /// Sets [`Config::multicast_address`].
+    pub fn multicast_address(mut self, multicast_address: impl Into<String>) -> Self {
+        self.multicast_address = Some(multicast_address.into());
+        self
+    }
+
+    /// Sets [`Config::multicast_port`].
+    pub fn multicast_port(mut self, multicast_port: u16) -> Self {
+        self.multicast_port = Some(multicast_port);
+        self
+    }
+
+    /// Sets [`Config::unicast_port`] to `port_base` and [`Config::multicast_port`] to `port_base + 1`.
+    ///
+    /// Giving every test that runs in parallel its own even `port_base` avoids port collisions deterministically.
+    ///
+    /// # Panics
+    ///
+    /// - When `port_base` is [`u16::MAX`].
+    pub fn port_base(self, port_base: u16) -> Self {
+        let multicast_port = port_base
+            .checked_add(1)
+            .expect("port base should leave room for the multicast port");
+        self.unicast_port(port_base).multicast_port(multicast_port)
+    }
+
+    /// Sets [`Config::logging_level`].
+    pub fn logging_level(mut self, logging_level: LoggingLevel) -> Self {
+        self.logging_level = Some(logging_level);
+        self
+    }
+
+    /// Creates the [`Config`].
+    ///
+    /// # Panics
+    ///
+    /// - When no unicast port was set and it is not possible to obtain a random free UDP port of the unicast address.
+    pub fn build(self) -> Config {
+        let unicast_address = self
+            .unicast_address
+            .unwrap_or_else(|| String::from(DEFAULT_UNICAST_ADDRESS));
+        let unicast_port = self
+            .unicast_port
+            .unwrap_or_else(|| get_free_udp_port(&unicast_address));
+        Config {
             unicast_address,
             unicast_port,
             // TODO: Figure out why service discovery is not working on a CI.
-            service_discovery: false,
-            multicast_address: String::from("224.244.224.245"),
-            multicast_port: 30490,
-            logging_level: LoggingLevel::Info,
+            service_discovery: self.service_discovery.unwrap_or(false),
+            multicast_address: self
+                .multicast_address
+                .unwrap_or_else(|| String::from(DEFAULT_MULTICAST_ADDRESS)),
+            multicast_port: self.multicast_port.unwrap_or(DEFAULT_MULTICAST_PORT),
+            logging_level: self.logging_level.unwrap_or(LoggingLevel::Info),
         }
     }
 }
 
+const DEFAULT_UNICAST_ADDRESS: &str = "127.0.0.1";
+const DEFAULT_MULTICAST_ADDRESS: &str = "224.244.224.245";
+const DEFAULT_MULTICAST_PORT: u16 = 30490;
+
 /// Returns a random free UDP port number of the provided interface.
 fn get_free_udp_port(interface_address: &str) -> u16 {
     let socket =
@@ -87,7 +205,9 @@ fn get_free_udp_port(interface_address: &str) -> u16 {
 ///
 /// See [`Self::to_common_api`] to understand how it is mapped to the CommonAPI.
 /// See [`Self::to_vsomeip`] to understand how it is mapped to vsomeip.
-#[derive(Clone, Debug, EnumString)]
+///
+/// Parsed from the lowercase variant names with [`FromStr`] or [`Self::from_name`].
+#[derive(Clone, Debug)]
 pub enum LoggingLevel {
     /// Print only fatal errors.
     Fatal,
@@ -109,6 +229,21 @@ pub enum LoggingLevel {
 }
 
 impl LoggingLevel {
+    /// Returns the level with the given lowercase name, e.g. `warning`, or `None` if there is none.
+    ///
+    /// Unlike [`FromStr`], this can be used in constants to validate the name at compile time.
+    pub const fn from_name(name: &str) -> Option<Self> {
+        match name.as_bytes() {
+            b"fatal" => Some(Self::Fatal),
+            b"error" => Some(Self::Error),
+            b"warning" => Some(Self::Warning),
+            b"info" => Some(Self::Info),
+            b"debug" => Some(Self::Debug),
+            b"trace" => Some(Self::Trace),
+            _ => None,
+        }
+    }
+
     /// Converts to a Common API log level.
     /// See: <https://github.com/COVESA/capicxx-core-tools/blob/master/docx/CommonAPICppUserGuide>
     pub fn to_common_api(&self) -> &'static str {
@@ -134,3 +269,15 @@ impl LoggingLevel {
         }
     }
 }
+
+impl FromStr for LoggingLevel {
+    type Err = anyhow::Error;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::from_name(name).ok_or_else(|| {
+            anyhow::anyhow!(
+                "invalid logging level `{name}`, expected one of `fatal`, `error`, `warning`, `info`, `debug`, `trace`"
+            )
+        })
+    }
+}
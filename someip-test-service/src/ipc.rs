@@ -6,11 +6,18 @@
 )]
 
 use std::env::var as env_var;
-use std::io::{Read, Write};
+use std::io::{ErrorKind, Read, Write};
+use std::net::Shutdown;
 use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::Path;
 
-use anyhow::Context;
-use tempfile::{Builder, NamedTempFile};
+use anyhow::{Context, bail};
+use tempfile::{Builder, NamedTempFile, TempDir};
+
+use crate::test_service::ReceivedCall;
+
+/// Query answered with the method calls received by the test service as a JSON list of [`ReceivedCall`]s.
+pub const RECEIVED_CALLS_QUERY: &str = "received_calls";
 
 /// Creates [`UnixListener`] bound to a temporary socket.
 ///
@@ -53,3 +60,68 @@ pub fn send_message(stream: &mut UnixStream, message: &str) -> anyhow::Result<()
         .context("failed to send message")?;
     Ok(())
 }
+
+/// Creates a temporary directory to place the socket the test service answers queries on in.
+///
+/// The directory is deleted once it goes out of scope.
+pub fn create_query_socket_dir() -> anyhow::Result<TempDir> {
+    Builder::new()
+        .prefix(&format!("someip-test-service-{}-", rand::random::<u64>()))
+        .tempdir()
+        .context("failed to create a temporary IPC query socket directory")
+}
+
+/// Creates a non-blocking [`UnixListener`] for queries, bound to the path provided by the main process.
+pub fn create_query_listener() -> anyhow::Result<UnixListener> {
+    let socket_path = env_var("IPC_QUERY_PATH").context("failed to get ipc query socket path")?;
+    let listener = UnixListener::bind(&socket_path).context("failed to bind ipc query socket")?;
+    listener
+        .set_nonblocking(true)
+        .context("failed to set ipc query socket non-blocking")?;
+    Ok(listener)
+}
+
+/// Answers all pending queries on `listener` without waiting for new ones.
+pub fn serve_queries(
+    listener: &UnixListener,
+    received_calls: impl Fn() -> Vec<ReceivedCall>,
+) -> anyhow::Result<()> {
+    loop {
+        let mut stream = match listener.accept() {
+            Ok((stream, _)) => stream,
+            Err(error) if error.kind() == ErrorKind::WouldBlock => return Ok(()),
+            Err(error) => return Err(error).context("failed to accept ipc query"),
+        };
+        stream
+            .set_nonblocking(false)
+            .context("failed to set ipc query stream blocking")?;
+
+        let mut query = String::new();
+        stream
+            .read_to_string(&mut query)
+            .context("failed to read query")?;
+        match query.as_str() {
+            RECEIVED_CALLS_QUERY => serde_json::to_writer(&mut stream, &received_calls())
+                .context("failed to send received calls")?,
+            _ => bail!("unknown query `{query}`"),
+        }
+    }
+}
+
+/// Sends `query` to the test service listening on `socket_path` and returns the answer.
+pub fn query(socket_path: &Path, query: &str) -> anyhow::Result<String> {
+    let mut stream =
+        UnixStream::connect(socket_path).context("failed to connect to ipc query socket")?;
+    stream
+        .write_all(query.as_bytes())
+        .context("failed to send query")?;
+    stream
+        .shutdown(Shutdown::Write)
+        .context("failed to finish query")?;
+
+    let mut answer = String::new();
+    stream
+        .read_to_string(&mut answer)
+        .context("failed to read query answer")?;
+    Ok(answer)
+}
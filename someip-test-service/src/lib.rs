@@ -6,8 +6,8 @@ mod ipc;
 mod subprocess;
 mod test_service;
 
-pub use config::test_service::{Config, LoggingLevel};
-pub use test_service::TestService;
+pub use config::test_service::{Config, ConfigBuilder, LoggingLevel};
+pub use test_service::{ReceivedCall, TestService};
 
 #[doc(hidden)]
 /// Private API, do not use.
@@ -16,8 +16,8 @@ pub mod reëxports {
     pub mod ipc {
         pub use crate::ipc::*;
     }
+    pub use crate::test_service::ReceivedCall;
     pub mod config {
         pub use crate::config::common_api::Config as CommonApiConfig;
-        pub use crate::config::vsomeip::Config as VSomeIpConfig;
     }
 }
@@ -3,11 +3,11 @@
 //! Intended to be launched by the library to ensure each service has a unique environment, do not run directly.
 
 use std::env::var as env_var;
+use std::ffi::{CStr, c_char, c_void};
 use std::fs::read_to_string;
 use std::io::ErrorKind;
-use std::net::UdpSocket;
+use std::net::{IpAddr, UdpSocket};
 use std::path::{Path, PathBuf};
-use std::str::FromStr;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread::sleep;
@@ -16,11 +16,12 @@ use std::time::{Duration, Instant};
 use anyhow::{Context, anyhow, bail};
 use signal_hook::consts::SIGTERM;
 use signal_hook::flag;
-use someip_test_service::reëxports::{config, ipc};
-use someip_test_service_sys::{launch, terminate};
+use someip_test_service::reëxports::{ReceivedCall, config, ipc};
+use someip_test_service_sys::{for_each_received_call, launch, terminate};
+use veecle_os_data_support_someip::config::ServiceConfig;
 
 /// Launches the test service and waits for it to start. Once ready, sends
-/// a confirmation message via IPC and waits for a SIGTERM signal to terminate.
+/// a confirmation message via IPC, then answers queries until a SIGTERM signal terminates it.
 fn main() {
     let terminated = Arc::new(AtomicBool::new(false));
 
@@ -41,22 +42,30 @@ fn main() {
     // Since `netlink` events are processed asynchronously, there's a race between `offer_service()`
     // returning and the UDP socket actually being bound. We poll until the port is connectable to
     // ensure the test client won't get "Connection refused".
-    let ip = &vsomeip_config.unicast;
-    let port = &vsomeip_config
+    let ip = vsomeip_config
+        .unicast
+        .expect("vsomeip config should have a unicast address");
+    let port = vsomeip_config
         .services
         .first()
         .expect("vsomeip config should have at least one service")
-        .unreliable;
-    let port = u16::from_str(port).expect("unreliable port should be a valid u16");
+        .unreliable_port
+        .expect("vsomeip config service should have an unreliable port");
     wait_for_port_ready(ip, port);
 
+    let query_listener =
+        ipc::create_query_listener().expect("ipc query listener should be created");
+
     let mut client = ipc::create_client().expect("ipc client should be created");
     ipc::send_message(&mut client, "Test service successfully launched")
         .expect("message should be sent");
     drop(client);
 
     while !terminated.load(Ordering::Relaxed) {
-        sleep(Duration::from_millis(500));
+        if let Err(error) = ipc::serve_queries(&query_listener, received_calls) {
+            eprintln!("failed to serve ipc query: {error:?}");
+        }
+        sleep(Duration::from_millis(50));
     }
 
     // SAFETY: There's no safety restriction for calling the terminate function.
@@ -65,13 +74,34 @@ fn main() {
     }
 }
 
-fn load_vsomeip_config() -> anyhow::Result<config::VSomeIpConfig> {
+/// Returns the method calls recorded by the test service so far.
+fn received_calls() -> Vec<ReceivedCall> {
+    unsafe extern "C" fn push(context: *mut c_void, method: *const c_char, payload: *const c_char) {
+        // SAFETY: `context` is the `Vec` passed to `for_each_received_call` below, which outlives the call.
+        let calls = unsafe { &mut *context.cast::<Vec<ReceivedCall>>() };
+        // SAFETY: The test service passes valid nul-terminated strings that live for the duration of the callback.
+        let (method, payload) = unsafe { (CStr::from_ptr(method), CStr::from_ptr(payload)) };
+        calls.push(ReceivedCall {
+            method: method.to_string_lossy().into_owned(),
+            payload: payload.to_string_lossy().into_owned(),
+        });
+    }
+
+    let mut calls = Vec::new();
+    // SAFETY: The callback only accesses `calls` through `context` while `for_each_received_call` runs.
+    unsafe {
+        for_each_received_call(Some(push), (&raw mut calls).cast());
+    }
+    calls
+}
+
+fn load_vsomeip_config() -> anyhow::Result<ServiceConfig> {
     let env_var_name = "VSOMEIP_CONFIGURATION";
     let file_path = path_from_env_var(env_var_name)
         .context(format!("failed to obtain path from {env_var_name}"))?;
     let file_content = read_to_string(&file_path)
         .context(format!("failed to read file {}", file_path.display()))?;
-    serde_json::from_str::<config::VSomeIpConfig>(&file_content).context(format!(
+    ServiceConfig::from_json(&file_content).context(format!(
         "configuration file provided via {env_var_name} is invalid"
     ))
 }
@@ -105,7 +135,7 @@ fn path_from_env_var(name: &str) -> anyhow::Result<PathBuf> {
 /// # Panics
 ///
 /// Panics if the port is not ready within 5 seconds.
-fn wait_for_port_ready(ip: &str, port: u16) {
+fn wait_for_port_ready(ip: IpAddr, port: u16) {
     let socket = UdpSocket::bind((ip, 0)).expect("failed to bind probe socket");
     socket
         .connect((ip, port))
@@ -1,5 +1,6 @@
 //! Functions to spawn a test service in a separate process.
 
+use std::path::Path;
 use std::process::{Child, Command};
 
 use anyhow::Context;
@@ -13,9 +14,12 @@ use crate::ipc;
 /// Running a test service in a separate process is required since it can only be configured through environment
 /// variables. Because setting environment variables from within the same process is unsafe, we launch a separate
 /// process for each test service instance and pass configuration through environment variables.
+///
+/// The test service answers queries on a socket it creates at `query_socket_path`.
 pub fn spawn(
     common_api_config: &CommonApiTempConfig,
     vsomeip_config: &VSomIpTempConfig,
+    query_socket_path: &Path,
 ) -> anyhow::Result<Child> {
     let listener = ipc::create_listener()?;
 
@@ -23,6 +27,7 @@ pub fn spawn(
     let args = ["run", "--package", env!("CARGO_PKG_NAME")];
     let env = [
         ("IPC_LISTENER_PATH", listener.path()),
+        ("IPC_QUERY_PATH", query_socket_path),
         ("COMMONAPI_CONFIG", common_api_config.path()),
         ("VSOMEIP_CONFIGURATION", vsomeip_config.path()),
     ];
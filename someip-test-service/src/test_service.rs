@@ -4,14 +4,33 @@ use std::env::consts::OS;
 use std::net::UdpSocket;
 use std::process::Child;
 
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use tempfile::TempDir;
+use veecle_os_data_support_someip::header::Header;
+
 use crate::config::{common_api, test_service, vsomeip};
-use crate::{endpoint, subprocess};
+use crate::{endpoint, ipc, subprocess};
+
+/// A method call received by the test service, see [`TestService::received_calls`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReceivedCall {
+    /// Name of the called method, e.g. `test_int8`.
+    pub method: String,
+
+    /// Textual representation of the input parameter, e.g. `18`, `"text"` or `[1, 2]`.
+    pub payload: String,
+}
+
+/// File name of the socket the test service answers queries on, in its query socket directory.
+const QUERY_SOCKET_NAME: &str = "query.sock";
 
 /// SOME/IP test service wrapper that manages the lifecycle of a test service instance.
 #[derive(Debug)]
 pub struct TestService {
     endpoint: UdpSocket,
     child_process: Child,
+    query_socket_dir: TempDir,
     _vsomeip_config: vsomeip::TempConfig,
     _common_api_config: common_api::TempConfig,
 }
@@ -26,6 +45,7 @@ impl TestService {
     /// - When the platform is not Linux.
     /// - When the vsomeip configuration file cannot be written to a temporary directory.
     /// - When the Common API configuration file cannot be written to a temporary directory.
+    /// - When the directory for the IPC query socket cannot be created.
     /// - When the test service cannot be spawned in a sub-process.
     /// - When the endpoint for communication with the test service cannot be created.
     pub fn new(config: &test_service::Config) -> Self {
@@ -37,8 +57,15 @@ impl TestService {
         let _vsomeip_config = vsomeip::TempConfig::from(config);
         let _common_api_config = common_api::TempConfig::from(config);
 
-        let child_process = subprocess::spawn(&_common_api_config, &_vsomeip_config)
-            .expect("test service should be spawned in a subprocess");
+        let query_socket_dir = ipc::create_query_socket_dir()
+            .expect("directory for the ipc query socket should be created");
+
+        let child_process = subprocess::spawn(
+            &_common_api_config,
+            &_vsomeip_config,
+            &query_socket_dir.path().join(QUERY_SOCKET_NAME),
+        )
+        .expect("test service should be spawned in a subprocess");
 
         let endpoint = endpoint::create(config)
             .expect("endpoint for communication with test service should be created");
@@ -46,6 +73,7 @@ impl TestService {
         Self {
             endpoint,
             child_process,
+            query_socket_dir,
             _vsomeip_config,
             _common_api_config,
         }
@@ -64,11 +92,31 @@ impl TestService {
     }
 
     /// Sends a request and waits for a response in a single operation.
+    ///
+    /// Requests that are not answered (e.g. fire&forget requests, see [`Header::is_response_expected`]) are only sent,
+    /// leaving `response` untouched.
     pub fn send_and_receive(&self, request: &[u8], response: &mut [u8]) -> anyhow::Result<()> {
+        let (header, _) =
+            Header::parse_with_payload(request).context("failed to parse request header")?;
+
         self.send(request)?;
-        self.receive(response)?;
+        if header.is_response_expected() {
+            self.receive(response)?;
+        }
         Ok(())
     }
+
+    /// Returns the method calls the test service received so far, in the order they were received.
+    ///
+    /// Calls answered with a response are recorded before the response is sent, calls without a response (e.g.
+    /// fire&forget requests) may only be returned after a short delay.
+    pub fn received_calls(&self) -> anyhow::Result<Vec<ReceivedCall>> {
+        let answer = ipc::query(
+            &self.query_socket_dir.path().join(QUERY_SOCKET_NAME),
+            ipc::RECEIVED_CALLS_QUERY,
+        )?;
+        serde_json::from_str(&answer).context("failed to parse received calls")
+    }
 }
 
 impl Drop for TestService {
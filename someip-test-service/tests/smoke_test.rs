@@ -1,6 +1,6 @@
 //! Sanity check test suite.
 
-use someip_test_service::{Config, TestService};
+use someip_test_service::{Config, ReceivedCall, TestService};
 
 #[test]
 #[ntest_timeout::timeout(240000)]
@@ -44,6 +44,14 @@ fn smoke_test() {
             0x12, // Payload: 12 (same as we sent).
         ]
     );
+
+    assert_eq!(
+        test_service.received_calls().unwrap(),
+        [ReceivedCall {
+            method: "test_int8".to_owned(),
+            payload: "18".to_owned(),
+        }]
+    );
 }
 
 #[test]
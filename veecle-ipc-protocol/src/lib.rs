@@ -3,12 +3,30 @@
 #![forbid(unsafe_code)]
 
 use std::borrow::Cow;
+use std::time::Duration;
 
 use tokio_util::bytes::BytesMut;
 use tokio_util::codec::{Decoder, Encoder, LinesCodec, LinesCodecError};
 pub use uuid::Uuid;
 use veecle_telemetry::protocol::owned;
 
+/// How often a runtime sends a [`Message::Heartbeat`] to the orchestrator.
+///
+/// The orchestrator considers a runtime unresponsive if it hasn't received a heartbeat for a few multiples of this.
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The version of this protocol, exchanged in [`Message::Hello`].
+///
+/// This is incremented on every incompatible change to [`Message`], a runtime and the orchestrator only communicate if
+/// they use the same version.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// The optional protocol features supported by this side of the connection, exchanged in [`Message::Hello`].
+pub const FEATURES: &[&str] = &["keepalive", "topic"];
+
+/// How long each side waits for the [`Message::Hello`] of the other side after connecting.
+pub const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Priority level for a runtime process.
 #[derive(Clone, Copy, Debug, Default, serde::Deserialize, serde::Serialize)]
 #[serde(rename_all = "lowercase")]
@@ -73,6 +91,73 @@ pub enum Message {
 
     /// A response to a control request sent from the orchestrator to a runtime.
     ControlResponse(ControlResponse),
+
+    /// A liveness signal sent from a runtime to the orchestrator every [`HEARTBEAT_INTERVAL`].
+    ///
+    /// There is no response to this message.
+    Heartbeat,
+
+    /// A connection liveness probe, sent periodically by a runtime's `veecle_ipc::Connector`.
+    ///
+    /// The orchestrator answers with a `Keepalive` of its own, so the runtime can detect a stalled connection.
+    /// Unlike [`Message::Heartbeat`] this only shows that the connection works, not that the runtime makes progress.
+    Keepalive,
+
+    /// The handshake, the first message sent by each side of a new connection.
+    ///
+    /// The runtime sends its `Hello` right after connecting, the orchestrator answers with its own and closes the
+    /// connection if the runtime is incompatible.
+    /// Each side checks the `Hello` of the other side with [`check_hello`].
+    Hello {
+        /// The [`PROTOCOL_VERSION`] of the sender.
+        protocol_version: u32,
+
+        /// The optional [`FEATURES`] supported by the sender.
+        features: Vec<String>,
+    },
+}
+
+impl Message {
+    /// Returns the [`Message::Hello`] describing this side of the connection.
+    pub fn hello() -> Self {
+        Self::Hello {
+            protocol_version: PROTOCOL_VERSION,
+            features: FEATURES.iter().map(|&feature| feature.to_owned()).collect(),
+        }
+    }
+}
+
+/// Checks that `message`, the first message received on a new connection, is a [`Message::Hello`] compatible with
+/// this side, returning the features supported by the other side.
+pub fn check_hello(message: Message) -> Result<Vec<String>, HandshakeError> {
+    match message {
+        Message::Hello {
+            protocol_version: PROTOCOL_VERSION,
+            features,
+        } => Ok(features),
+        Message::Hello {
+            protocol_version, ..
+        } => Err(HandshakeError::VersionMismatch {
+            local: PROTOCOL_VERSION,
+            peer: protocol_version,
+        }),
+        _ => Err(HandshakeError::MissingHello),
+    }
+}
+
+#[derive(Debug, thiserror::Error, displaydoc::Display)]
+/// The other side of a connection is incompatible, see [`check_hello`].
+pub enum HandshakeError {
+    /// The other side uses protocol version {peer}, incompatible with protocol version {local} used here.
+    VersionMismatch {
+        /// The [`PROTOCOL_VERSION`] of this side.
+        local: u32,
+        /// The protocol version of the other side.
+        peer: u32,
+    },
+
+    /// The other side did not start with a `Hello` message, it likely uses a protocol version without a handshake.
+    MissingHello,
 }
 
 /// A data value going between the local instance and another runtime instance (both input and output).
@@ -82,6 +167,16 @@ pub struct EncodedStorable {
     // TODO: using the type-name to tag messages doesn't guarantee uniqueness.
     pub type_name: Cow<'static, str>,
 
+    /// An optional topic, used together with `type_name` as routing tag to separate channels of the same type.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic: Option<Cow<'static, str>>,
+
+    /// The schema version of the `type_name` value, see `Storable::SCHEMA_VERSION` in `veecle-os-runtime`.
+    ///
+    /// Values encoded without a schema version have version `0`.
+    #[serde(default)]
+    pub schema_version: u32,
+
     /// JSON-encoded instance of a `type_name` value.
     pub value: String,
 }
@@ -94,9 +189,38 @@ impl EncodedStorable {
     {
         Ok(Self {
             type_name: Cow::Borrowed(std::any::type_name::<T>()),
+            topic: None,
+            schema_version: 0,
             value: serde_json::to_string(&value)?,
         })
     }
+
+    /// Tags this value with `topic`.
+    pub fn with_topic(mut self, topic: impl Into<Cow<'static, str>>) -> Self {
+        self.topic = Some(topic.into());
+        self
+    }
+
+    /// Tags this value with the `schema_version` it was encoded with.
+    pub fn with_schema_version(mut self, schema_version: u32) -> Self {
+        self.schema_version = schema_version;
+        self
+    }
+
+    /// Returns the key this value is routed by, see [`routing_key`].
+    pub fn routing_key(&self) -> Cow<'_, str> {
+        routing_key(&self.type_name, self.topic.as_deref())
+    }
+}
+
+/// Returns the key data of type `type_name` tagged with `topic` is routed by.
+///
+/// This is `type_name` without a topic and `type_name#topic` with one.
+pub fn routing_key<'a>(type_name: &'a str, topic: Option<&str>) -> Cow<'a, str> {
+    match topic {
+        Some(topic) => Cow::Owned(format!("{type_name}#{topic}")),
+        None => Cow::Borrowed(type_name),
+    }
 }
 
 #[derive(Debug, thiserror::Error, displaydoc::Display)]
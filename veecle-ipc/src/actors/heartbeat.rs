@@ -0,0 +1,35 @@
+use veecle_ipc_protocol::HEARTBEAT_INTERVAL;
+use veecle_os_runtime::Never;
+
+use crate::Connector;
+
+/// An actor that periodically sends a heartbeat to the orchestrator via the provided [`Connector`].
+///
+/// The orchestrator uses these heartbeats to report whether a runtime is healthy. Because the heartbeats are sent from
+/// an actor they stop if the runtime stops making progress, even if its process is still alive.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example() {
+/// # let connector: &'static veecle_ipc::Connector = todo!();
+/// veecle_os::runtime::execute! {
+///     actors: [
+///         veecle_ipc::Heartbeat: connector,
+///     ],
+/// }
+/// # .await;
+/// # }
+/// ```
+#[veecle_os_runtime::actor]
+pub async fn heartbeat(#[init_context] connector: &Connector) -> Never {
+    let output = connector.heartbeat_output();
+    let mut interval = tokio::time::interval(HEARTBEAT_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        // A full channel means a heartbeat is already waiting to be sent, so there's no need to queue another.
+        let _ = output.try_send(());
+    }
+}
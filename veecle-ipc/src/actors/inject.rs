@@ -0,0 +1,155 @@
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::{SendError, TrySendError};
+use veecle_os_runtime::single_writer::Writer;
+use veecle_os_runtime::{Never, Storable};
+
+/// A handle to write values of type `T` into a store from code outside of the actors, e.g. another thread or a
+/// custom ingress task.
+///
+/// An [`Injector`] is created together with an [`InjectReceiver`] by [`Injector::channel`].
+/// The receiver is passed to the [`Inject`] actor, which writes every value sent through the injector (or one of its
+/// clones) to the store in order.
+/// [`Input`](crate::Input) is built the same way, it injects the values it receives from the `veecle-orchestrator`.
+///
+/// Injectors are [`Send`] and [`Sync`] if `T::DataType` is [`Send`] and can be used from any thread.
+/// The channel is bounded: once `capacity` values are waiting for the [`Inject`] actor, [`Injector::write`] waits and
+/// [`Injector::try_write`] fails.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use veecle_os_runtime::Storable;
+/// # #[derive(Debug, Storable)]
+/// # struct Command(u32);
+/// # async fn example() {
+/// use veecle_ipc::Injector;
+///
+/// let (injector, receiver) = Injector::<Command>::channel(16);
+///
+/// std::thread::spawn(move || {
+///     injector.blocking_write(Command(1)).unwrap();
+/// });
+///
+/// veecle_os::runtime::execute! {
+///     actors: [
+///         veecle_ipc::Inject::<Command>: receiver,
+///         // ... the actors reading `Command`.
+///     ],
+/// }
+/// .await;
+/// # }
+/// ```
+pub struct Injector<T>
+where
+    T: Storable,
+{
+    sender: mpsc::Sender<T::DataType>,
+}
+
+impl<T> Injector<T>
+where
+    T: Storable,
+{
+    /// Creates an injector and the receiver to pass to the [`Inject`] actor, buffering up to `capacity` values.
+    ///
+    /// # Panics
+    ///
+    /// If `capacity` is zero.
+    pub fn channel(capacity: usize) -> (Self, InjectReceiver<T>) {
+        let (sender, receiver) = mpsc::channel(capacity);
+        (Self { sender }, InjectReceiver { receiver })
+    }
+
+    /// Writes `value` to the store, waiting for buffer space.
+    ///
+    /// Returns the value back if the [`Inject`] actor is gone.
+    pub async fn write(&self, value: T::DataType) -> Result<(), SendError<T::DataType>> {
+        self.sender.send(value).await
+    }
+
+    /// Writes `value` to the store if there is buffer space, without waiting.
+    pub fn try_write(&self, value: T::DataType) -> Result<(), TrySendError<T::DataType>> {
+        self.sender.try_send(value)
+    }
+
+    /// Writes `value` to the store, blocking the current thread until there is buffer space.
+    ///
+    /// # Panics
+    ///
+    /// If called within an asynchronous execution context, use [`Injector::write`] there instead.
+    pub fn blocking_write(&self, value: T::DataType) -> Result<(), SendError<T::DataType>> {
+        self.sender.blocking_send(value)
+    }
+}
+
+impl<T> Clone for Injector<T>
+where
+    T: Storable,
+{
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+impl<T> core::fmt::Debug for Injector<T>
+where
+    T: Storable,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Injector")
+            .field("type_name", &std::any::type_name::<T>())
+            .finish_non_exhaustive()
+    }
+}
+
+/// The receiving side of an [`Injector`], the init-context of the [`Inject`] actor.
+pub struct InjectReceiver<T>
+where
+    T: Storable,
+{
+    receiver: mpsc::Receiver<T::DataType>,
+}
+
+impl<T> core::fmt::Debug for InjectReceiver<T>
+where
+    T: Storable,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("InjectReceiver")
+            .field("type_name", &std::any::type_name::<T>())
+            .finish_non_exhaustive()
+    }
+}
+
+/// An actor that writes values of type `T` sent through an [`Injector`] to other actors.
+///
+/// Once all injectors are dropped the actor stops writing and the last value stays in the store.
+///
+/// See [`Injector`] for an example.
+#[veecle_os_runtime::actor]
+pub async fn inject<T>(
+    #[init_context] receiver: InjectReceiver<T>,
+    mut writer: Writer<'_, T>,
+) -> Never
+where
+    T: Storable + 'static,
+{
+    write_injected(receiver, &mut writer).await
+}
+
+/// Writes the values sent through the injectors of `receiver` to `writer`, see [`Inject`].
+pub(super) async fn write_injected<T>(
+    receiver: InjectReceiver<T>,
+    writer: &mut Writer<'_, T>,
+) -> Never
+where
+    T: Storable + 'static,
+{
+    let mut receiver = receiver.receiver;
+    while let Some(value) = receiver.recv().await {
+        writer.write(value).await;
+    }
+    core::future::pending().await
+}
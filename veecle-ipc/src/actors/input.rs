@@ -1,20 +1,75 @@
 use serde::de::DeserializeOwned;
+use tokio::sync::mpsc;
+use veecle_ipc_protocol::EncodedStorable;
 use veecle_os_runtime::single_writer::Writer;
 use veecle_os_runtime::{Never, Storable};
 
-use crate::Connector;
+use super::inject::write_injected;
+use crate::{Connector, Injector};
 
 /// An actor that will receive values of type `T` from the provided [`Connector`] and send them to other actors.
+///
+/// With [`InputConfig::with_topic`] only values sent with the same topic are received, see
+/// [`OutputConfig::with_topic`](crate::OutputConfig::with_topic).
+///
+/// Values encoded with another schema version than [`Storable::SCHEMA_VERSION`] of `T` are rejected.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use serde::{Serialize, Deserialize};
+/// # use veecle_os_runtime::Storable;
+/// # #[derive(Debug, Storable, Serialize, Deserialize)]
+/// # struct RadarScan;
+/// # async fn example() {
+/// # let connector: &'static veecle_ipc::Connector = todo!();
+/// use veecle_ipc::InputConfig;
+///
+/// veecle_os::runtime::execute! {
+///     actors: [
+///         veecle_ipc::Input::<RadarScan>: InputConfig::new(connector).with_topic("front"),
+///     ],
+/// }
+/// # .await;
+/// # }
+/// ```
 #[veecle_os_runtime::actor]
-pub async fn input<T>(#[init_context] connector: &Connector, mut writer: Writer<'_, T>) -> Never
+pub async fn input<T>(#[init_context] config: InputConfig<'_>, mut writer: Writer<'_, T>) -> Never
+where
+    T: Storable<DataType: DeserializeOwned> + 'static,
+{
+    let routing_key = veecle_ipc_protocol::routing_key(std::any::type_name::<T>(), config.topic);
+    let input = config.connector.storable_input(routing_key.into_owned());
+
+    // The decoded values are written like values injected from outside the actors, see `Injector`.
+    let (injector, receiver) = Injector::<T>::channel(1);
+    tokio::select! {
+        never = decode(input, injector) => never,
+        never = write_injected(receiver, &mut writer) => never,
+    }
+}
+
+/// Decodes the values received on `input` and writes them through `injector`.
+async fn decode<T>(mut input: mpsc::Receiver<EncodedStorable>, injector: Injector<T>) -> Never
 where
     T: Storable<DataType: DeserializeOwned> + 'static,
 {
-    let mut input = connector.storable_input(std::any::type_name::<T>());
     loop {
-        let value = input.recv().await.unwrap();
-        match serde_json::from_str(&value) {
-            Ok(value) => writer.write(value).await,
+        let storable = input.recv().await.unwrap();
+        if storable.schema_version != T::SCHEMA_VERSION {
+            veecle_telemetry::error!(
+                "ipc input with mismatched schema version",
+                type_name = std::any::type_name::<T>(),
+                expected = i64::from(T::SCHEMA_VERSION),
+                received = i64::from(storable.schema_version)
+            );
+            continue;
+        }
+        match serde_json::from_str(&storable.value) {
+            Ok(value) => {
+                // The receiver is only dropped together with this future.
+                let _ = injector.write(value).await;
+            }
             Err(error) => {
                 let error = anyhow::Error::new(error).context(format!(
                     "invalid ipc input for {}",
@@ -25,3 +80,32 @@ where
         }
     }
 }
+
+/// Configuration for the [`Input`] actor.
+#[derive(Debug, Clone, Copy)]
+pub struct InputConfig<'a> {
+    connector: &'a Connector,
+    topic: Option<&'static str>,
+}
+
+impl<'a> InputConfig<'a> {
+    /// Creates a new input configuration.
+    pub fn new(connector: &'a Connector) -> Self {
+        Self {
+            connector,
+            topic: None,
+        }
+    }
+
+    /// Only receives values tagged with `topic`.
+    pub fn with_topic(mut self, topic: &'static str) -> Self {
+        self.topic = Some(topic);
+        self
+    }
+}
+
+impl<'a> From<&'a Connector> for InputConfig<'a> {
+    fn from(connector: &'a Connector) -> Self {
+        Self::new(connector)
+    }
+}
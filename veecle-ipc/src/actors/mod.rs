@@ -1,7 +1,11 @@
 mod control;
+mod heartbeat;
+mod inject;
 mod input;
 mod output;
 
 pub use self::control::ControlHandler;
-pub use self::input::Input;
+pub use self::heartbeat::Heartbeat;
+pub use self::inject::{Inject, InjectReceiver, Injector};
+pub use self::input::{Input, InputConfig};
 pub use self::output::{Output, OutputConfig};
@@ -18,6 +18,11 @@ use crate::{Connector, SendPolicy};
 /// - **Drop behavior** ([`SendPolicy::Drop`]): Messages are dropped with a warning if the
 ///   channel is full. Use this for non-critical data like telemetry.
 ///
+/// - **Coalesce behavior** ([`SendPolicy::Coalesce`]): After sending a value, the actor waits for
+///   `interval` before reading again. Values written in the meantime replace each other in the
+///   store, so only the latest one is sent afterwards. Messages are dropped with a warning if
+///   the channel is full.
+///
 /// # Examples
 ///
 /// ```no_run
@@ -27,8 +32,12 @@ use crate::{Connector, SendPolicy};
 /// # struct CriticalData;
 /// # #[derive(Debug, Storable, Serialize, Deserialize)]
 /// # struct TelemetryData;
+/// # #[derive(Debug, Storable, Serialize, Deserialize)]
+/// # struct SensorData;
 /// # async fn example() {
 /// # let connector: &'static veecle_ipc::Connector = todo!();
+/// use std::time::Duration;
+///
 /// use veecle_ipc::SendPolicy;
 ///
 /// veecle_os::runtime::execute! {
@@ -37,6 +46,11 @@ use crate::{Connector, SendPolicy};
 ///         veecle_ipc::Output::<CriticalData>: connector.into(),
 ///         // Explicitly drop telemetry when buffer is full
 ///         veecle_ipc::Output::<TelemetryData>: (connector, SendPolicy::Drop).into(),
+///         // Send at most ten sensor values per second
+///         veecle_ipc::Output::<SensorData>: (
+///             connector,
+///             SendPolicy::Coalesce { interval: Duration::from_millis(100) },
+///         ).into(),
 ///     ],
 /// }
 /// # .await;
@@ -51,12 +65,19 @@ where
     let send_policy = config.send_policy;
 
     loop {
-        let value = reader
-            .read_updated(|value| EncodedStorable::new(value).unwrap())
+        let mut value = reader
+            .read_updated(|value| {
+                EncodedStorable::new(value)
+                    .unwrap()
+                    .with_schema_version(T::SCHEMA_VERSION)
+            })
             .await;
+        if let Some(topic) = config.topic {
+            value = value.with_topic(topic);
+        }
 
         match send_policy {
-            SendPolicy::Drop => {
+            SendPolicy::Drop | SendPolicy::Coalesce { .. } => {
                 if let Err(error) = output.try_send(value) {
                     veecle_telemetry::warn!(
                         "dropped IPC message due to full channel",
@@ -69,6 +90,10 @@ where
                 output.try_send(value).expect("IPC output channel is full");
             }
         }
+
+        if let SendPolicy::Coalesce { interval } = send_policy {
+            tokio::time::sleep(interval).await;
+        }
     }
 }
 
@@ -77,6 +102,7 @@ where
 pub struct OutputConfig<'a> {
     connector: &'a Connector,
     send_policy: SendPolicy,
+    topic: Option<&'static str>,
 }
 
 impl<'a> OutputConfig<'a> {
@@ -85,24 +111,29 @@ impl<'a> OutputConfig<'a> {
         Self {
             connector,
             send_policy,
+            topic: None,
         }
     }
+
+    /// Tags all sent values with `topic`.
+    ///
+    /// This allows separate channels of the same type, e.g. `RadarScan` values from a front and a rear radar.
+    /// The values are only received by an [`Input`](crate::Input) with the same topic, and need to be linked in the
+    /// `veecle-orchestrator` with the same topic.
+    pub fn with_topic(mut self, topic: &'static str) -> Self {
+        self.topic = Some(topic);
+        self
+    }
 }
 
 impl<'a> From<&'a Connector> for OutputConfig<'a> {
     fn from(connector: &'a Connector) -> Self {
-        Self {
-            connector,
-            send_policy: SendPolicy::default(),
-        }
+        Self::new(connector, SendPolicy::default())
     }
 }
 
 impl<'a> From<(&'a Connector, SendPolicy)> for OutputConfig<'a> {
     fn from((connector, send_policy): (&'a Connector, SendPolicy)) -> Self {
-        Self {
-            connector,
-            send_policy,
-        }
+        Self::new(connector, send_policy)
     }
 }
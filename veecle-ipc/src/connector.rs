@@ -2,18 +2,23 @@ use std::collections::HashMap;
 use std::collections::hash_map::Entry;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use futures::sink::SinkExt;
 use futures::stream::StreamExt;
 use tokio::net::UnixStream;
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
+use tokio::time::{Instant, MissedTickBehavior};
 use tokio_util::codec::Framed;
-use veecle_ipc_protocol::{Codec, ControlRequest, ControlResponse, EncodedStorable, Message, Uuid};
+use veecle_ipc_protocol::{
+    Codec, ControlRequest, ControlResponse, EncodedStorable, HANDSHAKE_TIMEOUT, HEARTBEAT_INTERVAL,
+    Message, Uuid,
+};
 
 use crate::Exporter;
 
-type Inputs = Arc<Mutex<HashMap<&'static str, mpsc::Sender<String>>>>;
+type Inputs = Arc<Mutex<HashMap<String, mpsc::Sender<EncodedStorable>>>>;
 
 /// Holds various output channel senders for the [`Connector`], separated so they have decoupled
 /// buffering and prioritization.
@@ -22,14 +27,16 @@ struct OutputTx {
     storable: mpsc::Sender<EncodedStorable>,
     telemetry: mpsc::Sender<veecle_telemetry::protocol::owned::InstanceMessage>,
     control: mpsc::Sender<ControlRequest>,
+    heartbeat: mpsc::Sender<()>,
 }
 
 /// The receivers for [`OutputTx`].
 #[derive(Debug)]
-struct OutputRx {
-    storable: mpsc::Receiver<EncodedStorable>,
+pub(crate) struct OutputRx {
+    pub(crate) storable: mpsc::Receiver<EncodedStorable>,
     telemetry: mpsc::Receiver<veecle_telemetry::protocol::owned::InstanceMessage>,
     control: mpsc::Receiver<ControlRequest>,
+    heartbeat: mpsc::Receiver<()>,
 }
 
 impl OutputRx {
@@ -41,6 +48,7 @@ impl OutputRx {
         Some(tokio::select! {
             biased; // Polls all branches in order to guarantee prioritization.
             Some(control) = self.control.recv() => Message::ControlRequest(control),
+            Some(()) = self.heartbeat.recv() => Message::Heartbeat,
             Some(storable) = self.storable.recv() => Message::Storable(storable),
             Some(telemetry) = self.telemetry.recv() => Message::Telemetry(telemetry),
             else => return None, // Only reached when all channels are closed.
@@ -52,6 +60,8 @@ fn outputs() -> (OutputTx, OutputRx) {
     // Control requests are request-response so there should never be buffering as the sender will
     // be waiting on a response.
     let (control_tx, control_rx) = mpsc::channel(1);
+    // Only the latest heartbeat matters, the `Heartbeat` actor skips sending if one is already pending.
+    let (heartbeat_tx, heartbeat_rx) = mpsc::channel(1);
     // The output channel capacity (128) determines buffering for IPC messages.
     // The `Output` actor uses `SendPolicy` to control behavior when this fills up:
     // - `SendPolicy::Panic` (default): panics to make buffer exhaustion visible
@@ -65,16 +75,193 @@ fn outputs() -> (OutputTx, OutputRx) {
         OutputTx {
             storable: storable_tx,
             control: control_tx,
+            heartbeat: heartbeat_tx,
             telemetry: telemetry_tx,
         },
         OutputRx {
             storable: storable_rx,
             control: control_rx,
+            heartbeat: heartbeat_rx,
             telemetry: telemetry_rx,
         },
     )
 }
 
+/// Configuration for a [`Connector`].
+///
+/// # Keepalive
+///
+/// The [`Connector`] sends a keepalive message every keepalive interval, which the `veecle-orchestrator` answers.
+/// If nothing was received from the orchestrator for the keepalive timeout, or a message cannot be sent within it,
+/// the connection is considered stalled (e.g. half-open) and the [`Connector`] reconnects.
+/// Messages that were being sent when the connection stalled are lost.
+/// Keepalives are only sent if the orchestrator advertises the `keepalive` feature in its handshake, as it would not
+/// answer them otherwise.
+///
+/// ```no_run
+/// # async fn example() {
+/// use std::time::Duration;
+///
+/// use veecle_ipc::{Connector, ConnectorConfig};
+///
+/// let config = ConnectorConfig::default().keepalive(Duration::from_millis(500), Duration::from_secs(2));
+/// let connector = Connector::connect_with_config(config).await;
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ConnectorConfig {
+    keepalive_interval: Duration,
+    keepalive_timeout: Duration,
+}
+
+impl Default for ConnectorConfig {
+    /// Returns a config sending a keepalive every [`HEARTBEAT_INTERVAL`] with a timeout of three intervals.
+    fn default() -> Self {
+        Self {
+            keepalive_interval: HEARTBEAT_INTERVAL,
+            keepalive_timeout: HEARTBEAT_INTERVAL.saturating_mul(3),
+        }
+    }
+}
+
+impl ConnectorConfig {
+    /// Sets how often a keepalive is sent and after how long without receiving anything the connection is considered
+    /// stalled.
+    ///
+    /// # Panics
+    ///
+    /// If `interval` is zero or `timeout` is not longer than `interval`.
+    pub fn keepalive(mut self, interval: Duration, timeout: Duration) -> Self {
+        assert!(!interval.is_zero(), "keepalive interval must be non-zero");
+        assert!(
+            timeout > interval,
+            "keepalive timeout must be longer than the interval"
+        );
+        self.keepalive_interval = interval;
+        self.keepalive_timeout = timeout;
+        self
+    }
+}
+
+type Stream = Framed<UnixStream, Codec>;
+
+/// Why [`serve`] returned.
+#[derive(Debug)]
+enum Disconnect {
+    /// The orchestrator closed the connection, or all output channels were closed.
+    Closed,
+    /// The connection stopped working without being closed.
+    Stalled,
+}
+
+/// A connection to the orchestrator after the handshake.
+#[derive(Debug)]
+struct Connection {
+    stream: Stream,
+    /// Whether the orchestrator answers [`Message::Keepalive`]s.
+    keepalive: bool,
+}
+
+/// Exchanges messages over `connection` until it is disconnected.
+async fn serve(
+    connection: &mut Connection,
+    config: &ConnectorConfig,
+    inputs: &Inputs,
+    output_rx: &mut OutputRx,
+    control_response_tx: &mpsc::Sender<ControlResponse>,
+) -> Result<Disconnect, Box<dyn std::error::Error + Send + Sync>> {
+    let mut keepalive = tokio::time::interval(config.keepalive_interval);
+    keepalive.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut last_received = Instant::now();
+    let Connection {
+        stream,
+        keepalive: keepalive_supported,
+    } = connection;
+
+    loop {
+        let outgoing = tokio::select! {
+            message = output_rx.recv() => {
+                let Some(message) = message else { return Ok(Disconnect::Closed) };
+                message
+            }
+            _ = keepalive.tick(), if *keepalive_supported => {
+                if last_received.elapsed() >= config.keepalive_timeout {
+                    return Ok(Disconnect::Stalled);
+                }
+                Message::Keepalive
+            }
+            message = stream.next() => {
+                let Some(message) = message else { return Ok(Disconnect::Closed) };
+                last_received = Instant::now();
+                let message = match message {
+                    Ok(message) => message,
+                    Err(error) => {
+                        let error = anyhow::Error::new(error).context("invalid ipc message");
+                        veecle_telemetry::error!("error", error = format!("{error:?}"));
+                        continue
+                    }
+                };
+                match message {
+                    Message::Storable(storable) => {
+                        let Some(sender) = inputs.lock().unwrap().get(&*storable.routing_key()).cloned() else {
+                            continue
+                        };
+                        let _ = sender.send(storable).await;
+                    }
+                    Message::Telemetry(_) => {
+                        veecle_telemetry::error!("received unexpected ipc message variant", message = format!("{message:?}"));
+                    }
+                    Message::ControlRequest(_) | Message::Heartbeat | Message::Hello { .. } => {
+                        veecle_telemetry::error!("received unexpected ipc message variant", message = format!("{message:?}"));
+                    }
+                    Message::ControlResponse(response) => {
+                        let _ = control_response_tx.send(response).await;
+                    }
+                    // Only used to update `last_received`.
+                    Message::Keepalive => {}
+                }
+                continue;
+            }
+        };
+
+        match tokio::time::timeout(config.keepalive_timeout, stream.send(&outgoing)).await {
+            Ok(result) => result?,
+            Err(_elapsed) => return Ok(Disconnect::Stalled),
+        }
+    }
+}
+
+/// Connects to the orchestrator at `socket` and exchanges [`Message::Hello`]s with it.
+async fn connect(socket: &str) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+    let stream = UnixStream::connect(socket).await?;
+    let mut stream = Framed::new(stream, Codec::new());
+
+    stream.send(&Message::hello()).await?;
+    let hello = tokio::time::timeout(HANDSHAKE_TIMEOUT, stream.next())
+        .await
+        .map_err(|_| "timed out waiting for the orchestrator's handshake")?
+        .ok_or("the orchestrator closed the connection during the handshake")??;
+    let features = veecle_ipc_protocol::check_hello(hello)?;
+
+    Ok(Connection {
+        stream,
+        keepalive: features.iter().any(|feature| feature == "keepalive"),
+    })
+}
+
+/// Connects to the orchestrator at `socket`, retrying every keepalive interval until it succeeds.
+async fn reconnect(socket: &str, config: &ConnectorConfig) -> Connection {
+    loop {
+        match connect(socket).await {
+            Ok(connection) => return connection,
+            Err(error) => {
+                veecle_telemetry::warn!("failed to reconnect ipc", error = format!("{error:?}"));
+                tokio::time::sleep(config.keepalive_interval).await;
+            }
+        }
+    }
+}
+
 /// Manages the connection to other runtimes via the `veecle-orchestrator`.
 #[derive(Debug)]
 pub struct Connector {
@@ -82,24 +269,35 @@ pub struct Connector {
     output_tx: OutputTx,
     inputs: Inputs,
     control_responses: Mutex<Option<mpsc::Receiver<ControlResponse>>>,
-    _task: JoinHandle<Result<(), Box<dyn std::error::Error + Send + Sync>>>,
+    /// The task exchanging messages with the orchestrator, `None` for an in-memory connector.
+    _task: Option<JoinHandle<Result<(), Box<dyn std::error::Error + Send + Sync>>>>,
 }
 
 impl Connector {
-    /// Finds and connects to the `veecle-orchestrator`.
+    /// Finds and connects to the `veecle-orchestrator` with the default [`ConnectorConfig`].
     ///
     /// See the [crate][`crate`] docs for an example.
     ///
     /// # Panics
     ///
-    /// If the connection cannot be established.
+    /// If the connection cannot be established or the orchestrator uses an incompatible protocol version.
     pub async fn connect() -> Self {
+        Self::connect_with_config(ConnectorConfig::default()).await
+    }
+
+    /// Finds and connects to the `veecle-orchestrator` with the given `config`.
+    ///
+    /// # Panics
+    ///
+    /// If the connection cannot be established or the orchestrator uses an incompatible protocol version.
+    pub async fn connect_with_config(config: ConnectorConfig) -> Self {
         let socket = std::env::var("VEECLE_IPC_SOCKET").unwrap();
         let runtime_id = std::env::var("VEECLE_RUNTIME_ID").unwrap();
         let runtime_id = Uuid::from_str(&runtime_id).unwrap();
 
-        let stream = UnixStream::connect(&socket).await.unwrap();
-        let mut stream = Framed::new(stream, Codec::new());
+        let mut connection = connect(&socket)
+            .await
+            .unwrap_or_else(|error| panic!("failed to connect to the orchestrator: {error}"));
 
         let inputs = Inputs::default();
         let (output_tx, mut output_rx) = outputs();
@@ -109,43 +307,25 @@ impl Connector {
             let inputs = inputs.clone();
             async move {
                 loop {
-                    tokio::select! {
-                        message = output_rx.recv() => {
-                            let Some(message) = message else { break };
-                            stream.send(&message).await?;
-                        }
-                        message = stream.next() => {
-                            let Some(message) = message else { break };
-                            let message = match message {
-                                Ok(message) => message,
-                                Err(error) => {
-                                    let error = anyhow::Error::new(error).context("invalid ipc message");
-                                    veecle_telemetry::error!("error", error = format!("{error:?}"));
-                                    continue
-                                }
-                            };
-                            match message {
-                                Message::Storable(storable) => {
-                                    let Some(sender) = inputs.lock().unwrap().get(&*storable.type_name).cloned() else {
-                                        continue
-                                    };
-                                    let _ = sender.send(storable.value).await;
-                                }
-                                Message::Telemetry(_) => {
-                                    veecle_telemetry::error!("received unexpected ipc message variant", message = format!("{message:?}"));
-                                }
-                                Message::ControlRequest(_) => {
-                                    veecle_telemetry::error!("received unexpected ipc message variant", message = format!("{message:?}"));
-                                }
-                                Message::ControlResponse(response) => {
-                                    let _ = control_response_tx.send(response).await;
-                                }
-                            }
+                    match serve(
+                        &mut connection,
+                        &config,
+                        &inputs,
+                        &mut output_rx,
+                        &control_response_tx,
+                    )
+                    .await?
+                    {
+                        Disconnect::Closed => return Ok(()),
+                        Disconnect::Stalled => {
+                            veecle_telemetry::warn!(
+                                "ipc connection stalled, reconnecting",
+                                timeout = format!("{:?}", config.keepalive_timeout)
+                            );
+                            connection = reconnect(&socket, &config).await;
                         }
                     }
                 }
-
-                Ok(())
             }
         });
 
@@ -154,10 +334,28 @@ impl Connector {
             output_tx,
             inputs,
             control_responses: Mutex::new(Some(control_response_rx)),
-            _task: task,
+            _task: Some(task),
         }
     }
 
+    /// Creates a connector that isn't connected to anything, returning the other ends of its channels.
+    ///
+    /// Used by [`MockConnector`](crate::MockConnector).
+    pub(crate) fn in_memory(runtime_id: Uuid) -> (Self, OutputRx, mpsc::Sender<ControlResponse>) {
+        let (output_tx, output_rx) = outputs();
+        let (control_response_tx, control_response_rx) = mpsc::channel(16);
+
+        let connector = Self {
+            runtime_id,
+            output_tx,
+            inputs: Inputs::default(),
+            control_responses: Mutex::new(Some(control_response_rx)),
+            _task: None,
+        };
+
+        (connector, output_rx, control_response_tx)
+    }
+
     /// Returns an [`Exporter`] that will forward [`veecle-telemetry`][veecle_telemetry] data over this IPC connection to
     /// be gathered by the `veecle-orchestrator`.
     ///
@@ -188,10 +386,12 @@ impl Connector {
         self.runtime_id
     }
 
-    /// Registers a new channel that will receive input from the `veecle-orchestrator` tagged with `type_name`.
-    pub(crate) fn storable_input(&self, type_name: &'static str) -> mpsc::Receiver<String> {
-        match self.inputs.lock().unwrap().entry(type_name) {
-            Entry::Occupied(_) => panic!("type name {type_name} already registered"),
+    /// Registers a new channel that will receive input from the `veecle-orchestrator` tagged with `routing_key`.
+    ///
+    /// See [`veecle_ipc_protocol::routing_key`].
+    pub(crate) fn storable_input(&self, routing_key: String) -> mpsc::Receiver<EncodedStorable> {
+        match self.inputs.lock().unwrap().entry(routing_key) {
+            Entry::Occupied(entry) => panic!("routing key {} already registered", entry.key()),
             Entry::Vacant(entry) => {
                 let (sender, receiver) = mpsc::channel(16);
                 entry.insert(sender);
@@ -200,11 +400,24 @@ impl Connector {
         }
     }
 
+    /// Returns the sender for the input channel registered for `routing_key`, if any.
+    pub(crate) fn storable_input_sender(
+        &self,
+        routing_key: &str,
+    ) -> Option<mpsc::Sender<EncodedStorable>> {
+        self.inputs.lock().unwrap().get(routing_key).cloned()
+    }
+
     /// Gets a new sender to send values to the `veecle-orchestrator`.
     pub(crate) fn storable_output(&self) -> mpsc::Sender<EncodedStorable> {
         self.output_tx.storable.clone()
     }
 
+    /// Gets a new sender to signal heartbeats to the `veecle-orchestrator`.
+    pub(crate) fn heartbeat_output(&self) -> mpsc::Sender<()> {
+        self.output_tx.heartbeat.clone()
+    }
+
     /// Gets the sender and receiver to send control messages and receive control responses from the `veecle-orchestrator`.
     ///
     /// This can only be called once, as there should only be one `ControlHandler` actor.
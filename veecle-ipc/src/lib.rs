@@ -35,7 +35,7 @@
 //!
 //!     veecle_os_runtime::execute! {
 //!         actors: [
-//!             veecle_ipc::Input<Ping>: &connector,
+//!             veecle_ipc::Input<Ping>: (&connector).into(),
 //!             veecle_ipc::Output<Pong>: (&connector).into(),
 //!         ],
 //!     }.await;
@@ -51,11 +51,16 @@ extern crate self as veecle_ipc;
 
 mod actors;
 mod connector;
+mod mock;
 mod send_policy;
 mod telemetry;
 
-pub use self::actors::{ControlHandler, Input, Output, OutputConfig};
-pub use self::connector::Connector;
+pub use self::actors::{
+    ControlHandler, Heartbeat, Inject, InjectReceiver, Injector, Input, InputConfig, Output,
+    OutputConfig,
+};
+pub use self::connector::{Connector, ConnectorConfig};
+pub use self::mock::MockConnector;
 pub use self::send_policy::SendPolicy;
 pub use self::telemetry::Exporter;
 pub use veecle_ipc_protocol::{ControlRequest, ControlResponse, Uuid};
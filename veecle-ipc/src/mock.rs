@@ -0,0 +1,160 @@
+//! An in-memory [`Connector`] for testing.
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tokio::sync::{Mutex, mpsc};
+use veecle_ipc_protocol::{ControlResponse, EncodedStorable, Uuid};
+use veecle_os_runtime::Storable;
+
+use crate::Connector;
+use crate::connector::OutputRx;
+
+/// A [`Connector`] that isn't connected to a `veecle-orchestrator`, for testing actors that use
+/// [`Input`](crate::Input) and [`Output`](crate::Output) without any sockets.
+///
+/// Pass [`MockConnector::connector`] to the actors, then inject inbound values with [`MockConnector::send`] and
+/// capture outbound values with [`MockConnector::recv`].
+/// Values are routed by type name and topic like with a real connection.
+///
+/// No tokio runtime is required, so this can be used with `veecle_os_test::execute!`, as long as no actor needs one
+/// itself (e.g. [`SendPolicy::Coalesce`](crate::SendPolicy::Coalesce) and [`Heartbeat`](crate::Heartbeat) use tokio
+/// timers).
+/// Telemetry, heartbeats and control requests are accepted but discarded, control requests are never answered.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::LazyLock;
+///
+/// use veecle_ipc::MockConnector;
+/// use veecle_os_runtime::single_writer::{Reader, Writer};
+/// use veecle_os_runtime::{Never, Storable};
+///
+/// #[derive(Clone, Copy, Debug, PartialEq, Storable, serde::Serialize, serde::Deserialize)]
+/// pub struct Ping(u8);
+///
+/// #[derive(Clone, Copy, Debug, PartialEq, Storable, serde::Serialize, serde::Deserialize)]
+/// pub struct Pong(u8);
+///
+/// #[veecle_os_runtime::actor]
+/// async fn pong_actor(mut ping: Reader<'_, Ping>, mut pong: Writer<'_, Pong>) -> Never {
+///     loop {
+///         let Ping(value) = ping.read_updated_cloned().await;
+///         pong.write(Pong(value + 1)).await;
+///     }
+/// }
+///
+/// // The validation can't capture local variables, so the mock is stored in a `static`.
+/// static MOCK: LazyLock<MockConnector> = LazyLock::new(MockConnector::new);
+///
+/// veecle_os_test::block_on_future(veecle_os_test::execute! {
+///     actors: [
+///         PongActor,
+///         veecle_ipc::Input<Ping>: MOCK.connector().into(),
+///         veecle_ipc::Output<Pong>: MOCK.connector().into(),
+///     ],
+///     validation: async || {
+///         MOCK.send(&Ping(1)).await;
+///         assert_eq!(MOCK.recv::<Pong>().await, Pong(2));
+///     },
+/// });
+/// ```
+#[derive(Debug)]
+pub struct MockConnector {
+    connector: Connector,
+    output_rx: Mutex<OutputRx>,
+    _control_response_tx: mpsc::Sender<ControlResponse>,
+}
+
+impl MockConnector {
+    /// Creates a new mock connector with a nil runtime id.
+    pub fn new() -> Self {
+        let (connector, output_rx, control_response_tx) = Connector::in_memory(Uuid::nil());
+
+        Self {
+            connector,
+            output_rx: Mutex::new(output_rx),
+            _control_response_tx: control_response_tx,
+        }
+    }
+
+    /// Returns the [`Connector`] to pass to the actors under test.
+    pub fn connector(&self) -> &Connector {
+        &self.connector
+    }
+
+    /// Sends `value` to the [`Input`](crate::Input) registered for its type, as if it came from another runtime.
+    ///
+    /// The value is tagged with the [`Storable::SCHEMA_VERSION`] of its type, like an [`Output`](crate::Output) does.
+    ///
+    /// # Panics
+    ///
+    /// If no [`Input`](crate::Input) is registered for the type of `value`.
+    pub async fn send<T>(&self, value: &T)
+    where
+        T: Storable + Serialize + 'static,
+    {
+        self.send_encoded(
+            EncodedStorable::new(value)
+                .unwrap()
+                .with_schema_version(T::SCHEMA_VERSION),
+        )
+        .await;
+    }
+
+    /// Sends an encoded value to the [`Input`](crate::Input) registered for its type and topic.
+    ///
+    /// # Panics
+    ///
+    /// If no [`Input`](crate::Input) is registered for the type and topic of `storable`.
+    pub async fn send_encoded(&self, storable: EncodedStorable) {
+        let routing_key = storable.routing_key();
+        let Some(sender) = self.connector.storable_input_sender(&routing_key) else {
+            panic!("no `Input` registered for {routing_key}");
+        };
+        sender
+            .send(storable)
+            .await
+            .expect("the `Input` actor should be running");
+    }
+
+    /// Waits for the next value sent by an [`Output`](crate::Output) and decodes it.
+    ///
+    /// # Panics
+    ///
+    /// If the next value is not of type `T` or cannot be decoded.
+    pub async fn recv<T>(&self) -> T
+    where
+        T: DeserializeOwned + 'static,
+    {
+        let storable = self.recv_encoded().await;
+        assert_eq!(
+            storable.type_name,
+            std::any::type_name::<T>(),
+            "unexpected type of sent value"
+        );
+        serde_json::from_str(&storable.value).unwrap()
+    }
+
+    /// Waits for the next value sent by an [`Output`](crate::Output).
+    pub async fn recv_encoded(&self) -> EncodedStorable {
+        self.output_rx
+            .lock()
+            .await
+            .storable
+            .recv()
+            .await
+            .expect("the connector keeps the sender alive")
+    }
+
+    /// Returns the next value sent by an [`Output`](crate::Output) if one is available.
+    pub fn try_recv_encoded(&self) -> Option<EncodedStorable> {
+        self.output_rx.try_lock().ok()?.storable.try_recv().ok()
+    }
+}
+
+impl Default for MockConnector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,5 +1,7 @@
 //! Policies for handling IPC message sending when the channel is full.
 
+use std::time::Duration;
+
 /// Policy for handling messages when the IPC output channel is full.
 ///
 /// The default is [`Panic`](SendPolicy::Panic) to make buffer exhaustion immediately visible
@@ -12,6 +14,15 @@ pub enum SendPolicy {
 
     /// Panic when the output channel is full.
     Panic,
+
+    /// Send at most one value per `interval`, coalescing the updates in between so only the latest value is sent.
+    ///
+    /// Use this for values written faster than consumers need them, e.g. high-frequency sensor values.
+    /// Messages are dropped with a warning when the output channel is full, like with [`Drop`](SendPolicy::Drop).
+    Coalesce {
+        /// The minimum time between two sent values.
+        interval: Duration,
+    },
 }
 
 impl Default for SendPolicy {
@@ -0,0 +1,71 @@
+//! Tests for `SendPolicy::Coalesce` with the `Output` actor.
+
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+use std::time::Duration;
+
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use veecle_ipc::{Connector, SendPolicy};
+use veecle_ipc_protocol::Message;
+use veecle_os_runtime::single_writer::Writer;
+use veecle_os_runtime::{Never, Storable};
+
+pub mod common;
+
+const INTERVAL: Duration = Duration::from_millis(200);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Storable, Serialize, Deserialize)]
+struct Sensor(u32);
+
+#[veecle_os_runtime::actor]
+async fn sensor_writer(mut writer: Writer<'_, Sensor>) -> Never {
+    for value in 0..100 {
+        writer.write(Sensor(value)).await;
+        tokio::task::yield_now().await;
+    }
+    core::future::pending().await
+}
+
+/// Test that rapid updates are coalesced into the first and the latest value.
+///
+/// The clock is paused, so it only advances once the writer is done and every task is idle.
+#[tokio::test(start_paused = true)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+async fn coalesces_rapid_updates() {
+    let directory = tempfile::tempdir().unwrap();
+    let listener = common::listen(directory.path());
+
+    let (connector, mut stream) = tokio::join!(Connector::connect(), common::accept(&listener));
+    let connector: &'static Connector = Box::leak(Box::new(connector));
+
+    let runtime = veecle_os_runtime::execute! {
+        actors: [
+            SensorWriter,
+            veecle_ipc::Output<Sensor>: (connector, SendPolicy::Coalesce { interval: INTERVAL }).into(),
+        ],
+    };
+
+    let received = async {
+        let mut received = Vec::new();
+        let deadline = tokio::time::sleep(INTERVAL * 3);
+        tokio::pin!(deadline);
+        loop {
+            tokio::select! {
+                _ = &mut deadline => return received,
+                message = stream.next() => {
+                    if let Some(Ok(Message::Storable(storable))) = message {
+                        received.push(serde_json::from_str::<Sensor>(&storable.value).unwrap());
+                    }
+                }
+            }
+        }
+    };
+
+    let received = tokio::select! {
+        received = received => received,
+        _ = runtime => unreachable!(),
+    };
+
+    assert_eq!(received, [Sensor(0), Sensor(99)]);
+}
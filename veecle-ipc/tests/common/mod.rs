@@ -0,0 +1,48 @@
+//! Helpers to fake the orchestrator side of the IPC connection in tests.
+
+use std::path::Path;
+
+use futures::{SinkExt, StreamExt};
+use tokio::net::{UnixListener, UnixStream};
+use tokio_util::codec::Framed;
+use veecle_ipc_protocol::{Codec, Message, PROTOCOL_VERSION};
+
+/// Binds a socket in `directory` and points the `Connector` of this test binary at it.
+///
+/// Must only be called by the single test of a binary, as it modifies the environment.
+pub fn listen(directory: &Path) -> UnixListener {
+    let socket = directory.join("ipc.sock");
+    let listener = UnixListener::bind(&socket).unwrap();
+
+    // SAFETY: The caller is the only test in this binary, so no other thread accesses the environment.
+    unsafe {
+        std::env::set_var("VEECLE_IPC_SOCKET", &socket);
+        std::env::set_var("VEECLE_RUNTIME_ID", veecle_ipc::Uuid::nil().to_string());
+    }
+
+    listener
+}
+
+/// Accepts a connection and answers its handshake.
+pub async fn accept(listener: &UnixListener) -> Framed<UnixStream, Codec> {
+    accept_with_hello(listener, &Message::hello()).await
+}
+
+/// Accepts a connection and answers its handshake with `hello`.
+pub async fn accept_with_hello(
+    listener: &UnixListener,
+    hello: &Message,
+) -> Framed<UnixStream, Codec> {
+    let (stream, _address) = listener.accept().await.unwrap();
+    let mut stream = Framed::new(stream, Codec::new());
+    let Some(Ok(Message::Hello {
+        protocol_version, ..
+    })) = stream.next().await
+    else {
+        panic!("connector should start with a handshake");
+    };
+    assert_eq!(protocol_version, PROTOCOL_VERSION);
+
+    stream.send(hello).await.unwrap();
+    stream
+}
@@ -0,0 +1,28 @@
+//! Tests for the `Connector` handshake.
+
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+use veecle_ipc::Connector;
+use veecle_ipc_protocol::{Message, PROTOCOL_VERSION};
+
+pub mod common;
+
+/// Test that the connector refuses an orchestrator using another protocol version.
+#[tokio::test]
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[should_panic(expected = "incompatible with protocol version")]
+async fn rejects_incompatible_version() {
+    let directory = tempfile::tempdir().unwrap();
+    let listener = common::listen(directory.path());
+
+    let orchestrator = tokio::spawn(async move {
+        let hello = Message::Hello {
+            protocol_version: PROTOCOL_VERSION + 1,
+            features: Vec::new(),
+        };
+        common::accept_with_hello(&listener, &hello).await
+    });
+
+    let _connector = Connector::connect().await;
+    drop(orchestrator);
+}
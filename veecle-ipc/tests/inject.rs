@@ -0,0 +1,44 @@
+//! Tests for `Injector`.
+
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+use veecle_ipc::Injector;
+use veecle_os_runtime::Storable;
+use veecle_os_runtime::single_writer::Reader;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Storable)]
+struct Command(u32);
+
+/// Test that values written from another thread reach the readers in order.
+#[test]
+#[cfg_attr(coverage_nightly, coverage(off))]
+fn writes_from_other_thread() {
+    let (injector, receiver) = Injector::<Command>::channel(1);
+
+    let thread = std::thread::spawn(move || {
+        injector.blocking_write(Command(1)).unwrap();
+        injector.blocking_write(Command(2)).unwrap();
+    });
+
+    veecle_os_test::block_on_future(veecle_os_test::execute! {
+        actors: [
+            veecle_ipc::Inject<Command>: receiver,
+        ],
+        validation: async |mut reader: Reader<'_, Command>| {
+            assert_eq!(reader.read_updated_cloned().await, Command(1));
+            assert_eq!(reader.read_updated_cloned().await, Command(2));
+        },
+    });
+
+    thread.join().unwrap();
+}
+
+/// Test that writing fails once the `Inject` actor is gone.
+#[test]
+#[cfg_attr(coverage_nightly, coverage(off))]
+fn write_without_receiver() {
+    let (injector, receiver) = Injector::<Command>::channel(1);
+    drop(receiver);
+
+    assert!(injector.try_write(Command(1)).is_err());
+}
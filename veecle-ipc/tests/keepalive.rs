@@ -0,0 +1,55 @@
+//! Tests for the `Connector` keepalive.
+
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+use std::time::Duration;
+
+use futures::{SinkExt, StreamExt};
+use tokio::net::{UnixListener, UnixStream};
+use tokio_util::codec::Framed;
+use veecle_ipc::{Connector, ConnectorConfig};
+use veecle_ipc_protocol::{Codec, Message};
+
+pub mod common;
+
+const INTERVAL: Duration = Duration::from_millis(20);
+const TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Accepts a connection and answers its handshake, failing if the connector doesn't connect in time.
+async fn accept(listener: &UnixListener) -> Framed<UnixStream, Codec> {
+    tokio::time::timeout(TIMEOUT * 5, common::accept(listener))
+        .await
+        .expect("connector should connect")
+}
+
+/// Test that the connector reconnects when its keepalives go unanswered, and keeps a working connection.
+#[tokio::test]
+#[cfg_attr(coverage_nightly, coverage(off))]
+async fn reconnects_stalled_connection() {
+    let directory = tempfile::tempdir().unwrap();
+    let listener = common::listen(directory.path());
+
+    let config = ConnectorConfig::default().keepalive(INTERVAL, TIMEOUT);
+    let (_connector, mut stalled) =
+        tokio::join!(Connector::connect_with_config(config), accept(&listener));
+
+    // Keep the first connection open without answering, like a half-open connection.
+    assert!(matches!(stalled.next().await, Some(Ok(Message::Keepalive))));
+
+    // Answer keepalives on the new connection.
+    let mut connection = accept(&listener).await;
+    let answer = tokio::spawn(async move {
+        while let Some(Ok(message)) = connection.next().await {
+            if let Message::Keepalive = message {
+                connection.send(&Message::Keepalive).await.unwrap();
+            }
+        }
+    });
+
+    // A healthy connection is kept.
+    let reconnect = tokio::time::timeout(TIMEOUT * 3, listener.accept()).await;
+    assert!(reconnect.is_err(), "connector should not reconnect");
+
+    answer.abort();
+    drop(stalled);
+}
@@ -0,0 +1,39 @@
+//! Tests for the `Connector` keepalive with an orchestrator that doesn't support it.
+
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+use std::time::Duration;
+
+use futures::StreamExt;
+use veecle_ipc::{Connector, ConnectorConfig};
+use veecle_ipc_protocol::{Message, PROTOCOL_VERSION};
+
+pub mod common;
+
+const INTERVAL: Duration = Duration::from_millis(20);
+const TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Test that the connector neither sends keepalives nor reconnects if the orchestrator doesn't advertise the
+/// `keepalive` feature.
+#[tokio::test]
+#[cfg_attr(coverage_nightly, coverage(off))]
+async fn skips_keepalive_without_feature() {
+    let directory = tempfile::tempdir().unwrap();
+    let listener = common::listen(directory.path());
+
+    let hello = Message::Hello {
+        protocol_version: PROTOCOL_VERSION,
+        features: Vec::new(),
+    };
+    let config = ConnectorConfig::default().keepalive(INTERVAL, TIMEOUT);
+    let (_connector, mut connection) = tokio::join!(
+        Connector::connect_with_config(config),
+        common::accept_with_hello(&listener, &hello)
+    );
+
+    let message = tokio::time::timeout(TIMEOUT * 3, connection.next()).await;
+    assert!(message.is_err(), "connector should not send keepalives");
+
+    let reconnect = tokio::time::timeout(TIMEOUT * 3, listener.accept()).await;
+    assert!(reconnect.is_err(), "connector should not reconnect");
+}
@@ -0,0 +1,103 @@
+//! Tests for `MockConnector`.
+
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+use std::sync::LazyLock;
+
+use serde::{Deserialize, Serialize};
+use veecle_ipc::{InputConfig, MockConnector, OutputConfig};
+use veecle_ipc_protocol::EncodedStorable;
+use veecle_os_runtime::single_writer::{Reader, Writer};
+use veecle_os_runtime::{Never, Storable};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Storable, Serialize, Deserialize)]
+struct Scan(u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Storable, Serialize, Deserialize)]
+struct Echo(u32);
+
+#[veecle_os_runtime::actor]
+async fn echo_actor(mut scan: Reader<'_, Scan>, mut echo: Writer<'_, Echo>) -> Never {
+    loop {
+        let Scan(value) = scan.read_updated_cloned().await;
+        echo.write(Echo(value)).await;
+    }
+}
+
+/// Test that values are routed by topic through the mock.
+#[test]
+#[cfg_attr(coverage_nightly, coverage(off))]
+fn routes_by_topic() {
+    static MOCK: LazyLock<MockConnector> = LazyLock::new(MockConnector::new);
+
+    veecle_os_test::block_on_future(veecle_os_test::execute! {
+        actors: [
+            veecle_ipc::Input<Scan>: InputConfig::new(MOCK.connector()).with_topic("front"),
+            EchoActor,
+            veecle_ipc::Output<Echo>: OutputConfig::from(MOCK.connector()).with_topic("echo"),
+        ],
+        validation: async || {
+            assert!(MOCK.try_recv_encoded().is_none());
+
+            MOCK.send_encoded(EncodedStorable::new(&Scan(1)).unwrap().with_topic("front")).await;
+
+            let storable = MOCK.recv_encoded().await;
+            assert_eq!(storable.topic.as_deref(), Some("echo"));
+            assert_eq!(serde_json::from_str::<Echo>(&storable.value).unwrap(), Echo(1));
+            assert!(MOCK.try_recv_encoded().is_none());
+        },
+    });
+}
+
+/// Test that sending a value without a registered input panics.
+#[test]
+#[should_panic(expected = "no `Input` registered")]
+#[cfg_attr(coverage_nightly, coverage(off))]
+fn send_without_input() {
+    let mock = MockConnector::new();
+    veecle_os_test::block_on_future(mock.send(&Scan(1)));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Storable, Serialize, Deserialize)]
+#[storable(schema_version = 2)]
+struct VersionedScan(u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Storable, Serialize, Deserialize)]
+#[storable(schema_version = 2)]
+struct VersionedEcho(u32);
+
+#[veecle_os_runtime::actor]
+async fn versioned_echo_actor(
+    mut scan: Reader<'_, VersionedScan>,
+    mut echo: Writer<'_, VersionedEcho>,
+) -> Never {
+    loop {
+        let VersionedScan(value) = scan.read_updated_cloned().await;
+        echo.write(VersionedEcho(value)).await;
+    }
+}
+
+/// Test that outputs tag values with their schema version and inputs drop values with another one.
+#[test]
+#[cfg_attr(coverage_nightly, coverage(off))]
+fn checks_schema_version() {
+    static MOCK: LazyLock<MockConnector> = LazyLock::new(MockConnector::new);
+
+    veecle_os_test::block_on_future(veecle_os_test::execute! {
+        actors: [
+            veecle_ipc::Input<VersionedScan>: InputConfig::from(MOCK.connector()),
+            VersionedEchoActor,
+            veecle_ipc::Output<VersionedEcho>: OutputConfig::from(MOCK.connector()),
+        ],
+        validation: async || {
+            // Encoded without a schema version, so it has version `0` and is dropped.
+            MOCK.send_encoded(EncodedStorable::new(&VersionedScan(1)).unwrap()).await;
+            MOCK.send(&VersionedScan(2)).await;
+
+            let storable = MOCK.recv_encoded().await;
+            assert_eq!(storable.schema_version, 2);
+            assert_eq!(serde_json::from_str::<VersionedEcho>(&storable.value).unwrap(), VersionedEcho(2));
+            assert!(MOCK.try_recv_encoded().is_none());
+        },
+    });
+}
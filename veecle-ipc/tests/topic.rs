@@ -0,0 +1,75 @@
+//! Tests for routing IPC values by topic.
+
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use veecle_ipc::{Connector, InputConfig, OutputConfig};
+use veecle_ipc_protocol::{EncodedStorable, Message};
+use veecle_os_runtime::single_writer::{Reader, Writer};
+use veecle_os_runtime::{Never, Storable};
+
+pub mod common;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Storable, Serialize, Deserialize)]
+struct Scan(u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Storable, Serialize, Deserialize)]
+struct Echo(u32);
+
+#[veecle_os_runtime::actor]
+async fn echo_actor(mut scan: Reader<'_, Scan>, mut echo: Writer<'_, Echo>) -> Never {
+    loop {
+        let Scan(value) = scan.read_updated_cloned().await;
+        echo.write(Echo(value)).await;
+    }
+}
+
+/// Test that inputs only receive values with their topic and outputs tag values with theirs.
+#[tokio::test]
+#[cfg_attr(coverage_nightly, coverage(off))]
+async fn routes_by_topic() {
+    let directory = tempfile::tempdir().unwrap();
+    let listener = common::listen(directory.path());
+
+    let (connector, mut stream) = tokio::join!(Connector::connect(), common::accept(&listener));
+    let connector: &'static Connector = Box::leak(Box::new(connector));
+
+    let runtime = veecle_os_runtime::execute! {
+        actors: [
+            veecle_ipc::Input<Scan>: InputConfig::new(connector).with_topic("front"),
+            EchoActor,
+            veecle_ipc::Output<Echo>: OutputConfig::from(connector).with_topic("echo"),
+        ],
+    };
+
+    let exchange = async {
+        for (topic, value) in [(None, 1), (Some("rear"), 2), (Some("front"), 3)] {
+            let mut storable = EncodedStorable::new(&Scan(value)).unwrap();
+            if let Some(topic) = topic {
+                storable = storable.with_topic(topic);
+            }
+            stream.send(&Message::Storable(storable)).await.unwrap();
+        }
+
+        loop {
+            if let Some(Ok(Message::Storable(storable))) = stream.next().await {
+                return storable;
+            }
+        }
+    };
+
+    let storable = tokio::select! {
+        storable = exchange => storable,
+        _ = runtime => unreachable!(),
+    };
+
+    assert_eq!(
+        storable.routing_key(),
+        format!("{}#echo", std::any::type_name::<Echo>())
+    );
+    assert_eq!(
+        serde_json::from_str::<Echo>(&storable.value).unwrap(),
+        Echo(3)
+    );
+}
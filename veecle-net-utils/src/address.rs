@@ -227,6 +227,117 @@ impl FromStr for UnresolvedMultiSocketAddress {
     }
 }
 
+/// A non-empty list of [`UnresolvedMultiSocketAddress`]es, e.g. redundant endpoints of a service.
+///
+/// Parses from a list separated by commas and/or whitespace, e.g. `a.example:80, b.example:80 /tmp/c.sock`.
+/// A single trailing comma is allowed, other empty entries are rejected.
+/// Because of the whitespace separator, Unix socket paths in the list cannot contain whitespace.
+#[derive(Debug, Clone)]
+pub struct UnresolvedMultiSocketAddressList(Vec<UnresolvedMultiSocketAddress>);
+
+impl UnresolvedMultiSocketAddressList {
+    /// Returns the addresses in the order they were listed.
+    pub fn as_slice(&self) -> &[UnresolvedMultiSocketAddress] {
+        &self.0
+    }
+}
+
+impl<'a> IntoIterator for &'a UnresolvedMultiSocketAddressList {
+    type Item = &'a UnresolvedMultiSocketAddress;
+    type IntoIter = std::slice::Iter<'a, UnresolvedMultiSocketAddress>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl IntoIterator for UnresolvedMultiSocketAddressList {
+    type Item = UnresolvedMultiSocketAddress;
+    type IntoIter = std::vec::IntoIter<UnresolvedMultiSocketAddress>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl Display for UnresolvedMultiSocketAddressList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (index, address) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            address.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Errors that can occur when parsing a list of socket addresses.
+#[derive(thiserror::Error, Debug)]
+pub enum UnresolvedMultiSocketAddressListParseError {
+    /// The list contains no addresses.
+    #[error("no addresses")]
+    Empty,
+
+    /// The list contains an empty entry, e.g. from two consecutive commas.
+    #[error("address {index} is empty")]
+    EmptyEntry {
+        /// The index of the empty entry within the list.
+        index: usize,
+    },
+
+    /// An entry of the list is not a valid address.
+    #[error("address {index} ({entry:?}) is invalid")]
+    Invalid {
+        /// The index of the invalid entry within the list.
+        index: usize,
+        /// The invalid entry.
+        entry: String,
+        /// Why the entry is invalid.
+        #[source]
+        source: UnresolvedMultiSocketAddressParseError,
+    },
+}
+
+impl FromStr for UnresolvedMultiSocketAddressList {
+    type Err = UnresolvedMultiSocketAddressListParseError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let mut addresses = Vec::new();
+
+        let mut segments = string.split(',').peekable();
+        while let Some(segment) = segments.next() {
+            let mut entries = segment.split_whitespace().peekable();
+
+            if entries.peek().is_none() {
+                // Allow a single trailing comma after at least one address.
+                if segments.peek().is_none() && !addresses.is_empty() {
+                    break;
+                }
+                if segments.peek().is_none() && string.trim().is_empty() {
+                    return Err(UnresolvedMultiSocketAddressListParseError::Empty);
+                }
+                return Err(UnresolvedMultiSocketAddressListParseError::EmptyEntry {
+                    index: addresses.len(),
+                });
+            }
+
+            for entry in entries {
+                let address = UnresolvedMultiSocketAddress::from_str(entry).map_err(|source| {
+                    UnresolvedMultiSocketAddressListParseError::Invalid {
+                        index: addresses.len(),
+                        entry: entry.to_owned(),
+                        source,
+                    }
+                })?;
+                addresses.push(address);
+            }
+        }
+
+        Ok(Self(addresses))
+    }
+}
+
 /// Errors that can occur when converting a [`MultiSocketAddress`] to an
 /// [`UnresolvedMultiSocketAddress`].
 #[derive(thiserror::Error, Debug)]
@@ -266,7 +377,9 @@ impl TryFrom<MultiSocketAddress> for UnresolvedMultiSocketAddress {
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
     use super::{
-        UnresolvedMultiSocketAddress, UnresolvedSocketAddress, UnresolvedSocketAddressParseError,
+        UnresolvedMultiSocketAddress, UnresolvedMultiSocketAddressList,
+        UnresolvedMultiSocketAddressListParseError, UnresolvedSocketAddress,
+        UnresolvedSocketAddressParseError,
     };
     use std::any::type_name;
     use std::net::{SocketAddr, ToSocketAddrs};
@@ -399,4 +512,86 @@ mod tests {
         assert!(UnresolvedMultiSocketAddress::from_str("relative/path").is_err());
         assert!(UnresolvedMultiSocketAddress::from_str("just-a-filename").is_err());
     }
+
+    #[test]
+    fn unresolved_multi_socket_address_list_parsing() {
+        let list = UnresolvedMultiSocketAddressList::parse(
+            "a.example:80, 127.0.0.1:80,[::1]:80 /tmp/test.sock\tb.example.:8080",
+        );
+        assert_eq!(
+            list.to_string(),
+            "a.example:80, 127.0.0.1:80, [::1]:80, /tmp/test.sock, b.example.:8080"
+        );
+        assert!(matches!(
+            list.as_slice(),
+            [
+                UnresolvedMultiSocketAddress::Tcp(_),
+                UnresolvedMultiSocketAddress::Tcp(_),
+                UnresolvedMultiSocketAddress::Tcp(_),
+                UnresolvedMultiSocketAddress::Unix(_),
+                UnresolvedMultiSocketAddress::Tcp(_),
+            ]
+        ));
+
+        // A single address.
+        assert_eq!(
+            UnresolvedMultiSocketAddressList::parse("localhost:80")
+                .as_slice()
+                .len(),
+            1
+        );
+
+        // Trailing commas:
+        assert_eq!(
+            UnresolvedMultiSocketAddressList::parse("a.example:80, b.example:80,")
+                .as_slice()
+                .len(),
+            2
+        );
+        assert_eq!(
+            UnresolvedMultiSocketAddressList::parse("a.example:80 , ")
+                .as_slice()
+                .len(),
+            1
+        );
+        assert!(matches!(
+            UnresolvedMultiSocketAddressList::from_str("a.example:80,,"),
+            Err(UnresolvedMultiSocketAddressListParseError::EmptyEntry { index: 1 }),
+        ));
+
+        // Empty entries:
+        assert!(matches!(
+            UnresolvedMultiSocketAddressList::from_str(""),
+            Err(UnresolvedMultiSocketAddressListParseError::Empty),
+        ));
+        assert!(matches!(
+            UnresolvedMultiSocketAddressList::from_str("  "),
+            Err(UnresolvedMultiSocketAddressListParseError::Empty),
+        ));
+        assert!(matches!(
+            UnresolvedMultiSocketAddressList::from_str(","),
+            Err(UnresolvedMultiSocketAddressListParseError::EmptyEntry { index: 0 }),
+        ));
+        assert!(matches!(
+            UnresolvedMultiSocketAddressList::from_str(", a.example:80"),
+            Err(UnresolvedMultiSocketAddressListParseError::EmptyEntry { index: 0 }),
+        ));
+        assert!(matches!(
+            UnresolvedMultiSocketAddressList::from_str("a.example:80, ,b.example:80"),
+            Err(UnresolvedMultiSocketAddressListParseError::EmptyEntry { index: 1 }),
+        ));
+
+        // Invalid entries point at their index:
+        let error =
+            UnresolvedMultiSocketAddressList::from_str("a.example:80 127.0.0.1:80, relative/path")
+                .unwrap_err();
+        assert!(matches!(
+            &error,
+            UnresolvedMultiSocketAddressListParseError::Invalid { index: 2, entry, .. } if entry == "relative/path",
+        ));
+        assert_eq!(
+            error.to_string(),
+            "address 2 (\"relative/path\") is invalid"
+        );
+    }
 }
@@ -8,7 +8,7 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use camino::{Utf8Path, Utf8PathBuf};
-use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt, ReadBuf};
 use tokio::net::{TcpListener, TcpStream, ToSocketAddrs, UnixListener, UnixStream};
 
 use crate::{MultiSocketAddress, UnresolvedMultiSocketAddress, UnresolvedSocketAddress};
@@ -143,6 +143,20 @@ impl AsyncSocketListener {
     }
 }
 
+impl AsyncSocketStream {
+    /// Shuts down the write half of the stream, while the read half stays open.
+    ///
+    /// The peer reads end-of-file after any data written before, but can still send a response, e.g. to signal the
+    /// end of a request of unknown length.
+    /// For TCP this sends a FIN, for Unix domain sockets it is `shutdown(SHUT_WR)`.
+    /// Writing after this returns an error.
+    ///
+    /// This is the same as [`AsyncWriteExt::shutdown`], which for these sockets also only closes the write half.
+    pub async fn shutdown_write(&mut self) -> Result<()> {
+        AsyncWriteExt::shutdown(self).await
+    }
+}
+
 impl AsyncRead for AsyncSocketStream {
     fn poll_read(
         mut self: Pin<&mut Self>,
@@ -264,4 +278,37 @@ mod tests {
 
         server.await.unwrap();
     }
+
+    #[tokio::test]
+    async fn async_socket_stream_shutdown_write() {
+        let listener = UnresolvedMultiSocketAddress::from_str("127.0.0.1:0")
+            .unwrap()
+            .bind_async()
+            .await
+            .unwrap();
+        let address =
+            UnresolvedMultiSocketAddress::try_from(listener.local_address().unwrap()).unwrap();
+
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            stream.read_to_end(&mut request).await.unwrap();
+            assert_eq!(request, b"request of unknown length");
+            stream.write_all(b"response").await.unwrap();
+        });
+
+        let mut stream = address.connect_async().await.unwrap();
+        stream
+            .write_all(b"request of unknown length")
+            .await
+            .unwrap();
+        stream.shutdown_write().await.unwrap();
+        assert!(stream.write_all(b"more").await.is_err());
+
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).await.unwrap();
+        assert_eq!(response, b"response");
+
+        server.await.unwrap();
+    }
 }
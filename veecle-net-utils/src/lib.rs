@@ -6,6 +6,7 @@
 //! # Features
 //!
 //! - `tokio`: Enable async networking support (requires Tokio). Default: disabled.
+//! - `tls`: Enable TLS encrypted async streams using rustls (implies `tokio`). Default: disabled.
 
 #![forbid(unsafe_code)]
 #![cfg_attr(coverage_nightly, feature(coverage_attribute))]
@@ -14,13 +15,18 @@ mod address;
 #[cfg(feature = "tokio")]
 mod async_io;
 mod blocking;
+mod stats;
+#[cfg(feature = "tls")]
+pub mod tls;
 
 pub use address::{
-    MultiSocketAddress, UnresolvedMultiSocketAddress, UnresolvedMultiSocketAddressParseError,
+    MultiSocketAddress, UnresolvedMultiSocketAddress, UnresolvedMultiSocketAddressList,
+    UnresolvedMultiSocketAddressListParseError, UnresolvedMultiSocketAddressParseError,
     UnresolvedSocketAddress, UnresolvedSocketAddressParseError,
 };
 
 pub use blocking::BlockingSocketStream;
+pub use stats::{CountingStream, StreamCounters, StreamStats};
 
 #[cfg(feature = "tokio")]
 pub use async_io::{AsyncSocketListener, AsyncSocketStream, AsyncUnixListener};
@@ -0,0 +1,207 @@
+//! Per-connection byte counters.
+//!
+//! [`CountingStream`] wraps any blocking or async stream, e.g. a [`BlockingSocketStream`](crate::BlockingSocketStream)
+//! or an `AsyncSocketStream`, and counts the bytes read from and written to it.
+
+use std::io::{Read, Result, Write};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// A snapshot of the counters of a [`CountingStream`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StreamStats {
+    /// Total bytes read from the stream.
+    pub bytes_read: u64,
+
+    /// Total bytes written to the stream.
+    pub bytes_written: u64,
+}
+
+/// The live counters of a [`CountingStream`], shared so they can be observed while the stream is owned elsewhere.
+#[derive(Debug, Default)]
+pub struct StreamCounters {
+    bytes_read: AtomicU64,
+    bytes_written: AtomicU64,
+}
+
+impl StreamCounters {
+    /// Returns the current values of the counters.
+    pub fn stats(&self) -> StreamStats {
+        StreamStats {
+            bytes_read: self.bytes_read.load(Ordering::Relaxed),
+            bytes_written: self.bytes_written.load(Ordering::Relaxed),
+        }
+    }
+
+    fn add_read(&self, bytes: usize) {
+        self.bytes_read.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    fn add_written(&self, bytes: usize) {
+        self.bytes_written
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+}
+
+/// Wraps a stream, counting the bytes successfully read from and written to it.
+///
+/// Counting is a single relaxed atomic increment per read or write call.
+#[derive(Debug)]
+pub struct CountingStream<S> {
+    inner: S,
+    counters: Arc<StreamCounters>,
+}
+
+impl<S> CountingStream<S> {
+    /// Wraps `inner` with counters starting at zero.
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            counters: Arc::default(),
+        }
+    }
+
+    /// Returns the current values of the counters.
+    pub fn stats(&self) -> StreamStats {
+        self.counters.stats()
+    }
+
+    /// Returns the shared counters, to observe them after the stream is moved, e.g. into a task.
+    pub fn counters(&self) -> Arc<StreamCounters> {
+        self.counters.clone()
+    }
+
+    /// Returns a reference to the wrapped stream.
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    /// Returns the wrapped stream, discarding the counters.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: Read> Read for CountingStream<S> {
+    fn read(&mut self, buffer: &mut [u8]) -> Result<usize> {
+        let bytes = self.inner.read(buffer)?;
+        self.counters.add_read(bytes);
+        Ok(bytes)
+    }
+}
+
+impl<S: Write> Write for CountingStream<S> {
+    fn write(&mut self, buffer: &[u8]) -> Result<usize> {
+        let bytes = self.inner.write(buffer)?;
+        self.counters.add_written(bytes);
+        Ok(bytes)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(feature = "tokio")]
+mod async_io {
+    use std::io::Result;
+    use std::pin::Pin;
+    use std::task::{Context, Poll, ready};
+
+    use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+    use super::CountingStream;
+
+    impl<S: AsyncRead + Unpin> AsyncRead for CountingStream<S> {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            context: &mut Context<'_>,
+            buffer: &mut ReadBuf<'_>,
+        ) -> Poll<Result<()>> {
+            let filled = buffer.filled().len();
+            ready!(Pin::new(&mut self.inner).poll_read(context, buffer))?;
+            self.counters.add_read(buffer.filled().len() - filled);
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    impl<S: AsyncWrite + Unpin> AsyncWrite for CountingStream<S> {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            context: &mut Context<'_>,
+            buffer: &[u8],
+        ) -> Poll<Result<usize>> {
+            let bytes = ready!(Pin::new(&mut self.inner).poll_write(context, buffer))?;
+            self.counters.add_written(bytes);
+            Poll::Ready(Ok(bytes))
+        }
+
+        fn poll_flush(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Result<()>> {
+            Pin::new(&mut self.inner).poll_flush(context)
+        }
+
+        fn poll_shutdown(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Result<()>> {
+            Pin::new(&mut self.inner).poll_shutdown(context)
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::io::{Cursor, Read, Write};
+
+    use super::{CountingStream, StreamStats};
+
+    #[test]
+    fn counting_stream_blocking() {
+        let mut stream = CountingStream::new(Cursor::new(b"hello world".to_vec()));
+        let counters = stream.counters();
+
+        let mut buffer = [0u8; 5];
+        stream.read_exact(&mut buffer).unwrap();
+        assert_eq!(
+            stream.stats(),
+            StreamStats {
+                bytes_read: 5,
+                bytes_written: 0
+            }
+        );
+
+        stream.write_all(b"there").unwrap();
+        stream.read_to_end(&mut Vec::new()).unwrap();
+        assert_eq!(
+            counters.stats(),
+            StreamStats {
+                bytes_read: 6,
+                bytes_written: 5
+            }
+        );
+        assert_eq!(stream.into_inner().into_inner(), b"hellothered");
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn counting_stream_async() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let (client, server) = tokio::io::duplex(64);
+        let mut client = CountingStream::new(client);
+        let mut server = CountingStream::new(server);
+
+        client.write_all(b"hello").await.unwrap();
+        let mut buffer = [0u8; 5];
+        server.read_exact(&mut buffer).await.unwrap();
+        server.write_all(b"hi").await.unwrap();
+        drop(server);
+        client.read_to_end(&mut Vec::new()).await.unwrap();
+
+        assert_eq!(
+            client.stats(),
+            StreamStats {
+                bytes_read: 2,
+                bytes_written: 5
+            }
+        );
+    }
+}
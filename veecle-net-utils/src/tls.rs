@@ -0,0 +1,406 @@
+//! TLS encrypted async socket streams.
+//!
+//! Wraps an [`AsyncSocketStream`] with [`rustls`], using the `ring` crypto provider.
+//!
+//! # Certificate verification
+//!
+//! Clients always verify the server certificate chain against the configured root certificates, and that the
+//! certificate is valid for the server name passed to [`TlsSocketStream::connect`].
+//! The server name can be a DNS name or an IP address, to connect to an IP address the certificate needs a matching
+//! IP address subject alternative name.
+//! There is no way to disable verification, use a private certificate authority for test setups instead.
+//!
+//! Servers only request client certificates if they are configured with client root certificates (mutual TLS), in
+//! which case connecting clients must present a certificate chain valid for those roots.
+
+use std::io::Result;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use rustls_pki_types::pem::PemObject;
+use rustls_pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio_rustls::rustls::server::{VerifierBuilderError, WebPkiClientVerifier};
+use tokio_rustls::rustls::{ClientConfig, RootCertStore, ServerConfig};
+use tokio_rustls::{TlsAcceptor, TlsConnector, TlsStream};
+
+use crate::AsyncSocketStream;
+
+/// The rustls version used, e.g. to build custom configurations for [`TlsClientConfig`] and [`TlsServerConfig`].
+pub use tokio_rustls::rustls;
+
+/// Errors that can occur when building a TLS configuration.
+#[derive(thiserror::Error, Debug)]
+pub enum TlsConfigError {
+    /// The PEM data could not be parsed.
+    #[error("invalid PEM data")]
+    Pem(#[from] rustls_pki_types::pem::Error),
+
+    /// The PEM data did not contain any certificates.
+    #[error("no certificates found in PEM data")]
+    NoCertificates,
+
+    /// rustls rejected the certificates, key or protocol configuration.
+    #[error("invalid TLS configuration")]
+    Rustls(#[from] rustls::Error),
+
+    /// The client root certificates could not be used to verify clients.
+    #[error("invalid client root certificates")]
+    ClientVerifier(#[from] VerifierBuilderError),
+}
+
+/// A certificate chain and its private key, identifying one side of a TLS connection.
+#[derive(Debug)]
+pub struct TlsIdentity {
+    certificate_chain: Vec<CertificateDer<'static>>,
+    key: PrivateKeyDer<'static>,
+}
+
+impl TlsIdentity {
+    /// Parses an identity from a PEM encoded certificate chain (leaf first) and a PEM encoded private key.
+    pub fn from_pem(
+        certificate_chain: &[u8],
+        key: &[u8],
+    ) -> std::result::Result<Self, TlsConfigError> {
+        Ok(Self {
+            certificate_chain: parse_certificates(certificate_chain)?,
+            key: PrivateKeyDer::from_pem_slice(key)?,
+        })
+    }
+}
+
+/// Client side configuration for [`TlsSocketStream::connect`].
+#[derive(Debug, Clone)]
+pub struct TlsClientConfig(Arc<ClientConfig>);
+
+impl TlsClientConfig {
+    /// Creates a configuration trusting the PEM encoded `root_certificates`.
+    ///
+    /// If `identity` is set, it is presented to servers requesting a client certificate.
+    pub fn new(
+        root_certificates: &[u8],
+        identity: Option<TlsIdentity>,
+    ) -> std::result::Result<Self, TlsConfigError> {
+        let builder = ClientConfig::builder_with_provider(provider())
+            .with_safe_default_protocol_versions()?
+            .with_root_certificates(parse_root_store(root_certificates)?);
+
+        let config = match identity {
+            Some(identity) => {
+                builder.with_client_auth_cert(identity.certificate_chain, identity.key)?
+            }
+            None => builder.with_no_client_auth(),
+        };
+
+        Ok(Self(Arc::new(config)))
+    }
+}
+
+impl From<Arc<ClientConfig>> for TlsClientConfig {
+    fn from(config: Arc<ClientConfig>) -> Self {
+        Self(config)
+    }
+}
+
+/// Server side configuration for [`TlsSocketStream::accept`].
+#[derive(Debug, Clone)]
+pub struct TlsServerConfig(Arc<ServerConfig>);
+
+impl TlsServerConfig {
+    /// Creates a configuration presenting `identity` to clients.
+    ///
+    /// If `client_root_certificates` is set, clients must present a certificate chain valid for these PEM encoded
+    /// roots, otherwise client certificates are not requested.
+    pub fn new(
+        identity: TlsIdentity,
+        client_root_certificates: Option<&[u8]>,
+    ) -> std::result::Result<Self, TlsConfigError> {
+        let builder = ServerConfig::builder_with_provider(provider())
+            .with_safe_default_protocol_versions()?;
+
+        let builder = match client_root_certificates {
+            Some(client_root_certificates) => {
+                let verifier = WebPkiClientVerifier::builder_with_provider(
+                    Arc::new(parse_root_store(client_root_certificates)?),
+                    provider(),
+                )
+                .build()?;
+                builder.with_client_cert_verifier(verifier)
+            }
+            None => builder.with_no_client_auth(),
+        };
+
+        let config = builder.with_single_cert(identity.certificate_chain, identity.key)?;
+
+        Ok(Self(Arc::new(config)))
+    }
+}
+
+impl From<Arc<ServerConfig>> for TlsServerConfig {
+    fn from(config: Arc<ServerConfig>) -> Self {
+        Self(config)
+    }
+}
+
+/// Async TLS stream over an [`AsyncSocketStream`].
+///
+/// See the [module documentation](self) for how certificates are verified.
+#[derive(Debug)]
+pub struct TlsSocketStream {
+    inner: TlsStream<AsyncSocketStream>,
+}
+
+impl TlsSocketStream {
+    /// Performs the client side TLS handshake on `stream`, verifying that the server is `server_name`.
+    pub async fn connect(
+        stream: AsyncSocketStream,
+        server_name: &str,
+        config: &TlsClientConfig,
+    ) -> Result<Self> {
+        let server_name = ServerName::try_from(server_name.to_owned())
+            .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidInput, error))?;
+        let stream = TlsConnector::from(config.0.clone())
+            .connect(server_name, stream)
+            .await?;
+        Ok(Self {
+            inner: TlsStream::Client(stream),
+        })
+    }
+
+    /// Performs the server side TLS handshake on `stream`.
+    pub async fn accept(stream: AsyncSocketStream, config: &TlsServerConfig) -> Result<Self> {
+        let stream = TlsAcceptor::from(config.0.clone()).accept(stream).await?;
+        Ok(Self {
+            inner: TlsStream::Server(stream),
+        })
+    }
+
+    /// Returns the underlying unencrypted stream.
+    pub fn get_ref(&self) -> &AsyncSocketStream {
+        self.inner.get_ref().0
+    }
+}
+
+impl AsyncRead for TlsSocketStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        context: &mut Context<'_>,
+        buffer: &mut ReadBuf<'_>,
+    ) -> Poll<Result<()>> {
+        Pin::new(&mut self.inner).poll_read(context, buffer)
+    }
+}
+
+impl AsyncWrite for TlsSocketStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        context: &mut Context<'_>,
+        buffer: &[u8],
+    ) -> Poll<Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(context, buffer)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(context)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(context)
+    }
+}
+
+fn provider() -> Arc<rustls::crypto::CryptoProvider> {
+    Arc::new(rustls::crypto::ring::default_provider())
+}
+
+fn parse_certificates(
+    pem: &[u8],
+) -> std::result::Result<Vec<CertificateDer<'static>>, TlsConfigError> {
+    let certificates =
+        CertificateDer::pem_slice_iter(pem).collect::<std::result::Result<Vec<_>, _>>()?;
+    if certificates.is_empty() {
+        return Err(TlsConfigError::NoCertificates);
+    }
+    Ok(certificates)
+}
+
+fn parse_root_store(pem: &[u8]) -> std::result::Result<RootCertStore, TlsConfigError> {
+    let mut roots = RootCertStore::empty();
+    for certificate in parse_certificates(pem)? {
+        roots.add(certificate)?;
+    }
+    Ok(roots)
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::str::FromStr;
+
+    use rcgen::{BasicConstraints, CertificateParams, CertifiedIssuer, IsCa, KeyPair};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::{TlsClientConfig, TlsIdentity, TlsServerConfig, TlsSocketStream};
+    use crate::{AsyncSocketListener, UnresolvedMultiSocketAddress};
+
+    struct Authority(CertifiedIssuer<'static, KeyPair>);
+
+    impl Authority {
+        fn new() -> Self {
+            let mut params = CertificateParams::new(Vec::new()).unwrap();
+            params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+            Self(CertifiedIssuer::self_signed(params, KeyPair::generate().unwrap()).unwrap())
+        }
+
+        fn pem(&self) -> String {
+            self.0.pem()
+        }
+
+        fn identity(&self, name: &str) -> TlsIdentity {
+            let key = KeyPair::generate().unwrap();
+            let certificate = CertificateParams::new(vec![name.to_owned()])
+                .unwrap()
+                .signed_by(&key, &self.0)
+                .unwrap();
+            TlsIdentity::from_pem(certificate.pem().as_bytes(), key.serialize_pem().as_bytes())
+                .unwrap()
+        }
+    }
+
+    async fn listen() -> (AsyncSocketListener, UnresolvedMultiSocketAddress) {
+        let listener = UnresolvedMultiSocketAddress::from_str("127.0.0.1:0")
+            .unwrap()
+            .bind_async()
+            .await
+            .unwrap();
+        let address =
+            UnresolvedMultiSocketAddress::try_from(listener.local_address().unwrap()).unwrap();
+        (listener, address)
+    }
+
+    #[tokio::test]
+    async fn tls_socket_stream_round_trip() {
+        let authority = Authority::new();
+        let server_config = TlsServerConfig::new(authority.identity("127.0.0.1"), None).unwrap();
+        let client_config = TlsClientConfig::new(authority.pem().as_bytes(), None).unwrap();
+        let (listener, address) = listen().await;
+
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut stream = TlsSocketStream::accept(stream, &server_config)
+                .await
+                .unwrap();
+            let mut buffer = [0u8; 5];
+            stream.read_exact(&mut buffer).await.unwrap();
+            assert_eq!(&buffer, b"hello");
+            stream.write_all(b"world").await.unwrap();
+            stream.shutdown().await.unwrap();
+        });
+
+        let stream = address.connect_async().await.unwrap();
+        let mut stream = TlsSocketStream::connect(stream, "127.0.0.1", &client_config)
+            .await
+            .unwrap();
+
+        stream.write_all(b"hello").await.unwrap();
+        stream.flush().await.unwrap();
+        let mut buffer = [0u8; 5];
+        stream.read_exact(&mut buffer).await.unwrap();
+        assert_eq!(&buffer, b"world");
+        stream.shutdown().await.unwrap();
+
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn tls_socket_stream_rejects_untrusted_server() {
+        let authority = Authority::new();
+        let server_config = TlsServerConfig::new(authority.identity("127.0.0.1"), None).unwrap();
+        let client_config = TlsClientConfig::new(Authority::new().pem().as_bytes(), None).unwrap();
+        let (listener, address) = listen().await;
+
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            TlsSocketStream::accept(stream, &server_config).await
+        });
+
+        let stream = address.connect_async().await.unwrap();
+        let error = TlsSocketStream::connect(stream, "127.0.0.1", &client_config)
+            .await
+            .unwrap_err();
+        assert!(
+            error.to_string().starts_with("invalid peer certificate"),
+            "{error}"
+        );
+
+        assert!(server.await.unwrap().is_err());
+    }
+
+    #[tokio::test]
+    async fn tls_socket_stream_rejects_wrong_server_name() {
+        let authority = Authority::new();
+        let server_config = TlsServerConfig::new(authority.identity("a.example"), None).unwrap();
+        let client_config = TlsClientConfig::new(authority.pem().as_bytes(), None).unwrap();
+        let (listener, address) = listen().await;
+
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            TlsSocketStream::accept(stream, &server_config).await
+        });
+
+        let stream = address.connect_async().await.unwrap();
+        let error = TlsSocketStream::connect(stream, "b.example", &client_config)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("not valid for name"), "{error}");
+
+        assert!(server.await.unwrap().is_err());
+    }
+
+    #[tokio::test]
+    async fn tls_socket_stream_mutual_authentication() {
+        let authority = Authority::new();
+        let server_config = TlsServerConfig::new(
+            authority.identity("127.0.0.1"),
+            Some(authority.pem().as_bytes()),
+        )
+        .unwrap();
+        let (listener, address) = listen().await;
+
+        let server = tokio::spawn(async move {
+            for _ in 0..2 {
+                let (stream, _) = listener.accept().await.unwrap();
+                let Ok(mut stream) = TlsSocketStream::accept(stream, &server_config).await else {
+                    continue;
+                };
+                stream.write_all(b"hello").await.unwrap();
+                stream.shutdown().await.unwrap();
+            }
+        });
+
+        // Without a client certificate the server aborts the connection.
+        let client_config = TlsClientConfig::new(authority.pem().as_bytes(), None).unwrap();
+        let stream = address.connect_async().await.unwrap();
+        let mut stream = TlsSocketStream::connect(stream, "127.0.0.1", &client_config)
+            .await
+            .unwrap();
+        // With TLS 1.3 the client only learns about the rejection when reading.
+        assert!(stream.read_to_end(&mut Vec::new()).await.is_err());
+
+        let client_config = TlsClientConfig::new(
+            authority.pem().as_bytes(),
+            Some(authority.identity("client")),
+        )
+        .unwrap();
+        let stream = address.connect_async().await.unwrap();
+        let mut stream = TlsSocketStream::connect(stream, "127.0.0.1", &client_config)
+            .await
+            .unwrap();
+        let mut buffer = Vec::new();
+        stream.read_to_end(&mut buffer).await.unwrap();
+        assert_eq!(buffer, b"hello");
+
+        server.await.unwrap();
+    }
+}
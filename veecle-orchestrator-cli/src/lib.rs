@@ -1,8 +1,12 @@
 //! `veecle-os orchestrator` CLI
 
 #![forbid(unsafe_code)]
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
 
+use std::collections::BTreeMap;
 use std::io::{BufRead, BufReader, Cursor, Write};
+use std::process::ExitCode;
+use std::time::Duration;
 
 use anyhow::Context;
 use camino::Utf8PathBuf;
@@ -11,13 +15,22 @@ use indicatif::{ProgressBar, ProgressStyle};
 use itertools::Itertools;
 use serde::de::DeserializeOwned;
 use veecle_net_utils::{BlockingSocketStream, UnresolvedMultiSocketAddress};
-use veecle_orchestrator_protocol::{Info, InstanceId, LinkTarget, Priority, Request, Response};
+use veecle_orchestrator_protocol::{
+    BinaryEncoding, ErrorKind, Health, Info, InstanceId, LinkMatch, LinkTarget, MAX_BINARY_SIZE,
+    Priority, Request, Response, ServerError, StopOutcome,
+};
 
 /// Veecle OS Orchestrator CLI interface
 ///
 /// Communicates with the control socket of a local Veecle OS Orchestrator.
 #[derive(clap::Parser, Debug)]
-#[command(disable_help_subcommand = true, version)]
+#[command(
+    disable_help_subcommand = true,
+    version,
+    after_help = "Exit status: 0 on success, 3 if the instance was not found, 4 if it already exists, 5 if it is \
+                  running, 6 if it is not running, 7 if permission was denied, 8 for an invalid request and 1 for \
+                  any other failure."
+)]
 pub struct Arguments {
     /// The socket address to connect to (Unix path or TCP host:port), can be set via environment for easy sharing between the orchestrator and CLI.
     #[arg(long, env = "VEECLE_ORCHESTRATOR_SOCKET")]
@@ -53,13 +66,22 @@ enum Runtime {
         #[arg(long)]
         id: Option<InstanceId>,
 
-        /// Send the binary file content instead of just the path (useful for remote orchestrators).
+        /// Send the binary file content instead of just the path (useful for remote orchestrators), compressed with
+        /// Zstandard.
         #[arg(long)]
         copy: bool,
 
         /// Mark this runtime as privileged, allowing it to send control messages.
         #[arg(long, default_value_t = false)]
         privileged: bool,
+
+        /// Set an environment variable for the runtime process, may be repeated.
+        #[arg(long, value_name = "KEY=VALUE", value_parser = parse_env_var)]
+        env: Vec<(String, String)>,
+
+        /// Command line arguments passed to the runtime process.
+        #[arg(last = true)]
+        args: Vec<String>,
     },
 
     /// Remove the runtime instance with the passed id.
@@ -75,7 +97,16 @@ enum Runtime {
     },
 
     /// Stop the runtime instance with the passed id.
-    Stop { id: InstanceId },
+    Stop {
+        id: InstanceId,
+
+        /// Seconds to wait for the runtime to exit after `SIGTERM` before killing it.
+        #[arg(long, value_name = "SECONDS", value_parser = parse_seconds)]
+        grace: Option<Duration>,
+    },
+
+    /// Check the health of the runtime instance with the passed id, based on its heartbeats.
+    Health { id: InstanceId },
 
     /// List known runtime instances.
     List,
@@ -90,15 +121,133 @@ enum Link {
         #[arg(long = "type")]
         type_name: String,
 
+        /// The topic separating channels of the same type, if the data is sent with one.
+        #[arg(long)]
+        topic: Option<String>,
+
         /// The instance that will receive the data.
         #[arg(long)]
         to: LinkTarget,
+
+        /// Skip forwarding values identical to the last value forwarded over this link.
+        ///
+        /// Repeated values are then only delivered again once a different value was sent in between.
+        #[arg(long)]
+        dedup: bool,
+
+        /// Check that the instance can receive data before adding the link.
+        ///
+        /// Local instances must have an executable binary, remote addresses must not reject datagrams.
+        #[arg(long)]
+        validate: bool,
+
+        /// How the type name is matched against the type name of data, `prefix` and `glob` link all matching types.
+        ///
+        /// If several links match, only the most specific ones are used: exact links, otherwise the longest prefix,
+        /// otherwise the longest glob.
+        #[arg(long = "match", value_enum, default_value_t = LinkMatch::Exact)]
+        match_mode: LinkMatch,
     },
 
     /// List configured IPC links.
     List,
 }
 
+/// Returns the process exit code for a failed [`Arguments::run`], distinguishing the [`ErrorKind`] of server errors.
+pub fn exit_code(error: &anyhow::Error) -> ExitCode {
+    let kind = error
+        .chain()
+        .find_map(|error| error.downcast_ref::<ServerError>())
+        .map(ServerError::kind);
+
+    ExitCode::from(match kind {
+        Some(ErrorKind::NotFound) => 3,
+        Some(ErrorKind::AlreadyExists) => 4,
+        Some(ErrorKind::Running) => 5,
+        Some(ErrorKind::NotRunning) => 6,
+        Some(ErrorKind::PermissionDenied) => 7,
+        Some(ErrorKind::InvalidRequest) => 8,
+        Some(ErrorKind::Other) | None => 1,
+    })
+}
+
+/// Parses a `KEY=VALUE` environment variable assignment.
+fn parse_env_var(value: &str) -> anyhow::Result<(String, String)> {
+    let (key, value) = value
+        .split_once('=')
+        .context("expected an assignment in the form `KEY=VALUE`")?;
+    anyhow::ensure!(!key.is_empty(), "environment variable name cannot be empty");
+    Ok((key.to_owned(), value.to_owned()))
+}
+
+/// Parses a possibly fractional number of seconds into a [`Duration`].
+fn parse_seconds(value: &str) -> anyhow::Result<Duration> {
+    Ok(Duration::try_from_secs_f64(value.parse()?)?)
+}
+
+/// How many runtime instances and links are fetched per [`Request::InfoPage`].
+const INFO_PAGE_SIZE: usize = 100;
+
+/// Returns whether `error` is a server rejecting a request variant it doesn't know, e.g. an older server.
+fn is_unknown_request(error: &anyhow::Error) -> bool {
+    error
+        .chain()
+        .any(|error| error.downcast_ref::<ServerError>().is_some())
+        && error
+            .chain()
+            .any(|error| error.to_string().contains("unknown variant"))
+}
+
+/// Fetches the [`Info`] page by page with [`Request::InfoPage`] and merges the pages.
+///
+/// Falls back to a single [`Request::Info`] for servers that don't know [`Request::InfoPage`].
+fn fetch_info(stream: &mut BufReader<BlockingSocketStream>) -> anyhow::Result<Info> {
+    let first_page = send(
+        stream,
+        Request::InfoPage {
+            offset: 0,
+            limit: INFO_PAGE_SIZE,
+        },
+    );
+    let mut info: Info = match first_page {
+        Err(error) if is_unknown_request(&error) => return send(stream, Request::Info),
+        result => result?,
+    };
+
+    let mut offset = INFO_PAGE_SIZE;
+    while offset < info.total_runtimes.max(info.total_links) {
+        let page: Info = send(
+            stream,
+            Request::InfoPage {
+                offset,
+                limit: INFO_PAGE_SIZE,
+            },
+        )?;
+        // Instances or links may have been removed since the first page.
+        if page.runtimes.is_empty() && page.links.is_empty() {
+            break;
+        }
+        info.runtimes.extend(page.runtimes);
+        info.links.extend(page.links);
+        info.deduplicated_links.extend(page.deduplicated_links);
+        info.link_modes.extend(page.link_modes);
+        offset += INFO_PAGE_SIZE;
+    }
+
+    Ok(info)
+}
+
+/// Formats how long a runtime has been running, e.g. `1h 2m 3s`.
+fn format_uptime(uptime: Duration) -> String {
+    let seconds = uptime.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    match (hours, minutes) {
+        (0, 0) => format!("{seconds}s"),
+        (0, _) => format!("{minutes}m {seconds}s"),
+        _ => format!("{hours}h {minutes}m {seconds}s"),
+    }
+}
+
 /// Reads, deserializes and checks [`Response::Err`] for a <code>[Response]\<T></code> from `stream`.
 fn receive<T>(stream: &mut BufReader<BlockingSocketStream>) -> anyhow::Result<T>
 where
@@ -136,17 +285,39 @@ where
     receive(stream)
 }
 
-/// Sends a [`Request::AddWithBinary`] followed by the binary data with progress reporting.
+/// Sends a [`Request::AddWithBinary`] followed by the Zstandard compressed binary data with progress reporting.
+///
+/// Returns the number of bytes sent.
 fn send_add_with_binary(
     stream: &mut BufReader<BlockingSocketStream>,
     id: InstanceId,
     data: &[u8],
     privileged: bool,
-) -> anyhow::Result<()> {
-    let () = send(stream, Request::add_with_binary(id, data, privileged))
-        .context("sending AddWithBinary request, receiving initial response")?;
+    env: BTreeMap<String, String>,
+    args: Vec<String>,
+) -> anyhow::Result<usize> {
+    anyhow::ensure!(
+        data.len() <= MAX_BINARY_SIZE,
+        "binary is larger than the maximum size of {MAX_BINARY_SIZE} bytes"
+    );
 
-    let pb = ProgressBar::new(data.len() as u64);
+    let compressed = zstd::encode_all(data, 0).context("compressing binary data")?;
+
+    let () = send(
+        stream,
+        Request::add_with_encoded_binary(
+            id,
+            data,
+            compressed.len(),
+            BinaryEncoding::Zstd,
+            privileged,
+            env,
+            args,
+        ),
+    )
+    .context("sending AddWithBinary request, receiving initial response")?;
+
+    let pb = ProgressBar::new(compressed.len() as u64);
     pb.set_style(
         ProgressStyle::default_bar()
             .template(
@@ -156,14 +327,17 @@ fn send_add_with_binary(
             .progress_chars("█▓░"),
     );
 
-    std::io::copy(&mut Cursor::new(data), &mut pb.wrap_write(stream.get_mut()))
-        .context("sending binary data")?;
+    std::io::copy(
+        &mut Cursor::new(&compressed),
+        &mut pb.wrap_write(stream.get_mut()),
+    )
+    .context("sending binary data")?;
 
     pb.finish_and_clear();
 
     let () = receive(stream).context("receiving final response")?;
 
-    Ok(())
+    Ok(compressed.len())
 }
 
 impl Arguments {
@@ -185,13 +359,19 @@ impl Arguments {
                 id,
                 copy,
                 privileged,
+                env,
+                args,
             }) => {
                 let id = id.unwrap_or_else(InstanceId::new);
+                let env = env.into_iter().collect();
                 if copy {
                     let data = std::fs::read(&path)
                         .with_context(|| format!("reading binary file '{path}'"))?;
-                    send_add_with_binary(&mut stream, id, &data, privileged)?;
-                    println!("added instance {id} (sent {} bytes)", data.len());
+                    let sent = send_add_with_binary(&mut stream, id, &data, privileged, env, args)?;
+                    println!(
+                        "added instance {id} (sent {sent} bytes compressed from {} bytes)",
+                        data.len()
+                    );
                 } else {
                     let () = send(
                         &mut stream,
@@ -199,6 +379,8 @@ impl Arguments {
                             path,
                             id,
                             privileged,
+                            env,
+                            args,
                         },
                     )?;
                     println!("added instance {id}");
@@ -212,54 +394,118 @@ impl Arguments {
                 let () = send(&mut stream, Request::Start { id, priority })?;
                 println!("started instance {id}");
             }
-            Command::Runtime(Runtime::Stop { id }) => {
-                let () = send(&mut stream, Request::Stop(id))?;
-                println!("stopped instance {id}");
+            Command::Runtime(Runtime::Stop { id, grace }) => {
+                let outcome: StopOutcome = send(&mut stream, Request::Stop { id, grace })?;
+                match outcome {
+                    StopOutcome::Graceful => println!("stopped instance {id}"),
+                    StopOutcome::Killed => {
+                        println!("killed instance {id} after it did not stop in time")
+                    }
+                }
+            }
+            Command::Runtime(Runtime::Health { id }) => {
+                let health: Health = send(&mut stream, Request::HealthCheck(id))?;
+                match health {
+                    Health::Stopped => println!("instance {id} is {health}"),
+                    Health::Healthy { last_heartbeat }
+                    | Health::Unresponsive {
+                        last_heartbeat: Some(last_heartbeat),
+                    } => {
+                        let elapsed = last_heartbeat.elapsed().unwrap_or_default();
+                        println!(
+                            "instance {id} is {health} (last heartbeat {:.1}s ago)",
+                            elapsed.as_secs_f64()
+                        );
+                    }
+                    Health::Unresponsive {
+                        last_heartbeat: None,
+                    } => println!("instance {id} is {health} (no heartbeat received)"),
+                }
             }
             Command::Runtime(Runtime::List) => {
-                let info: Info = send(&mut stream, Request::Info)?;
+                let info = fetch_info(&mut stream)?;
 
                 println!(
                     "{}",
                     Table::new()
                         .load_preset(comfy_table::presets::UTF8_FULL)
-                        .set_header(["Id", "Binary", "Running"])
+                        .set_header(["Id", "Binary", "Running", "Uptime"])
                         .add_rows(info.runtimes.iter().map(|(id, info)| {
+                            let running = match info.exit_code {
+                                Some(exit_code) if !info.running => {
+                                    format!("false (exit code {exit_code})")
+                                }
+                                _ => info.running.to_string(),
+                            };
+                            let uptime = info
+                                .started_at
+                                .map(|started_at| {
+                                    format_uptime(started_at.elapsed().unwrap_or_default())
+                                })
+                                .unwrap_or_default();
                             [
                                 id.into(),
                                 (&info.binary).into(),
-                                Cell::new(info.running).fg(if info.running {
+                                Cell::new(running).fg(if info.running {
                                     Color::DarkGreen
                                 } else {
                                     Color::DarkRed
                                 }),
+                                uptime.into(),
                             ]
                         }))
                 );
             }
-            Command::Link(Link::Add { type_name, to }) => {
+            Command::Link(Link::Add {
+                type_name,
+                topic,
+                to,
+                dedup,
+                validate,
+                match_mode,
+            }) => {
                 let () = send(
                     &mut stream,
                     Request::Link {
                         type_name: type_name.clone(),
+                        topic: topic.clone(),
                         to,
+                        dedup,
+                        validate,
+                        match_mode,
                     },
                 )?;
-                println!("linked {type_name} to {to}");
+                match topic {
+                    Some(topic) => println!("linked {type_name} on topic {topic} to {to}"),
+                    None => println!("linked {type_name} to {to}"),
+                }
             }
             Command::Link(Link::List) => {
-                let info: Info = send(&mut stream, Request::Info)?;
+                let info = fetch_info(&mut stream)?;
 
                 println!(
                     "{}",
                     Table::new()
                         .load_preset(comfy_table::presets::UTF8_FULL)
                         .set_header(["For Type", "To Instance(s)"])
-                        .add_rows(
-                            info.links
-                                .iter()
-                                .map(|(ty, to)| { [ty.to_string(), to.iter().join("\n")] })
-                        )
+                        .add_rows(info.links.iter().map(|(ty, to)| {
+                            let deduplicated = info.deduplicated_links.get(ty);
+                            let to = to.iter().map(|target| {
+                                if deduplicated
+                                    .is_some_and(|deduplicated| deduplicated.contains(target))
+                                {
+                                    format!("{target} (dedup)")
+                                } else {
+                                    target.to_string()
+                                }
+                            });
+                            let ty = match info.link_modes.get(ty) {
+                                Some(LinkMatch::Prefix) => format!("{ty} (prefix)"),
+                                Some(LinkMatch::Glob) => format!("{ty} (glob)"),
+                                Some(LinkMatch::Exact) | None => ty.clone(),
+                            };
+                            [ty, to.join("\n")]
+                        }))
                 );
             }
             Command::Clear => {
@@ -270,3 +516,59 @@ impl Arguments {
         Ok(())
     }
 }
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::time::Duration;
+
+    use veecle_orchestrator_protocol::{Info, Response};
+
+    use super::{format_uptime, is_unknown_request};
+
+    /// Returns the error for an error `response` as received by [`super::receive`].
+    fn server_error(response: &str) -> anyhow::Error {
+        let response: Response<Info> = serde_json::from_str(response).unwrap();
+        response.into_result().unwrap_err().into()
+    }
+
+    #[test]
+    fn unknown_request() {
+        let error = server_error(
+            r#"{"Err":{"kind":"InvalidRequest","messages":["parsing request","unknown variant `InfoPage`, expected one of `Version`, `Info`"]}}"#,
+        );
+        assert!(is_unknown_request(&error));
+
+        // Older servers only send the messages.
+        let error = server_error(
+            r#"{"Err":["parsing request","unknown variant `InfoPage`, expected one of `Version`, `Info`"]}"#,
+        );
+        assert!(is_unknown_request(&error));
+    }
+
+    #[test]
+    fn other_errors() {
+        let error = server_error(r#"{"Err":{"kind":"Other","messages":["collecting info"]}}"#);
+        assert!(!is_unknown_request(&error));
+
+        let error = anyhow::anyhow!("unknown variant `Err`").context("parsing response");
+        assert!(!is_unknown_request(&error));
+    }
+
+    #[test]
+    fn uptime_boundaries() {
+        let cases = [
+            (Duration::ZERO, "0s"),
+            (Duration::from_millis(999), "0s"),
+            (Duration::from_secs(59), "59s"),
+            (Duration::from_secs(60), "1m 0s"),
+            (Duration::from_secs(3599), "59m 59s"),
+            (Duration::from_secs(3600), "1h 0m 0s"),
+            (Duration::from_secs(3661), "1h 1m 1s"),
+            (Duration::from_secs(100 * 3600), "100h 0m 0s"),
+        ];
+        for (uptime, expected) in cases {
+            assert_eq!(format_uptime(uptime), expected, "{uptime:?}");
+        }
+    }
+}
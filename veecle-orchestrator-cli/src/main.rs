@@ -1,7 +1,15 @@
 //! `veecle-os orchestrator` CLI
 
+use std::process::ExitCode;
+
 use clap::Parser;
 
-fn main() -> anyhow::Result<()> {
-    veecle_orchestrator_cli::Arguments::parse().run()
+fn main() -> ExitCode {
+    match veecle_orchestrator_cli::Arguments::parse().run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Error: {error:?}");
+            veecle_orchestrator_cli::exit_code(&error)
+        }
+    }
 }
@@ -6,15 +6,23 @@
 //! `Response` inner type is.
 
 #![forbid(unsafe_code)]
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
 
 use std::collections::BTreeMap;
 
 /// Buffer size for binary data transfer in [`Request::AddWithBinary`].
 pub const BINARY_TRANSFER_CHUNK_SIZE: usize = 8192;
+
+/// Maximum size of the decoded binary data of a [`Request::AddWithBinary`], larger binaries are rejected.
+pub const MAX_BINARY_SIZE: usize = 1 << 30;
+
+/// How long a runtime process is given to exit after `SIGTERM` when [`Request::Stop`] doesn't specify a grace period.
+pub const DEFAULT_STOP_GRACE: Duration = Duration::from_millis(100);
 use std::error::Error;
 use std::fmt;
 use std::net::SocketAddr;
 use std::str::FromStr;
+use std::time::{Duration, SystemTime};
 
 use camino::Utf8PathBuf;
 use serde::{Deserialize, Serialize};
@@ -101,27 +109,56 @@ pub enum Request {
 
         /// Whether this runtime is privileged and can send control messages.
         privileged: bool,
+
+        /// Additional environment variables set for the runtime process.
+        ///
+        /// `VEECLE_IPC_SOCKET` and `VEECLE_RUNTIME_ID` are always set by the orchestrator and override any values
+        /// passed here.
+        #[serde(default)]
+        env: BTreeMap<String, String>,
+
+        /// Command line arguments passed to the runtime process.
+        #[serde(default)]
+        args: Vec<String>,
     },
 
     /// Add a new runtime instance with binary data sent after this command.
     ///
-    /// The server should respond with <code>[Response]<()></code>, then the binary data of exactly
-    /// `length` bytes should be sent, then the server should again respond with
+    /// The server should respond with <code>[Response]<()></code>, then the binary data encoded with `encoding` of
+    /// exactly `length` bytes should be sent, then the server should again respond with
     /// <code>[Response]<()></code>.
     ///
-    /// The data will be validated against the provided SHA-256 `hash`.
+    /// The decoded data will be validated against the provided SHA-256 `hash`, it must not be larger than
+    /// [`MAX_BINARY_SIZE`].
     AddWithBinary {
         /// The id that will be used to interact with this instance later.
         id: InstanceId,
 
-        /// The expected length of the binary data in bytes.
+        /// The expected length of the binary data in bytes, as sent (i.e. after encoding).
         length: usize,
 
-        /// The SHA-256 hash of the expected binary data for validation.
+        /// The SHA-256 hash of the expected binary data for validation, computed over the decoded data.
         hash: [u8; 32],
 
+        /// How the binary data is encoded for the transfer.
+        ///
+        /// If not specified, defaults to [`BinaryEncoding::Raw`].
+        #[serde(default)]
+        encoding: BinaryEncoding,
+
         /// Whether this runtime is privileged and can send control messages.
         privileged: bool,
+
+        /// Additional environment variables set for the runtime process.
+        ///
+        /// `VEECLE_IPC_SOCKET` and `VEECLE_RUNTIME_ID` are always set by the orchestrator and override any values
+        /// passed here.
+        #[serde(default)]
+        env: BTreeMap<String, String>,
+
+        /// Command line arguments passed to the runtime process.
+        #[serde(default)]
+        args: Vec<String>,
     },
 
     /// Remove the runtime instance with the passed id.
@@ -145,19 +182,61 @@ pub enum Request {
 
     /// Stop the runtime instance with the passed id.
     ///
-    /// Responds with <code>[Response]<()></code>.
-    Stop(InstanceId),
+    /// The process is sent `SIGTERM` and given `grace` to exit, after which it is killed with `SIGKILL`.
+    ///
+    /// Responds with <code>[Response]<[StopOutcome]></code>.
+    Stop {
+        /// The id of the instance to stop.
+        id: InstanceId,
 
-    /// Link IPC for a data type identified by `type_name` to `to`.
+        /// How long to wait for the process to exit after `SIGTERM` before killing it.
+        ///
+        /// If not specified, defaults to [`DEFAULT_STOP_GRACE`].
+        #[serde(default)]
+        grace: Option<Duration>,
+    },
+
+    /// Query the health of the runtime instance with the passed id, based on the heartbeats it sends.
+    ///
+    /// Responds with <code>[Response]<[Health]></code>.
+    HealthCheck(InstanceId),
+
+    /// Link IPC for a data type identified by `type_name` and optionally `topic` to `to`.
     ///
     /// The same `type_name` can have multiple destinations, the data will be cloned to all.
+    /// Data tagged with a topic is only routed by links with the same topic, links with a topic are listed in
+    /// [`Info::links`] as `type_name#topic`.
+    ///
+    /// With a `match_mode` other than [`LinkMatch::Exact`], `type_name` is a pattern that links all matching data
+    /// types, see [`LinkMatch`] for which links are used if several of them match.
+    /// A `type_name` and `topic` can only be linked with a single match mode.
     ///
     /// Responds with <code>[Response]<()></code>.
     Link {
         /// The type name identifying the data.
         type_name: String,
+        /// The topic separating channels of the same type.
+        #[serde(default)]
+        topic: Option<String>,
         /// A target instance that will receive the data.
         to: LinkTarget,
+        /// Skip forwarding values that are identical to the last value forwarded over this link.
+        ///
+        /// This protects the target from producers that repeatedly send an unchanged value. A value that legitimately
+        /// repeats (e.g. the same reading sampled twice) is only delivered again once a different value has been sent
+        /// in between, so the target cannot use the arrival of a value as a freshness signal, and a restarted target
+        /// will not see the current value until it changes.
+        #[serde(default)]
+        dedup: bool,
+        /// Check that `to` can receive data before adding the link, failing the request otherwise.
+        ///
+        /// A [`LinkTarget::Local`] instance must be registered with an executable binary. A [`LinkTarget::Remote`]
+        /// address must not reject datagrams, a host that silently drops them (e.g. behind a firewall) still passes.
+        #[serde(default)]
+        validate: bool,
+        /// How `type_name` is matched against the type name of data.
+        #[serde(default)]
+        match_mode: LinkMatch,
     },
 
     /// Query info about the current server state.
@@ -165,12 +244,105 @@ pub enum Request {
     /// Response with <code>[Response]<[Info]></code>
     Info,
 
+    /// Query a page of the info about the current server state, for deployments with too many instances or links to
+    /// list in a single response.
+    ///
+    /// [`Info::runtimes`] and [`Info::links`] each hold up to `limit` entries starting at `offset`, in order of their
+    /// keys, [`Info::deduplicated_links`] only holds entries for the links in the page.
+    /// [`Info::total_runtimes`] and [`Info::total_links`] tell how many pages are left to fetch.
+    ///
+    /// Responds with <code>[Response]<[Info]></code>.
+    InfoPage {
+        /// The number of runtime instances and links to skip.
+        offset: usize,
+        /// The maximum number of runtime instances and links to return.
+        limit: usize,
+    },
+
     /// Stop all active runtimes and clear all orchestrator state.
     ///
     /// Responds with <code>[Response]<()></code>.
     Clear,
 }
 
+/// How the binary data of a [`Request::AddWithBinary`] is encoded for the transfer.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, Eq, PartialEq, Hash)]
+pub enum BinaryEncoding {
+    /// The binary data is sent as is.
+    #[default]
+    Raw,
+
+    /// The binary data is compressed as a single Zstandard stream.
+    Zstd,
+}
+
+/// How the `type_name` of a [`Request::Link`] is matched against the type name of data.
+///
+/// If the links of several `type_name`s match some data, only the most specific ones are used: exact links, otherwise
+/// the prefix links with the longest prefix, otherwise the glob links with the longest pattern.
+/// Ties between patterns of the same length are broken by their order in [`Info::links`], the last one is used.
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Serialize, Eq, PartialEq, Hash, Ord, PartialOrd,
+)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[serde(rename_all = "lowercase")]
+pub enum LinkMatch {
+    /// The type name must equal `type_name`.
+    #[default]
+    Exact,
+
+    /// The type name must start with `type_name`, e.g. `myapp::sensors::` links all types of that module.
+    Prefix,
+
+    /// The type name must match `type_name` as a glob pattern, where `*` matches any sequence of characters (including
+    /// `::`) and `?` matches any single character, e.g. `myapp::*::Speed`.
+    Glob,
+}
+
+impl LinkMatch {
+    /// Returns whether `type_name` is matched by `pattern` in this mode.
+    pub fn matches(self, pattern: &str, type_name: &str) -> bool {
+        match self {
+            Self::Exact => pattern == type_name,
+            Self::Prefix => type_name.starts_with(pattern),
+            Self::Glob => glob_matches(pattern, type_name),
+        }
+    }
+}
+
+/// Returns whether `text` matches the glob `pattern`, see [`LinkMatch::Glob`].
+///
+/// This is called for every routed value, so it walks both strings by byte offset instead of collecting their
+/// characters.
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // The offset after the last `*` in the pattern and the offset in the text it currently extends to.
+    let mut star = None;
+    while let Some(c) = text[t..].chars().next() {
+        match pattern[p..].chars().next() {
+            Some('*') => {
+                p += 1;
+                star = Some((p, t));
+            }
+            Some(pattern_c) if pattern_c == '?' || pattern_c == c => {
+                p += pattern_c.len_utf8();
+                t += c.len_utf8();
+            }
+            // Let the last `*` match one more character and retry from there.
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    let star_c = text[star_t..].chars().next().unwrap_or_default();
+                    p = star_p;
+                    t = star_t + star_c.len_utf8();
+                    star = Some((p, t));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].bytes().all(|b| b == b'*')
+}
+
 /// A local or remote instance for an IPC link target.
 #[derive(Copy, Clone, Debug, Deserialize, Serialize, Eq, PartialEq, Hash, Ord, PartialOrd)]
 #[serde(untagged)]
@@ -211,9 +383,11 @@ impl Request {
             Self::AddWithBinary { .. } => "AddWithBinary",
             Self::Remove(_) => "Remove",
             Self::Start { .. } => "Start",
-            Self::Stop(_) => "Stop",
+            Self::Stop { .. } => "Stop",
+            Self::HealthCheck(_) => "HealthCheck",
             Self::Link { .. } => "Link",
             Self::Info => "Info",
+            Self::InfoPage { .. } => "InfoPage",
             Self::Clear => "Clear",
         }
     }
@@ -221,12 +395,44 @@ impl Request {
     /// Creates a new `AddWithBinary` request from binary data.
     ///
     /// Automatically calculates the length and SHA-256 hash of the provided data.
-    pub fn add_with_binary(id: InstanceId, data: &[u8], privileged: bool) -> Self {
+    pub fn add_with_binary(
+        id: InstanceId,
+        data: &[u8],
+        privileged: bool,
+        env: BTreeMap<String, String>,
+        args: Vec<String>,
+    ) -> Self {
+        Self::add_with_encoded_binary(
+            id,
+            data,
+            data.len(),
+            BinaryEncoding::Raw,
+            privileged,
+            env,
+            args,
+        )
+    }
+
+    /// Creates a new `AddWithBinary` request from binary data that is sent encoded with `encoding`.
+    ///
+    /// The hash is calculated over `data`, `encoded_length` is the length of the encoded data that will be sent.
+    pub fn add_with_encoded_binary(
+        id: InstanceId,
+        data: &[u8],
+        encoded_length: usize,
+        encoding: BinaryEncoding,
+        privileged: bool,
+        env: BTreeMap<String, String>,
+        args: Vec<String>,
+    ) -> Self {
         Self::AddWithBinary {
             id,
-            length: data.len(),
+            length: encoded_length,
             hash: Sha256::digest(data).into(),
+            encoding,
             privileged,
+            env,
+            args,
         }
     }
 }
@@ -238,10 +444,72 @@ pub enum Response<T> {
     Ok(T),
 
     /// A failure response.
-    ///
-    /// The error is returned as a series of messages that create the chain of context, similar to
-    /// what recursively calling [`Error::source`] would give.
-    Err(Vec<String>),
+    Err(ErrorResponse),
+}
+
+/// The category of a failure, so that clients can react to specific failures without parsing messages.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, Eq, PartialEq, Hash)]
+pub enum ErrorKind {
+    /// The referenced instance is not registered.
+    NotFound,
+
+    /// An instance with the same id is already registered.
+    AlreadyExists,
+
+    /// The instance is running, but the request requires it to be stopped.
+    Running,
+
+    /// The instance is not running, but the request requires it to be running.
+    NotRunning,
+
+    /// The orchestrator lacks the permissions to fulfill the request.
+    PermissionDenied,
+
+    /// The request or its associated data was malformed.
+    InvalidRequest,
+
+    /// Any other failure, also used for unknown kinds sent by newer servers.
+    #[default]
+    #[serde(other)]
+    Other,
+}
+
+/// The content of a [`Response::Err`].
+///
+/// Also deserializes from a plain list of messages, as sent by older servers, using [`ErrorKind::Other`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(from = "ErrorResponseRepr")]
+pub struct ErrorResponse {
+    /// The category of the failure.
+    pub kind: ErrorKind,
+
+    /// The series of messages that create the chain of context, similar to what recursively calling [`Error::source`]
+    /// would give.
+    pub messages: Vec<String>,
+}
+
+/// The accepted encodings of an [`ErrorResponse`].
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ErrorResponseRepr {
+    Structured {
+        #[serde(default)]
+        kind: ErrorKind,
+        messages: Vec<String>,
+    },
+    Messages(Vec<String>),
+}
+
+impl From<ErrorResponseRepr> for ErrorResponse {
+    fn from(repr: ErrorResponseRepr) -> Self {
+        match repr {
+            ErrorResponseRepr::Structured { kind, messages } => Self { kind, messages },
+            ErrorResponseRepr::Messages(messages) => Self {
+                kind: ErrorKind::Other,
+                messages,
+            },
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -262,11 +530,20 @@ impl Error for StringError {
     }
 }
 
+/// An error returned by the server, created by [`Response::into_result`].
 #[derive(Clone, Debug)]
-struct ServerError {
+pub struct ServerError {
+    kind: ErrorKind,
     source: Option<Box<StringError>>,
 }
 
+impl ServerError {
+    /// Returns the category of the failure.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+}
+
 impl fmt::Display for ServerError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str("server returned error")
@@ -284,30 +561,35 @@ impl<T> Response<T> {
     ///
     /// This re-attaches the messages into a chain via [`Error::source`], so it has the structure
     /// expected by error reporters.
-    pub fn into_result(self) -> Result<T, impl Error> {
+    pub fn into_result(self) -> Result<T, ServerError> {
         match self {
             Response::Ok(value) => Ok(value),
-            Response::Err(mut messages) => {
+            Response::Err(ErrorResponse { kind, mut messages }) => {
                 let mut source = None;
 
                 for message in messages.drain(..).rev() {
                     source = Some(Box::new(StringError { message, source }))
                 }
 
-                Err(ServerError { source })
+                Err(ServerError { kind, source })
             }
         }
     }
 
-    /// Creates a response for an error, serializing its context.
+    /// Creates a response for an error, serializing its context, with [`ErrorKind::Other`].
     pub fn err(error: impl Error) -> Self {
+        Self::err_with_kind(ErrorKind::Other, error)
+    }
+
+    /// Creates a response for an error of a specific kind, serializing its context.
+    pub fn err_with_kind(kind: ErrorKind, error: impl Error) -> Self {
         let mut messages = Vec::new();
         let mut source: Option<&dyn Error> = Some(&error);
         while let Some(error) = source {
             messages.push(error.to_string());
             source = error.source();
         }
-        Self::Err(messages)
+        Self::Err(ErrorResponse { kind, messages })
     }
 }
 
@@ -322,6 +604,74 @@ pub struct RuntimeInfo {
 
     /// Whether this runtime is privileged and can send control messages.
     pub privileged: bool,
+
+    /// Additional environment variables set for the runtime process.
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+
+    /// Command line arguments passed to the runtime process.
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// When the last heartbeat was received from this instance, if ever.
+    #[serde(default)]
+    pub last_heartbeat: Option<SystemTime>,
+
+    /// When the currently running process was started, `None` if the instance is not running.
+    #[serde(default)]
+    pub started_at: Option<SystemTime>,
+
+    /// The exit code of the last process of this instance once it stopped.
+    ///
+    /// `None` while the instance is running, if it was never started, or if the process was terminated by a signal.
+    #[serde(default)]
+    pub exit_code: Option<i32>,
+
+    /// The priority the last process of this instance was started with, `None` if it was never started or started
+    /// without one.
+    #[serde(default)]
+    pub priority: Option<Priority>,
+}
+
+/// How a runtime process exited when handling [`Request::Stop`].
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Eq, PartialEq)]
+pub enum StopOutcome {
+    /// The process exited within the grace period after `SIGTERM`.
+    Graceful,
+
+    /// The process did not exit within the grace period and was killed with `SIGKILL`.
+    Killed,
+}
+
+/// The health of a runtime instance, as reported for [`Request::HealthCheck`].
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Eq, PartialEq)]
+pub enum Health {
+    /// The instance is not running.
+    Stopped,
+
+    /// The instance is running and has recently sent a heartbeat.
+    Healthy {
+        /// When the last heartbeat was received.
+        last_heartbeat: SystemTime,
+    },
+
+    /// The instance is running but has not sent a heartbeat recently.
+    ///
+    /// Either the runtime is hung, or it does not run the `veecle_ipc::Heartbeat` actor.
+    Unresponsive {
+        /// When the last heartbeat was received, if ever.
+        last_heartbeat: Option<SystemTime>,
+    },
+}
+
+impl fmt::Display for Health {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Stopped => "stopped",
+            Self::Healthy { .. } => "healthy",
+            Self::Unresponsive { .. } => "unresponsive",
+        })
+    }
 }
 
 /// Information about the current orchestrator state.
@@ -332,4 +682,206 @@ pub struct Info {
 
     /// IPC links within and without this orchestrator.
     pub links: BTreeMap<String, Vec<LinkTarget>>,
+
+    /// The subset of [`Info::links`] that skip unchanged values, see `dedup` on [`Request::Link`].
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub deduplicated_links: BTreeMap<String, Vec<LinkTarget>>,
+
+    /// The match modes of the keys of [`Info::links`] that are patterns, see `match_mode` on [`Request::Link`].
+    ///
+    /// Keys that are not in here match exactly.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub link_modes: BTreeMap<String, LinkMatch>,
+
+    /// The total number of runtime instances, more than in [`Info::runtimes`] if this is a page requested with
+    /// [`Request::InfoPage`].
+    #[serde(default)]
+    pub total_runtimes: usize,
+
+    /// The total number of links, more than in [`Info::links`] if this is a page requested with
+    /// [`Request::InfoPage`].
+    #[serde(default)]
+    pub total_links: usize,
+}
+
+impl Info {
+    /// Returns the page of `limit` runtime instances and links starting at `offset`, see [`Request::InfoPage`].
+    pub fn page(self, offset: usize, limit: usize) -> Self {
+        let links: BTreeMap<_, _> = self.links.into_iter().skip(offset).take(limit).collect();
+        let deduplicated_links = self
+            .deduplicated_links
+            .into_iter()
+            .filter(|(routing_key, _)| links.contains_key(routing_key))
+            .collect();
+        let link_modes = self
+            .link_modes
+            .into_iter()
+            .filter(|(routing_key, _)| links.contains_key(routing_key))
+            .collect();
+        Self {
+            runtimes: self.runtimes.into_iter().skip(offset).take(limit).collect(),
+            links,
+            deduplicated_links,
+            link_modes,
+            total_runtimes: self.total_runtimes,
+            total_links: self.total_links,
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use uuid::Uuid;
+
+    use super::{
+        BinaryEncoding, ErrorKind, Info, InstanceId, LinkMatch, LinkTarget, Request, Response,
+        RuntimeInfo,
+    };
+
+    #[test]
+    fn error_response_round_trip() {
+        let response = Response::<()>::err_with_kind(
+            ErrorKind::NotFound,
+            std::io::Error::other("instance id 1 was not registered"),
+        );
+        let encoded = serde_json::to_string(&response).unwrap();
+        assert_eq!(
+            encoded,
+            r#"{"Err":{"kind":"NotFound","messages":["instance id 1 was not registered"]}}"#
+        );
+
+        let decoded: Response<()> = serde_json::from_str(&encoded).unwrap();
+        let error = decoded.into_result().unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn error_response_without_kind() {
+        let decoded: Response<()> =
+            serde_json::from_str(r#"{"Err":["adding instance","already registered"]}"#).unwrap();
+        let error = decoded.into_result().unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::Other);
+        assert_eq!(
+            std::error::Error::source(&error).unwrap().to_string(),
+            "adding instance"
+        );
+
+        let decoded: Response<()> =
+            serde_json::from_str(r#"{"Err":{"kind":"SomethingNew","messages":[]}}"#).unwrap();
+        assert_eq!(decoded.into_result().unwrap_err().kind(), ErrorKind::Other);
+    }
+
+    #[test]
+    fn info_requests() {
+        assert!(matches!(
+            serde_json::from_str(r#""Info""#).unwrap(),
+            Request::Info
+        ));
+        assert!(matches!(
+            serde_json::from_str(r#"{"InfoPage":{"offset":2,"limit":10}}"#).unwrap(),
+            Request::InfoPage {
+                offset: 2,
+                limit: 10
+            }
+        ));
+    }
+
+    #[test]
+    fn add_with_binary_encoding() {
+        let request = Request::add_with_encoded_binary(
+            InstanceId(Uuid::nil()),
+            b"binary",
+            4,
+            BinaryEncoding::Zstd,
+            false,
+            BTreeMap::new(),
+            Vec::new(),
+        );
+        let encoded = serde_json::to_string(&request).unwrap();
+        assert!(matches!(
+            serde_json::from_str(&encoded).unwrap(),
+            Request::AddWithBinary {
+                length: 4,
+                encoding: BinaryEncoding::Zstd,
+                ..
+            }
+        ));
+
+        let mut value: serde_json::Value = serde_json::from_str(&encoded).unwrap();
+        value["AddWithBinary"]
+            .as_object_mut()
+            .unwrap()
+            .remove("encoding");
+        assert!(matches!(
+            serde_json::from_value(value).unwrap(),
+            Request::AddWithBinary {
+                encoding: BinaryEncoding::Raw,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn info_page() {
+        let id = |id| InstanceId(Uuid::from_u128(id));
+        let runtime = |n| {
+            let info = RuntimeInfo {
+                running: false,
+                binary: format!("/bin/runtime-{n}").into(),
+                privileged: false,
+                env: BTreeMap::new(),
+                args: Vec::new(),
+                last_heartbeat: None,
+                started_at: None,
+                exit_code: None,
+                priority: None,
+            };
+            (id(n), info)
+        };
+        let target = LinkTarget::Local(id(1));
+        let info = Info {
+            runtimes: BTreeMap::from([runtime(1), runtime(2), runtime(3)]),
+            links: BTreeMap::from([
+                ("a".to_owned(), vec![target]),
+                ("b".to_owned(), vec![target]),
+            ]),
+            deduplicated_links: BTreeMap::from([
+                ("a".to_owned(), vec![target]),
+                ("b".to_owned(), vec![target]),
+            ]),
+            link_modes: BTreeMap::from([("a".to_owned(), LinkMatch::Prefix)]),
+            total_runtimes: 3,
+            total_links: 2,
+        };
+
+        let page = info.page(1, 1);
+        assert_eq!(page.runtimes.keys().collect::<Vec<_>>(), [&id(2)]);
+        assert_eq!(page.links.keys().collect::<Vec<_>>(), ["b"]);
+        assert_eq!(page.deduplicated_links.keys().collect::<Vec<_>>(), ["b"]);
+        assert!(page.link_modes.is_empty());
+        assert_eq!((page.total_runtimes, page.total_links), (3, 2));
+    }
+
+    #[test]
+    fn link_match() {
+        assert!(LinkMatch::Exact.matches("app::Speed", "app::Speed"));
+        assert!(!LinkMatch::Exact.matches("app::Speed", "app::SpeedLimit"));
+
+        assert!(LinkMatch::Prefix.matches("app::sensors::", "app::sensors::Speed"));
+        assert!(!LinkMatch::Prefix.matches("app::sensors::", "app::actuators::Brake"));
+
+        assert!(LinkMatch::Glob.matches("app::sensors::*", "app::sensors::Speed"));
+        assert!(LinkMatch::Glob.matches("app::*::Speed", "app::sensors::front::Speed"));
+        assert!(LinkMatch::Glob.matches("*Speed*", "app::SpeedLimit"));
+        assert!(LinkMatch::Glob.matches("app::Gear?", "app::Gear1"));
+        assert!(LinkMatch::Glob.matches("*", ""));
+        assert!(!LinkMatch::Glob.matches("app::*::Speed", "app::sensors::Speed2"));
+        assert!(!LinkMatch::Glob.matches("app::Gear?", "app::Gear"));
+        assert!(LinkMatch::Glob.matches("app::Grö?e", "app::Größe"));
+        assert!(LinkMatch::Glob.matches("*ß*", "app::Größe"));
+        assert!(!LinkMatch::Glob.matches("app::Gr?e", "app::Größe"));
+    }
 }
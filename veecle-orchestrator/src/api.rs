@@ -1,4 +1,6 @@
+use std::collections::BTreeMap;
 use std::fmt::Debug;
+use std::io::Write;
 use std::ops::ControlFlow;
 use std::os::unix::fs::PermissionsExt;
 use std::sync::Arc;
@@ -16,11 +18,15 @@ use tokio_util::codec::{Framed, LinesCodec};
 use tracing::Instrument;
 use veecle_net_utils::{AsyncSocketStream, UnresolvedMultiSocketAddress};
 use veecle_orchestrator_protocol::{
-    BINARY_TRANSFER_CHUNK_SIZE, Info, InstanceId, Request, Response,
+    BINARY_TRANSFER_CHUNK_SIZE, BinaryEncoding, ErrorKind, Info, InstanceId, LinkTarget,
+    MAX_BINARY_SIZE, Request, Response,
 };
+use zstd::stream::zio;
 
 use crate::distributor::Distributor;
+use crate::error::{KindError, bail_kind, error_kind};
 use crate::runtime::Conductor;
+use crate::state_file::StateFile;
 
 type Responder = Box<
     dyn FnOnce(
@@ -31,37 +37,203 @@ type Responder = Box<
         > + Send,
 >;
 
+/// Collects the [`Info`] about all runtime instances and links.
+pub(crate) async fn info(distributor: &Distributor, conductor: &Conductor) -> eyre::Result<Info> {
+    let runtimes = conductor.info().await?;
+    let links = distributor.info().await?;
+    Ok(Info {
+        total_runtimes: runtimes.len(),
+        total_links: links.links.len(),
+        runtimes,
+        links: links.links,
+        deduplicated_links: links.deduplicated_links,
+        link_modes: links.link_modes,
+    })
+}
+
+/// Checks that `target` can receive data, see `validate` on [`Request::Link`].
+async fn validate_link_target(target: LinkTarget, conductor: &Conductor) -> eyre::Result<()> {
+    match target {
+        LinkTarget::Local(id) => {
+            let runtimes = conductor.info().await?;
+            let Some(runtime) = runtimes.get(&id) else {
+                bail_kind!(NotFound, "instance id {id} was not registered");
+            };
+            let metadata = tokio::fs::metadata(&runtime.binary)
+                .await
+                .wrap_err_with(|| format!("reading metadata of '{}'", runtime.binary))?;
+            if !metadata.is_file() || metadata.permissions().mode() & 0o111 == 0 {
+                eyre::bail!(
+                    "binary '{}' of instance id {id} is not an executable file",
+                    runtime.binary
+                );
+            }
+            Ok(())
+        }
+        LinkTarget::Remote(address) => crate::external::probe(address).await,
+    }
+}
+
 /// Handles a [`Request::AddWithBinary`] message.
 ///
 /// Reads and verifies the binary data from the stream, then adds the instance to the conductor.
+#[expect(clippy::too_many_arguments)]
 async fn handle_add_with_binary(
     stream: &mut AsyncSocketStream,
-    conductor: Arc<Conductor>,
+    conductor: &Conductor,
     id: InstanceId,
     length: usize,
     hash: [u8; 32],
+    encoding: BinaryEncoding,
     privileged: bool,
+    env: BTreeMap<String, String>,
+    args: Vec<String>,
 ) -> eyre::Result<()> {
-    let path = read_binary_to_temp_file(stream, length, hash)
+    let path = read_binary_to_temp_file(stream, length, hash, encoding)
         .await
         .wrap_err("reading binary data")?;
 
     conductor
-        .add(id, path.into(), privileged)
+        .add(id, path.into(), privileged, env, args)
         .await
         .wrap_err("adding binary instance")?;
 
     Ok(())
 }
 
+/// Decodes binary data sent with a [`BinaryEncoding`] chunk by chunk.
+enum BinaryDecoder {
+    /// Passes the data through unchanged.
+    Raw {
+        /// How many bytes were passed through so far.
+        decoded: usize,
+
+        /// How many bytes may be passed through in total.
+        max_size: usize,
+    },
+
+    /// Decompresses a Zstandard stream, the decompressed data of each chunk is collected in the inner
+    /// [`DecodedBuffer`].
+    Zstd(zio::Writer<DecodedBuffer, zstd::stream::raw::Decoder<'static>>),
+}
+
+impl BinaryDecoder {
+    /// Creates a decoder for data encoded with `encoding` that fails once more than `max_size` bytes were decoded.
+    fn new(encoding: BinaryEncoding, max_size: usize) -> eyre::Result<Self> {
+        Ok(match encoding {
+            BinaryEncoding::Raw => Self::Raw {
+                decoded: 0,
+                max_size,
+            },
+            BinaryEncoding::Zstd => Self::Zstd(zio::Writer::new(
+                DecodedBuffer::new(max_size),
+                zstd::stream::raw::Decoder::new().wrap_err("creating zstd decoder")?,
+            )),
+        })
+    }
+
+    /// Decodes the next `chunk`, returning the decoded data that is available so far.
+    fn decode<'a>(&'a mut self, chunk: &'a [u8]) -> eyre::Result<&'a [u8]> {
+        match self {
+            Self::Raw { decoded, max_size } => {
+                *decoded += chunk.len();
+                check_decoded_size(*decoded, *max_size)?;
+                Ok(chunk)
+            }
+            Self::Zstd(decoder) => {
+                decoder.writer_mut().data.clear();
+                let result = decoder.write_all(chunk);
+                decoder.writer().check_size()?;
+                result.wrap_err("decompressing binary data")?;
+                Ok(&decoder.writer().data)
+            }
+        }
+    }
+
+    /// Returns the remaining decoded data after all chunks were passed to [`Self::decode`].
+    ///
+    /// Fails if the data ended within a Zstandard frame, i.e. it was truncated.
+    fn finish(&mut self) -> eyre::Result<&[u8]> {
+        match self {
+            Self::Raw { .. } => Ok(&[]),
+            Self::Zstd(decoder) => {
+                decoder.writer_mut().data.clear();
+                let result = decoder.finish();
+                decoder.writer().check_size()?;
+                result.wrap_err("decompressing binary data")?;
+                Ok(&decoder.writer().data)
+            }
+        }
+    }
+}
+
+/// Collects the data decompressed from a chunk, refusing to take more than `max_size` bytes in total.
+///
+/// This bounds the memory and disk space a small, highly compressed upload can use.
+struct DecodedBuffer {
+    /// The data decompressed from the current chunk.
+    data: Vec<u8>,
+
+    /// How many bytes were decompressed in total, including refused ones.
+    total: usize,
+
+    /// How many bytes may be decompressed in total.
+    max_size: usize,
+}
+
+impl DecodedBuffer {
+    fn new(max_size: usize) -> Self {
+        Self {
+            data: Vec::new(),
+            total: 0,
+            max_size,
+        }
+    }
+
+    /// Fails if more than `max_size` bytes were decompressed.
+    fn check_size(&self) -> eyre::Result<()> {
+        check_decoded_size(self.total, self.max_size)
+    }
+}
+
+impl Write for DecodedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.total = self.total.saturating_add(buf.len());
+        if self.total > self.max_size {
+            return Err(std::io::Error::other("maximum binary size exceeded"));
+        }
+        self.data.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Fails if `decoded` bytes exceed `max_size`.
+fn check_decoded_size(decoded: usize, max_size: usize) -> eyre::Result<()> {
+    if decoded > max_size {
+        bail_kind!(
+            InvalidRequest,
+            "binary data exceeds the maximum size of {max_size} bytes"
+        );
+    }
+    Ok(())
+}
+
 /// Reads and verifies binary data from a stream into a temporary executable file.
 ///
-/// Creates a new temporary file, reads `length` bytes from the stream, validates the SHA-256 hash,
-/// sets executable permissions, and returns a [`TempPath`] that will clean up the file when dropped.
+/// Creates a new temporary file, reads `length` bytes encoded with `encoding` from the stream, decodes them, validates
+/// the SHA-256 hash of the decoded data, sets executable permissions, and returns a [`TempPath`] that will clean up the
+/// file when dropped.
+///
+/// Fails as soon as more than [`MAX_BINARY_SIZE`] bytes were decoded.
 async fn read_binary_to_temp_file(
     stream: &mut AsyncSocketStream,
     length: usize,
     hash: [u8; 32],
+    encoding: BinaryEncoding,
 ) -> eyre::Result<TempPath> {
     let mut file = tokio::task::spawn_blocking(|| {
         Builder::new()
@@ -72,6 +244,7 @@ async fn read_binary_to_temp_file(
     })
     .await??;
 
+    let mut decoder = BinaryDecoder::new(encoding, MAX_BINARY_SIZE)?;
     let mut hasher = Sha256::new();
     let mut remaining = length;
     let mut buffer = [0u8; BINARY_TRANSFER_CHUNK_SIZE];
@@ -88,7 +261,7 @@ async fn read_binary_to_temp_file(
             eyre::bail!("connection closed before receiving all binary data");
         }
 
-        let chunk = &buffer[..bytes_read];
+        let chunk = decoder.decode(&buffer[..bytes_read])?;
         hasher.update(chunk);
         file.as_file_mut()
             .write_all(chunk)
@@ -98,9 +271,16 @@ async fn read_binary_to_temp_file(
         remaining -= bytes_read;
     }
 
+    let chunk = decoder.finish()?;
+    hasher.update(chunk);
+    file.as_file_mut()
+        .write_all(chunk)
+        .await
+        .wrap_err("writing binary data to temporary file")?;
+
     let computed_hash: [u8; 32] = hasher.finalize().into();
     if computed_hash != hash {
-        eyre::bail!("binary data hash verification failed");
+        bail_kind!(InvalidRequest, "binary data hash verification failed");
     }
 
     file.as_file_mut()
@@ -122,12 +302,15 @@ async fn read_binary_to_temp_file(
 #[tracing::instrument(skip_all, fields(request.variant))]
 async fn handle_request(
     request: &str,
-    distributor: &Distributor,
+    distributor: &Arc<Distributor>,
     conductor: &Arc<Conductor>,
+    state_file: Option<&Arc<StateFile>>,
 ) -> eyre::Result<(String, Option<Responder>)> {
     tracing::debug!(request.unparsed = %request);
 
-    let request: Request = serde_json::from_str(request).wrap_err("parsing request")?;
+    let request: Request = serde_json::from_str(request).map_err(|error| {
+        KindError::with_source(ErrorKind::InvalidRequest, "parsing request".into(), error)
+    })?;
 
     tracing::info!(request.parsed = ?request);
     tracing::Span::current().record(
@@ -140,15 +323,22 @@ async fn handle_request(
         Ok(serde_json::to_string(&Response::Ok(response))?)
     }
 
+    let mutating = !matches!(
+        request,
+        Request::Version | Request::Info | Request::InfoPage { .. } | Request::HealthCheck(_)
+    );
+
     let response = match request {
         Request::Version => encode(env!("CARGO_PKG_VERSION"))?,
         Request::Add {
             id,
             path,
             privileged,
+            env,
+            args,
         } => {
             conductor
-                .add(id, path.into(), privileged)
+                .add(id, path.into(), privileged, env, args)
                 .await
                 .wrap_err("adding instance")?;
             encode(())?
@@ -157,9 +347,14 @@ async fn handle_request(
             id,
             length,
             hash,
+            encoding,
             privileged,
+            env,
+            args,
         } => {
+            let distributor = Arc::clone(distributor);
             let conductor = Arc::clone(conductor);
+            let state_file = state_file.cloned();
 
             let responder: Responder = Box::new(move |mut stream| {
                 Box::pin(async move {
@@ -172,19 +367,30 @@ async fn handle_request(
                     // received any of the binary data into the read buffer.
                     match handle_add_with_binary(
                         stream.get_mut(),
-                        conductor,
+                        &conductor,
                         id,
                         length,
                         hash,
+                        encoding,
                         privileged,
+                        env,
+                        args,
                     )
                     .await
                     {
-                        Ok(()) => Ok(ControlFlow::Continue((stream, encode(())?))),
+                        Ok(()) => {
+                            if let Some(state_file) = state_file {
+                                state_file.save_or_warn(&distributor, &conductor).await;
+                            }
+                            Ok(ControlFlow::Continue((stream, encode(())?)))
+                        }
                         Err(error) => {
                             tracing::warn!(?error);
-                            let response = serde_json::to_string(&Response::<()>::err(&*error))
-                                .wrap_err("encoding error response")?;
+                            let response = serde_json::to_string(&Response::<()>::err_with_kind(
+                                error_kind(&error),
+                                &*error,
+                            ))
+                            .wrap_err("encoding error response")?;
                             Ok(ControlFlow::Continue((stream, response)))
                         }
                     }
@@ -204,21 +410,42 @@ async fn handle_request(
                 .wrap_err("starting instance")?;
             encode(())?
         }
-        Request::Stop(id) => {
-            conductor.stop(id).await.wrap_err("stopping instance")?;
-            encode(())?
-        }
-        Request::Link { type_name, to } => {
+        Request::Stop { id, grace } => encode(
+            conductor
+                .stop(id, grace)
+                .await
+                .wrap_err("stopping instance")?,
+        )?,
+        Request::HealthCheck(id) => encode(
+            conductor
+                .health(id)
+                .await
+                .wrap_err("checking instance health")?,
+        )?,
+        Request::Link {
+            type_name,
+            topic,
+            to,
+            dedup,
+            validate,
+            match_mode,
+        } => {
+            if validate {
+                validate_link_target(to, conductor)
+                    .await
+                    .wrap_err("validating link target")?;
+            }
+            let routing_key = veecle_ipc_protocol::routing_key(&type_name, topic.as_deref());
             distributor
-                .link(type_name, to)
+                .link(routing_key.into_owned(), to, dedup, match_mode)
                 .await
                 .wrap_err("linking instances")?;
             encode(())?
         }
-        Request::Info => encode(Info {
-            runtimes: conductor.info().await?,
-            links: distributor.info().await?,
-        })?,
+        Request::Info => encode(info(distributor, conductor).await?)?,
+        Request::InfoPage { offset, limit } => {
+            encode(info(distributor, conductor).await?.page(offset, limit))?
+        }
         Request::Clear => {
             conductor.clear().await;
             distributor.clear().await.wrap_err("clearing distributor")?;
@@ -226,14 +453,19 @@ async fn handle_request(
         }
     };
 
+    if mutating && let Some(state_file) = state_file {
+        state_file.save_or_warn(distributor, conductor).await;
+    }
+
     Ok((response, None))
 }
 
 /// Handles all API requests from a single client.
 async fn handle_client(
     stream: AsyncSocketStream,
-    distributor: &Distributor,
+    distributor: &Arc<Distributor>,
     conductor: &Arc<Conductor>,
+    state_file: Option<&Arc<StateFile>>,
 ) -> eyre::Result<()> {
     let mut stream = Framed::new(stream, LinesCodec::new());
 
@@ -245,7 +477,7 @@ async fn handle_client(
         .transpose()
         .wrap_err("receiving request")?
     {
-        match handle_request(&line, distributor, conductor).await {
+        match handle_request(&line, distributor, conductor, state_file).await {
             Ok((response, responder)) => {
                 stream.send(response).await.wrap_err("sending response")?;
                 if let Some(responder) = responder {
@@ -265,8 +497,11 @@ async fn handle_client(
             }
             Err(error) => {
                 tracing::warn!(?error, "error handling client request");
-                let response = serde_json::to_string(&Response::<()>::err(&*error))
-                    .wrap_err("encoding error response")?;
+                let response = serde_json::to_string(&Response::<()>::err_with_kind(
+                    error_kind(&error),
+                    &*error,
+                ))
+                .wrap_err("encoding error response")?;
                 stream
                     .send(response)
                     .await
@@ -286,6 +521,7 @@ pub async fn run(
     address: UnresolvedMultiSocketAddress,
     distributor: Arc<Distributor>,
     conductor: Arc<Conductor>,
+    state_file: Option<Arc<StateFile>>,
 ) -> eyre::Result<()> {
     let listener = address.bind_async().await.wrap_err("binding socket")?;
     let mut connection_ids = 0..u64::MAX;
@@ -296,9 +532,12 @@ pub async fn run(
         let connection_id = connection_ids.next().unwrap();
         let distributor = distributor.clone();
         let conductor = conductor.clone();
+        let state_file = state_file.clone();
         tokio::spawn(
             async move {
-                if let Err(error) = handle_client(stream, &distributor, &conductor).await {
+                if let Err(error) =
+                    handle_client(stream, &distributor, &conductor, state_file.as_ref()).await
+                {
                     tracing::error!(?error, "handling client failed");
                 }
             }
@@ -311,3 +550,89 @@ pub async fn run(
         );
     }
 }
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::io::Read;
+
+    use veecle_orchestrator_protocol::{BINARY_TRANSFER_CHUNK_SIZE, BinaryEncoding, ErrorKind};
+
+    use super::BinaryDecoder;
+    use crate::error::error_kind;
+
+    /// Decodes `encoded` in chunks of the size they are transferred in.
+    fn decode(encoding: BinaryEncoding, encoded: &[u8], max_size: usize) -> eyre::Result<Vec<u8>> {
+        let mut decoder = BinaryDecoder::new(encoding, max_size)?;
+        let mut decoded = Vec::new();
+        for chunk in encoded.chunks(BINARY_TRANSFER_CHUNK_SIZE) {
+            decoded.extend_from_slice(decoder.decode(chunk)?);
+        }
+        decoded.extend_from_slice(decoder.finish()?);
+        Ok(decoded)
+    }
+
+    /// Returns pseudo-random nibbles, which only compress to about half their size, so the compressed data still spans
+    /// several chunks.
+    fn binary() -> Vec<u8> {
+        let mut state = 1u32;
+        (0..100_000)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (state >> 28) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn round_trip() {
+        let binary = binary();
+        let compressed = zstd::encode_all(binary.as_slice(), 0).unwrap();
+        assert!(compressed.len() > 2 * BINARY_TRANSFER_CHUNK_SIZE);
+
+        assert_eq!(
+            decode(BinaryEncoding::Raw, &binary, binary.len()).unwrap(),
+            binary
+        );
+        assert_eq!(
+            decode(BinaryEncoding::Zstd, &compressed, binary.len()).unwrap(),
+            binary
+        );
+    }
+
+    #[test]
+    fn truncated_stream() {
+        let compressed = zstd::encode_all(binary().as_slice(), 0).unwrap();
+
+        let error = decode(
+            BinaryEncoding::Zstd,
+            &compressed[..compressed.len() - 1],
+            usize::MAX,
+        )
+        .unwrap_err();
+        assert_eq!(error.to_string(), "decompressing binary data");
+    }
+
+    #[test]
+    fn exceeds_max_size() {
+        let binary = binary();
+        let compressed = zstd::encode_all(binary.as_slice(), 0).unwrap();
+
+        for (encoding, encoded) in [
+            (BinaryEncoding::Raw, binary.as_slice()),
+            (BinaryEncoding::Zstd, compressed.as_slice()),
+        ] {
+            let error = decode(encoding, encoded, binary.len() - 1).unwrap_err();
+            assert_eq!(
+                error_kind(&error),
+                ErrorKind::InvalidRequest,
+                "{encoding:?}"
+            );
+        }
+
+        // Highly compressed data is rejected without decompressing all of it.
+        let zeros = zstd::encode_all(std::io::repeat(0).take(1 << 26), 0).unwrap();
+        let error = decode(BinaryEncoding::Zstd, &zeros, binary.len()).unwrap_err();
+        assert_eq!(error_kind(&error), ErrorKind::InvalidRequest);
+    }
+}
@@ -1,10 +1,13 @@
 use std::collections::BTreeMap;
 use std::collections::btree_map::Entry;
 use std::net::SocketAddr;
+use std::time::SystemTime;
 
 use tokio::sync::{mpsc, oneshot};
 use veecle_ipc_protocol::EncodedStorable;
-use veecle_orchestrator_protocol::{InstanceId, LinkTarget};
+use veecle_orchestrator_protocol::{InstanceId, LinkMatch, LinkTarget};
+
+use crate::error::bail_kind;
 
 /// Operations sent to the actor.
 #[derive(Debug)]
@@ -15,13 +18,28 @@ enum Command {
     },
 
     AddLink {
-        type_name: String,
+        routing_key: String,
         target: LinkTarget,
+        dedup: bool,
+        match_mode: LinkMatch,
         response_tx: oneshot::Sender<eyre::Result<()>>,
     },
 
     GetInfo {
-        response_tx: oneshot::Sender<BTreeMap<String, Vec<LinkTarget>>>,
+        response_tx: oneshot::Sender<LinksInfo>,
+    },
+
+    GetHeartbeats {
+        response_tx: oneshot::Sender<BTreeMap<InstanceId, SystemTime>>,
+    },
+
+    ResetInstance {
+        id: InstanceId,
+        response_tx: oneshot::Sender<()>,
+    },
+
+    GetMessageCounts {
+        response_tx: oneshot::Sender<BTreeMap<String, Vec<(LinkTarget, u64)>>>,
     },
 
     Clear {
@@ -29,9 +47,25 @@ enum Command {
     },
 }
 
+/// The configured links, as returned by [`Distributor::info`].
+#[derive(Debug)]
+pub struct LinksInfo {
+    /// All links, for a specific routing key, to a list of target instances.
+    pub links: BTreeMap<String, Vec<LinkTarget>>,
+
+    /// The subset of `links` that skip unchanged values.
+    pub deduplicated_links: BTreeMap<String, Vec<LinkTarget>>,
+
+    /// The match modes of the keys of `links` that are patterns.
+    pub link_modes: BTreeMap<String, LinkMatch>,
+}
+
 /// Handles routing `EncodedStorable` messages between different instances based on the configured links.
+///
+/// Also tracks when each instance last sent a heartbeat.
 pub struct Distributor {
     input_tx: mpsc::Sender<EncodedStorable>,
+    heartbeat_tx: mpsc::Sender<InstanceId>,
     command_tx: mpsc::Sender<Command>,
     _task: tokio::task::JoinHandle<eyre::Result<()>>,
 }
@@ -49,18 +83,20 @@ impl Distributor {
     pub fn new(external_output_tx: Option<mpsc::Sender<(SocketAddr, EncodedStorable)>>) -> Self {
         let (input_tx, input_rx) =
             mpsc::channel::<EncodedStorable>(crate::ARBITRARY_CHANNEL_BUFFER);
+        let (heartbeat_tx, heartbeat_rx) = mpsc::channel(crate::ARBITRARY_CHANNEL_BUFFER);
         let (command_tx, command_rx) = mpsc::channel(crate::ARBITRARY_CHANNEL_BUFFER);
 
         // This is using an actor model, a single task owns the configuration and receives both the messages to
         // route and updates to the configuration.
         let _task = tokio::task::spawn(async move {
-            Inner::new(input_rx, command_rx, external_output_tx)
+            Inner::new(input_rx, heartbeat_rx, command_rx, external_output_tx)
                 .run()
                 .await
         });
 
         Self {
             input_tx,
+            heartbeat_tx,
             command_tx,
             _task,
         }
@@ -71,6 +107,11 @@ impl Distributor {
         self.input_tx.clone()
     }
 
+    /// Returns a sender that can be used to record a heartbeat received from an instance.
+    pub fn heartbeat_sender(&self) -> mpsc::Sender<InstanceId> {
+        self.heartbeat_tx.clone()
+    }
+
     /// Registers a new known runtime instance and returns a channel that will receive any messages routed to it.
     pub async fn channel(&self, id: InstanceId) -> eyre::Result<mpsc::Receiver<EncodedStorable>> {
         let (response_tx, response_rx) = oneshot::channel();
@@ -84,14 +125,31 @@ impl Distributor {
         Ok(rx)
     }
 
-    /// Adds a link to instance `target` for any IPC messages tagged with `type_name`.
-    pub async fn link(&self, type_name: String, target: LinkTarget) -> eyre::Result<()> {
+    /// Adds a link to instance `target` for any IPC messages with `routing_key`.
+    ///
+    /// See [`veecle_ipc_protocol::routing_key`].
+    ///
+    /// If `dedup` is set, messages whose value is identical to the last value forwarded over this link are dropped.
+    /// This trades the delivery of legitimately repeated values for less traffic, see
+    /// [`veecle_orchestrator_protocol::Request::Link`].
+    ///
+    /// With a `match_mode` other than [`LinkMatch::Exact`], the type name of `routing_key` is a pattern, see
+    /// [`LinkMatch`].
+    pub async fn link(
+        &self,
+        routing_key: String,
+        target: LinkTarget,
+        dedup: bool,
+        match_mode: LinkMatch,
+    ) -> eyre::Result<()> {
         let (response_tx, response_rx) = oneshot::channel();
 
         self.command_tx
             .send(Command::AddLink {
-                type_name,
+                routing_key,
                 target,
+                dedup,
+                match_mode,
                 response_tx,
             })
             .await?;
@@ -102,7 +160,7 @@ impl Distributor {
     }
 
     /// Returns info about the current state.
-    pub async fn info(&self) -> eyre::Result<BTreeMap<String, Vec<LinkTarget>>> {
+    pub async fn info(&self) -> eyre::Result<LinksInfo> {
         let (response_tx, response_rx) = oneshot::channel();
 
         self.command_tx
@@ -114,7 +172,52 @@ impl Distributor {
         Ok(info)
     }
 
-    /// Clears all links and instance registrations.
+    /// Returns when each instance last sent a heartbeat, instances that never sent one are omitted.
+    pub async fn heartbeats(&self) -> eyre::Result<BTreeMap<InstanceId, SystemTime>> {
+        let (response_tx, response_rx) = oneshot::channel();
+
+        self.command_tx
+            .send(Command::GetHeartbeats { response_tx })
+            .await?;
+
+        let heartbeats = response_rx.await?;
+
+        Ok(heartbeats)
+    }
+
+    /// Forgets what is known about the process of instance `id`, because it is restarted or the instance is removed.
+    ///
+    /// Its last heartbeat no longer counts towards the health of the new process, and deduplicated links to it forward
+    /// the next value even if it is unchanged, as the new process has not received it yet.
+    pub async fn reset_instance(&self, id: InstanceId) -> eyre::Result<()> {
+        let (response_tx, response_rx) = oneshot::channel();
+
+        self.command_tx
+            .send(Command::ResetInstance { id, response_tx })
+            .await?;
+
+        response_rx.await?;
+
+        Ok(())
+    }
+
+    /// Returns how many messages were forwarded over each link, for a specific routing key, to a list of target
+    /// instances.
+    ///
+    /// Values skipped by deduplicated links are not counted.
+    pub async fn message_counts(&self) -> eyre::Result<BTreeMap<String, Vec<(LinkTarget, u64)>>> {
+        let (response_tx, response_rx) = oneshot::channel();
+
+        self.command_tx
+            .send(Command::GetMessageCounts { response_tx })
+            .await?;
+
+        let message_counts = response_rx.await?;
+
+        Ok(message_counts)
+    }
+
+    /// Clears all links, instance registrations and heartbeats.
     pub async fn clear(&self) -> eyre::Result<()> {
         let (response_tx, response_rx) = oneshot::channel();
 
@@ -126,53 +229,131 @@ impl Distributor {
     }
 }
 
+/// A single link from a routing key to a target instance.
+#[derive(Debug)]
+struct Link {
+    target: LinkTarget,
+
+    /// Whether to skip values identical to the last one forwarded over this link.
+    dedup: bool,
+
+    /// The encoded last value forwarded over this link, only tracked if `dedup` is set.
+    ///
+    /// Tracked per link rather than per routing key so that a newly added link always receives the next value.
+    /// The value itself is kept rather than a hash of it, so a changed value is never mistaken for the last one.
+    last_value: Option<String>,
+
+    /// How many messages have been forwarded over this link.
+    forwarded: u64,
+}
+
+/// The type name pattern and topic of links that don't match exactly, see [`LinkMatch`].
+#[derive(Debug)]
+struct Pattern {
+    match_mode: LinkMatch,
+    type_name: String,
+    topic: Option<String>,
+}
+
+impl Pattern {
+    /// Returns whether data of type `type_name` tagged with `topic` is routed by links with this pattern.
+    fn matches(&self, type_name: &str, topic: Option<&str>) -> bool {
+        self.topic.as_deref() == topic && self.match_mode.matches(&self.type_name, type_name)
+    }
+}
+
 /// The actual [`Distributor`] state machine, running in a background task and accepting commands over channels from its
 /// façade.
 struct Inner {
     /// Input messages from both local and remote instances.
     input_rx: mpsc::Receiver<EncodedStorable>,
 
+    /// Heartbeats received from local instances.
+    heartbeat_rx: mpsc::Receiver<InstanceId>,
+
     /// Input commands to reconfigure the links.
     command_rx: mpsc::Receiver<Command>,
 
     /// Output messages to any remote instance.
     external_output_tx: Option<mpsc::Sender<(SocketAddr, EncodedStorable)>>,
 
-    /// The links, for a specific data type, to a list of target instances.
-    links: BTreeMap<String, Vec<LinkTarget>>,
+    /// The links, for a specific routing key (data type and topic), to a list of target instances.
+    ///
+    /// For links with a pattern, this is the routing key of the pattern, which is also a key in `patterns`.
+    links: BTreeMap<String, Vec<Link>>,
+
+    /// The patterns of the keys of `links` that don't match exactly.
+    patterns: BTreeMap<String, Pattern>,
 
     /// How to actually send a message to the chosen target instances.
     instance_txs: BTreeMap<InstanceId, mpsc::Sender<EncodedStorable>>,
+
+    /// When each instance last sent a heartbeat.
+    heartbeats: BTreeMap<InstanceId, SystemTime>,
 }
 
 impl Inner {
     fn new(
         input_rx: mpsc::Receiver<EncodedStorable>,
+        heartbeat_rx: mpsc::Receiver<InstanceId>,
         command_rx: mpsc::Receiver<Command>,
         external_output_tx: Option<mpsc::Sender<(SocketAddr, EncodedStorable)>>,
     ) -> Self {
         Self {
             input_rx,
+            heartbeat_rx,
             command_rx,
             external_output_tx,
             links: BTreeMap::new(),
+            patterns: BTreeMap::new(),
             instance_txs: BTreeMap::new(),
+            heartbeats: BTreeMap::new(),
         }
     }
 
+    /// Returns the key of the links `storable` is routed by, see [`LinkMatch`] for the precedence of patterns.
+    fn links_key(&self, storable: &EncodedStorable) -> Option<String> {
+        let routing_key = storable.routing_key();
+        if self.links.contains_key(&*routing_key) && !self.patterns.contains_key(&*routing_key) {
+            return Some(routing_key.into_owned());
+        }
+
+        self.patterns
+            .iter()
+            .filter(|(_, pattern)| pattern.matches(&storable.type_name, storable.topic.as_deref()))
+            .max_by_key(|(_, pattern)| {
+                (
+                    pattern.match_mode == LinkMatch::Prefix,
+                    pattern.type_name.len(),
+                )
+            })
+            .map(|(key, _)| key.clone())
+    }
+
     async fn route_message(&mut self, storable: EncodedStorable) -> eyre::Result<()> {
-        let type_name = &storable.type_name;
-        let Some(targets) = self.links.get(&**type_name) else {
-            tracing::warn!(%type_name, "no registered ipc link");
+        let routing_key = storable.routing_key();
+        let Some(links) = self
+            .links_key(&storable)
+            .and_then(|key| self.links.get_mut(&key))
+        else {
+            tracing::warn!(%routing_key, "no registered ipc link");
             return Ok(());
         };
 
-        for target in targets {
-            match target {
+        for link in links {
+            if link.dedup {
+                if link.last_value.as_ref() == Some(&storable.value) {
+                    tracing::trace!(%routing_key, target = %link.target, "skipping unchanged value");
+                    continue;
+                }
+                link.last_value = Some(storable.value.clone());
+            }
+
+            match &link.target {
                 LinkTarget::Local(id) => {
                     let Some(sender) = self.instance_txs.get(id) else {
                         // Should be unreachable as this is checked in `add_link`.
-                        tracing::warn!(%type_name, %id, "no instance");
+                        tracing::warn!(%routing_key, %id, "no instance");
                         continue;
                     };
                     sender.send(storable.clone()).await?;
@@ -186,6 +367,8 @@ impl Inner {
                     sender.send((address, storable.clone())).await?;
                 }
             }
+
+            link.forwarded += 1;
         }
 
         Ok(())
@@ -193,7 +376,7 @@ impl Inner {
 
     fn add_instance(&mut self, id: InstanceId) -> eyre::Result<mpsc::Receiver<EncodedStorable>> {
         let Entry::Vacant(entry) = self.instance_txs.entry(id) else {
-            eyre::bail!("instance id {id} already registered");
+            bail_kind!(AlreadyExists, "instance id {id} already registered");
         };
 
         let (tx, rx) = mpsc::channel(crate::ARBITRARY_CHANNEL_BUFFER);
@@ -201,13 +384,18 @@ impl Inner {
         Ok(rx)
     }
 
-    fn add_link(&mut self, type_name: String, target: LinkTarget) -> eyre::Result<()> {
+    fn add_link(
+        &mut self,
+        routing_key: String,
+        target: LinkTarget,
+        dedup: bool,
+        match_mode: LinkMatch,
+    ) -> eyre::Result<()> {
         match &target {
             LinkTarget::Local(id) => {
-                eyre::ensure!(
-                    self.instance_txs.contains_key(id),
-                    "instance id {target} was not registered"
-                );
+                if !self.instance_txs.contains_key(id) {
+                    bail_kind!(NotFound, "instance id {target} was not registered");
+                }
             }
             LinkTarget::Remote(_) => {
                 eyre::ensure!(
@@ -217,11 +405,90 @@ impl Inner {
             }
         }
 
-        self.links.entry(type_name).or_default().push(target);
+        let existing_mode = self
+            .patterns
+            .get(&routing_key)
+            .map_or(LinkMatch::Exact, |pattern| pattern.match_mode);
+        if self.links.contains_key(&routing_key) && existing_mode != match_mode {
+            bail_kind!(
+                AlreadyExists,
+                "{routing_key} is already linked with match mode {existing_mode:?}"
+            );
+        }
+
+        if match_mode != LinkMatch::Exact {
+            // Type names can't contain `#`, so the topic starts at the first one.
+            let (type_name, topic) = match routing_key.split_once('#') {
+                Some((type_name, topic)) => (type_name, Some(topic.to_owned())),
+                None => (routing_key.as_str(), None),
+            };
+            let pattern = Pattern {
+                match_mode,
+                type_name: type_name.to_owned(),
+                topic,
+            };
+            self.patterns.insert(routing_key.clone(), pattern);
+        }
+
+        self.links.entry(routing_key).or_default().push(Link {
+            target,
+            dedup,
+            last_value: None,
+            forwarded: 0,
+        });
 
         Ok(())
     }
 
+    fn reset_instance(&mut self, id: InstanceId) {
+        self.heartbeats.remove(&id);
+        for link in self.links.values_mut().flatten() {
+            if link.target == LinkTarget::Local(id) {
+                link.last_value = None;
+            }
+        }
+    }
+
+    fn links_info(&self) -> LinksInfo {
+        let targets = |filter: fn(&Link) -> bool| {
+            self.links
+                .iter()
+                .map(|(routing_key, links)| {
+                    let targets = links
+                        .iter()
+                        .filter(|link| filter(link))
+                        .map(|link| link.target)
+                        .collect::<Vec<_>>();
+                    (routing_key.clone(), targets)
+                })
+                .filter(|(_, targets)| !targets.is_empty())
+                .collect()
+        };
+
+        LinksInfo {
+            links: targets(|_| true),
+            deduplicated_links: targets(|link| link.dedup),
+            link_modes: self
+                .patterns
+                .iter()
+                .map(|(routing_key, pattern)| (routing_key.clone(), pattern.match_mode))
+                .collect(),
+        }
+    }
+
+    fn message_counts(&self) -> BTreeMap<String, Vec<(LinkTarget, u64)>> {
+        self.links
+            .iter()
+            .map(|(routing_key, links)| {
+                let counts = links
+                    .iter()
+                    .map(|link| (link.target, link.forwarded))
+                    .collect();
+                (routing_key.clone(), counts)
+            })
+            .collect()
+    }
+
     fn apply_command(&mut self, command: Command) {
         match command {
             Command::AddInstance { id, response_tx } => {
@@ -229,19 +496,33 @@ impl Inner {
                 let _ = response_tx.send(response);
             }
             Command::AddLink {
-                type_name,
+                routing_key,
                 target,
+                dedup,
+                match_mode,
                 response_tx,
             } => {
-                let response = self.add_link(type_name, target);
+                let response = self.add_link(routing_key, target, dedup, match_mode);
                 let _ = response_tx.send(response);
             }
             Command::GetInfo { response_tx } => {
-                let _ = response_tx.send(self.links.clone());
+                let _ = response_tx.send(self.links_info());
+            }
+            Command::GetHeartbeats { response_tx } => {
+                let _ = response_tx.send(self.heartbeats.clone());
+            }
+            Command::ResetInstance { id, response_tx } => {
+                self.reset_instance(id);
+                let _ = response_tx.send(());
+            }
+            Command::GetMessageCounts { response_tx } => {
+                let _ = response_tx.send(self.message_counts());
             }
             Command::Clear { response_tx } => {
                 self.links.clear();
+                self.patterns.clear();
                 self.instance_txs.clear();
+                self.heartbeats.clear();
                 let _ = response_tx.send(());
             }
         }
@@ -255,6 +536,11 @@ impl Inner {
                     self.route_message(storable).await?;
                 }
 
+                id = self.heartbeat_rx.recv() => {
+                    let Some(id) = id else { break };
+                    self.heartbeats.insert(id, SystemTime::now());
+                }
+
                 command = self.command_rx.recv() => {
                     let Some(command) = command else { break };
                     self.apply_command(command);
@@ -265,3 +551,237 @@ impl Inner {
         Ok(())
     }
 }
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::borrow::Cow;
+
+    use tokio::sync::mpsc;
+    use veecle_ipc_protocol::EncodedStorable;
+    use veecle_orchestrator_protocol::{InstanceId, LinkMatch, LinkTarget};
+
+    use super::{Distributor, Inner};
+
+    /// Returns a state machine with a single registered instance to link to.
+    fn inner() -> (Inner, LinkTarget) {
+        let (_, input_rx) = mpsc::channel(1);
+        let (_, heartbeat_rx) = mpsc::channel(1);
+        let (_, command_rx) = mpsc::channel(1);
+        let mut inner = Inner::new(input_rx, heartbeat_rx, command_rx, None);
+
+        let id = InstanceId::new();
+        inner.add_instance(id).unwrap();
+
+        (inner, LinkTarget::Local(id))
+    }
+
+    fn storable(type_name: &'static str, topic: Option<&'static str>) -> EncodedStorable {
+        EncodedStorable {
+            type_name: Cow::Borrowed(type_name),
+            topic: topic.map(Cow::Borrowed),
+            schema_version: 0,
+            value: "null".to_owned(),
+        }
+    }
+
+    #[test]
+    fn most_specific_links_win() {
+        let (mut inner, target) = inner();
+        let speed = storable("app::sensors::Speed", None);
+
+        // Each added link is more specific than the previous ones.
+        for (routing_key, match_mode) in [
+            ("*", LinkMatch::Glob),
+            ("app::*::Speed", LinkMatch::Glob),
+            ("app::", LinkMatch::Prefix),
+            ("app::sensors::", LinkMatch::Prefix),
+            ("app::sensors::Speed", LinkMatch::Exact),
+        ] {
+            inner
+                .add_link(routing_key.to_owned(), target, false, match_mode)
+                .unwrap();
+            assert_eq!(inner.links_key(&speed).as_deref(), Some(routing_key));
+        }
+
+        // Less specific links don't take over.
+        inner
+            .add_link(
+                "app::sensors::S".to_owned(),
+                target,
+                false,
+                LinkMatch::Prefix,
+            )
+            .unwrap();
+        assert_eq!(
+            inner.links_key(&speed).as_deref(),
+            Some("app::sensors::Speed")
+        );
+
+        assert_eq!(
+            inner.links_key(&storable("other::Speed", None)).as_deref(),
+            Some("*")
+        );
+    }
+
+    #[test]
+    fn prefix_wins_over_longer_glob() {
+        let (mut inner, target) = inner();
+        inner
+            .add_link("app::sensors::*".to_owned(), target, false, LinkMatch::Glob)
+            .unwrap();
+        inner
+            .add_link("app::".to_owned(), target, false, LinkMatch::Prefix)
+            .unwrap();
+
+        assert_eq!(
+            inner
+                .links_key(&storable("app::sensors::Speed", None))
+                .as_deref(),
+            Some("app::")
+        );
+    }
+
+    #[test]
+    fn last_key_wins_ties() {
+        let (mut inner, target) = inner();
+        // Added in the reverse of their key order, which is what breaks the tie.
+        for routing_key in ["app::*", "*eed"] {
+            inner
+                .add_link(routing_key.to_owned(), target, false, LinkMatch::Glob)
+                .unwrap();
+        }
+
+        assert_eq!(
+            inner
+                .links_key(&storable("app::sensors::Speed", None))
+                .as_deref(),
+            Some("app::*")
+        );
+    }
+
+    #[test]
+    fn topics_must_match() {
+        let (mut inner, target) = inner();
+        inner
+            .add_link(
+                "app::Speed#front".to_owned(),
+                target,
+                false,
+                LinkMatch::Exact,
+            )
+            .unwrap();
+        inner
+            .add_link("app::#rear".to_owned(), target, false, LinkMatch::Prefix)
+            .unwrap();
+        inner
+            .add_link("app::".to_owned(), target, false, LinkMatch::Prefix)
+            .unwrap();
+
+        let key = |topic| inner.links_key(&storable("app::Speed", topic));
+        assert_eq!(key(Some("front")).as_deref(), Some("app::Speed#front"));
+        assert_eq!(key(Some("rear")).as_deref(), Some("app::#rear"));
+        assert_eq!(key(None).as_deref(), Some("app::"));
+        // A pattern without a topic doesn't match data with one.
+        assert_eq!(key(Some("left")), None);
+    }
+
+    #[tokio::test]
+    async fn deduplicates_per_link_until_reset() {
+        let (_, input_rx) = mpsc::channel(1);
+        let (_, heartbeat_rx) = mpsc::channel(1);
+        let (_, command_rx) = mpsc::channel(1);
+        let mut inner = Inner::new(input_rx, heartbeat_rx, command_rx, None);
+        let first = InstanceId::new();
+        let second = InstanceId::new();
+        let mut first_rx = inner.add_instance(first).unwrap();
+        let mut second_rx = inner.add_instance(second).unwrap();
+
+        let speed = |value: &str| EncodedStorable {
+            value: value.to_owned(),
+            ..storable("app::Speed", None)
+        };
+        let received = |rx: &mut mpsc::Receiver<EncodedStorable>| {
+            std::iter::from_fn(|| rx.try_recv().ok())
+                .map(|storable| storable.value)
+                .collect::<Vec<_>>()
+        };
+
+        inner
+            .add_link(
+                "app::Speed".to_owned(),
+                LinkTarget::Local(first),
+                true,
+                LinkMatch::Exact,
+            )
+            .unwrap();
+        inner.route_message(speed("1")).await.unwrap();
+        inner.route_message(speed("1")).await.unwrap();
+        assert_eq!(received(&mut first_rx), ["1"]);
+
+        // A link added later receives the current value even though the other link already forwarded it.
+        inner
+            .add_link(
+                "app::Speed".to_owned(),
+                LinkTarget::Local(second),
+                true,
+                LinkMatch::Exact,
+            )
+            .unwrap();
+        inner.route_message(speed("1")).await.unwrap();
+        assert!(received(&mut first_rx).is_empty());
+        assert_eq!(received(&mut second_rx), ["1"]);
+
+        inner.route_message(speed("2")).await.unwrap();
+        assert_eq!(received(&mut first_rx), ["2"]);
+        assert_eq!(received(&mut second_rx), ["2"]);
+
+        // A restarted instance receives the unchanged value again, other links stay deduplicated.
+        inner.reset_instance(first);
+        inner.route_message(speed("2")).await.unwrap();
+        assert_eq!(received(&mut first_rx), ["2"]);
+        assert!(received(&mut second_rx).is_empty());
+    }
+
+    #[tokio::test]
+    async fn routes_to_most_specific_links_only() {
+        let distributor = Distributor::new(None);
+        let exact = InstanceId::new();
+        let prefix = InstanceId::new();
+        let mut exact_rx = distributor.channel(exact).await.unwrap();
+        let mut prefix_rx = distributor.channel(prefix).await.unwrap();
+
+        distributor
+            .link(
+                "app::Speed".to_owned(),
+                LinkTarget::Local(exact),
+                false,
+                LinkMatch::Exact,
+            )
+            .await
+            .unwrap();
+        distributor
+            .link(
+                "app::".to_owned(),
+                LinkTarget::Local(prefix),
+                false,
+                LinkMatch::Prefix,
+            )
+            .await
+            .unwrap();
+
+        let sender = distributor.sender();
+        sender.send(storable("app::Speed", None)).await.unwrap();
+        sender.send(storable("app::Gear", None)).await.unwrap();
+
+        assert_eq!(exact_rx.recv().await.unwrap().type_name, "app::Speed");
+        assert_eq!(prefix_rx.recv().await.unwrap().type_name, "app::Gear");
+
+        // Routing a message, including counting it, finishes before the next command is handled.
+        let counts = distributor.message_counts().await.unwrap();
+        assert_eq!(counts["app::Speed"], [(LinkTarget::Local(exact), 1)]);
+        assert_eq!(counts["app::"], [(LinkTarget::Local(prefix), 1)]);
+        assert!(exact_rx.try_recv().is_err());
+        assert!(prefix_rx.try_recv().is_err());
+    }
+}
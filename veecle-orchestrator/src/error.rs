@@ -0,0 +1,68 @@
+//! Tags errors with an [`ErrorKind`] so they can be reported to API clients.
+
+use veecle_orchestrator_protocol::ErrorKind;
+
+/// An error message tagged with the [`ErrorKind`] it should be reported as.
+#[derive(Debug, thiserror::Error)]
+#[error("{message}")]
+pub(crate) struct KindError {
+    kind: ErrorKind,
+    message: String,
+    #[source]
+    source: Option<Box<dyn std::error::Error + Send + Sync + 'static>>,
+}
+
+impl KindError {
+    /// Returns a new `KindError` with the given kind and message.
+    pub(crate) fn new(kind: ErrorKind, message: String) -> Self {
+        Self {
+            kind,
+            message,
+            source: None,
+        }
+    }
+
+    /// Returns a new `KindError` with the given kind and message, caused by `source`.
+    pub(crate) fn with_source(
+        kind: ErrorKind,
+        message: String,
+        source: impl std::error::Error + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            kind,
+            message,
+            source: Some(Box::new(source)),
+        }
+    }
+}
+
+/// Returns an [`eyre::Report`] tagged with an [`ErrorKind`] from the current function, like [`eyre::bail!`].
+macro_rules! bail_kind {
+    ($kind:ident, $($arg:tt)+) => {
+        return Err($crate::error::KindError::new(
+            veecle_orchestrator_protocol::ErrorKind::$kind,
+            format!($($arg)+),
+        )
+        .into())
+    };
+}
+
+pub(crate) use bail_kind;
+
+/// Finds the [`ErrorKind`] to report for `error`.
+///
+/// Uses the first [`KindError`] in the chain of sources, falling back to [`ErrorKind::PermissionDenied`] for IO
+/// permission errors and [`ErrorKind::Other`] for anything else.
+pub(crate) fn error_kind(error: &eyre::Report) -> ErrorKind {
+    error
+        .chain()
+        .find_map(|error| {
+            if let Some(error) = error.downcast_ref::<KindError>() {
+                return Some(error.kind);
+            }
+            let error = error.downcast_ref::<std::io::Error>()?;
+            (error.kind() == std::io::ErrorKind::PermissionDenied)
+                .then_some(ErrorKind::PermissionDenied)
+        })
+        .unwrap_or(ErrorKind::Other)
+}
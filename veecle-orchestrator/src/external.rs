@@ -1,7 +1,11 @@
-use std::net::SocketAddr;
+use std::io::ErrorKind;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
 
+use eyre::WrapErr;
 use tokio::net::UdpSocket;
 use tokio::sync::mpsc;
+use tokio::time::timeout;
 use veecle_ipc_protocol::EncodedStorable;
 use veecle_net_utils::UnresolvedSocketAddress;
 
@@ -21,6 +25,10 @@ pub async fn run(
         tokio::select! {
             received = socket.recv(&mut buffer) => {
                 match received {
+                    // Sent by `probe`.
+                    Ok(0) => {
+                        tracing::trace!("ignoring empty probe datagram");
+                    }
                     Ok(length) => {
                         match serde_json::from_slice(&buffer[..length]) {
                             Ok(storable) => {
@@ -53,3 +61,36 @@ pub async fn run(
         }
     }
 }
+
+/// How long to wait for a remote host to reject a probe, see [`probe`].
+const PROBE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Checks that a remote orchestrator at `address` does not reject datagrams.
+///
+/// Sends an empty datagram (ignored by [`run`]) from a connected socket, if nothing listens on the port the remote
+/// host answers with an ICMP "port unreachable" that surfaces as a connection refused error. UDP does not acknowledge
+/// datagrams though, so a host that silently drops them is assumed to be reachable.
+#[tracing::instrument]
+pub async fn probe(address: SocketAddr) -> eyre::Result<()> {
+    let unspecified = match address {
+        SocketAddr::V4(_) => SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0)),
+        SocketAddr::V6(_) => SocketAddr::from((Ipv6Addr::UNSPECIFIED, 0)),
+    };
+    let socket = UdpSocket::bind(unspecified).await?;
+    socket
+        .connect(address)
+        .await
+        .wrap_err_with(|| format!("connecting to {address}"))?;
+    socket
+        .send(&[])
+        .await
+        .wrap_err_with(|| format!("sending probe to {address}"))?;
+
+    match timeout(PROBE_TIMEOUT, socket.recv(&mut [0])).await {
+        Ok(Err(error)) if error.kind() == ErrorKind::ConnectionRefused => {
+            Err(error).wrap_err_with(|| format!("{address} rejected the probe"))
+        }
+        Ok(Err(error)) => Err(error).wrap_err_with(|| format!("probing {address}")),
+        Ok(Ok(_)) | Err(_) => Ok(()),
+    }
+}
@@ -1,10 +1,13 @@
 //! Veecle OS Orchestrator.
 
 #![forbid(unsafe_code)]
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
 
 use std::sync::Arc;
 
+use camino::Utf8PathBuf;
 use clap::Parser;
+use eyre::WrapErr;
 use tracing_subscriber::EnvFilter;
 use tracing_subscriber::filter::LevelFilter;
 use tracing_subscriber::layer::SubscriberExt;
@@ -12,13 +15,17 @@ use veecle_net_utils::{UnresolvedMultiSocketAddress, UnresolvedSocketAddress};
 
 use self::distributor::Distributor;
 use self::runtime::Conductor;
+use self::state_file::StateFile;
 use self::telemetry::Exporter;
 
 mod api;
 mod distributor;
+mod error;
 mod external;
 mod eyre_tracing_error;
+mod metrics;
 mod runtime;
+mod state_file;
 mod telemetry;
 
 #[derive(Parser)]
@@ -32,6 +39,14 @@ struct Arguments {
 
     #[arg(long, env = "VEECLE_TELEMETRY_SOCKET")]
     telemetry_socket: Option<UnresolvedSocketAddress>,
+
+    /// A file to persist the added instances and links to, they will be restored from it on startup.
+    #[arg(long, env = "VEECLE_ORCHESTRATOR_STATE_FILE")]
+    state_file: Option<Utf8PathBuf>,
+
+    /// An address to serve metrics in the Prometheus text format on over HTTP, at `/metrics`.
+    #[arg(long, env = "VEECLE_ORCHESTRATOR_METRICS_ADDRESS")]
+    metrics_address: Option<UnresolvedSocketAddress>,
 }
 
 // 16 arbitrarily chosen for channel sizing because it looks nice.
@@ -82,10 +97,27 @@ async fn main() -> eyre::Result<()> {
 
     let conductor = Arc::new(Conductor::new(distributor.clone(), exporter.clone())?);
 
+    let state_file = args.state_file.map(|path| Arc::new(StateFile::new(path)));
+    if let Some(state_file) = &state_file {
+        state_file
+            .restore(&distributor, &conductor)
+            .await
+            .wrap_err("restoring orchestrator state")?;
+    }
+
+    let metrics = args.metrics_address.map(|address| {
+        tokio::spawn(metrics::run(
+            address,
+            distributor.clone(),
+            conductor.clone(),
+        ))
+    });
+
     let api = tokio::spawn(api::run(
         args.control_socket,
         distributor.clone(),
         conductor.clone(),
+        state_file,
     ));
 
     let mut sigint = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::interrupt())?;
@@ -103,6 +135,9 @@ async fn main() -> eyre::Result<()> {
     if let Some(external) = external {
         external.abort();
     }
+    if let Some(metrics) = metrics {
+        metrics.abort();
+    }
     api.abort();
 
     conductor.shutdown().await;
@@ -0,0 +1,240 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Arc;
+use std::time::Duration;
+
+use eyre::{OptionExt, WrapErr};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use veecle_net_utils::UnresolvedSocketAddress;
+use veecle_orchestrator_protocol::{InstanceId, LinkTarget};
+
+use crate::distributor::Distributor;
+use crate::runtime::{Conductor, InstanceMetrics};
+
+/// How long a client has to send its request before the connection is closed.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The content type of the Prometheus text exposition format.
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Accepts HTTP connections on `address` and answers `GET /metrics` requests with metrics about the instances and
+/// links in the Prometheus text format.
+#[tracing::instrument(skip_all, fields(%address))]
+pub async fn run(
+    address: UnresolvedSocketAddress,
+    distributor: Arc<Distributor>,
+    conductor: Arc<Conductor>,
+) -> eyre::Result<()> {
+    let listener = TcpListener::bind(address.as_to_socket_addrs())
+        .await
+        .wrap_err_with(|| format!("binding metrics address {address}"))?;
+
+    tracing::info!("listening");
+
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        let distributor = distributor.clone();
+        let conductor = conductor.clone();
+        tokio::spawn(async move {
+            let response = tokio::time::timeout(
+                REQUEST_TIMEOUT,
+                handle_connection(stream, &distributor, &conductor),
+            )
+            .await;
+            match response {
+                Ok(Ok(())) => {}
+                Ok(Err(error)) => tracing::debug!(%peer, "metrics request failed: {error:?}"),
+                Err(_) => tracing::debug!(%peer, "metrics request timed out"),
+            }
+        });
+    }
+}
+
+/// Reads a single HTTP request from `stream` and writes the response.
+async fn handle_connection(
+    mut stream: TcpStream,
+    distributor: &Distributor,
+    conductor: &Conductor,
+) -> eyre::Result<()> {
+    let (reader, mut writer) = stream.split();
+    let mut lines = BufReader::new(reader).lines();
+
+    let request_line = lines
+        .next_line()
+        .await?
+        .ok_or_eyre("connection closed before request")?;
+
+    // The headers are irrelevant, but have to be read before responding.
+    while let Some(line) = lines.next_line().await? {
+        if line.is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next();
+    let path = parts
+        .next()
+        .map(|target| target.split('?').next().unwrap_or(target));
+
+    let (status, content_type, body) = match (method, path) {
+        (Some("GET"), Some("/metrics")) => {
+            let instances = conductor.metrics().await?;
+            let message_counts = distributor.message_counts().await?;
+            ("200 OK", CONTENT_TYPE, render(&instances, &message_counts))
+        }
+        (Some("GET"), _) => ("404 Not Found", "text/plain", "not found\n".to_owned()),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain",
+            "method not allowed\n".to_owned(),
+        ),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len(),
+    );
+    writer.write_all(response.as_bytes()).await?;
+    writer.shutdown().await?;
+
+    Ok(())
+}
+
+/// Renders the metrics in the Prometheus text format.
+fn render(
+    instances: &BTreeMap<InstanceId, InstanceMetrics>,
+    message_counts: &BTreeMap<String, Vec<(LinkTarget, u64)>>,
+) -> String {
+    let mut output = String::new();
+
+    let running = instances
+        .values()
+        .filter(|instance| instance.running)
+        .count();
+
+    // Writing to a `String` can't fail.
+    let _ = writeln!(
+        output,
+        "# HELP veecle_orchestrator_instances Number of registered runtime instances.\n\
+         # TYPE veecle_orchestrator_instances gauge\n\
+         veecle_orchestrator_instances {}",
+        instances.len(),
+    );
+    let _ = writeln!(
+        output,
+        "# HELP veecle_orchestrator_instances_running Number of running runtime instances.\n\
+         # TYPE veecle_orchestrator_instances_running gauge\n\
+         veecle_orchestrator_instances_running {running}",
+    );
+
+    let _ = writeln!(
+        output,
+        "# HELP veecle_orchestrator_instance_restarts_total Number of times a runtime instance was started again after its first start.\n\
+         # TYPE veecle_orchestrator_instance_restarts_total counter",
+    );
+    for (id, instance) in instances {
+        let _ = writeln!(
+            output,
+            "veecle_orchestrator_instance_restarts_total{{instance=\"{id}\"}} {}",
+            instance.restarts,
+        );
+    }
+
+    let _ = writeln!(
+        output,
+        "# HELP veecle_orchestrator_link_messages_total Number of messages forwarded over a link.\n\
+         # TYPE veecle_orchestrator_link_messages_total counter",
+    );
+    for (routing_key, counts) in message_counts {
+        for (target, count) in counts {
+            let _ = writeln!(
+                output,
+                "veecle_orchestrator_link_messages_total{{routing_key=\"{}\",target=\"{}\"}} {count}",
+                escape_label_value(routing_key),
+                escape_label_value(&target.to_string()),
+            );
+        }
+    }
+
+    output
+}
+
+/// Escapes backslashes, double quotes and line feeds in a label value as required by the Prometheus text format.
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('"', r#"\""#)
+        .replace('\n', r"\n")
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use veecle_orchestrator_protocol::{InstanceId, LinkTarget};
+
+    use super::{escape_label_value, render};
+    use crate::runtime::InstanceMetrics;
+
+    #[test]
+    fn escapes_label_values() {
+        assert_eq!(escape_label_value("plain::Type"), "plain::Type");
+        assert_eq!(
+            escape_label_value("a \"quoted\" \\path\\\nline"),
+            r#"a \"quoted\" \\path\\\nline"#,
+        );
+    }
+
+    #[test]
+    fn renders_metrics() {
+        let first: InstanceId = "00000000-0000-0000-0000-000000000001".parse().unwrap();
+        let second: InstanceId = "00000000-0000-0000-0000-000000000002".parse().unwrap();
+
+        let instances = BTreeMap::from([
+            (
+                first,
+                InstanceMetrics {
+                    running: true,
+                    restarts: 2,
+                },
+            ),
+            (
+                second,
+                InstanceMetrics {
+                    running: false,
+                    restarts: 0,
+                },
+            ),
+        ]);
+        let message_counts = BTreeMap::from([(
+            "my::Type\"\n".to_owned(),
+            vec![
+                (LinkTarget::Local(second), 3),
+                (LinkTarget::Remote("127.0.0.1:1234".parse().unwrap()), 5),
+            ],
+        )]);
+
+        assert_eq!(
+            render(&instances, &message_counts),
+            "\
+# HELP veecle_orchestrator_instances Number of registered runtime instances.
+# TYPE veecle_orchestrator_instances gauge
+veecle_orchestrator_instances 2
+# HELP veecle_orchestrator_instances_running Number of running runtime instances.
+# TYPE veecle_orchestrator_instances_running gauge
+veecle_orchestrator_instances_running 1
+# HELP veecle_orchestrator_instance_restarts_total Number of times a runtime instance was started again after its first start.
+# TYPE veecle_orchestrator_instance_restarts_total counter
+veecle_orchestrator_instance_restarts_total{instance=\"00000000-0000-0000-0000-000000000001\"} 2
+veecle_orchestrator_instance_restarts_total{instance=\"00000000-0000-0000-0000-000000000002\"} 0
+# HELP veecle_orchestrator_link_messages_total Number of messages forwarded over a link.
+# TYPE veecle_orchestrator_link_messages_total counter
+veecle_orchestrator_link_messages_total{routing_key=\"my::Type\\\"\\n\",target=\"00000000-0000-0000-0000-000000000002\"} 3
+veecle_orchestrator_link_messages_total{routing_key=\"my::Type\\\"\\n\",target=\"127.0.0.1:1234\"} 5
+",
+        );
+    }
+}
@@ -1,8 +1,9 @@
 use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use tokio::sync::{mpsc, oneshot};
-use veecle_orchestrator_protocol::{InstanceId, Priority, RuntimeInfo};
+use veecle_orchestrator_protocol::{Health, InstanceId, Priority, RuntimeInfo, StopOutcome};
 
 use crate::distributor::Distributor;
 use crate::telemetry::Exporter;
@@ -24,6 +25,16 @@ impl std::fmt::Debug for Conductor {
     }
 }
 
+/// Metrics of a single runtime instance, as returned by [`Conductor::metrics`].
+#[derive(Debug)]
+pub(crate) struct InstanceMetrics {
+    /// Whether this instance is currently running.
+    pub(crate) running: bool,
+
+    /// How often this instance has been started again after its first start.
+    pub(crate) restarts: u64,
+}
+
 /// Operations sent to the actor.
 #[derive(Debug)]
 pub(crate) enum Command {
@@ -31,6 +42,8 @@ pub(crate) enum Command {
         id: InstanceId,
         binary: BinarySource,
         privileged: bool,
+        env: BTreeMap<String, String>,
+        args: Vec<String>,
         response_tx: oneshot::Sender<eyre::Result<()>>,
     },
 
@@ -47,11 +60,21 @@ pub(crate) enum Command {
 
     StopInstance {
         id: InstanceId,
-        response_tx: oneshot::Sender<eyre::Result<()>>,
+        grace: Option<Duration>,
+        response_tx: oneshot::Sender<eyre::Result<StopOutcome>>,
     },
 
     GetInfo {
-        response_tx: oneshot::Sender<BTreeMap<InstanceId, RuntimeInfo>>,
+        response_tx: oneshot::Sender<eyre::Result<BTreeMap<InstanceId, RuntimeInfo>>>,
+    },
+
+    GetHealth {
+        id: InstanceId,
+        response_tx: oneshot::Sender<eyre::Result<Health>>,
+    },
+
+    GetMetrics {
+        response_tx: oneshot::Sender<BTreeMap<InstanceId, InstanceMetrics>>,
     },
 
     Shutdown {
@@ -80,13 +103,15 @@ impl Conductor {
         Ok(Self { command_tx, _task })
     }
 
-    /// Adds a new runtime instance with the specified binary source.
+    /// Adds a new runtime instance with the specified binary source, environment variables and arguments.
     #[tracing::instrument(skip(self))]
     pub(crate) async fn add(
         &self,
         id: InstanceId,
         binary: BinarySource,
         privileged: bool,
+        env: BTreeMap<String, String>,
+        args: Vec<String>,
     ) -> eyre::Result<()> {
         let (response_tx, response_rx) = oneshot::channel();
 
@@ -95,6 +120,8 @@ impl Conductor {
                 id,
                 binary,
                 privileged,
+                env,
+                args,
                 response_tx,
             })
             .await?;
@@ -134,13 +161,21 @@ impl Conductor {
         response_rx.await?
     }
 
-    /// Stops the runtime instance with the passed id.
+    /// Stops the runtime instance with the passed id, killing it if it doesn't exit within `grace`.
     #[tracing::instrument(skip(self))]
-    pub(crate) async fn stop(&self, id: InstanceId) -> eyre::Result<()> {
+    pub(crate) async fn stop(
+        &self,
+        id: InstanceId,
+        grace: Option<Duration>,
+    ) -> eyre::Result<StopOutcome> {
         let (response_tx, response_rx) = oneshot::channel();
 
         self.command_tx
-            .send(Command::StopInstance { id, response_tx })
+            .send(Command::StopInstance {
+                id,
+                grace,
+                response_tx,
+            })
             .await?;
 
         response_rx.await?
@@ -154,7 +189,29 @@ impl Conductor {
             .send(Command::GetInfo { response_tx })
             .await?;
 
-        response_rx.await.map_err(Into::into)
+        response_rx.await?
+    }
+
+    /// Returns the health of the runtime instance with the passed id.
+    pub(crate) async fn health(&self, id: InstanceId) -> eyre::Result<Health> {
+        let (response_tx, response_rx) = oneshot::channel();
+
+        self.command_tx
+            .send(Command::GetHealth { id, response_tx })
+            .await?;
+
+        response_rx.await?
+    }
+
+    /// Returns metrics about all runtime instances.
+    pub(crate) async fn metrics(&self) -> eyre::Result<BTreeMap<InstanceId, InstanceMetrics>> {
+        let (response_tx, response_rx) = oneshot::channel();
+
+        self.command_tx
+            .send(Command::GetMetrics { response_tx })
+            .await?;
+
+        Ok(response_rx.await?)
     }
 
     /// Stops all runtime instances.
@@ -188,16 +245,23 @@ async fn run(
     command_tx_weak: mpsc::WeakSender<Command>,
 ) -> eyre::Result<()> {
     while let Some(command) = command_rx.recv().await {
+        // Processes may have exited on their own since the last command, which every command needs to observe.
+        state.reap_exited();
+
         match command {
             Command::AddInstance {
                 id,
                 binary,
                 privileged,
+                env,
+                args,
                 response_tx,
             } => {
                 let response = match command_tx_weak.upgrade() {
                     Some(command_tx) => {
-                        state.add_instance(id, binary, privileged, command_tx).await
+                        state
+                            .add_instance(id, binary, privileged, env, args, command_tx)
+                            .await
                     }
                     None => Err(eyre::eyre!("conductor has been dropped")),
                 };
@@ -212,15 +276,25 @@ async fn run(
                 priority,
                 response_tx,
             } => {
-                let response = state.start_instance(id, priority);
+                let response = state.start_instance(id, priority).await;
                 let _ = response_tx.send(response);
             }
-            Command::StopInstance { id, response_tx } => {
-                let response = state.stop_instance(id).await;
+            Command::StopInstance {
+                id,
+                grace,
+                response_tx,
+            } => {
+                let response = state.stop_instance(id, grace).await;
                 let _ = response_tx.send(response);
             }
             Command::GetInfo { response_tx } => {
-                let _ = response_tx.send(state.get_info());
+                let _ = response_tx.send(state.get_info().await);
+            }
+            Command::GetHealth { id, response_tx } => {
+                let _ = response_tx.send(state.get_health(id).await);
+            }
+            Command::GetMetrics { response_tx } => {
+                let _ = response_tx.send(state.get_metrics());
             }
             Command::Shutdown { response_tx } => {
                 state.shutdown().await;
@@ -235,3 +309,132 @@ async fn run(
 
     Ok(())
 }
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::os::unix::fs::PermissionsExt;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use camino::{Utf8Path, Utf8PathBuf};
+    use veecle_orchestrator_protocol::{Health, InstanceId};
+
+    use super::Conductor;
+    use crate::distributor::Distributor;
+
+    /// Sends a heartbeat for `id` and waits until the distributor recorded it.
+    async fn heartbeat(distributor: &Distributor, id: InstanceId) {
+        distributor.heartbeat_sender().send(id).await.unwrap();
+        while !distributor.heartbeats().await.unwrap().contains_key(&id) {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn health() {
+        let distributor = Arc::new(Distributor::new(None));
+        let conductor = Conductor::new(distributor.clone(), None).unwrap();
+        let id = InstanceId::new();
+
+        conductor
+            .add(
+                id,
+                Utf8PathBuf::from("sleep").into(),
+                false,
+                Default::default(),
+                vec!["60".to_owned()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(conductor.health(id).await.unwrap(), Health::Stopped);
+
+        conductor.start(id, None).await.unwrap();
+        assert_eq!(
+            conductor.health(id).await.unwrap(),
+            Health::Unresponsive {
+                last_heartbeat: None
+            }
+        );
+
+        heartbeat(&distributor, id).await;
+        assert!(matches!(
+            conductor.health(id).await.unwrap(),
+            Health::Healthy { .. }
+        ));
+
+        conductor.stop(id, None).await.unwrap();
+        assert_eq!(conductor.health(id).await.unwrap(), Health::Stopped);
+
+        // The heartbeat of the previous process doesn't count for the new one.
+        conductor.start(id, None).await.unwrap();
+        assert_eq!(
+            conductor.health(id).await.unwrap(),
+            Health::Unresponsive {
+                last_heartbeat: None
+            }
+        );
+
+        heartbeat(&distributor, id).await;
+        conductor.stop(id, None).await.unwrap();
+        conductor.remove(id).await.unwrap();
+        assert!(!distributor.heartbeats().await.unwrap().contains_key(&id));
+        assert!(conductor.health(id).await.is_err());
+
+        conductor.clear().await;
+    }
+
+    #[tokio::test]
+    async fn env_and_args_reach_process() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let directory = Utf8Path::from_path(temp_dir.path()).unwrap();
+        let output = directory.join("output");
+
+        // Reports its environment and arguments one per line, moved into place once complete.
+        let binary = directory.join("runtime");
+        std::fs::write(
+            &binary,
+            "#!/bin/sh\n\
+             printf '%s\\n' \"$GREETING\" \"$VEECLE_RUNTIME_ID\" \"$#\" \"$@\" > \"$OUTPUT.tmp\"\n\
+             mv \"$OUTPUT.tmp\" \"$OUTPUT\"\n\
+             exec sleep 60\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let distributor = Arc::new(Distributor::new(None));
+        let conductor = Conductor::new(distributor, None).unwrap();
+        let id = InstanceId::new();
+        let env = BTreeMap::from([
+            ("GREETING".to_owned(), "hello world".to_owned()),
+            ("OUTPUT".to_owned(), output.to_string()),
+            // The IPC configuration takes precedence over user-provided variables.
+            ("VEECLE_RUNTIME_ID".to_owned(), "overridden".to_owned()),
+        ]);
+        let args = vec!["first argument".to_owned(), "--second".to_owned()];
+
+        conductor
+            .add(id, binary.into(), false, env, args)
+            .await
+            .unwrap();
+        conductor.start(id, None).await.unwrap();
+
+        let reported = tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                if let Ok(reported) = std::fs::read_to_string(&output) {
+                    return reported;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(
+            reported,
+            format!("hello world\n{id}\n2\nfirst argument\n--second\n")
+        );
+
+        conductor.clear().await;
+    }
+}
@@ -1,5 +1,5 @@
 mod actor;
 mod state;
 
-pub(crate) use self::actor::{Command, Conductor};
+pub(crate) use self::actor::{Command, Conductor, InstanceMetrics};
 use self::state::State;
@@ -1,20 +1,26 @@
 use std::collections::hash_map::Entry;
 use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
 use camino::Utf8Path;
-use eyre::{OptionExt, Result, bail};
+use eyre::{OptionExt, Result};
 use futures::stream::StreamExt;
 use tempfile::TempDir;
 use tokio::sync::mpsc;
-use veecle_orchestrator_protocol::{InstanceId, Priority, RuntimeInfo};
+use veecle_ipc_protocol::HEARTBEAT_INTERVAL;
+use veecle_orchestrator_protocol::{Health, InstanceId, Priority, RuntimeInfo, StopOutcome};
 
 use crate::distributor::Distributor;
-use crate::runtime::conductor::Command;
+use crate::error::bail_kind;
+use crate::runtime::conductor::{Command, InstanceMetrics};
 use crate::telemetry::Exporter;
 
 use crate::runtime::{BinarySource, RuntimeInstance};
 
+/// How long after its last heartbeat a running instance is considered unresponsive.
+const HEARTBEAT_TIMEOUT: Duration = HEARTBEAT_INTERVAL.saturating_mul(3);
+
 /// The actual state machine for managing runtime instances, running in a background task and accepting commands over channels from its
 /// façade ([`super::Conductor`]).
 pub(super) struct State {
@@ -51,13 +57,16 @@ impl State {
         id: InstanceId,
         binary: BinarySource,
         privileged: bool,
+        env: BTreeMap<String, String>,
+        args: Vec<String>,
         command_tx: mpsc::Sender<Command>,
     ) -> Result<()> {
         if self.runtimes.contains_key(&id) {
-            bail!("instance id {id} already registered");
+            bail_kind!(AlreadyExists, "instance id {id} already registered");
         }
 
         let ipc_tx = self.distributor.sender();
+        let heartbeat_tx = self.distributor.heartbeat_sender();
         let ipc_rx = self.distributor.channel(id).await?;
         let socket_dir = self.ipc_socket_dir_utf8();
 
@@ -67,8 +76,11 @@ impl State {
             binary,
             ipc_tx,
             ipc_rx,
+            heartbeat_tx,
             self.exporter.clone(),
             privileged,
+            env,
+            args,
             command_tx,
         )?;
 
@@ -80,46 +92,60 @@ impl State {
     #[tracing::instrument(skip(self))]
     pub(super) async fn remove_instance(&mut self, id: InstanceId) -> Result<()> {
         let Entry::Occupied(entry) = self.runtimes.entry(id) else {
-            bail!("instance id {id} was not registered");
+            bail_kind!(NotFound, "instance id {id} was not registered");
         };
 
         if entry.get().is_running() {
-            bail!("instance id {id} is still running, you must stop it before removing");
+            bail_kind!(
+                Running,
+                "instance id {id} is still running, you must stop it before removing"
+            );
         }
 
         entry.remove().cleanup().await?;
+        self.distributor.reset_instance(id).await?;
 
         Ok(())
     }
 
     #[tracing::instrument(skip(self))]
-    pub(super) fn start_instance(
+    pub(super) async fn start_instance(
         &mut self,
         id: InstanceId,
         priority: Option<Priority>,
     ) -> Result<()> {
         let Some(instance) = self.runtimes.get_mut(&id) else {
-            bail!("instance id {id} was not registered");
+            bail_kind!(NotFound, "instance id {id} was not registered");
         };
 
+        if !instance.is_running() {
+            // The previous process' heartbeats and received values don't apply to the new one.
+            self.distributor.reset_instance(id).await?;
+        }
+
         instance.start(priority)?;
 
         Ok(())
     }
 
     #[tracing::instrument(skip(self))]
-    pub(super) async fn stop_instance(&mut self, id: InstanceId) -> Result<()> {
+    pub(super) async fn stop_instance(
+        &mut self,
+        id: InstanceId,
+        grace: Option<Duration>,
+    ) -> Result<StopOutcome> {
         let Some(instance) = self.runtimes.get_mut(&id) else {
-            bail!("instance id {id} was not registered");
+            bail_kind!(NotFound, "instance id {id} was not registered");
         };
 
-        instance.stop().await?;
-
-        Ok(())
+        instance.stop(grace).await
     }
 
-    pub(super) fn get_info(&self) -> BTreeMap<InstanceId, RuntimeInfo> {
-        self.runtimes
+    pub(super) async fn get_info(&self) -> Result<BTreeMap<InstanceId, RuntimeInfo>> {
+        let heartbeats = self.distributor.heartbeats().await?;
+
+        Ok(self
+            .runtimes
             .iter()
             .map(|(&id, instance)| {
                 (
@@ -128,12 +154,60 @@ impl State {
                         running: instance.is_running(),
                         binary: instance.binary().path().to_path_buf(),
                         privileged: instance.privileged(),
+                        env: instance.env().clone(),
+                        args: instance.args().to_vec(),
+                        last_heartbeat: heartbeats.get(&id).copied(),
+                        started_at: instance.started_at(),
+                        exit_code: instance.exit_code(),
+                        priority: instance.priority(),
+                    },
+                )
+            })
+            .collect())
+    }
+
+    pub(super) fn get_metrics(&self) -> BTreeMap<InstanceId, InstanceMetrics> {
+        self.runtimes
+            .iter()
+            .map(|(&id, instance)| {
+                (
+                    id,
+                    InstanceMetrics {
+                        running: instance.is_running(),
+                        restarts: instance.restarts(),
                     },
                 )
             })
             .collect()
     }
 
+    pub(super) async fn get_health(&self, id: InstanceId) -> Result<Health> {
+        let Some(instance) = self.runtimes.get(&id) else {
+            bail_kind!(NotFound, "instance id {id} was not registered");
+        };
+
+        if !instance.is_running() {
+            return Ok(Health::Stopped);
+        }
+
+        let last_heartbeat = self.distributor.heartbeats().await?.get(&id).copied();
+
+        // A heartbeat from the future (e.g. the system clock was adjusted) is treated as recent.
+        let recent = |heartbeat: SystemTime| !matches!(heartbeat.elapsed(), Ok(elapsed) if elapsed > HEARTBEAT_TIMEOUT);
+
+        Ok(match last_heartbeat {
+            Some(last_heartbeat) if recent(last_heartbeat) => Health::Healthy { last_heartbeat },
+            last_heartbeat => Health::Unresponsive { last_heartbeat },
+        })
+    }
+
+    /// Records the exit of any runtime process that has exited on its own.
+    pub(super) fn reap_exited(&mut self) {
+        for instance in self.runtimes.values_mut() {
+            instance.reap();
+        }
+    }
+
     #[tracing::instrument(skip(self))]
     pub(super) async fn shutdown(&mut self) {
         futures::stream::iter(self.runtimes.iter_mut())
@@ -143,7 +217,7 @@ impl State {
                     reason = "separate condition check from error handling"
                 )]
                 if runtime.is_running() {
-                    if let Err(error) = runtime.stop().await {
+                    if let Err(error) = runtime.stop(None).await {
                         tracing::info!("child {id} failed to stop: {error:?}");
                     }
                 }
@@ -1,9 +1,10 @@
+use std::collections::BTreeMap;
 use std::process::{ExitStatus, Stdio};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 use camino::{Utf8Path, Utf8PathBuf};
-use eyre::{OptionExt, Result, WrapErr, bail};
+use eyre::{OptionExt, Result, WrapErr};
 use futures::sink::SinkExt;
 use futures::stream::StreamExt;
 use tempfile::TempPath;
@@ -12,9 +13,10 @@ use tokio::sync::{mpsc, oneshot};
 use tokio::time::timeout;
 use tokio_util::codec::Framed;
 use tokio_util::sync::CancellationToken;
-use veecle_ipc_protocol::{ControlRequest, ControlResponse, EncodedStorable};
-use veecle_orchestrator_protocol::{InstanceId, Priority};
+use veecle_ipc_protocol::{ControlRequest, ControlResponse, EncodedStorable, HANDSHAKE_TIMEOUT};
+use veecle_orchestrator_protocol::{DEFAULT_STOP_GRACE, InstanceId, Priority, StopOutcome};
 
+use crate::error::bail_kind;
 use crate::runtime::conductor::Command;
 use crate::telemetry::Exporter;
 use veecle_net_utils::AsyncUnixListener;
@@ -64,6 +66,20 @@ pub(crate) struct RuntimeInstance {
     ipc_shutdown: CancellationToken,
     socket_path: Utf8PathBuf,
     privileged: bool,
+    env: BTreeMap<String, String>,
+    args: Vec<String>,
+
+    /// How often the process has been started.
+    starts: u64,
+
+    /// When the currently running process was started.
+    started_at: Option<SystemTime>,
+
+    /// The exit code of the last stopped or exited process, see [`ExitStatus::code`].
+    exit_code: Option<i32>,
+
+    /// The priority the last process was started with.
+    priority: Option<Priority>,
 }
 
 impl Drop for RuntimeInstance {
@@ -117,6 +133,7 @@ async fn handle_control_request(
                 let id = InstanceId(id);
                 send_command(command_tx, |response_tx| Command::StopInstance {
                     id,
+                    grace: None,
                     response_tx,
                 })
                 .await?;
@@ -132,12 +149,34 @@ async fn handle_control_request(
     }
 }
 
+/// Waits for the [`Message::Hello`](veecle_ipc_protocol::Message::Hello) of a runtime on a new connection and answers
+/// with our own.
+///
+/// The answer is sent even if the runtime is incompatible, so it can report the mismatch too.
+/// Returns the optional protocol features supported by the runtime.
+async fn handshake<S>(stream: &mut Framed<S, veecle_ipc_protocol::Codec>) -> Result<Vec<String>>
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    let hello = timeout(HANDSHAKE_TIMEOUT, stream.next())
+        .await
+        .wrap_err("timed out waiting for the handshake")?
+        .ok_or_eyre("connection closed during the handshake")??;
+    stream.send(&veecle_ipc_protocol::Message::hello()).await?;
+    let features = veecle_ipc_protocol::check_hello(hello)?;
+    tracing::debug!(?features, "runtime connected");
+    Ok(features)
+}
+
 /// Handles the IPC for a single runtime instance.
 ///
 /// This expects to have the runtime instance connect using `veecle-ipc` to the provided `socket` (only one client at a
 /// time, but maybe reconnecting if the instance is stopped and restarted).
+/// Connections from an instance with an incompatible IPC protocol version are rejected.
+/// Values tagged with a topic are only sent to an instance supporting the `topic` protocol feature.
 /// Any messages arriving on `ipc_rx` will be encoded and sent to the instance.
 /// Any `Storable` messages arriving from the instance will be decoded and forwarded to `ipc_tx`.
+/// Any heartbeats arriving from the instance will be recorded via `heartbeat_tx`.
 #[tracing::instrument(skip_all, fields(%id))]
 #[expect(clippy::too_many_arguments)]
 async fn handle_instance_ipc(
@@ -145,6 +184,7 @@ async fn handle_instance_ipc(
     socket: tempfile::NamedTempFile<AsyncUnixListener>,
     ipc_tx: mpsc::Sender<EncodedStorable>,
     mut ipc_rx: mpsc::Receiver<EncodedStorable>,
+    heartbeat_tx: mpsc::Sender<InstanceId>,
     shutdown: CancellationToken,
     exporter: Option<Arc<Exporter>>,
     privileged: bool,
@@ -156,10 +196,39 @@ async fn handle_instance_ipc(
             accept_result = socket.accept() => {
                 let (stream, _address) = accept_result?;
                 let mut stream = Framed::new(stream, veecle_ipc_protocol::Codec::new());
+                let mut features = match handshake(&mut stream).await {
+                    Ok(features) => features,
+                    Err(error) => {
+                        tracing::error!("rejecting ipc connection of runtime {id}: {error:?}");
+                        continue;
+                    }
+                };
                 loop {
                     tokio::select! {
+                        accept_result = socket.accept() => {
+                            // A runtime reconnects when its keepalives go unanswered, the new connection replaces the
+                            // stalled one.
+                            let (new_stream, _address) = accept_result?;
+                            tracing::warn!("runtime {id} reconnected, replacing its previous ipc connection");
+                            stream = Framed::new(new_stream, veecle_ipc_protocol::Codec::new());
+                            features = match handshake(&mut stream).await {
+                                Ok(features) => features,
+                                Err(error) => {
+                                    tracing::error!("rejecting ipc connection of runtime {id}: {error:?}");
+                                    break;
+                                }
+                            };
+                        }
                         storable = ipc_rx.recv() => {
                             let Some(storable) = storable else { break };
+                            // A runtime without topic support would ignore the topic and misroute the value.
+                            if storable.topic.is_some() && !features.iter().any(|feature| feature == "topic") {
+                                tracing::warn!(
+                                    type_name = %storable.type_name,
+                                    "dropping topic-tagged value for runtime {id} without topic support",
+                                );
+                                continue;
+                            }
                             let message = veecle_ipc_protocol::Message::Storable(storable);
                             stream.send(&message).await?;
                         }
@@ -187,6 +256,15 @@ async fn handle_instance_ipc(
                                 veecle_ipc_protocol::Message::ControlResponse(_) => {
                                     tracing::warn!("received unexpected ControlResponse");
                                 }
+                                veecle_ipc_protocol::Message::Hello { .. } => {
+                                    tracing::warn!("received unexpected Hello after the handshake");
+                                }
+                                veecle_ipc_protocol::Message::Heartbeat => {
+                                    heartbeat_tx.send(id).await?;
+                                }
+                                veecle_ipc_protocol::Message::Keepalive => {
+                                    stream.send(&veecle_ipc_protocol::Message::Keepalive).await?;
+                                }
                             }
                         }
                     }
@@ -208,8 +286,11 @@ impl RuntimeInstance {
         binary: BinarySource,
         ipc_tx: mpsc::Sender<EncodedStorable>,
         ipc_rx: mpsc::Receiver<EncodedStorable>,
+        heartbeat_tx: mpsc::Sender<InstanceId>,
         exporter: Option<Arc<Exporter>>,
         privileged: bool,
+        env: BTreeMap<String, String>,
+        args: Vec<String>,
         command_tx: mpsc::Sender<Command>,
     ) -> Result<Self> {
         let socket = tempfile::Builder::new()
@@ -232,6 +313,7 @@ impl RuntimeInstance {
             socket,
             ipc_tx,
             ipc_rx,
+            heartbeat_tx,
             ipc_shutdown.clone(),
             exporter,
             privileged,
@@ -246,6 +328,12 @@ impl RuntimeInstance {
             ipc_shutdown,
             socket_path,
             privileged,
+            env,
+            args,
+            starts: 0,
+            started_at: None,
+            exit_code: None,
+            priority: None,
         })
     }
 
@@ -264,6 +352,37 @@ impl RuntimeInstance {
         self.privileged
     }
 
+    /// Returns the additional environment variables set for this instance's process.
+    pub(crate) fn env(&self) -> &BTreeMap<String, String> {
+        &self.env
+    }
+
+    /// Returns the command line arguments passed to this instance's process.
+    pub(crate) fn args(&self) -> &[String] {
+        &self.args
+    }
+
+    /// Returns how often the process has been started again after its first start.
+    pub(crate) fn restarts(&self) -> u64 {
+        self.starts.saturating_sub(1)
+    }
+
+    /// Returns when the currently running process was started, `None` if it is not running.
+    pub(crate) fn started_at(&self) -> Option<SystemTime> {
+        self.started_at
+    }
+
+    /// Returns the exit code of the last stopped or exited process, `None` if it is running, was never started or was
+    /// terminated by a signal.
+    pub(crate) fn exit_code(&self) -> Option<i32> {
+        self.exit_code
+    }
+
+    /// Returns the priority the last process was started with, `None` if it was never started or started without one.
+    pub(crate) fn priority(&self) -> Option<Priority> {
+        self.priority
+    }
+
     /// Starts the process for this instance.
     pub(crate) fn start(&mut self, priority: Option<Priority>) -> Result<()> {
         /// Sets the process priority for the given PID.
@@ -276,14 +395,17 @@ impl RuntimeInstance {
         }
 
         if self.process.is_some() {
-            bail!("instance id {} is already running", self.id);
+            bail_kind!(Running, "instance id {} is already running", self.id);
         }
 
         let binary = self.binary.path();
         let process = tokio::process::Command::new(binary)
+            .args(&self.args)
             .stdin(Stdio::null())
             .stdout(Stdio::null())
             .stderr(Stdio::null())
+            // Set after the user-provided environment so the IPC configuration can't be overridden.
+            .envs(&self.env)
             .env("VEECLE_IPC_SOCKET", &self.socket_path)
             .env("VEECLE_RUNTIME_ID", self.id.to_string())
             .spawn()
@@ -300,27 +422,53 @@ impl RuntimeInstance {
         }
 
         self.process = Some(process);
+        self.starts += 1;
+        self.started_at = Some(SystemTime::now());
+        self.exit_code = None;
+        self.priority = priority;
 
         Ok(())
     }
 
     /// Stops the process for this instance (but allows it to be started again later).
-    pub(crate) async fn stop(&mut self) -> Result<()> {
+    ///
+    /// The process is given `grace` (or [`DEFAULT_STOP_GRACE`]) to exit after being terminated before it is killed.
+    pub(crate) async fn stop(&mut self, grace: Option<Duration>) -> Result<StopOutcome> {
         let Some(process) = self.process.take() else {
-            bail!("instance id {} is not running", self.id);
+            bail_kind!(NotRunning, "instance id {} is not running", self.id);
         };
 
-        let status = kill_child(process).await?;
+        self.started_at = None;
+        let (status, outcome) = kill_child(process, grace.unwrap_or(DEFAULT_STOP_GRACE)).await?;
+        self.exit_code = status.code();
 
-        tracing::info!("child stop exit status {status:?}");
+        tracing::info!("child stop exit status {status:?} ({outcome:?})");
 
-        Ok(())
+        Ok(outcome)
+    }
+
+    /// Records the exit of a process that has exited on its own since it was last checked.
+    pub(crate) fn reap(&mut self) {
+        let Some(process) = &mut self.process else {
+            return;
+        };
+
+        match process.try_wait() {
+            Ok(Some(status)) => {
+                tracing::info!("child {} exited with status {status:?}", self.id);
+                self.process = None;
+                self.started_at = None;
+                self.exit_code = status.code();
+            }
+            Ok(None) => {}
+            Err(error) => tracing::warn!("failed to check child {} status: {error}", self.id),
+        }
     }
 
     /// Stops all processing for this instance and cleans up any associated temporary files.
     pub(crate) async fn cleanup(mut self) -> Result<()> {
         if self.is_running() {
-            self.stop().await?;
+            self.stop(None).await?;
         }
         self.ipc_shutdown.cancel();
         self.ipc_task
@@ -333,17 +481,17 @@ impl RuntimeInstance {
     }
 }
 
-/// Attempts to nicely kill a child process, first attempting to interrupt it and give it 100ms to shutdown before
+/// Attempts to nicely kill a child process, first attempting to terminate it and give it `grace` to shutdown before
 /// killing it.
-async fn kill_child(mut process: Child) -> Result<ExitStatus> {
+async fn kill_child(mut process: Child, grace: Duration) -> Result<(ExitStatus, StopOutcome)> {
     if let Some(id) = process.id() {
         let pid = nix::unistd::Pid::from_raw(libc::pid_t::try_from(id).unwrap());
-        nix::sys::signal::kill(pid, nix::sys::signal::Signal::SIGINT)
-            .wrap_err("interrupting child")?;
-        if let Ok(status) = timeout(Duration::from_millis(100), process.wait()).await {
-            return Ok(status?);
+        nix::sys::signal::kill(pid, nix::sys::signal::Signal::SIGTERM)
+            .wrap_err("terminating child")?;
+        if let Ok(status) = timeout(grace, process.wait()).await {
+            return Ok((status?, StopOutcome::Graceful));
         }
-        tracing::warn!("child did not stop in time after interrupt");
+        tracing::warn!("child did not stop in time after terminate");
     }
 
     // `Child::kill` does not return the `ExitStatus`, for consistency with the process exiting itself we manually
@@ -353,5 +501,85 @@ async fn kill_child(mut process: Child) -> Result<ExitStatus> {
         .await
         .wrap_err("waiting for child to be killed")??;
 
-    Ok(status)
+    Ok((status, StopOutcome::Killed))
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use futures::sink::SinkExt;
+    use futures::stream::StreamExt;
+    use tokio_util::codec::Framed;
+    use veecle_ipc_protocol::{Codec, HandshakeError, Message, PROTOCOL_VERSION};
+
+    use super::handshake;
+
+    /// Runs the orchestrator side of the handshake against a runtime sending `first`, returning the result and the
+    /// answer received by the runtime.
+    async fn handshake_with(first: Message) -> (eyre::Result<Vec<String>>, Option<Message>) {
+        let (orchestrator, runtime) = tokio::io::duplex(1024);
+        let mut orchestrator = Framed::new(orchestrator, Codec::new());
+        let mut runtime = Framed::new(runtime, Codec::new());
+
+        runtime.send(&first).await.unwrap();
+        let result = handshake(&mut orchestrator).await;
+        drop(orchestrator);
+        let answer = runtime.next().await.transpose().unwrap();
+
+        (result, answer)
+    }
+
+    #[tokio::test]
+    async fn accepts_compatible_runtime() {
+        let hello = Message::Hello {
+            protocol_version: PROTOCOL_VERSION,
+            features: vec!["topic".to_owned()],
+        };
+        let (result, answer) = handshake_with(hello).await;
+
+        assert_eq!(result.unwrap(), ["topic"]);
+        assert!(matches!(answer, Some(Message::Hello { .. })));
+    }
+
+    #[tokio::test]
+    async fn refuses_other_version() {
+        let hello = Message::Hello {
+            protocol_version: PROTOCOL_VERSION + 1,
+            features: Vec::new(),
+        };
+        let (result, answer) = handshake_with(hello).await;
+
+        let error = result.unwrap_err();
+        assert!(
+            matches!(
+                error.downcast_ref::<HandshakeError>(),
+                Some(HandshakeError::VersionMismatch { local, peer })
+                    if *local == PROTOCOL_VERSION && *peer == PROTOCOL_VERSION + 1
+            ),
+            "{error:?}"
+        );
+        // The runtime still receives our hello to report the mismatch on its side.
+        assert!(matches!(
+            answer,
+            Some(Message::Hello {
+                protocol_version: PROTOCOL_VERSION,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn refuses_missing_hello() {
+        let (result, answer) = handshake_with(Message::Heartbeat).await;
+
+        let error = result.unwrap_err();
+        assert!(
+            matches!(
+                error.downcast_ref::<HandshakeError>(),
+                Some(HandshakeError::MissingHello)
+            ),
+            "{error:?}"
+        );
+        assert!(matches!(answer, Some(Message::Hello { .. })));
+    }
 }
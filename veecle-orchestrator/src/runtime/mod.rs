@@ -1,6 +1,6 @@
 mod conductor;
 mod instance;
 
-pub(crate) use self::conductor::Conductor;
+pub(crate) use self::conductor::{Conductor, InstanceMetrics};
 pub(crate) use self::instance::BinarySource;
 use self::instance::RuntimeInstance;
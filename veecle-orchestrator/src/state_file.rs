@@ -0,0 +1,289 @@
+use camino::Utf8PathBuf;
+use eyre::WrapErr;
+use tokio::sync::Mutex;
+use veecle_orchestrator_protocol::Info;
+
+use crate::distributor::Distributor;
+use crate::runtime::Conductor;
+
+/// Persists the orchestrator state to a file so it can be restored after a restart.
+///
+/// The state is stored as the JSON encoding of [`Info`], which contains everything needed to re-create the runtime
+/// instances and links.
+#[derive(Debug)]
+pub(crate) struct StateFile {
+    path: Utf8PathBuf,
+    /// Serializes concurrent saves from multiple API clients so they don't interleave writes to the temporary file.
+    lock: Mutex<()>,
+}
+
+impl StateFile {
+    /// Returns a new `StateFile` that will read from and write to `path`.
+    pub(crate) fn new(path: Utf8PathBuf) -> Self {
+        Self {
+            path,
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Writes the current state of `distributor` and `conductor` to the file.
+    ///
+    /// The state is written to a temporary file next to the target which is then renamed over it, so a crash while
+    /// saving never leaves a truncated state file behind.
+    #[tracing::instrument(skip_all, fields(path = %self.path))]
+    pub(crate) async fn save(
+        &self,
+        distributor: &Distributor,
+        conductor: &Conductor,
+    ) -> eyre::Result<()> {
+        let _guard = self.lock.lock().await;
+
+        let info = crate::api::info(distributor, conductor).await?;
+        let encoded = serde_json::to_vec_pretty(&info).wrap_err("encoding state")?;
+
+        let temporary = self.path.with_extension("tmp");
+        tokio::fs::write(&temporary, encoded)
+            .await
+            .wrap_err_with(|| format!("writing '{temporary}'"))?;
+        tokio::fs::rename(&temporary, &self.path)
+            .await
+            .wrap_err_with(|| format!("renaming '{temporary}' to '{}'", self.path))?;
+
+        tracing::debug!("saved state");
+
+        Ok(())
+    }
+
+    /// Saves the current state, logging instead of returning any error.
+    ///
+    /// Used after mutating requests, where the request itself already succeeded and failing to persist it should not
+    /// be reported as a failure of the request.
+    pub(crate) async fn save_or_warn(&self, distributor: &Distributor, conductor: &Conductor) {
+        if let Err(error) = self.save(distributor, conductor).await {
+            tracing::warn!(?error, "failed to save orchestrator state");
+        }
+    }
+
+    /// Re-creates the runtime instances and links stored in the file, starting all instances that were running with
+    /// the priority they were last started with.
+    ///
+    /// A missing file is treated as empty state. Instances whose binary no longer exists (e.g. binaries sent with
+    /// [`veecle_orchestrator_protocol::Request::AddWithBinary`], which are stored in temporary files) are skipped with
+    /// a warning, as are links to them.
+    #[tracing::instrument(skip_all, fields(path = %self.path))]
+    pub(crate) async fn restore(
+        &self,
+        distributor: &Distributor,
+        conductor: &Conductor,
+    ) -> eyre::Result<()> {
+        let encoded = match tokio::fs::read(&self.path).await {
+            Ok(encoded) => encoded,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                tracing::info!("no saved state");
+                return Ok(());
+            }
+            Err(error) => {
+                return Err(error).wrap_err_with(|| format!("reading '{}'", self.path));
+            }
+        };
+
+        let info: Info = serde_json::from_slice(&encoded)
+            .wrap_err_with(|| format!("parsing '{}'", self.path))?;
+
+        for (id, runtime) in info.runtimes {
+            if !tokio::fs::try_exists(&runtime.binary)
+                .await
+                .unwrap_or(false)
+            {
+                tracing::warn!(%id, binary = %runtime.binary, "binary no longer exists, skipping instance");
+                continue;
+            }
+
+            if let Err(error) = conductor
+                .add(
+                    id,
+                    runtime.binary.into(),
+                    runtime.privileged,
+                    runtime.env,
+                    runtime.args,
+                )
+                .await
+            {
+                tracing::warn!(%id, ?error, "failed to restore instance");
+                continue;
+            }
+
+            if runtime.running
+                && let Err(error) = conductor.start(id, runtime.priority).await
+            {
+                tracing::warn!(%id, ?error, "failed to restart instance");
+            }
+        }
+
+        for (routing_key, targets) in info.links {
+            let deduplicated = info.deduplicated_links.get(&routing_key);
+            let match_mode = info
+                .link_modes
+                .get(&routing_key)
+                .copied()
+                .unwrap_or_default();
+            for target in targets {
+                let dedup = deduplicated.is_some_and(|deduplicated| deduplicated.contains(&target));
+                if let Err(error) = distributor
+                    .link(routing_key.clone(), target, dedup, match_mode)
+                    .await
+                {
+                    tracing::warn!(%routing_key, %target, ?error, "failed to restore link");
+                }
+            }
+        }
+
+        tracing::info!("restored state");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::os::unix::fs::PermissionsExt;
+    use std::sync::Arc;
+
+    use camino::{Utf8Path, Utf8PathBuf};
+    use veecle_orchestrator_protocol::{InstanceId, LinkMatch, LinkTarget, Priority};
+
+    use super::StateFile;
+    use crate::distributor::Distributor;
+    use crate::runtime::Conductor;
+
+    /// Writes an executable script to `path` that runs until it is stopped.
+    fn write_runtime(path: &Utf8Path) {
+        std::fs::write(path, "#!/bin/sh\nexec sleep 60\n").unwrap();
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    fn orchestrator() -> (Arc<Distributor>, Conductor) {
+        let distributor = Arc::new(Distributor::new(None));
+        let conductor = Conductor::new(distributor.clone(), None).unwrap();
+        (distributor, conductor)
+    }
+
+    #[tokio::test]
+    async fn round_trip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let directory = Utf8Path::from_path(temp_dir.path()).unwrap();
+        let state_file = StateFile::new(directory.join("state.json"));
+
+        let binary = directory.join("runtime");
+        let removed_binary = directory.join("removed");
+        write_runtime(&binary);
+        write_runtime(&removed_binary);
+
+        let kept = InstanceId::new();
+        let removed = InstanceId::new();
+        let env = BTreeMap::from([("KEY".to_owned(), "value".to_owned())]);
+        let args = vec!["--flag".to_owned()];
+
+        let (distributor, conductor) = orchestrator();
+        conductor
+            .add(kept, binary.clone().into(), true, env.clone(), args.clone())
+            .await
+            .unwrap();
+        conductor
+            .add(
+                removed,
+                removed_binary.clone().into(),
+                false,
+                BTreeMap::new(),
+                Vec::new(),
+            )
+            .await
+            .unwrap();
+        conductor.start(kept, Some(Priority::Low)).await.unwrap();
+        distributor
+            .link(
+                "app::Speed".to_owned(),
+                LinkTarget::Local(kept),
+                true,
+                LinkMatch::Exact,
+            )
+            .await
+            .unwrap();
+        distributor
+            .link(
+                "app::Gear".to_owned(),
+                LinkTarget::Local(kept),
+                false,
+                LinkMatch::Exact,
+            )
+            .await
+            .unwrap();
+        distributor
+            .link(
+                "app::".to_owned(),
+                LinkTarget::Local(removed),
+                false,
+                LinkMatch::Prefix,
+            )
+            .await
+            .unwrap();
+
+        state_file.save(&distributor, &conductor).await.unwrap();
+        conductor.clear().await;
+        std::fs::remove_file(&removed_binary).unwrap();
+
+        let (distributor, conductor) = orchestrator();
+        state_file.restore(&distributor, &conductor).await.unwrap();
+        let info = crate::api::info(&distributor, &conductor).await.unwrap();
+
+        // The instance whose binary is gone is skipped, as is the link to it.
+        assert_eq!(info.runtimes.keys().collect::<Vec<_>>(), [&kept]);
+        let runtime = &info.runtimes[&kept];
+        assert!(runtime.running);
+        assert_eq!(runtime.binary, binary);
+        assert!(runtime.privileged);
+        assert_eq!(runtime.env, env);
+        assert_eq!(runtime.args, args);
+        assert_eq!(runtime.priority, Some(Priority::Low));
+
+        let speed = ("app::Speed".to_owned(), vec![LinkTarget::Local(kept)]);
+        let gear = ("app::Gear".to_owned(), vec![LinkTarget::Local(kept)]);
+        assert_eq!(info.links, BTreeMap::from([speed.clone(), gear]));
+        assert_eq!(info.deduplicated_links, BTreeMap::from([speed]));
+        assert!(info.link_modes.is_empty());
+
+        conductor.clear().await;
+    }
+
+    #[tokio::test]
+    async fn missing_file() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = Utf8PathBuf::from_path_buf(directory.path().join("state.json")).unwrap();
+
+        let (distributor, conductor) = orchestrator();
+        StateFile::new(path)
+            .restore(&distributor, &conductor)
+            .await
+            .unwrap();
+
+        assert!(conductor.info().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn corrupt_file() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = Utf8PathBuf::from_path_buf(directory.path().join("state.json")).unwrap();
+        std::fs::write(&path, "{\"runtimes\":").unwrap();
+
+        let (distributor, conductor) = orchestrator();
+        let error = StateFile::new(path.clone())
+            .restore(&distributor, &conductor)
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.to_string(), format!("parsing '{path}'"));
+        assert!(conductor.info().await.unwrap().is_empty());
+    }
+}
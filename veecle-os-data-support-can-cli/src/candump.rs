@@ -0,0 +1,121 @@
+//! Parsing of `candump` log files, as written by `candump -l` or printed by `candump -L`.
+
+use anyhow::{Context, Result, bail, ensure};
+use can_dbc::MessageId;
+
+/// A single classic CAN data frame from a `candump` log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LogFrame<'a> {
+    /// The timestamp as written in the log, without the surrounding parentheses.
+    pub(crate) timestamp: &'a str,
+    pub(crate) interface: &'a str,
+    pub(crate) id: MessageId,
+    pub(crate) data: Vec<u8>,
+}
+
+/// Parses a single log line like `(1436509052.249713) vcan0 18FEF100#2A366C2BBA`.
+///
+/// Returns `None` for empty lines and remote frames, which carry no data to decode.
+pub(crate) fn parse_line(line: &str) -> Result<Option<LogFrame<'_>>> {
+    let mut parts = line.split_whitespace();
+
+    let Some(timestamp) = parts.next() else {
+        return Ok(None);
+    };
+    let (Some(interface), Some(frame)) = (parts.next(), parts.next()) else {
+        bail!("expected `(timestamp) interface id#data`");
+    };
+
+    let timestamp = timestamp
+        .strip_prefix('(')
+        .and_then(|timestamp| timestamp.strip_suffix(')'))
+        .with_context(|| format!("invalid timestamp {timestamp:?}"))?;
+
+    let (id, data) = frame
+        .split_once('#')
+        .with_context(|| format!("missing `#` in frame {frame:?}"))?;
+
+    ensure!(!data.starts_with('#'), "CAN FD frames are not supported");
+    if data.starts_with('R') {
+        return Ok(None);
+    }
+
+    let id = match id.len() {
+        3 => MessageId::Standard(
+            u16::from_str_radix(id, 16)
+                .ok()
+                .filter(|&id| id <= 0x7FF)
+                .with_context(|| format!("invalid standard id {id:?}"))?,
+        ),
+        8 => MessageId::Extended(
+            u32::from_str_radix(id, 16)
+                .ok()
+                .filter(|&id| id <= 0x1FFF_FFFF)
+                .with_context(|| format!("invalid extended id {id:?}"))?,
+        ),
+        _ => bail!("invalid id {id:?}, expected 3 or 8 hex digits"),
+    };
+
+    ensure!(
+        data.is_ascii() && data.len() % 2 == 0 && data.len() <= 16,
+        "invalid data {data:?}, expected up to 8 hex encoded bytes"
+    );
+    let data = Result::<Vec<u8>>::from_iter((0..data.len()).step_by(2).map(|index| {
+        u8::from_str_radix(&data[index..index + 2], 16)
+            .with_context(|| format!("invalid data {data:?}"))
+    }))?;
+
+    Ok(Some(LogFrame {
+        timestamp,
+        interface,
+        id,
+        data,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use can_dbc::MessageId;
+
+    use super::{LogFrame, parse_line};
+
+    #[test]
+    fn standard() {
+        assert_eq!(
+            parse_line("(1436509052.249713) vcan0 044#2A366C2BBA").unwrap(),
+            Some(LogFrame {
+                timestamp: "1436509052.249713",
+                interface: "vcan0",
+                id: MessageId::Standard(0x44),
+                data: vec![0x2A, 0x36, 0x6C, 0x2B, 0xBA],
+            })
+        );
+    }
+
+    #[test]
+    fn extended() {
+        assert_eq!(
+            parse_line("(1.0) can0 18FEF100#").unwrap(),
+            Some(LogFrame {
+                timestamp: "1.0",
+                interface: "can0",
+                id: MessageId::Extended(0x18FE_F100),
+                data: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn skipped() {
+        assert_eq!(parse_line("").unwrap(), None);
+        assert_eq!(parse_line("(1.0) can0 123#R").unwrap(), None);
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(parse_line("can0 123#00").is_err());
+        assert!(parse_line("(1.0) can0 800#00").is_err());
+        assert!(parse_line("(1.0) can0 123#0").is_err());
+        assert!(parse_line("(1.0) can0 123##100").is_err());
+    }
+}
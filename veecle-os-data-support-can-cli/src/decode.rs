@@ -0,0 +1,224 @@
+//! Decoding of frames into signal values according to a DBC.
+
+use can_dbc::{
+    ByteOrder, Dbc, Message, MultiplexIndicator, Signal, SignalExtendedValueType, ValueType,
+};
+use veecle_os_data_support_can::bits;
+
+/// The decoded value of a single signal.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DecodedSignal<'a> {
+    pub(crate) name: &'a str,
+    /// The physical value, after applying factor and offset.
+    pub(crate) value: f64,
+    pub(crate) unit: &'a str,
+    /// The value description from the DBC matching the raw value, if any.
+    pub(crate) description: Option<&'a str>,
+}
+
+/// The raw value of a signal as read from the frame.
+#[derive(Debug, Clone, Copy)]
+enum Raw {
+    Unsigned(u64),
+    Signed(i64),
+}
+
+impl Raw {
+    fn as_i64(self) -> i64 {
+        match self {
+            Self::Unsigned(value) => value as i64,
+            Self::Signed(value) => value,
+        }
+    }
+}
+
+fn read_raw(signal: &Signal, data: &[u8]) -> Option<Raw> {
+    let (start_bit, size) = (
+        usize::try_from(signal.start_bit).ok()?,
+        usize::try_from(signal.size).ok()?,
+    );
+
+    let start_bit = match signal.byte_order {
+        ByteOrder::LittleEndian => start_bit,
+        // CAN-DBC uses `Lsb0` indexing of the bits even for big endian values, translate into the `Msb0` index used
+        // by the bit helpers.
+        ByteOrder::BigEndian => (start_bit / 8) * 8 + (7 - start_bit % 8),
+    };
+
+    if size == 0 || data.len() > 8 || start_bit + size > data.len() * 8 {
+        return None;
+    }
+
+    Some(match (signal.byte_order, signal.value_type) {
+        (ByteOrder::LittleEndian, ValueType::Unsigned) => {
+            Raw::Unsigned(bits::read_little_endian_unsigned(data, start_bit, size))
+        }
+        (ByteOrder::LittleEndian, ValueType::Signed) => {
+            Raw::Signed(bits::read_little_endian_signed(data, start_bit, size))
+        }
+        (ByteOrder::BigEndian, ValueType::Unsigned) => {
+            Raw::Unsigned(bits::read_big_endian_unsigned(data, start_bit, size))
+        }
+        (ByteOrder::BigEndian, ValueType::Signed) => {
+            Raw::Signed(bits::read_big_endian_signed(data, start_bit, size))
+        }
+    })
+}
+
+/// Decodes all signals of `message` present in `data`.
+///
+/// Multiplexed signals are only decoded if the multiplexor selects them, signals that do not fit within `data` or
+/// the supported signal sizes are skipped.
+pub(crate) fn decode<'a>(
+    dbc: &'a Dbc,
+    message: &'a Message,
+    data: &[u8],
+) -> Vec<DecodedSignal<'a>> {
+    let multiplexor = message
+        .signals
+        .iter()
+        .find(|signal| {
+            matches!(
+                signal.multiplexer_indicator,
+                MultiplexIndicator::Multiplexor
+                    | MultiplexIndicator::MultiplexorAndMultiplexedSignal(_)
+            )
+        })
+        .and_then(|signal| read_raw(signal, data))
+        .map(|raw| raw.as_i64() as u64);
+
+    Vec::from_iter(message.signals.iter().filter_map(|signal| {
+        match signal.multiplexer_indicator {
+            MultiplexIndicator::MultiplexedSignal(selector)
+            | MultiplexIndicator::MultiplexorAndMultiplexedSignal(selector)
+                if multiplexor != Some(selector) =>
+            {
+                return None;
+            }
+            _ => {}
+        }
+
+        let raw = read_raw(signal, data)?;
+
+        let value = match dbc.extended_value_type_for_signal(message.id, &signal.name) {
+            Some(SignalExtendedValueType::IEEEfloat32Bit) => {
+                f64::from(f32::from_bits(raw.as_i64() as u32))
+            }
+            Some(SignalExtendedValueType::IEEEdouble64bit) => f64::from_bits(raw.as_i64() as u64),
+            Some(SignalExtendedValueType::SignedOrUnsignedInteger) | None => match raw {
+                Raw::Unsigned(value) => value as f64,
+                Raw::Signed(value) => value as f64,
+            },
+        };
+
+        let description = dbc
+            .value_descriptions_for_signal(message.id, &signal.name)
+            .into_iter()
+            .flatten()
+            .find(|description| description.id == raw.as_i64())
+            .map(|description| description.description.as_str());
+
+        Some(DecodedSignal {
+            name: &signal.name,
+            value: value * signal.factor + signal.offset,
+            unit: &signal.unit,
+            description,
+        })
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use can_dbc::Dbc;
+
+    use super::{DecodedSignal, decode};
+
+    const DBC: &str = r#"
+VERSION ""
+
+NS_ :
+
+BO_ 2364540158 EEC1: 8 Vector__XXX
+ SG_ EngineSpeed : 24|16@1+ (0.125,0) [0|8031.875] "rpm" Vector__XXX
+ SG_ Temperature : 7|8@0- (1,-40) [-168|87] "degC" Vector__XXX
+
+BO_ 256 Muxed: 2 Vector__XXX
+ SG_ Selector M : 0|8@1+ (1,0) [0|0] "" Vector__XXX
+ SG_ First m0 : 8|8@1+ (1,0) [0|0] "" Vector__XXX
+ SG_ Second m1 : 8|8@1+ (1,0) [0|0] "" Vector__XXX
+
+VAL_ 256 First 5 "Five" ;
+"#;
+
+    #[test]
+    fn plain() {
+        let dbc = Dbc::try_from(DBC).unwrap();
+        let message = &dbc.messages[0];
+
+        let decoded = decode(&dbc, message, &[0xFE, 0, 0, 0x40, 0x25, 0, 0, 0]);
+
+        assert_eq!(
+            decoded,
+            [
+                DecodedSignal {
+                    name: "EngineSpeed",
+                    value: 1192.0,
+                    unit: "rpm",
+                    description: None,
+                },
+                DecodedSignal {
+                    name: "Temperature",
+                    value: -42.0,
+                    unit: "degC",
+                    description: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn short_frame() {
+        let dbc = Dbc::try_from(DBC).unwrap();
+        let message = &dbc.messages[0];
+
+        // `EngineSpeed` ends after the fourth byte, the big endian `Temperature` fits into the first byte.
+        let decoded = decode(&dbc, message, &[0xFE, 0, 0, 0x40]);
+        assert_eq!(
+            decoded,
+            [DecodedSignal {
+                name: "Temperature",
+                value: -42.0,
+                unit: "degC",
+                description: None,
+            }]
+        );
+
+        assert_eq!(decode(&dbc, message, &[]), []);
+    }
+
+    #[test]
+    fn multiplexed() {
+        let dbc = Dbc::try_from(DBC).unwrap();
+        let message = &dbc.messages[1];
+
+        let decoded = decode(&dbc, message, &[0, 5]);
+        assert_eq!(
+            Vec::from_iter(
+                decoded
+                    .iter()
+                    .map(|signal| (signal.name, signal.description))
+            ),
+            [("Selector", None), ("First", Some("Five"))]
+        );
+
+        let decoded = decode(&dbc, message, &[1, 5]);
+        assert_eq!(
+            Vec::from_iter(
+                decoded
+                    .iter()
+                    .map(|signal| (signal.name, signal.description))
+            ),
+            [("Selector", None), ("Second", None)]
+        );
+    }
+}
@@ -0,0 +1,312 @@
+//! `veecle-os can` CLI
+
+#![forbid(unsafe_code)]
+
+use std::io::Write;
+use std::process::ExitCode;
+
+use anyhow::Context;
+use camino::{Utf8Path, Utf8PathBuf};
+use can_dbc::{Dbc, MessageId};
+use veecle_os_data_support_can_codegen::{DbcDiff, Generator, Options};
+
+mod candump;
+mod decode;
+
+/// Veecle OS CAN CLI interface
+///
+/// Tools for working with CAN-DBC files.
+#[derive(clap::Parser, Debug)]
+#[command(disable_help_subcommand = true, version)]
+pub struct Arguments {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Compare two versions of a DBC file, reporting changes that affect the generated Rust types.
+    ///
+    /// Exits with status 1 if there are any differences.
+    Diff {
+        /// The old version of the DBC file.
+        old: Utf8PathBuf,
+
+        /// The new version of the DBC file.
+        new: Utf8PathBuf,
+    },
+
+    /// Decode the frames of a `candump` log file (as written by `candump -l`) into signal values.
+    Decode {
+        /// The DBC file describing the messages.
+        #[arg(long)]
+        dbc: Utf8PathBuf,
+
+        /// The `candump` log file.
+        log: Utf8PathBuf,
+
+        /// Print one JSON object per frame instead of human readable text.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Generate Rust code for the messages of a DBC file.
+    ///
+    /// The generated code refers to the runtime and `serde` through the re-exports of the
+    /// `veecle-os-data-support-can` crate, so that is the only dependency it needs.
+    Codegen {
+        /// The DBC file to generate code for.
+        dbc: Utf8PathBuf,
+
+        /// The file to write the generated code to, if not given the code is written to stdout.
+        #[arg(long, short)]
+        output: Option<Utf8PathBuf>,
+
+        /// Wrap the generated code in a `pub mod` with this name, instead of generating the contents of a module
+        /// file.
+        ///
+        /// This allows `include!`ing the generated code.
+        #[arg(long)]
+        module: Option<String>,
+
+        /// Only check that valid code can be generated, without writing any output.
+        ///
+        /// Exits with a non-zero status and reports the error if the DBC cannot be parsed or converted.
+        #[arg(long, conflicts_with = "output")]
+        check: bool,
+    },
+}
+
+/// Formats an id like `candump` does.
+fn format_id(id: MessageId) -> String {
+    match id {
+        MessageId::Standard(id) => format!("{id:03X}"),
+        MessageId::Extended(id) => format!("{id:08X}"),
+    }
+}
+
+/// Generates formatted code for the DBC `source`, using `path` to identify it in error messages.
+///
+/// If `module` is given, the code is wrapped in a module of that name.
+fn generate(path: &Utf8Path, source: &str, module: Option<&str>) -> anyhow::Result<String> {
+    let module = module
+        .map(|module| {
+            syn::parse_str::<syn::Ident>(module)
+                .with_context(|| format!("invalid module name {module:?}"))
+        })
+        .transpose()?;
+
+    let options = Options {
+        veecle_os_runtime: syn::parse_quote!(
+            ::veecle_os_data_support_can::reëxports::veecle_os_runtime
+        ),
+        veecle_os_data_support_can: syn::parse_quote!(::veecle_os_data_support_can),
+        arbitrary: None,
+        serde: syn::parse_quote!(::veecle_os_data_support_can::reëxports::serde),
+        message_frame_validations: Box::new(|_| None),
+        message_checksums: Box::new(|_| None),
+        byte_order: None,
+    };
+
+    let generator = Generator::new(path.as_str(), options, source);
+
+    let Some(module) = module else {
+        return generator.try_into_string();
+    };
+
+    let tokens = generator.try_into_token_stream()?;
+    let file = syn::parse2(quote::quote!(pub mod #module { #tokens }))
+        .context("parsing generated code to prettify")?;
+
+    Ok(prettyplease::unparse(&file))
+}
+
+fn decode_log(dbc: &Dbc, log: &str, json: bool, mut out: impl Write) -> anyhow::Result<()> {
+    for (index, line) in log.lines().enumerate() {
+        let Some(frame) =
+            candump::parse_line(line).with_context(|| format!("invalid log line {}", index + 1))?
+        else {
+            continue;
+        };
+
+        let message = dbc.messages.iter().find(|message| message.id == frame.id);
+        let signals = message
+            .map(|message| decode::decode(dbc, message, &frame.data))
+            .unwrap_or_default();
+        let id = format_id(frame.id);
+
+        if json {
+            let signals = Vec::from_iter(signals.iter().map(|signal| {
+                serde_json::json!({
+                    "name": signal.name,
+                    "value": signal.value,
+                    "unit": signal.unit,
+                    "description": signal.description,
+                })
+            }));
+            let value = serde_json::json!({
+                "timestamp": frame.timestamp,
+                "interface": frame.interface,
+                "id": id,
+                "message": message.map(|message| &message.name),
+                "signals": signals,
+            });
+            writeln!(out, "{value}")?;
+        } else {
+            let name = message.map_or("<unknown>", |message| &message.name);
+            writeln!(out, "({}) {} {id} {name}", frame.timestamp, frame.interface)?;
+            for signal in signals {
+                write!(out, "    {}: {}", signal.name, signal.value)?;
+                if !signal.unit.is_empty() {
+                    write!(out, " {}", signal.unit)?;
+                }
+                if let Some(description) = signal.description {
+                    write!(out, " ({description})")?;
+                }
+                writeln!(out)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+impl Arguments {
+    /// Runs the CLI command.
+    pub fn run(self) -> anyhow::Result<ExitCode> {
+        match self.command {
+            Command::Diff { old, new } => {
+                let old = fs_err::read_to_string(old)?;
+                let new = fs_err::read_to_string(new)?;
+
+                let diff = DbcDiff::new(&old, &new)?;
+                print!("{diff}");
+
+                Ok(if diff.is_empty() {
+                    ExitCode::SUCCESS
+                } else {
+                    ExitCode::FAILURE
+                })
+            }
+            Command::Decode { dbc, log, json } => {
+                let source = fs_err::read_to_string(&dbc)?;
+                let dbc = Dbc::try_from(source.as_str())
+                    .with_context(|| format!("failed to parse `{dbc}`"))?;
+                let log = fs_err::read_to_string(log)?;
+
+                decode_log(&dbc, &log, json, std::io::stdout().lock())?;
+
+                Ok(ExitCode::SUCCESS)
+            }
+            Command::Codegen {
+                dbc,
+                output,
+                module,
+                check,
+            } => {
+                let source = fs_err::read_to_string(&dbc)?;
+                let code = generate(&dbc, &source, module.as_deref())?;
+
+                match output {
+                    _ if check => {}
+                    Some(output) => fs_err::write(output, code)?,
+                    None => std::io::stdout().lock().write_all(code.as_bytes())?,
+                }
+
+                Ok(ExitCode::SUCCESS)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use can_dbc::Dbc;
+
+    const DBC: &str = r#"
+VERSION ""
+
+NS_ :
+
+BO_ 2364540158 EEC1: 8 Vector__XXX
+ SG_ EngineSpeed : 24|16@1+ (0.125,0) [0|8031.875] "rpm" Vector__XXX
+"#;
+
+    const LOG: &str = "\
+(1.000000) can0 0CF004FE#FE00004025000000
+(2.000000) can0 123#00
+";
+
+    fn decode_log(json: bool) -> String {
+        let dbc = Dbc::try_from(DBC).unwrap();
+        let mut out = Vec::new();
+        super::decode_log(&dbc, LOG, json, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn generate() {
+        let code = super::generate("demo.dbc".into(), DBC, None).unwrap();
+        assert!(code.starts_with("//!"));
+        assert!(code.contains("mod eec1"));
+    }
+
+    #[test]
+    fn generate_module() {
+        let code = super::generate("demo.dbc".into(), DBC, Some("demo")).unwrap();
+        assert!(code.starts_with("pub mod demo {"));
+        assert!(code.contains("mod eec1"));
+
+        assert!(super::generate("demo.dbc".into(), DBC, Some("not a name")).is_err());
+    }
+
+    #[test]
+    fn generate_invalid() {
+        let error = super::generate("broken.dbc".into(), "BO_ invalid", None).unwrap_err();
+        assert!(error.to_string().contains("`broken.dbc`"));
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!(
+            decode_log(false),
+            "\
+(1.000000) can0 0CF004FE EEC1
+    EngineSpeed: 1192 rpm
+(2.000000) can0 123 <unknown>
+"
+        );
+    }
+
+    #[test]
+    fn json() {
+        let output = decode_log(true);
+        let lines = Vec::from_iter(
+            output
+                .lines()
+                .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()),
+        );
+
+        assert_eq!(
+            lines,
+            [
+                serde_json::json!({
+                    "timestamp": "1.000000",
+                    "interface": "can0",
+                    "id": "0CF004FE",
+                    "message": "EEC1",
+                    "signals": [
+                        { "name": "EngineSpeed", "value": 1192.0, "unit": "rpm", "description": null },
+                    ],
+                }),
+                serde_json::json!({
+                    "timestamp": "2.000000",
+                    "interface": "can0",
+                    "id": "123",
+                    "message": null,
+                    "signals": [],
+                }),
+            ]
+        );
+    }
+}
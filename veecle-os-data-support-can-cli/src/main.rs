@@ -0,0 +1,9 @@
+//! `veecle-os can` CLI
+
+use std::process::ExitCode;
+
+use clap::Parser;
+
+fn main() -> anyhow::Result<ExitCode> {
+    veecle_os_data_support_can_cli::Arguments::parse().run()
+}
@@ -2,10 +2,16 @@
 
 pub(crate) trait AttributeValueExt {
     fn as_str(&self) -> Option<&str>;
+    fn as_u64(&self) -> Option<u64>;
 }
 
 pub(crate) trait DbcExt {
     fn find_raw_attribute_string(&self, name: &str) -> Option<&str>;
+    fn find_message_attribute(
+        &self,
+        message_id: &can_dbc::MessageId,
+        name: &str,
+    ) -> Option<&can_dbc::AttributeValue>;
 }
 
 impl AttributeValueExt for can_dbc::AttributeValue {
@@ -15,6 +21,16 @@ impl AttributeValueExt for can_dbc::AttributeValue {
             _ => None,
         }
     }
+
+    fn as_u64(&self) -> Option<u64> {
+        match *self {
+            Self::U64(value) => Some(value),
+            Self::I64(value) => u64::try_from(value).ok(),
+            // Integer attributes of some tools are written with a decimal point.
+            Self::Double(value) if value >= 0.0 && value.fract() == 0.0 => Some(value as u64),
+            _ => None,
+        }
+    }
 }
 
 impl DbcExt for can_dbc::Dbc {
@@ -25,4 +41,15 @@ impl DbcExt for can_dbc::Dbc {
             .value
             .as_str()
     }
+
+    fn find_message_attribute(
+        &self,
+        message_id: &can_dbc::MessageId,
+        name: &str,
+    ) -> Option<&can_dbc::AttributeValue> {
+        self.attribute_values_message
+            .iter()
+            .find(|value| value.message_id == *message_id && value.name == name)
+            .map(|value| &value.value)
+    }
 }
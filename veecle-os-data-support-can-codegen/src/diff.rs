@@ -0,0 +1,540 @@
+//! Comparison of two CAN-DBC versions at the level of the generated Rust types.
+
+use std::fmt;
+
+use anyhow::{Context, Result};
+use can_dbc::{Dbc, Message, Signal, ValueType};
+
+use crate::ByteOrder;
+
+/// Differences between two versions of a CAN-DBC file that affect the generated code.
+///
+/// Messages and signals are matched by name, so a renamed message or signal is reported as removed and added.
+///
+/// ```
+/// use veecle_os_data_support_can_codegen::DbcDiff;
+///
+/// let old = r#"
+/// VERSION ""
+///
+/// NS_ :
+///
+/// BO_ 256 Speed: 8 Vector__XXX
+///  SG_ Value : 0|16@1+ (0.125,0) [0|8031.875] "rpm" Vector__XXX
+/// "#;
+///
+/// let new = r#"
+/// VERSION ""
+///
+/// NS_ :
+///
+/// BO_ 256 Speed: 8 Vector__XXX
+///  SG_ Value : 0|16@1+ (0.25,0) [0|16063.75] "rpm" Vector__XXX
+/// "#;
+///
+/// let diff = DbcDiff::new(old, new)?;
+///
+/// assert_eq!(diff.changed_messages.len(), 1);
+/// assert!(!diff.is_empty());
+///
+/// # anyhow::Ok(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DbcDiff {
+    /// Names of the messages only present in the new DBC.
+    pub added_messages: Vec<String>,
+
+    /// Names of the messages only present in the old DBC.
+    pub removed_messages: Vec<String>,
+
+    /// Messages present in both DBCs that changed.
+    pub changed_messages: Vec<MessageDiff>,
+}
+
+/// Changes of a message present in both DBCs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageDiff {
+    /// The name of the message.
+    pub name: String,
+
+    /// Changes of the message itself.
+    pub changes: Vec<MessageChange>,
+
+    /// Names of the signals only present in the new message.
+    pub added_signals: Vec<String>,
+
+    /// Names of the signals only present in the old message.
+    pub removed_signals: Vec<String>,
+
+    /// Signals present in both messages that changed.
+    pub changed_signals: Vec<SignalDiff>,
+}
+
+/// A change of a message's properties.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum MessageChange {
+    /// The frame id changed.
+    ///
+    /// Ids are in their DBC representation, with bit 31 set for extended ids.
+    Id {
+        /// The old frame id.
+        old: u32,
+        /// The new frame id.
+        new: u32,
+    },
+
+    /// The frame length changed.
+    Size {
+        /// The old frame length in bytes.
+        old: u64,
+        /// The new frame length in bytes.
+        new: u64,
+    },
+}
+
+/// Changes of a signal present in both messages.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalDiff {
+    /// The name of the signal.
+    pub name: String,
+
+    /// Changes of the signal.
+    pub changes: Vec<SignalChange>,
+}
+
+/// A change of a signal's properties.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum SignalChange {
+    /// The Rust type of the signal's value changed, `None` if the signal is not supported by the code generator.
+    Type {
+        /// The old value type.
+        old: Option<String>,
+        /// The new value type.
+        new: Option<String>,
+    },
+
+    /// The factor or offset used to convert between raw and physical value changed.
+    Scaling {
+        /// The old scaling.
+        old: Scaling,
+        /// The new scaling.
+        new: Scaling,
+    },
+
+    /// The range of valid physical values changed.
+    Range {
+        /// The old `(min, max)` range.
+        old: (f64, f64),
+        /// The new `(min, max)` range.
+        new: (f64, f64),
+    },
+
+    /// The position or encoding of the signal within the frame changed.
+    Layout {
+        /// The old layout.
+        old: Layout,
+        /// The new layout.
+        new: Layout,
+    },
+
+    /// The unit changed.
+    Unit {
+        /// The old unit.
+        old: String,
+        /// The new unit.
+        new: String,
+    },
+}
+
+/// The conversion between a signal's raw and physical value, `physical = raw * factor + offset`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scaling {
+    /// The factor.
+    pub factor: f64,
+    /// The offset.
+    pub offset: f64,
+}
+
+/// The position and encoding of a signal within a frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Layout {
+    /// The start bit as written in the DBC.
+    pub start_bit: u64,
+    /// The size in bits.
+    pub size: u64,
+    /// The byte order.
+    pub byte_order: ByteOrder,
+    /// Whether the raw value is signed.
+    pub signed: bool,
+}
+
+impl DbcDiff {
+    /// Parses the `old` and `new` CAN-DBC sources and compares them.
+    pub fn new(old: &str, new: &str) -> Result<Self> {
+        let old = Dbc::try_from(old).context("failed to parse old DBC")?;
+        let new = Dbc::try_from(new).context("failed to parse new DBC")?;
+
+        Ok(Self::from_dbcs(&old, &new))
+    }
+
+    fn from_dbcs(old: &Dbc, new: &Dbc) -> Self {
+        fn find<'a>(dbc: &'a Dbc, name: &str) -> Option<&'a Message> {
+            dbc.messages.iter().find(|message| message.name == name)
+        }
+
+        let mut diff = Self::default();
+
+        for old_message in &old.messages {
+            match find(new, &old_message.name) {
+                Some(new_message) => {
+                    let message_diff = MessageDiff::new(old, old_message, new, new_message);
+                    if !message_diff.is_empty() {
+                        diff.changed_messages.push(message_diff);
+                    }
+                }
+                None => diff.removed_messages.push(old_message.name.clone()),
+            }
+        }
+
+        for new_message in &new.messages {
+            if find(old, &new_message.name).is_none() {
+                diff.added_messages.push(new_message.name.clone());
+            }
+        }
+
+        diff
+    }
+
+    /// Returns whether there are no differences.
+    pub fn is_empty(&self) -> bool {
+        self.added_messages.is_empty()
+            && self.removed_messages.is_empty()
+            && self.changed_messages.is_empty()
+    }
+}
+
+impl MessageDiff {
+    fn new(old_dbc: &Dbc, old: &Message, new_dbc: &Dbc, new: &Message) -> Self {
+        let mut changes = Vec::new();
+        if old.id != new.id {
+            changes.push(MessageChange::Id {
+                old: old.id.raw(),
+                new: new.id.raw(),
+            });
+        }
+        if old.size != new.size {
+            changes.push(MessageChange::Size {
+                old: old.size,
+                new: new.size,
+            });
+        }
+
+        fn find<'a>(message: &'a Message, name: &str) -> Option<&'a Signal> {
+            message.signals.iter().find(|signal| signal.name == name)
+        }
+
+        let mut diff = Self {
+            name: old.name.clone(),
+            changes,
+            added_signals: Vec::new(),
+            removed_signals: Vec::new(),
+            changed_signals: Vec::new(),
+        };
+
+        for old_signal in &old.signals {
+            match find(new, &old_signal.name) {
+                Some(new_signal) => {
+                    let changes =
+                        signal_changes((old_dbc, old, old_signal), (new_dbc, new, new_signal));
+                    if !changes.is_empty() {
+                        diff.changed_signals.push(SignalDiff {
+                            name: old_signal.name.clone(),
+                            changes,
+                        });
+                    }
+                }
+                None => diff.removed_signals.push(old_signal.name.clone()),
+            }
+        }
+
+        for new_signal in &new.signals {
+            if find(old, &new_signal.name).is_none() {
+                diff.added_signals.push(new_signal.name.clone());
+            }
+        }
+
+        diff
+    }
+
+    /// Returns whether there are no differences.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+            && self.added_signals.is_empty()
+            && self.removed_signals.is_empty()
+            && self.changed_signals.is_empty()
+    }
+}
+
+fn signal_changes(
+    (old_dbc, old_message, old): (&Dbc, &Message, &Signal),
+    (new_dbc, new_message, new): (&Dbc, &Message, &Signal),
+) -> Vec<SignalChange> {
+    let mut changes = Vec::new();
+
+    let old_ty = crate::generate::signal_value_type(old_dbc, old_message, old);
+    let new_ty = crate::generate::signal_value_type(new_dbc, new_message, new);
+    if old_ty != new_ty {
+        changes.push(SignalChange::Type {
+            old: old_ty,
+            new: new_ty,
+        });
+    }
+
+    let scaling = |signal: &Signal| Scaling {
+        factor: signal.factor,
+        offset: signal.offset,
+    };
+    if scaling(old) != scaling(new) {
+        changes.push(SignalChange::Scaling {
+            old: scaling(old),
+            new: scaling(new),
+        });
+    }
+
+    if (old.min, old.max) != (new.min, new.max) {
+        changes.push(SignalChange::Range {
+            old: (old.min, old.max),
+            new: (new.min, new.max),
+        });
+    }
+
+    let layout = |signal: &Signal| Layout {
+        start_bit: signal.start_bit,
+        size: signal.size,
+        byte_order: match signal.byte_order {
+            can_dbc::ByteOrder::LittleEndian => ByteOrder::LittleEndian,
+            can_dbc::ByteOrder::BigEndian => ByteOrder::BigEndian,
+        },
+        signed: signal.value_type == ValueType::Signed,
+    };
+    if layout(old) != layout(new) {
+        changes.push(SignalChange::Layout {
+            old: layout(old),
+            new: layout(new),
+        });
+    }
+
+    if old.unit != new.unit {
+        changes.push(SignalChange::Unit {
+            old: old.unit.clone(),
+            new: new.unit.clone(),
+        });
+    }
+
+    changes
+}
+
+/// Bit set in the DBC representation of extended ids.
+const EXTENDED_ID_BIT: u32 = 1 << 31;
+
+struct DisplayId(u32);
+
+impl fmt::Display for DisplayId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 & EXTENDED_ID_BIT != 0 {
+            write!(f, "{:#x} (extended)", self.0 & !EXTENDED_ID_BIT)
+        } else {
+            write!(f, "{:#x}", self.0)
+        }
+    }
+}
+
+struct DisplayType<'a>(&'a Option<String>);
+
+impl fmt::Display for DisplayType<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0.as_deref().unwrap_or("<unsupported>"))
+    }
+}
+
+impl fmt::Display for Scaling {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "factor {}, offset {}", self.factor, self.offset)
+    }
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let byte_order = match self.byte_order {
+            ByteOrder::LittleEndian => "little endian",
+            ByteOrder::BigEndian => "big endian",
+        };
+        let signed = if self.signed { "signed" } else { "unsigned" };
+        write!(f, "{}|{} {byte_order} {signed}", self.start_bit, self.size)
+    }
+}
+
+impl fmt::Display for SignalChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Type { old, new } => {
+                write!(f, "type: {} -> {}", DisplayType(old), DisplayType(new))
+            }
+            Self::Scaling { old, new } => write!(f, "scaling: {old} -> {new}"),
+            Self::Range {
+                old: (old_min, old_max),
+                new: (new_min, new_max),
+            } => write!(f, "range: [{old_min}|{old_max}] -> [{new_min}|{new_max}]"),
+            Self::Layout { old, new } => write!(f, "layout: {old} -> {new}"),
+            Self::Unit { old, new } => write!(f, "unit: {old:?} -> {new:?}"),
+        }
+    }
+}
+
+impl fmt::Display for MessageChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Id { old, new } => write!(f, "id: {} -> {}", DisplayId(*old), DisplayId(*new)),
+            Self::Size { old, new } => write!(f, "size: {old} -> {new}"),
+        }
+    }
+}
+
+/// Formats the differences as a human readable report, with `+` marking added, `-` removed and `~` changed items.
+impl fmt::Display for DbcDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for name in &self.added_messages {
+            writeln!(f, "+ message {name}")?;
+        }
+        for name in &self.removed_messages {
+            writeln!(f, "- message {name}")?;
+        }
+        for message in &self.changed_messages {
+            writeln!(f, "~ message {}", message.name)?;
+            for change in &message.changes {
+                writeln!(f, "    {change}")?;
+            }
+            for name in &message.added_signals {
+                writeln!(f, "    + signal {name}")?;
+            }
+            for name in &message.removed_signals {
+                writeln!(f, "    - signal {name}")?;
+            }
+            for signal in &message.changed_signals {
+                writeln!(f, "    ~ signal {}", signal.name)?;
+                for change in &signal.changes {
+                    writeln!(f, "        {change}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DbcDiff, SignalChange};
+
+    const OLD: &str = r#"
+VERSION ""
+
+NS_ :
+
+BO_ 256 Speed: 8 Vector__XXX
+ SG_ Value : 0|16@1+ (0.125,0) [0|8031.875] "rpm" Vector__XXX
+ SG_ Removed : 16|8@1+ (1,0) [0|0] "" Vector__XXX
+
+BO_ 257 Unchanged: 8 Vector__XXX
+ SG_ Value : 0|8@1+ (1,0) [0|0] "" Vector__XXX
+
+BO_ 258 Removed: 8 Vector__XXX
+ SG_ Value : 0|8@1+ (1,0) [0|0] "" Vector__XXX
+"#;
+
+    const NEW: &str = r#"
+VERSION ""
+
+NS_ :
+
+BO_ 256 Speed: 8 Vector__XXX
+ SG_ Value : 0|16@1+ (0.5,0) [0|32767.5] "km/h" Vector__XXX
+ SG_ Added : 16|8@1+ (1,0) [0|0] "" Vector__XXX
+
+BO_ 257 Unchanged: 8 Vector__XXX
+ SG_ Value : 0|8@1+ (1,0) [0|0] "" Vector__XXX
+
+BO_ 259 Added: 8 Vector__XXX
+ SG_ Value : 0|8@1+ (1,0) [0|0] "" Vector__XXX
+"#;
+
+    #[test]
+    fn identical() {
+        let diff = DbcDiff::new(OLD, OLD).unwrap();
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "");
+    }
+
+    #[test]
+    fn changes() {
+        let diff = DbcDiff::new(OLD, NEW).unwrap();
+
+        assert_eq!(diff.added_messages, ["Added"]);
+        assert_eq!(diff.removed_messages, ["Removed"]);
+
+        let [speed] = &diff.changed_messages[..] else {
+            panic!("expected a single changed message: {diff:?}");
+        };
+        assert_eq!(speed.name, "Speed");
+        assert_eq!(speed.added_signals, ["Added"]);
+        assert_eq!(speed.removed_signals, ["Removed"]);
+
+        let [value] = &speed.changed_signals[..] else {
+            panic!("expected a single changed signal: {speed:?}");
+        };
+        assert!(matches!(
+            value.changes[..],
+            [
+                SignalChange::Scaling { .. },
+                SignalChange::Range { .. },
+                SignalChange::Unit { .. }
+            ]
+        ));
+
+        assert_eq!(
+            diff.to_string(),
+            "\
++ message Added
+- message Removed
+~ message Speed
+    + signal Added
+    - signal Removed
+    ~ signal Value
+        scaling: factor 0.125, offset 0 -> factor 0.5, offset 0
+        range: [0|8031.875] -> [0|32767.5]
+        unit: \"rpm\" -> \"km/h\"
+"
+        );
+    }
+
+    #[test]
+    fn retyped() {
+        let new = OLD.replace("0|16@1+ (0.125,0) [0|8031.875]", "0|16@1- (1,0) [0|0]");
+        let diff = DbcDiff::new(OLD, &new).unwrap();
+
+        let changes = &diff.changed_messages[0].changed_signals[0].changes;
+        assert_eq!(
+            changes[0],
+            SignalChange::Type {
+                old: Some("f64".to_owned()),
+                new: Some("i16".to_owned()),
+            }
+        );
+        assert!(
+            changes
+                .iter()
+                .any(|change| matches!(change, SignalChange::Layout { .. }))
+        );
+    }
+}
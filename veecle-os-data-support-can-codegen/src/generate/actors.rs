@@ -31,7 +31,8 @@ pub(super) fn generate(options: &crate::Options, dbc: &Dbc) -> Result<TokenStrea
 
         /// An actor that will attempt to parse any [`Frame`] messages and publish the parsed messages.
         ///
-        /// If used you must also provide some interface-actor that writes the `Frame`s from your transceiver.
+        /// If used you must also provide some interface-actor that writes the `Frame`s from your transceiver, e.g. a
+        /// `veecle_os_data_support_can::ReceiveFrames` actor for a `CanTransport`.
         #[#veecle_os_runtime::actor #actor_args]
         #allow
         pub async fn deserialize_frames(
@@ -1,14 +1,24 @@
 use std::collections::HashSet;
 
-use anyhow::{Result, bail, ensure};
-use can_dbc::{Comment, Dbc, Message, Signal, SignalExtendedValueType, ValueType};
+use anyhow::{Context, Result, bail, ensure};
+use can_dbc::{Comment, Dbc, Message, Signal, SignalExtendedValueType, Transmitter, ValueType};
 use heck::{ToPascalCase, ToSnakeCase};
 use proc_macro2::{Span, TokenStream};
 use quote::{ToTokens, quote};
 
+use crate::dbc_ext::{AttributeValueExt, DbcExt};
+
 struct GeneratedSignal {
+    /// The name of the signal as written in the DBC.
+    dbc_name: String,
     name: syn::Ident,
     snake_case_name: syn::Ident,
+    /// The type returned by the signal's `value()`.
+    ty: syn::Ident,
+    /// Turns a value into a `ty` literal.
+    make_lit: fn(FloatOrInt) -> syn::Lit,
+    /// The doc comment lines from the DBC comments on the signal.
+    comments: Vec<String>,
     definition: TokenStream,
 }
 
@@ -363,6 +373,28 @@ fn signal_type(
     }
 }
 
+/// Returns the Rust type used for the value of `signal`, or `None` if the signal is not supported.
+pub(crate) fn signal_value_type(dbc: &Dbc, message: &Message, signal: &Signal) -> Option<String> {
+    signal_type(
+        dbc,
+        message,
+        signal,
+        signal.factor.into(),
+        signal.offset.into(),
+        signal.max.into(),
+        signal.min.into(),
+    )
+    .ok()
+    .map(|signal_type| signal_type.ty.to_string())
+}
+
+/// Returns the byte order of `signal`, taking the override from `options` into account.
+fn signal_byte_order(options: &crate::Options, signal: &Signal) -> can_dbc::ByteOrder {
+    options
+        .byte_order
+        .map_or(signal.byte_order, crate::ByteOrder::to_dbc)
+}
+
 fn translate_be_signal_start(start_bit: usize) -> usize {
     // CAN-DBC appears to use `Lsb0` indexing of the bits even for BE values, so we have to invert the bit-offset within
     // the target byte to get the `Msb0` index.
@@ -411,7 +443,7 @@ fn generate_signal(
             } if message_id == &message.id && name == &signal.name => Some(comment),
             _ => None,
         })
-        .map(|comment| format!(" ```text\n{comment}\n```"))
+        .map(|comment| super::comment_doc(comment))
         .collect::<Vec<_>>();
 
     let SignalType {
@@ -462,29 +494,31 @@ fn generate_signal(
     let start_bit = usize::try_from(signal.start_bit)?;
     let signal_size = usize::try_from(signal.size)?;
 
-    let (start_bit, read_bits, write_bits) =
-        match (signal.byte_order, raw_ty.to_string().starts_with("u")) {
-            (can_dbc::ByteOrder::LittleEndian, true) => (
-                start_bit,
-                quote!(read_little_endian_unsigned),
-                quote!(write_little_endian_unsigned),
-            ),
-            (can_dbc::ByteOrder::LittleEndian, false) => (
-                start_bit,
-                quote!(read_little_endian_signed),
-                quote!(write_little_endian_signed),
-            ),
-            (can_dbc::ByteOrder::BigEndian, true) => (
-                translate_be_signal_start(start_bit),
-                quote!(read_big_endian_unsigned),
-                quote!(write_big_endian_unsigned),
-            ),
-            (can_dbc::ByteOrder::BigEndian, false) => (
-                translate_be_signal_start(start_bit),
-                quote!(read_big_endian_signed),
-                quote!(write_big_endian_signed),
-            ),
-        };
+    let (start_bit, read_bits, write_bits) = match (
+        signal_byte_order(options, signal),
+        raw_ty.to_string().starts_with("u"),
+    ) {
+        (can_dbc::ByteOrder::LittleEndian, true) => (
+            start_bit,
+            quote!(read_little_endian_unsigned),
+            quote!(write_little_endian_unsigned),
+        ),
+        (can_dbc::ByteOrder::LittleEndian, false) => (
+            start_bit,
+            quote!(read_little_endian_signed),
+            quote!(write_little_endian_signed),
+        ),
+        (can_dbc::ByteOrder::BigEndian, true) => (
+            translate_be_signal_start(start_bit),
+            quote!(read_big_endian_unsigned),
+            quote!(write_big_endian_unsigned),
+        ),
+        (can_dbc::ByteOrder::BigEndian, false) => (
+            translate_be_signal_start(start_bit),
+            quote!(read_big_endian_signed),
+            quote!(write_big_endian_signed),
+        ),
+    };
 
     ensure!(
         start_bit + signal_size <= 64,
@@ -503,6 +537,27 @@ fn generate_signal(
         max.to_token_stream()
     );
 
+    // The raw type can be wider than the signal, raw values using the extra bits cannot be encoded.
+    let raw_ty_size = raw_ty.to_string()[1..].parse::<u64>()?;
+    let raw_range_check = (signal.size < raw_ty_size).then(|| {
+        let raw_ty_min = make_raw_lit(ty_min);
+        let raw_ty_max = make_raw_lit(ty_max);
+        let raw_out_of_range_error = format!(
+            "raw value out of range {}..={}",
+            raw_ty_min.to_token_stream(),
+            raw_ty_max.to_token_stream()
+        );
+        quote! {
+            if !(#raw_ty_min..=#raw_ty_max).contains(&raw) {
+                return Err(#veecle_os_data_support_can::CanDecodeError::OutOfRange {
+                    name: stringify!(#name),
+                    ty: stringify!(#raw_ty),
+                    message: #raw_out_of_range_error,
+                });
+            }
+        }
+    });
+
     let arbitrary_impl = options.arbitrary.as_ref().map(|a| {
         let arbitrary = &a.path;
         let cfg = a.to_cfg();
@@ -511,7 +566,7 @@ fn generate_signal(
         let value = quote! {
             let min = Self::MIN.raw();
             let max = Self::MAX.raw();
-            Ok(Self::try_from_raw(u.int_in_range(min..=max)?).expect("we generate in range"))
+            Ok(Self::from_raw(u.int_in_range(min..=max)?).expect("we generate in range"))
         };
         quote! {
             #cfg
@@ -667,16 +722,23 @@ fn generate_signal(
 
                 #(#consts)*
 
-                fn try_from_raw(raw: #raw_ty) -> Result<Self, #veecle_os_data_support_can::CanDecodeError> {
-                    Self::try_from(#from_raw_with_factor)
+                /// Creates the signal from its raw value as encoded in the frame, without applying the factor and
+                /// offset.
+                ///
+                /// Fails if the raw value does not fit in the signal's bits or its physical value is out of range.
+                pub fn from_raw(raw: #raw_ty) -> Result<Self, #veecle_os_data_support_can::CanDecodeError> {
+                    #raw_range_check
+                    Self::try_from(#from_raw_with_factor)?;
+                    Ok(Self { raw })
                 }
 
-                fn raw(&self) -> #raw_ty {
+                /// Returns the raw value as encoded in the frame, without applying the factor and offset.
+                pub fn raw(&self) -> #raw_ty {
                     self.raw
                 }
 
                 pub(super) fn read_bits(bytes: &[u8]) -> Result<Self, #veecle_os_data_support_can::CanDecodeError> {
-                    Self::try_from_raw(#raw_ty::try_from(bits::#read_bits(bytes, #start_bit, #signal_size)).unwrap())
+                    Self::from_raw(#raw_ty::try_from(bits::#read_bits(bytes, #start_bit, #signal_size)).unwrap())
                 }
 
                 pub(super) fn write_bits(&self, bytes: &mut [u8]) {
@@ -710,11 +772,119 @@ fn generate_signal(
 
             #arbitrary_impl
         },
+        dbc_name: signal.name.clone(),
         name,
         snake_case_name,
+        ty,
+        make_lit,
+        comments,
     })
 }
 
+/// Generates the validations configured by `checksum` for `message`.
+///
+/// Returns the validation of the checksum, run on the frame `bytes`, and the validation of the counter, run on the
+/// decoded `value`.
+fn generate_checksum_validation(
+    options: &crate::Options,
+    message: &Message,
+    signals: &[GeneratedSignal],
+    checksum: &crate::ChecksumOptions,
+) -> Result<(Option<TokenStream>, Option<TokenStream>)> {
+    let veecle_os_data_support_can = &options.veecle_os_data_support_can;
+
+    let Some(signal) = message
+        .signals
+        .iter()
+        .find(|signal| signal.name == checksum.signal)
+    else {
+        bail!(
+            "checksum signal {:?} not found in message {:?} [id {:?}]",
+            checksum.signal,
+            message.name,
+            message.id
+        );
+    };
+
+    let start_bit = usize::try_from(signal.start_bit)?;
+    let byte_aligned = match signal_byte_order(options, signal) {
+        can_dbc::ByteOrder::LittleEndian => start_bit % 8 == 0,
+        // Big endian signals start at their most significant bit.
+        can_dbc::ByteOrder::BigEndian => start_bit % 8 == 7,
+    };
+    ensure!(
+        signal.size == 8 && byte_aligned,
+        "checksum signal {:?} of message {:?} [id {:?}] must be 8 bits wide and byte aligned",
+        signal.name,
+        message.name,
+        message.id
+    );
+
+    let index = proc_macro2::Literal::usize_unsuffixed(start_bit / 8);
+
+    let data = quote! {
+        bytes.iter().enumerate().filter(|&(index, _)| index != #index).map(|(_, &byte)| byte)
+    };
+
+    let actual = match checksum.algorithm {
+        crate::ChecksumAlgorithm::Crc8SaeJ1850 => {
+            quote!(#veecle_os_data_support_can::checksum::crc8_sae_j1850(#data))
+        }
+        crate::ChecksumAlgorithm::Crc8Autosar => {
+            quote!(#veecle_os_data_support_can::checksum::crc8_autosar(#data))
+        }
+        crate::ChecksumAlgorithm::E2eProfile1 { data_id } => {
+            let data_id = syn::LitInt::new(&format!("{data_id:#x}"), Span::call_site());
+            quote!(#veecle_os_data_support_can::checksum::e2e_profile1(#data_id, #data))
+        }
+    };
+
+    let checksum_validation = quote! {
+        {
+            let expected = bytes[#index];
+            let actual = #actual;
+            if expected != actual {
+                return Err(#veecle_os_data_support_can::CanDecodeError::ChecksumMismatch { expected, actual });
+            }
+        }
+    };
+
+    let counter_validation = checksum
+        .counter
+        .as_ref()
+        .map(|counter| {
+            let Some(GeneratedSignal {
+                name,
+                snake_case_name,
+                ty,
+                make_lit,
+                ..
+            }) = signals
+                .iter()
+                .find(|signal| signal.dbc_name == counter.signal)
+            else {
+                bail!(
+                    "counter signal {:?} not found in message {:?} [id {:?}]",
+                    counter.signal,
+                    message.name,
+                    message.id
+                );
+            };
+
+            let max = make_lit(i128::from(counter.max).into());
+            let out_of_range_error = format!("counter out of range ..={}", max.to_token_stream());
+
+            Ok(quote! {
+                if value.#snake_case_name.value() > #max {
+                    return Err(#veecle_os_data_support_can::CanDecodeError::OutOfRange { name: stringify!(#name), ty: stringify!(#ty), message: #out_of_range_error });
+                }
+            })
+        })
+        .transpose()?;
+
+    Ok((Some(checksum_validation), counter_validation))
+}
+
 /// Generates a module for data types and conversions related to `message`.
 fn generate_message(options: &crate::Options, dbc: &Dbc, message: &Message) -> Result<TokenStream> {
     let crate::Options {
@@ -722,6 +892,7 @@ fn generate_message(options: &crate::Options, dbc: &Dbc, message: &Message) -> R
         veecle_os_data_support_can,
         serde,
         message_frame_validations,
+        message_checksums,
         ..
     } = options;
 
@@ -735,7 +906,7 @@ fn generate_message(options: &crate::Options, dbc: &Dbc, message: &Message) -> R
             Comment::Message { id, comment } if id == &message.id => Some(comment),
             _ => None,
         })
-        .map(|comment| format!(" ```text\n{comment}\n```"))
+        .map(|comment| super::comment_doc(comment))
         .collect::<Vec<_>>();
 
     let validation =
@@ -764,6 +935,21 @@ fn generate_message(options: &crate::Options, dbc: &Dbc, message: &Message) -> R
         }
     };
 
+    let transmitters = message_transmitters(dbc, message);
+    let transmitters_doc = if transmitters.is_empty() {
+        " The nodes transmitting this message, none are declared in the DBC.".to_owned()
+    } else {
+        format!(
+            " The nodes transmitting this message: `{}`.",
+            transmitters.join("`, `")
+        )
+    };
+
+    let cycle_time = match message_cycle_time(dbc, message) {
+        Some(cycle_time) => quote!(Some(#cycle_time)),
+        None => quote!(None),
+    };
+
     let signals = Result::<Vec<_>>::from_iter(
         message
             .signals
@@ -775,6 +961,29 @@ fn generate_message(options: &crate::Options, dbc: &Dbc, message: &Message) -> R
     let signal_names = Vec::from_iter(signals.iter().map(|signal| &signal.name));
     let signal_snake_case_names =
         Vec::from_iter(signals.iter().map(|signal| &signal.snake_case_name));
+    let signal_comments = Vec::from_iter(signals.iter().map(|signal| &signal.comments));
+
+    let (checksum_validation, counter_validation) = match message_checksums(&name) {
+        Some(checksum) => generate_checksum_validation(options, message, &signals, &checksum)?,
+        None => (None, None),
+    };
+
+    let decode = match counter_validation {
+        Some(counter_validation) => quote! {
+            let value = Self {
+                #(#signal_snake_case_names: #snake_case_name::#signal_names::read_bits(&bytes)?,)*
+            };
+
+            #counter_validation
+
+            Ok(value)
+        },
+        None => quote! {
+            Ok(Self {
+                #(#signal_snake_case_names: #snake_case_name::#signal_names::read_bits(&bytes)?,)*
+            })
+        },
+    };
 
     let arbitrary_impl = options.arbitrary.as_ref().map(|a| {
         let arbitrary = &a.path;
@@ -803,12 +1012,19 @@ fn generate_message(options: &crate::Options, dbc: &Dbc, message: &Message) -> R
         #[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, _serde::Serialize)]
         #[serde(crate = "_serde")]
         pub struct #name {
-            #(pub #signal_snake_case_names: #snake_case_name::#signal_names,)*
+            #(
+                #(#[doc = #signal_comments])*
+                pub #signal_snake_case_names: #snake_case_name::#signal_names,
+            )*
         }
 
         impl #name {
             pub const FRAME_ID: #veecle_os_data_support_can::Id = #frame_id;
             pub const FRAME_LENGTH: usize = #message_size;
+            #[doc = #transmitters_doc]
+            pub const TRANSMITTERS: &'static [&'static str] = &[#(#transmitters),*];
+            /// The cycle time of this message in milliseconds from its `GenMsgCycleTime` attribute, `None` if it is only sent on change.
+            pub const CYCLE_TIME_MS: Option<u32> = #cycle_time;
         }
 
         impl TryFrom<&#veecle_os_data_support_can::Frame> for #name {
@@ -824,9 +1040,9 @@ fn generate_message(options: &crate::Options, dbc: &Dbc, message: &Message) -> R
 
                 #validation
 
-                Ok(Self {
-                    #(#signal_snake_case_names: #snake_case_name::#signal_names::read_bits(&bytes)?,)*
-                })
+                #checksum_validation
+
+                #decode
             }
         }
 
@@ -857,10 +1073,117 @@ fn generate_message(options: &crate::Options, dbc: &Dbc, message: &Message) -> R
             type DataType = Self;
         }
 
+        impl #veecle_os_data_support_can::PeriodicMessage for #name {
+            const CYCLE_TIME_MS: Option<u32> = Self::CYCLE_TIME_MS;
+
+            fn to_frame(&self) -> #veecle_os_data_support_can::Frame {
+                #veecle_os_data_support_can::Frame::from(self)
+            }
+        }
+
         #arbitrary_impl
     })
 }
 
+/// Returns the cycle time of `message` in milliseconds from its `GenMsgCycleTime` attribute, a cycle time of `0` means
+/// the message is not sent periodically.
+fn message_cycle_time(dbc: &Dbc, message: &Message) -> Option<u32> {
+    let cycle_time = dbc
+        .find_message_attribute(&message.id, "GenMsgCycleTime")?
+        .as_u64()?;
+    u32::try_from(cycle_time)
+        .ok()
+        .filter(|&cycle_time| cycle_time != 0)
+}
+
+/// Returns the names of the nodes transmitting `message`, from its definition and any `BO_TX_BU_` entries.
+///
+/// `Vector__XXX`, i.e. no transmitter, is skipped.
+fn message_transmitters<'a>(dbc: &'a Dbc, message: &'a Message) -> Vec<&'a str> {
+    let additional = dbc
+        .message_transmitters
+        .iter()
+        .filter(|transmitters| transmitters.message_id == message.id)
+        .flat_map(|transmitters| &transmitters.transmitter);
+
+    let mut names = Vec::new();
+    for transmitter in core::iter::once(&message.transmitter).chain(additional) {
+        if let Transmitter::NodeName(name) = transmitter
+            && !names.contains(&name.as_str())
+        {
+            names.push(name.as_str());
+        }
+    }
+    names
+}
+
+/// Returns `name` as an identifier, using a raw identifier for keywords, e.g. `r#type` for a node named `Type`.
+fn module_ident(name: &str) -> Result<syn::Ident> {
+    syn::parse_str::<syn::Ident>(name)
+        .or_else(|_| syn::parse_str::<syn::Ident>(&format!("r#{name}")))
+        .with_context(|| format!("invalid module name {name:?}"))
+}
+
+/// Generates a `nodes` module with a module per node, re-exporting the messages that node transmits.
+fn generate_nodes(dbc: &Dbc) -> Result<Option<TokenStream>> {
+    let mut nodes = Vec::<(&str, Vec<syn::Ident>)>::from_iter(
+        dbc.nodes.iter().map(|node| (node.0.as_str(), Vec::new())),
+    );
+    for message in &dbc.messages {
+        let name = syn::parse_str::<syn::Ident>(&message.name.to_pascal_case())?;
+        for transmitter in message_transmitters(dbc, message) {
+            match nodes.iter_mut().find(|(node, _)| *node == transmitter) {
+                Some((_, messages)) => messages.push(name.clone()),
+                None => nodes.push((transmitter, vec![name.clone()])),
+            }
+        }
+    }
+
+    nodes.retain(|(_, messages)| !messages.is_empty());
+    if nodes.is_empty() {
+        return Ok(None);
+    }
+
+    ensure!(
+        !dbc.messages
+            .iter()
+            .any(|message| message.name.to_snake_case() == "nodes"),
+        "the `nodes` module conflicts with the module of a message named like `nodes`"
+    );
+
+    let mut module_names = HashSet::new();
+    let nodes = Result::<Vec<_>>::from_iter(nodes.into_iter().map(|(node, messages)| {
+        let module_name = node.to_snake_case();
+        ensure!(
+            module_names.insert(module_name.clone()),
+            "multiple nodes map to the module name {module_name:?}"
+        );
+        let module_name = module_ident(&module_name)?;
+        let docs = format!(" The messages transmitted by the `{node}` node.");
+        let comments = dbc.comments.iter().filter_map(|comment| match comment {
+            Comment::Node { name, comment } if name == node => Some(super::comment_doc(comment)),
+            _ => None,
+        });
+        Ok(quote! {
+            #[doc = #docs]
+            #(
+                #[doc = ""]
+                #[doc = #comments]
+            )*
+            pub mod #module_name {
+                pub use super::super::{#(#messages),*};
+            }
+        })
+    }))?;
+
+    Ok(Some(quote! {
+        /// The messages grouped by the node transmitting them.
+        pub mod nodes {
+            #(#nodes)*
+        }
+    }))
+}
+
 pub(super) fn generate(options: &crate::Options, dbc: &Dbc) -> Result<TokenStream> {
     let serde = &options.serde;
 
@@ -870,9 +1193,13 @@ pub(super) fn generate(options: &crate::Options, dbc: &Dbc) -> Result<TokenStrea
             .map(|message| generate_message(options, dbc, message)),
     )?;
 
+    let nodes = generate_nodes(dbc)?;
+
     Ok(quote! {
         use #serde as _serde;
 
         #(#messages)*
+
+        #nodes
     })
 }
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use can_dbc::Dbc;
+use can_dbc::{Comment, Dbc};
 use proc_macro2::TokenStream;
 use quote::quote;
 
@@ -8,6 +8,31 @@ use crate::dbc_ext::DbcExt;
 mod actors;
 mod messages;
 
+pub(crate) use self::messages::signal_value_type;
+
+/// Formats a DBC `CM_` comment as a doc comment, keeping it verbatim in a `text` code block.
+///
+/// Line endings are normalized and the fence is made longer than any backtick run in the comment so the comment
+/// cannot end the block early.
+pub(crate) fn comment_doc(comment: &str) -> String {
+    let comment = comment.replace("\r\n", "\n").replace('\r', "\n");
+    let comment = comment
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let comment = comment.trim_matches('\n');
+
+    let longest_backticks = comment
+        .split(|character| character != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    let fence = "`".repeat(usize::max(3, longest_backticks + 1));
+
+    format!(" {fence}text\n{comment}\n{fence}")
+}
+
 fn database_comment(dbc: &Dbc) -> String {
     let version = dbc
         .find_raw_attribute_string("DatabaseVersion")
@@ -22,7 +47,14 @@ fn database_comment(dbc: &Dbc) -> String {
         .find_raw_attribute_string("DatabaseCompiler")
         .unwrap_or("unknown");
 
-    format!(" {protocol} v{version} for {bus} by {compiler}")
+    let mut docs = format!(" {protocol} v{version} for {bus} by {compiler}");
+    for comment in &dbc.comments {
+        if let Comment::Plain { comment } = comment {
+            docs.push_str("\n\n");
+            docs.push_str(&comment_doc(comment));
+        }
+    }
+    docs
 }
 
 /// Generates a module for everything defined by the `dbc`.
@@ -30,11 +62,25 @@ fn database_comment(dbc: &Dbc) -> String {
 /// `krate` should be a path to the `veecle-os-data-support-can` crate.
 pub(crate) fn generate(options: &crate::Options, dbc: &Dbc) -> Result<TokenStream> {
     let docs = database_comment(dbc);
+    let byte_order_warning = options.byte_order.map(|byte_order| {
+        let warning = format!(
+            " **Warning**: all signals use {} byte order, overriding the byte order declared in the DBC.",
+            match byte_order {
+                crate::ByteOrder::LittleEndian => "little endian",
+                crate::ByteOrder::BigEndian => "big endian",
+            }
+        );
+        quote! {
+            #![doc = ""]
+            #![doc = #warning]
+        }
+    });
     let messages = messages::generate(options, dbc)?;
     let actors = actors::generate(options, dbc)?;
 
     Ok(quote! {
         #![doc = #docs]
+        #byte_order_warning
 
         #![allow(dead_code)]
 
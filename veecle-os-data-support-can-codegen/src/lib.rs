@@ -14,6 +14,8 @@
 //!     }),
 //!     serde: syn::parse_str("my_serde")?,
 //!     message_frame_validations: Box::new(|_| None),
+//!     message_checksums: Box::new(|_| None),
+//!     byte_order: None,
 //! };
 //!
 //! let code = Generator::new("demo.dbc", options, &input).into_string();
@@ -25,16 +27,24 @@
 
 #![forbid(unsafe_code)]
 
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use anyhow::{Context, Result};
 use can_dbc::Dbc;
+use heck::ToPascalCase;
 use proc_macro2::{Literal, TokenStream};
-use quote::quote;
+use quote::{ToTokens, quote};
 
 mod dbc_ext;
+mod diff;
 mod generate;
 
+pub use self::diff::{
+    DbcDiff, Layout, MessageChange, MessageDiff, Scaling, SignalChange, SignalDiff,
+};
+
 /// Options to customize the generated code.
 #[derive(Debug)]
 pub struct ArbitraryOptions {
@@ -55,6 +65,73 @@ impl ArbitraryOptions {
     }
 }
 
+/// Checksum algorithms supported by [`ChecksumOptions`].
+///
+/// All algorithms compute the checksum over all bytes of the frame except the checksum byte, in order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ChecksumAlgorithm {
+    /// CRC-8 SAE J1850 (polynomial `0x1D`, initial value `0xFF`, final XOR `0xFF`).
+    Crc8SaeJ1850,
+
+    /// AUTOSAR CRC-8 `0x2F`, also known as CRC-8H2F (polynomial `0x2F`, initial value `0xFF`, final XOR `0xFF`).
+    Crc8Autosar,
+
+    /// AUTOSAR E2E profile 1 with data ID mode "both", a CRC-8 SAE J1850 over the two bytes of the data ID (low byte
+    /// first) followed by the frame's data.
+    E2eProfile1 {
+        /// The data ID identifying the message.
+        data_id: u16,
+    },
+}
+
+/// Options to validate a checksum and optionally a rolling counter when decoding a message.
+#[derive(Debug, Clone, Hash)]
+pub struct ChecksumOptions {
+    /// The name of the signal containing the checksum, as written in the DBC.
+    ///
+    /// The signal must be 8 bits wide and aligned to a byte.
+    pub signal: String,
+
+    /// The algorithm used to compute the checksum.
+    pub algorithm: ChecksumAlgorithm,
+
+    /// The rolling counter of the message, if any.
+    pub counter: Option<CounterOptions>,
+}
+
+/// Options to validate a rolling counter when decoding a message.
+///
+/// Decoding a single frame is stateless, so only the counter's value is validated; checking that the counter advances
+/// between frames is left to the receiver.
+#[derive(Debug, Clone, Hash)]
+pub struct CounterOptions {
+    /// The name of the signal containing the counter, as written in the DBC.
+    pub signal: String,
+
+    /// The maximum valid value of the counter, e.g. `14` for AUTOSAR E2E profile 1 where `15` is reserved.
+    pub max: u64,
+}
+
+/// Byte order of a signal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ByteOrder {
+    /// Little endian (Intel, `@1` in the DBC).
+    LittleEndian,
+
+    /// Big endian (Motorola, `@0` in the DBC).
+    BigEndian,
+}
+
+impl ByteOrder {
+    fn to_dbc(self) -> can_dbc::ByteOrder {
+        match self {
+            Self::LittleEndian => can_dbc::ByteOrder::LittleEndian,
+            Self::BigEndian => can_dbc::ByteOrder::BigEndian,
+        }
+    }
+}
+
 /// Options to customize the generated code.
 pub struct Options {
     /// A path to the `veecle-os-runtime` crate, e.g. `::veecle_os_runtime` if it is a dependency of the crate the generated code
@@ -76,6 +153,19 @@ pub struct Options {
     /// will be called to validate the frame during deserialization.
     #[allow(clippy::type_complexity)]
     pub message_frame_validations: Box<dyn Fn(&syn::Ident) -> Option<syn::Expr>>,
+
+    /// For each message name there can be an associated checksum configuration that will be validated during
+    /// deserialization, failing with `CanDecodeError::ChecksumMismatch` on mismatch.
+    ///
+    /// The checksum is validated after any `message_frame_validations`.
+    #[allow(clippy::type_complexity)]
+    pub message_checksums: Box<dyn Fn(&syn::Ident) -> Option<ChecksumOptions>>,
+
+    /// Overrides the byte order declared for each signal in the DBC, as a workaround for DBCs with incorrect
+    /// `@0`/`@1` markers.
+    ///
+    /// The override is noted as a warning in the generated module documentation.
+    pub byte_order: Option<ByteOrder>,
 }
 
 impl core::fmt::Debug for Options {
@@ -87,6 +177,7 @@ impl core::fmt::Debug for Options {
                 &self.veecle_os_data_support_can,
             )
             .field("arbitrary", &self.arbitrary)
+            .field("byte_order", &self.byte_order)
             .field(
                 "message_frame_validation",
                 &format!(
@@ -94,15 +185,74 @@ impl core::fmt::Debug for Options {
                     core::any::type_name_of_val(&*self.message_frame_validations)
                 ),
             )
+            .field(
+                "message_checksums",
+                &format!(
+                    "<value of type {}>",
+                    core::any::type_name_of_val(&*self.message_checksums)
+                ),
+            )
             .finish()
     }
 }
 
+impl Options {
+    /// Feeds everything that affects the generated code for the DBC `messages` into `hasher`.
+    ///
+    /// The per-message callbacks can't be hashed themselves, so their results for the name of every message are
+    /// hashed instead.
+    fn hash_for<'a>(&self, messages: impl IntoIterator<Item = &'a str>, hasher: &mut impl Hasher) {
+        let Self {
+            veecle_os_runtime,
+            veecle_os_data_support_can,
+            arbitrary,
+            serde,
+            message_frame_validations,
+            message_checksums,
+            byte_order,
+        } = self;
+
+        // Hash the tokens rather than the syntax trees, as the latter include the spans which differ between runs.
+        veecle_os_runtime.to_token_stream().to_string().hash(hasher);
+        veecle_os_data_support_can
+            .to_token_stream()
+            .to_string()
+            .hash(hasher);
+        serde.to_token_stream().to_string().hash(hasher);
+        arbitrary
+            .as_ref()
+            .map(|arbitrary| {
+                (
+                    arbitrary.path.to_token_stream().to_string(),
+                    arbitrary
+                        .cfg
+                        .as_ref()
+                        .map(|cfg| cfg.to_token_stream().to_string()),
+                )
+            })
+            .hash(hasher);
+        byte_order.hash(hasher);
+
+        for message in messages {
+            let Ok(name) = syn::parse_str::<syn::Ident>(&message.to_pascal_case()) else {
+                continue;
+            };
+            message_frame_validations(&name)
+                .map(|validation| validation.to_token_stream().to_string())
+                .hash(hasher);
+            message_checksums(&name).hash(hasher);
+        }
+    }
+}
+
 /// Generates Veecle OS code from a CAN-DBC file.
 #[derive(Debug)]
 pub struct Generator {
     options: Options,
-    inner: Result<Dbc>,
+    /// Identifier of the input for error messages.
+    context: String,
+    input: String,
+    cache_dir: Option<PathBuf>,
 }
 
 impl Generator {
@@ -112,15 +262,88 @@ impl Generator {
     pub fn new(context: &str, options: Options, input: &str) -> Self {
         Self {
             options,
-            // We don't return the error here so that we can decide later whether to report it via a `Result` or by
-            // generating `compile_error!`.
-            inner: Dbc::try_from(input).with_context(|| format!("failed to parse `{context}`")),
+            context: context.to_owned(),
+            // The input is only parsed once the code is generated, so that a cache hit skips parsing and we can decide
+            // later whether to report errors via a `Result` or by generating `compile_error!`.
+            input: input.to_owned(),
+            cache_dir: None,
         }
     }
 
+    /// Reuses previously generated code stored in `dir`, e.g. a subdirectory of `OUT_DIR` in a build script.
+    ///
+    /// Generated code is stored under a hash of the input, the [`Options`] and the version of this crate, so changing
+    /// any of them generates the code again. On a cache hit the input is not parsed at all. Errors are never cached,
+    /// and failing to write to the cache does not fail the generation. Entries are never removed from `dir`; building
+    /// with a different version of the Rust toolchain may change the hashes and leave unused entries behind.
+    pub fn with_cache(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = Some(dir.into());
+        self
+    }
+
+    /// Parses the CAN-DBC input.
+    fn parse(&self) -> Result<Dbc> {
+        Dbc::try_from(self.input.as_str())
+            .with_context(|| format!("failed to parse `{}`", self.context))
+    }
+
+    /// Returns the code cached for this input with `extension`, generating and caching it with `generate` if missing.
+    fn cached(
+        self,
+        extension: &str,
+        generate: impl FnOnce(&Options, &Dbc) -> Result<String>,
+    ) -> Result<String> {
+        let Some(dir) = &self.cache_dir else {
+            return generate(&self.options, &self.parse()?);
+        };
+
+        let mut hasher = DefaultHasher::new();
+        self.input.hash(&mut hasher);
+        let input_hash = hasher.finish();
+
+        let entry_path = |messages: &[&str]| {
+            let mut hasher = DefaultHasher::new();
+            env!("CARGO_PKG_VERSION").hash(&mut hasher);
+            input_hash.hash(&mut hasher);
+            self.options.hash_for(messages.iter().copied(), &mut hasher);
+            dir.join(format!("{:016x}.{extension}", hasher.finish()))
+        };
+
+        // The options are hashed with the message names, which only depend on the input. They are stored alongside
+        // the generated code so that a cache hit does not need to parse the input to find them.
+        let messages_path = dir.join(format!("{input_hash:016x}.messages"));
+        if let Ok(messages) = std::fs::read_to_string(&messages_path)
+            && let Ok(code) = std::fs::read_to_string(entry_path(&Vec::from_iter(messages.lines())))
+        {
+            return Ok(code);
+        }
+
+        let dbc = self.parse()?;
+        let messages = Vec::from_iter(dbc.messages.iter().map(|message| message.name.as_str()));
+        let path = entry_path(&messages);
+
+        let code = generate(&self.options, &dbc)?;
+
+        let _ = std::fs::create_dir_all(dir);
+        write_cache_entry(&messages_path, &messages.join("\n"));
+        write_cache_entry(&path, &code);
+
+        Ok(code)
+    }
+
     /// Converts the input into a [`TokenStream`], returning any parsing or semantic errors.
     pub fn try_into_token_stream(self) -> Result<TokenStream> {
-        generate::generate(&self.options, &self.inner?)
+        if self.cache_dir.is_none() {
+            return generate::generate(&self.options, &self.parse()?);
+        }
+
+        let code = self.cached("tokens", |options, dbc| {
+            Ok(generate::generate(options, dbc)?.to_string())
+        })?;
+
+        TokenStream::from_str(&code)
+            .map_err(|error| anyhow::anyhow!("{error}"))
+            .context("parsing cached generated code")
     }
 
     /// Converts the input into a [`TokenStream`], converting any error into a generated [`compile_error!`].
@@ -154,10 +377,12 @@ impl Generator {
 
     /// Converts the input into a formatted code [`String`], returning any parsing or semantic errors.
     pub fn try_into_string(self) -> Result<String> {
-        Ok(prettyplease::unparse(
-            &syn::parse_file(&self.try_into_token_stream()?.to_string())
-                .context("parsing generated code to prettify")?,
-        ))
+        self.cached("rs", |options, dbc| {
+            Ok(prettyplease::unparse(
+                &syn::parse_file(&generate::generate(options, dbc)?.to_string())
+                    .context("parsing generated code to prettify")?,
+            ))
+        })
     }
 
     /// Converts the input into a formatted code [`String`], converting any error into a generated [`compile_error!`].
@@ -172,3 +397,13 @@ impl Generator {
         maybe_pretty(self.into_token_stream().to_string())
     }
 }
+
+/// Writes `contents` to the cache entry at `path`.
+///
+/// The contents are written to a temporary file first so concurrent builds never read a partially written entry. The
+/// cache is only an optimization, so errors are ignored and the next build will try again.
+fn write_cache_entry(path: &Path, contents: &str) {
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(format!(".{}.tmp", std::process::id()));
+    let _ = std::fs::write(&temporary, contents).and_then(|()| std::fs::rename(&temporary, path));
+}
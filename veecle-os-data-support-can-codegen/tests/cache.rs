@@ -0,0 +1,133 @@
+#![expect(missing_docs)]
+
+use veecle_os_data_support_can_codegen::{Generator, Options};
+
+const INPUT: &str = include_str!("cases/CSS-Electronics-SAE-J1939-DEMO.dbc");
+
+fn options(serde: &str) -> Options {
+    Options {
+        veecle_os_runtime: syn::parse_str("::my_veecle_os_runtime").unwrap(),
+        veecle_os_data_support_can: syn::parse_str("::my_veecle_os_data_support_can").unwrap(),
+        arbitrary: None,
+        serde: syn::parse_str(serde).unwrap(),
+        message_frame_validations: Box::new(|_| None),
+        message_checksums: Box::new(|_| None),
+        byte_order: None,
+    }
+}
+
+/// Returns the cached generated code, without the message names stored per input.
+fn cache_entries(dir: &tempfile::TempDir) -> Vec<std::path::PathBuf> {
+    let mut entries = Vec::from_iter(
+        std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| {
+                path.extension()
+                    .is_none_or(|extension| extension != "messages")
+            }),
+    );
+    entries.sort();
+    entries
+}
+
+#[test]
+fn reuses_cached_code() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let uncached = Generator::new("demo.dbc", options("::my_serde"), INPUT).into_string();
+    let cached = Generator::new("demo.dbc", options("::my_serde"), INPUT)
+        .with_cache(dir.path())
+        .try_into_string()
+        .unwrap();
+    assert_eq!(cached, uncached);
+
+    let [entry] = <[_; 1]>::try_from(cache_entries(&dir)).unwrap();
+    std::fs::write(&entry, "// from the cache").unwrap();
+
+    let cached = Generator::new("demo.dbc", options("::my_serde"), INPUT)
+        .with_cache(dir.path())
+        .try_into_string()
+        .unwrap();
+    assert_eq!(cached, "// from the cache");
+}
+
+#[test]
+fn finds_entries_without_parsing() {
+    let dir = tempfile::tempdir().unwrap();
+
+    Generator::new("demo.dbc", options("::my_serde"), INPUT)
+        .with_cache(dir.path())
+        .try_into_string()
+        .unwrap();
+
+    // The message names needed to hash the options are stored per input, so a cache hit doesn't parse the input.
+    let [messages] = <[_; 1]>::try_from(Vec::from_iter(
+        std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == "messages")
+            }),
+    ))
+    .unwrap();
+    let names = std::fs::read_to_string(&messages).unwrap();
+    assert!(names.lines().any(|name| name == "EEC1"));
+
+    // Without the message names the entry can't be found, so the code is generated and cached again.
+    let [entry] = <[_; 1]>::try_from(cache_entries(&dir)).unwrap();
+    std::fs::write(&entry, "// from the cache").unwrap();
+    std::fs::remove_file(&messages).unwrap();
+
+    let generated = Generator::new("demo.dbc", options("::my_serde"), INPUT)
+        .with_cache(dir.path())
+        .try_into_string()
+        .unwrap();
+    assert_ne!(generated, "// from the cache");
+    assert_eq!(std::fs::read_to_string(&messages).unwrap(), names);
+}
+
+#[test]
+fn invalidates_on_changes() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let generate = |options, input: &str| {
+        Generator::new("demo.dbc", options, input)
+            .with_cache(dir.path())
+            .try_into_string()
+            .unwrap()
+    };
+
+    generate(options("::my_serde"), INPUT);
+    generate(options("::my_serde"), INPUT);
+    assert_eq!(cache_entries(&dir).len(), 1);
+
+    generate(options("::other_serde"), INPUT);
+    assert_eq!(cache_entries(&dir).len(), 2);
+
+    generate(options("::my_serde"), &INPUT.replace("EEC1", "EEC2"));
+    assert_eq!(cache_entries(&dir).len(), 3);
+
+    // Token streams are cached separately from formatted code.
+    Generator::new("demo.dbc", options("::my_serde"), INPUT)
+        .with_cache(dir.path())
+        .try_into_token_stream()
+        .unwrap();
+    assert_eq!(cache_entries(&dir).len(), 4);
+}
+
+#[test]
+fn does_not_cache_errors() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let result = Generator::new(
+        "broken.dbc",
+        options("::my_serde"),
+        include_str!("cases/fragment.dbc"),
+    )
+    .with_cache(dir.path())
+    .try_into_string();
+    assert!(result.is_err());
+    assert_eq!(cache_entries(&dir), Vec::<std::path::PathBuf>::new());
+}
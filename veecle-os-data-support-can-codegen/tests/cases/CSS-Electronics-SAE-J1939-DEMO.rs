@@ -16,18 +16,24 @@ Actual engine speed which is calculated over a minimum crankshaft angle of 720 d
     impl EngineSpeed {
         pub const MAX: Self = Self { raw: 64255 };
         pub const MIN: Self = Self { raw: 0 };
-        fn try_from_raw(
+        /// Creates the signal from its raw value as encoded in the frame, without applying the factor and
+        /// offset.
+        ///
+        /// Fails if the raw value does not fit in the signal's bits or its physical value is out of range.
+        pub fn from_raw(
             raw: u16,
         ) -> Result<Self, ::my_veecle_os_data_support_can::CanDecodeError> {
-            Self::try_from(raw as f64 * 0.125)
+            Self::try_from(raw as f64 * 0.125)?;
+            Ok(Self { raw })
         }
-        fn raw(&self) -> u16 {
+        /// Returns the raw value as encoded in the frame, without applying the factor and offset.
+        pub fn raw(&self) -> u16 {
             self.raw
         }
         pub(super) fn read_bits(
             bytes: &[u8],
         ) -> Result<Self, ::my_veecle_os_data_support_can::CanDecodeError> {
-            Self::try_from_raw(
+            Self::from_raw(
                 u16::try_from(bits::read_little_endian_unsigned(bytes, 24, 16)).unwrap(),
             )
         }
@@ -77,10 +83,7 @@ Actual engine speed which is calculated over a minimum crankshaft angle of 720 d
         ) -> ::my_arbitrary::Result<Self> {
             let min = Self::MIN.raw();
             let max = Self::MAX.raw();
-            Ok(
-                Self::try_from_raw(u.int_in_range(min..=max)?)
-                    .expect("we generate in range"),
-            )
+            Ok(Self::from_raw(u.int_in_range(min..=max)?).expect("we generate in range"))
         }
     }
 }
@@ -90,6 +93,9 @@ Electronic Engine Controller 1
 #[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, _serde::Serialize)]
 #[serde(crate = "_serde")]
 pub struct Eec1 {
+    /** ```text
+Actual engine speed which is calculated over a minimum crankshaft angle of 720 degrees divided by the number of cylinders.…
+```*/
     pub engine_speed: eec1::EngineSpeed,
 }
 impl Eec1 {
@@ -97,6 +103,10 @@ impl Eec1 {
         ::my_veecle_os_data_support_can::ExtendedId::new_unwrap(0xcf004fe),
     );
     pub const FRAME_LENGTH: usize = 8usize;
+    /// The nodes transmitting this message, none are declared in the DBC.
+    pub const TRANSMITTERS: &'static [&'static str] = &[];
+    /// The cycle time of this message in milliseconds from its `GenMsgCycleTime` attribute, `None` if it is only sent on change.
+    pub const CYCLE_TIME_MS: Option<u32> = None;
 }
 impl TryFrom<&::my_veecle_os_data_support_can::Frame> for Eec1 {
     type Error = ::my_veecle_os_data_support_can::CanDecodeError;
@@ -140,6 +150,12 @@ impl From<Eec1> for ::my_veecle_os_data_support_can::Frame {
 impl ::my_veecle_os_runtime::Storable for Eec1 {
     type DataType = Self;
 }
+impl ::my_veecle_os_data_support_can::PeriodicMessage for Eec1 {
+    const CYCLE_TIME_MS: Option<u32> = Self::CYCLE_TIME_MS;
+    fn to_frame(&self) -> ::my_veecle_os_data_support_can::Frame {
+        ::my_veecle_os_data_support_can::Frame::from(self)
+    }
+}
 #[cfg(all())]
 impl<'a> ::my_arbitrary::Arbitrary<'a> for Eec1 {
     fn arbitrary(
@@ -164,18 +180,24 @@ Wheel-Based Vehicle Speed: Speed of the vehicle as calculated from wheel or tail
     impl WheelBasedVehicleSpeed {
         pub const MAX: Self = Self { raw: 64255 };
         pub const MIN: Self = Self { raw: 0 };
-        fn try_from_raw(
+        /// Creates the signal from its raw value as encoded in the frame, without applying the factor and
+        /// offset.
+        ///
+        /// Fails if the raw value does not fit in the signal's bits or its physical value is out of range.
+        pub fn from_raw(
             raw: u16,
         ) -> Result<Self, ::my_veecle_os_data_support_can::CanDecodeError> {
-            Self::try_from(raw as f64 * 0.00390625)
+            Self::try_from(raw as f64 * 0.00390625)?;
+            Ok(Self { raw })
         }
-        fn raw(&self) -> u16 {
+        /// Returns the raw value as encoded in the frame, without applying the factor and offset.
+        pub fn raw(&self) -> u16 {
             self.raw
         }
         pub(super) fn read_bits(
             bytes: &[u8],
         ) -> Result<Self, ::my_veecle_os_data_support_can::CanDecodeError> {
-            Self::try_from_raw(
+            Self::from_raw(
                 u16::try_from(bits::read_little_endian_unsigned(bytes, 8, 16)).unwrap(),
             )
         }
@@ -225,10 +247,7 @@ Wheel-Based Vehicle Speed: Speed of the vehicle as calculated from wheel or tail
         ) -> ::my_arbitrary::Result<Self> {
             let min = Self::MIN.raw();
             let max = Self::MAX.raw();
-            Ok(
-                Self::try_from_raw(u.int_in_range(min..=max)?)
-                    .expect("we generate in range"),
-            )
+            Ok(Self::from_raw(u.int_in_range(min..=max)?).expect("we generate in range"))
         }
     }
 }
@@ -238,6 +257,9 @@ Cruise Control/Vehicle Speed 1
 #[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, _serde::Serialize)]
 #[serde(crate = "_serde")]
 pub struct Ccvs1 {
+    /** ```text
+Wheel-Based Vehicle Speed: Speed of the vehicle as calculated from wheel or tailshaft speed.
+```*/
     pub wheel_based_vehicle_speed: ccvs1::WheelBasedVehicleSpeed,
 }
 impl Ccvs1 {
@@ -245,6 +267,10 @@ impl Ccvs1 {
         ::my_veecle_os_data_support_can::ExtendedId::new_unwrap(0x18fef1fe),
     );
     pub const FRAME_LENGTH: usize = 8usize;
+    /// The nodes transmitting this message, none are declared in the DBC.
+    pub const TRANSMITTERS: &'static [&'static str] = &[];
+    /// The cycle time of this message in milliseconds from its `GenMsgCycleTime` attribute, `None` if it is only sent on change.
+    pub const CYCLE_TIME_MS: Option<u32> = None;
 }
 impl TryFrom<&::my_veecle_os_data_support_can::Frame> for Ccvs1 {
     type Error = ::my_veecle_os_data_support_can::CanDecodeError;
@@ -288,6 +314,12 @@ impl From<Ccvs1> for ::my_veecle_os_data_support_can::Frame {
 impl ::my_veecle_os_runtime::Storable for Ccvs1 {
     type DataType = Self;
 }
+impl ::my_veecle_os_data_support_can::PeriodicMessage for Ccvs1 {
+    const CYCLE_TIME_MS: Option<u32> = Self::CYCLE_TIME_MS;
+    fn to_frame(&self) -> ::my_veecle_os_data_support_can::Frame {
+        ::my_veecle_os_data_support_can::Frame::from(self)
+    }
+}
 #[cfg(all())]
 impl<'a> ::my_arbitrary::Arbitrary<'a> for Ccvs1 {
     fn arbitrary(
@@ -301,7 +333,8 @@ impl<'a> ::my_arbitrary::Arbitrary<'a> for Ccvs1 {
 use ::my_veecle_os_data_support_can::Frame;
 /// An actor that will attempt to parse any [`Frame`] messages and publish the parsed messages.
 ///
-/// If used you must also provide some interface-actor that writes the `Frame`s from your transceiver.
+/// If used you must also provide some interface-actor that writes the `Frame`s from your transceiver, e.g. a
+/// `veecle_os_data_support_can::ReceiveFrames` actor for a `CanTransport`.
 #[::my_veecle_os_runtime::actor(crate = ::my_veecle_os_runtime)]
 pub async fn deserialize_frames(
     mut reader: ::my_veecle_os_runtime::single_writer::Reader<'_, Frame>,
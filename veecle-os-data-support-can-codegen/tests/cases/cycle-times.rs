@@ -0,0 +1,632 @@
+// editorconfig-checker-disable
+//! unknown vunknown for unknown by unknown
+#![allow(dead_code)]
+use ::my_serde as _serde;
+pub mod engine_status {
+    use ::my_veecle_os_data_support_can::reëxports::bits;
+    use ::my_serde as _serde;
+    #[derive(Clone, Copy, PartialEq, PartialOrd, _serde::Serialize)]
+    #[serde(crate = "_serde")]
+    pub struct Rpm {
+        raw: u16,
+    }
+    impl Rpm {
+        pub const MAX: Self = Self { raw: 65535 };
+        pub const MIN: Self = Self { raw: 0 };
+        /// Creates the signal from its raw value as encoded in the frame, without applying the factor and
+        /// offset.
+        ///
+        /// Fails if the raw value does not fit in the signal's bits or its physical value is out of range.
+        pub fn from_raw(
+            raw: u16,
+        ) -> Result<Self, ::my_veecle_os_data_support_can::CanDecodeError> {
+            Self::try_from(raw)?;
+            Ok(Self { raw })
+        }
+        /// Returns the raw value as encoded in the frame, without applying the factor and offset.
+        pub fn raw(&self) -> u16 {
+            self.raw
+        }
+        pub(super) fn read_bits(
+            bytes: &[u8],
+        ) -> Result<Self, ::my_veecle_os_data_support_can::CanDecodeError> {
+            Self::from_raw(
+                u16::try_from(bits::read_little_endian_unsigned(bytes, 0, 16)).unwrap(),
+            )
+        }
+        pub(super) fn write_bits(&self, bytes: &mut [u8]) {
+            bits::write_little_endian_unsigned(bytes, 0, 16, self.raw().into())
+        }
+        pub fn value(&self) -> u16 {
+            self.raw
+        }
+    }
+    impl Default for Rpm {
+        fn default() -> Self {
+            Self::MIN
+        }
+    }
+    impl TryFrom<u16> for Rpm {
+        type Error = ::my_veecle_os_data_support_can::CanDecodeError;
+        fn try_from(value: u16) -> Result<Self, Self::Error> {
+            Ok(Self { raw: value })
+        }
+    }
+    impl ::my_veecle_os_runtime::Storable for Rpm {
+        type DataType = Self;
+    }
+    impl core::fmt::Debug for Rpm {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.debug_struct("Rpm")
+                .field("raw", &self.raw)
+                .field("value", &self.value())
+                .finish()
+        }
+    }
+    #[cfg(all())]
+    impl<'a> ::my_arbitrary::Arbitrary<'a> for Rpm {
+        fn arbitrary(
+            u: &mut ::my_arbitrary::Unstructured<'a>,
+        ) -> ::my_arbitrary::Result<Self> {
+            let min = Self::MIN.raw();
+            let max = Self::MAX.raw();
+            Ok(Self::from_raw(u.int_in_range(min..=max)?).expect("we generate in range"))
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, _serde::Serialize)]
+#[serde(crate = "_serde")]
+pub struct EngineStatus {
+    pub rpm: engine_status::Rpm,
+}
+impl EngineStatus {
+    pub const FRAME_ID: ::my_veecle_os_data_support_can::Id = ::my_veecle_os_data_support_can::Id::Standard(
+        ::my_veecle_os_data_support_can::StandardId::new_unwrap(0x64),
+    );
+    pub const FRAME_LENGTH: usize = 2usize;
+    /// The nodes transmitting this message: `Engine`.
+    pub const TRANSMITTERS: &'static [&'static str] = &["Engine"];
+    /// The cycle time of this message in milliseconds from its `GenMsgCycleTime` attribute, `None` if it is only sent on change.
+    pub const CYCLE_TIME_MS: Option<u32> = Some(100u32);
+}
+impl TryFrom<&::my_veecle_os_data_support_can::Frame> for EngineStatus {
+    type Error = ::my_veecle_os_data_support_can::CanDecodeError;
+    fn try_from(
+        frame: &::my_veecle_os_data_support_can::Frame,
+    ) -> Result<Self, Self::Error> {
+        if frame.id() != Self::FRAME_ID {
+            return Err(::my_veecle_os_data_support_can::CanDecodeError::IncorrectId);
+        }
+        let bytes: [u8; Self::FRAME_LENGTH] = frame
+            .data()
+            .try_into()
+            .map_err(|_| {
+                ::my_veecle_os_data_support_can::CanDecodeError::IncorrectBufferSize
+            })?;
+        Ok(Self {
+            rpm: engine_status::Rpm::read_bits(&bytes)?,
+        })
+    }
+}
+impl TryFrom<::my_veecle_os_data_support_can::Frame> for EngineStatus {
+    type Error = ::my_veecle_os_data_support_can::CanDecodeError;
+    fn try_from(
+        frame: ::my_veecle_os_data_support_can::Frame,
+    ) -> Result<Self, Self::Error> {
+        Self::try_from(&frame)
+    }
+}
+impl From<&EngineStatus> for ::my_veecle_os_data_support_can::Frame {
+    fn from(value: &EngineStatus) -> Self {
+        let mut bytes = [0u8; EngineStatus::FRAME_LENGTH];
+        value.rpm.write_bits(&mut bytes);
+        Frame::new(EngineStatus::FRAME_ID, bytes)
+    }
+}
+impl From<EngineStatus> for ::my_veecle_os_data_support_can::Frame {
+    fn from(value: EngineStatus) -> Self {
+        Self::from(&value)
+    }
+}
+impl ::my_veecle_os_runtime::Storable for EngineStatus {
+    type DataType = Self;
+}
+impl ::my_veecle_os_data_support_can::PeriodicMessage for EngineStatus {
+    const CYCLE_TIME_MS: Option<u32> = Self::CYCLE_TIME_MS;
+    fn to_frame(&self) -> ::my_veecle_os_data_support_can::Frame {
+        ::my_veecle_os_data_support_can::Frame::from(self)
+    }
+}
+#[cfg(all())]
+impl<'a> ::my_arbitrary::Arbitrary<'a> for EngineStatus {
+    fn arbitrary(
+        u: &mut ::my_arbitrary::Unstructured<'a>,
+    ) -> ::my_arbitrary::Result<Self> {
+        Ok(Self { rpm: u.arbitrary()? })
+    }
+}
+pub mod gateway_request {
+    use ::my_veecle_os_data_support_can::reëxports::bits;
+    use ::my_serde as _serde;
+    #[derive(Clone, Copy, PartialEq, PartialOrd, _serde::Serialize)]
+    #[serde(crate = "_serde")]
+    pub struct Mode {
+        raw: u8,
+    }
+    impl Mode {
+        pub const MAX: Self = Self { raw: 255 };
+        pub const MIN: Self = Self { raw: 0 };
+        /// Creates the signal from its raw value as encoded in the frame, without applying the factor and
+        /// offset.
+        ///
+        /// Fails if the raw value does not fit in the signal's bits or its physical value is out of range.
+        pub fn from_raw(
+            raw: u8,
+        ) -> Result<Self, ::my_veecle_os_data_support_can::CanDecodeError> {
+            Self::try_from(raw)?;
+            Ok(Self { raw })
+        }
+        /// Returns the raw value as encoded in the frame, without applying the factor and offset.
+        pub fn raw(&self) -> u8 {
+            self.raw
+        }
+        pub(super) fn read_bits(
+            bytes: &[u8],
+        ) -> Result<Self, ::my_veecle_os_data_support_can::CanDecodeError> {
+            Self::from_raw(
+                u8::try_from(bits::read_little_endian_unsigned(bytes, 0, 8)).unwrap(),
+            )
+        }
+        pub(super) fn write_bits(&self, bytes: &mut [u8]) {
+            bits::write_little_endian_unsigned(bytes, 0, 8, self.raw().into())
+        }
+        pub fn value(&self) -> u8 {
+            self.raw
+        }
+    }
+    impl Default for Mode {
+        fn default() -> Self {
+            Self::MIN
+        }
+    }
+    impl TryFrom<u8> for Mode {
+        type Error = ::my_veecle_os_data_support_can::CanDecodeError;
+        fn try_from(value: u8) -> Result<Self, Self::Error> {
+            Ok(Self { raw: value })
+        }
+    }
+    impl ::my_veecle_os_runtime::Storable for Mode {
+        type DataType = Self;
+    }
+    impl core::fmt::Debug for Mode {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.debug_struct("Mode")
+                .field("raw", &self.raw)
+                .field("value", &self.value())
+                .finish()
+        }
+    }
+    #[cfg(all())]
+    impl<'a> ::my_arbitrary::Arbitrary<'a> for Mode {
+        fn arbitrary(
+            u: &mut ::my_arbitrary::Unstructured<'a>,
+        ) -> ::my_arbitrary::Result<Self> {
+            let min = Self::MIN.raw();
+            let max = Self::MAX.raw();
+            Ok(Self::from_raw(u.int_in_range(min..=max)?).expect("we generate in range"))
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, _serde::Serialize)]
+#[serde(crate = "_serde")]
+pub struct GatewayRequest {
+    pub mode: gateway_request::Mode,
+}
+impl GatewayRequest {
+    pub const FRAME_ID: ::my_veecle_os_data_support_can::Id = ::my_veecle_os_data_support_can::Id::Standard(
+        ::my_veecle_os_data_support_can::StandardId::new_unwrap(0xc8),
+    );
+    pub const FRAME_LENGTH: usize = 1usize;
+    /// The nodes transmitting this message: `Gateway`.
+    pub const TRANSMITTERS: &'static [&'static str] = &["Gateway"];
+    /// The cycle time of this message in milliseconds from its `GenMsgCycleTime` attribute, `None` if it is only sent on change.
+    pub const CYCLE_TIME_MS: Option<u32> = None;
+}
+impl TryFrom<&::my_veecle_os_data_support_can::Frame> for GatewayRequest {
+    type Error = ::my_veecle_os_data_support_can::CanDecodeError;
+    fn try_from(
+        frame: &::my_veecle_os_data_support_can::Frame,
+    ) -> Result<Self, Self::Error> {
+        if frame.id() != Self::FRAME_ID {
+            return Err(::my_veecle_os_data_support_can::CanDecodeError::IncorrectId);
+        }
+        let bytes: [u8; Self::FRAME_LENGTH] = frame
+            .data()
+            .try_into()
+            .map_err(|_| {
+                ::my_veecle_os_data_support_can::CanDecodeError::IncorrectBufferSize
+            })?;
+        Ok(Self {
+            mode: gateway_request::Mode::read_bits(&bytes)?,
+        })
+    }
+}
+impl TryFrom<::my_veecle_os_data_support_can::Frame> for GatewayRequest {
+    type Error = ::my_veecle_os_data_support_can::CanDecodeError;
+    fn try_from(
+        frame: ::my_veecle_os_data_support_can::Frame,
+    ) -> Result<Self, Self::Error> {
+        Self::try_from(&frame)
+    }
+}
+impl From<&GatewayRequest> for ::my_veecle_os_data_support_can::Frame {
+    fn from(value: &GatewayRequest) -> Self {
+        let mut bytes = [0u8; GatewayRequest::FRAME_LENGTH];
+        value.mode.write_bits(&mut bytes);
+        Frame::new(GatewayRequest::FRAME_ID, bytes)
+    }
+}
+impl From<GatewayRequest> for ::my_veecle_os_data_support_can::Frame {
+    fn from(value: GatewayRequest) -> Self {
+        Self::from(&value)
+    }
+}
+impl ::my_veecle_os_runtime::Storable for GatewayRequest {
+    type DataType = Self;
+}
+impl ::my_veecle_os_data_support_can::PeriodicMessage for GatewayRequest {
+    const CYCLE_TIME_MS: Option<u32> = Self::CYCLE_TIME_MS;
+    fn to_frame(&self) -> ::my_veecle_os_data_support_can::Frame {
+        ::my_veecle_os_data_support_can::Frame::from(self)
+    }
+}
+#[cfg(all())]
+impl<'a> ::my_arbitrary::Arbitrary<'a> for GatewayRequest {
+    fn arbitrary(
+        u: &mut ::my_arbitrary::Unstructured<'a>,
+    ) -> ::my_arbitrary::Result<Self> {
+        Ok(Self { mode: u.arbitrary()? })
+    }
+}
+pub mod shared_heartbeat {
+    use ::my_veecle_os_data_support_can::reëxports::bits;
+    use ::my_serde as _serde;
+    #[derive(Clone, Copy, PartialEq, PartialOrd, _serde::Serialize)]
+    #[serde(crate = "_serde")]
+    pub struct Counter {
+        raw: u8,
+    }
+    impl Counter {
+        pub const MAX: Self = Self { raw: 255 };
+        pub const MIN: Self = Self { raw: 0 };
+        /// Creates the signal from its raw value as encoded in the frame, without applying the factor and
+        /// offset.
+        ///
+        /// Fails if the raw value does not fit in the signal's bits or its physical value is out of range.
+        pub fn from_raw(
+            raw: u8,
+        ) -> Result<Self, ::my_veecle_os_data_support_can::CanDecodeError> {
+            Self::try_from(raw)?;
+            Ok(Self { raw })
+        }
+        /// Returns the raw value as encoded in the frame, without applying the factor and offset.
+        pub fn raw(&self) -> u8 {
+            self.raw
+        }
+        pub(super) fn read_bits(
+            bytes: &[u8],
+        ) -> Result<Self, ::my_veecle_os_data_support_can::CanDecodeError> {
+            Self::from_raw(
+                u8::try_from(bits::read_little_endian_unsigned(bytes, 0, 8)).unwrap(),
+            )
+        }
+        pub(super) fn write_bits(&self, bytes: &mut [u8]) {
+            bits::write_little_endian_unsigned(bytes, 0, 8, self.raw().into())
+        }
+        pub fn value(&self) -> u8 {
+            self.raw
+        }
+    }
+    impl Default for Counter {
+        fn default() -> Self {
+            Self::MIN
+        }
+    }
+    impl TryFrom<u8> for Counter {
+        type Error = ::my_veecle_os_data_support_can::CanDecodeError;
+        fn try_from(value: u8) -> Result<Self, Self::Error> {
+            Ok(Self { raw: value })
+        }
+    }
+    impl ::my_veecle_os_runtime::Storable for Counter {
+        type DataType = Self;
+    }
+    impl core::fmt::Debug for Counter {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.debug_struct("Counter")
+                .field("raw", &self.raw)
+                .field("value", &self.value())
+                .finish()
+        }
+    }
+    #[cfg(all())]
+    impl<'a> ::my_arbitrary::Arbitrary<'a> for Counter {
+        fn arbitrary(
+            u: &mut ::my_arbitrary::Unstructured<'a>,
+        ) -> ::my_arbitrary::Result<Self> {
+            let min = Self::MIN.raw();
+            let max = Self::MAX.raw();
+            Ok(Self::from_raw(u.int_in_range(min..=max)?).expect("we generate in range"))
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, _serde::Serialize)]
+#[serde(crate = "_serde")]
+pub struct SharedHeartbeat {
+    pub counter: shared_heartbeat::Counter,
+}
+impl SharedHeartbeat {
+    pub const FRAME_ID: ::my_veecle_os_data_support_can::Id = ::my_veecle_os_data_support_can::Id::Standard(
+        ::my_veecle_os_data_support_can::StandardId::new_unwrap(0x12c),
+    );
+    pub const FRAME_LENGTH: usize = 1usize;
+    /// The nodes transmitting this message: `Engine`, `Gateway`.
+    pub const TRANSMITTERS: &'static [&'static str] = &["Engine", "Gateway"];
+    /// The cycle time of this message in milliseconds from its `GenMsgCycleTime` attribute, `None` if it is only sent on change.
+    pub const CYCLE_TIME_MS: Option<u32> = Some(1000u32);
+}
+impl TryFrom<&::my_veecle_os_data_support_can::Frame> for SharedHeartbeat {
+    type Error = ::my_veecle_os_data_support_can::CanDecodeError;
+    fn try_from(
+        frame: &::my_veecle_os_data_support_can::Frame,
+    ) -> Result<Self, Self::Error> {
+        if frame.id() != Self::FRAME_ID {
+            return Err(::my_veecle_os_data_support_can::CanDecodeError::IncorrectId);
+        }
+        let bytes: [u8; Self::FRAME_LENGTH] = frame
+            .data()
+            .try_into()
+            .map_err(|_| {
+                ::my_veecle_os_data_support_can::CanDecodeError::IncorrectBufferSize
+            })?;
+        Ok(Self {
+            counter: shared_heartbeat::Counter::read_bits(&bytes)?,
+        })
+    }
+}
+impl TryFrom<::my_veecle_os_data_support_can::Frame> for SharedHeartbeat {
+    type Error = ::my_veecle_os_data_support_can::CanDecodeError;
+    fn try_from(
+        frame: ::my_veecle_os_data_support_can::Frame,
+    ) -> Result<Self, Self::Error> {
+        Self::try_from(&frame)
+    }
+}
+impl From<&SharedHeartbeat> for ::my_veecle_os_data_support_can::Frame {
+    fn from(value: &SharedHeartbeat) -> Self {
+        let mut bytes = [0u8; SharedHeartbeat::FRAME_LENGTH];
+        value.counter.write_bits(&mut bytes);
+        Frame::new(SharedHeartbeat::FRAME_ID, bytes)
+    }
+}
+impl From<SharedHeartbeat> for ::my_veecle_os_data_support_can::Frame {
+    fn from(value: SharedHeartbeat) -> Self {
+        Self::from(&value)
+    }
+}
+impl ::my_veecle_os_runtime::Storable for SharedHeartbeat {
+    type DataType = Self;
+}
+impl ::my_veecle_os_data_support_can::PeriodicMessage for SharedHeartbeat {
+    const CYCLE_TIME_MS: Option<u32> = Self::CYCLE_TIME_MS;
+    fn to_frame(&self) -> ::my_veecle_os_data_support_can::Frame {
+        ::my_veecle_os_data_support_can::Frame::from(self)
+    }
+}
+#[cfg(all())]
+impl<'a> ::my_arbitrary::Arbitrary<'a> for SharedHeartbeat {
+    fn arbitrary(
+        u: &mut ::my_arbitrary::Unstructured<'a>,
+    ) -> ::my_arbitrary::Result<Self> {
+        Ok(Self { counter: u.arbitrary()? })
+    }
+}
+pub mod unassigned {
+    use ::my_veecle_os_data_support_can::reëxports::bits;
+    use ::my_serde as _serde;
+    #[derive(Clone, Copy, PartialEq, PartialOrd, _serde::Serialize)]
+    #[serde(crate = "_serde")]
+    pub struct Value {
+        raw: u8,
+    }
+    impl Value {
+        pub const MAX: Self = Self { raw: 255 };
+        pub const MIN: Self = Self { raw: 0 };
+        /// Creates the signal from its raw value as encoded in the frame, without applying the factor and
+        /// offset.
+        ///
+        /// Fails if the raw value does not fit in the signal's bits or its physical value is out of range.
+        pub fn from_raw(
+            raw: u8,
+        ) -> Result<Self, ::my_veecle_os_data_support_can::CanDecodeError> {
+            Self::try_from(raw)?;
+            Ok(Self { raw })
+        }
+        /// Returns the raw value as encoded in the frame, without applying the factor and offset.
+        pub fn raw(&self) -> u8 {
+            self.raw
+        }
+        pub(super) fn read_bits(
+            bytes: &[u8],
+        ) -> Result<Self, ::my_veecle_os_data_support_can::CanDecodeError> {
+            Self::from_raw(
+                u8::try_from(bits::read_little_endian_unsigned(bytes, 0, 8)).unwrap(),
+            )
+        }
+        pub(super) fn write_bits(&self, bytes: &mut [u8]) {
+            bits::write_little_endian_unsigned(bytes, 0, 8, self.raw().into())
+        }
+        pub fn value(&self) -> u8 {
+            self.raw
+        }
+    }
+    impl Default for Value {
+        fn default() -> Self {
+            Self::MIN
+        }
+    }
+    impl TryFrom<u8> for Value {
+        type Error = ::my_veecle_os_data_support_can::CanDecodeError;
+        fn try_from(value: u8) -> Result<Self, Self::Error> {
+            Ok(Self { raw: value })
+        }
+    }
+    impl ::my_veecle_os_runtime::Storable for Value {
+        type DataType = Self;
+    }
+    impl core::fmt::Debug for Value {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.debug_struct("Value")
+                .field("raw", &self.raw)
+                .field("value", &self.value())
+                .finish()
+        }
+    }
+    #[cfg(all())]
+    impl<'a> ::my_arbitrary::Arbitrary<'a> for Value {
+        fn arbitrary(
+            u: &mut ::my_arbitrary::Unstructured<'a>,
+        ) -> ::my_arbitrary::Result<Self> {
+            let min = Self::MIN.raw();
+            let max = Self::MAX.raw();
+            Ok(Self::from_raw(u.int_in_range(min..=max)?).expect("we generate in range"))
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, _serde::Serialize)]
+#[serde(crate = "_serde")]
+pub struct Unassigned {
+    pub value: unassigned::Value,
+}
+impl Unassigned {
+    pub const FRAME_ID: ::my_veecle_os_data_support_can::Id = ::my_veecle_os_data_support_can::Id::Standard(
+        ::my_veecle_os_data_support_can::StandardId::new_unwrap(0x190),
+    );
+    pub const FRAME_LENGTH: usize = 1usize;
+    /// The nodes transmitting this message, none are declared in the DBC.
+    pub const TRANSMITTERS: &'static [&'static str] = &[];
+    /// The cycle time of this message in milliseconds from its `GenMsgCycleTime` attribute, `None` if it is only sent on change.
+    pub const CYCLE_TIME_MS: Option<u32> = None;
+}
+impl TryFrom<&::my_veecle_os_data_support_can::Frame> for Unassigned {
+    type Error = ::my_veecle_os_data_support_can::CanDecodeError;
+    fn try_from(
+        frame: &::my_veecle_os_data_support_can::Frame,
+    ) -> Result<Self, Self::Error> {
+        if frame.id() != Self::FRAME_ID {
+            return Err(::my_veecle_os_data_support_can::CanDecodeError::IncorrectId);
+        }
+        let bytes: [u8; Self::FRAME_LENGTH] = frame
+            .data()
+            .try_into()
+            .map_err(|_| {
+                ::my_veecle_os_data_support_can::CanDecodeError::IncorrectBufferSize
+            })?;
+        Ok(Self {
+            value: unassigned::Value::read_bits(&bytes)?,
+        })
+    }
+}
+impl TryFrom<::my_veecle_os_data_support_can::Frame> for Unassigned {
+    type Error = ::my_veecle_os_data_support_can::CanDecodeError;
+    fn try_from(
+        frame: ::my_veecle_os_data_support_can::Frame,
+    ) -> Result<Self, Self::Error> {
+        Self::try_from(&frame)
+    }
+}
+impl From<&Unassigned> for ::my_veecle_os_data_support_can::Frame {
+    fn from(value: &Unassigned) -> Self {
+        let mut bytes = [0u8; Unassigned::FRAME_LENGTH];
+        value.value.write_bits(&mut bytes);
+        Frame::new(Unassigned::FRAME_ID, bytes)
+    }
+}
+impl From<Unassigned> for ::my_veecle_os_data_support_can::Frame {
+    fn from(value: Unassigned) -> Self {
+        Self::from(&value)
+    }
+}
+impl ::my_veecle_os_runtime::Storable for Unassigned {
+    type DataType = Self;
+}
+impl ::my_veecle_os_data_support_can::PeriodicMessage for Unassigned {
+    const CYCLE_TIME_MS: Option<u32> = Self::CYCLE_TIME_MS;
+    fn to_frame(&self) -> ::my_veecle_os_data_support_can::Frame {
+        ::my_veecle_os_data_support_can::Frame::from(self)
+    }
+}
+#[cfg(all())]
+impl<'a> ::my_arbitrary::Arbitrary<'a> for Unassigned {
+    fn arbitrary(
+        u: &mut ::my_arbitrary::Unstructured<'a>,
+    ) -> ::my_arbitrary::Result<Self> {
+        Ok(Self { value: u.arbitrary()? })
+    }
+}
+/// The messages grouped by the node transmitting them.
+pub mod nodes {
+    /// The messages transmitted by the `Engine` node.
+    pub mod engine {
+        pub use super::super::{EngineStatus, SharedHeartbeat};
+    }
+    /// The messages transmitted by the `Gateway` node.
+    pub mod gateway {
+        pub use super::super::{GatewayRequest, SharedHeartbeat};
+    }
+}
+use ::my_veecle_os_data_support_can::Frame;
+/// An actor that will attempt to parse any [`Frame`] messages and publish the parsed messages.
+///
+/// If used you must also provide some interface-actor that writes the `Frame`s from your transceiver, e.g. a
+/// `veecle_os_data_support_can::ReceiveFrames` actor for a `CanTransport`.
+#[::my_veecle_os_runtime::actor(crate = ::my_veecle_os_runtime)]
+pub async fn deserialize_frames(
+    mut reader: ::my_veecle_os_runtime::single_writer::Reader<'_, Frame>,
+    mut engine_status_writer: ::my_veecle_os_runtime::single_writer::Writer<
+        '_,
+        EngineStatus,
+    >,
+    mut gateway_request_writer: ::my_veecle_os_runtime::single_writer::Writer<
+        '_,
+        GatewayRequest,
+    >,
+    mut shared_heartbeat_writer: ::my_veecle_os_runtime::single_writer::Writer<
+        '_,
+        SharedHeartbeat,
+    >,
+    mut unassigned_writer: ::my_veecle_os_runtime::single_writer::Writer<'_, Unassigned>,
+) -> ::my_veecle_os_runtime::Never {
+    loop {
+        let frame = reader.read_updated_cloned().await;
+        match frame.id() {
+            EngineStatus::FRAME_ID => {
+                let Ok(msg) = EngineStatus::try_from(frame) else { continue };
+                engine_status_writer.write(msg).await;
+            }
+            GatewayRequest::FRAME_ID => {
+                let Ok(msg) = GatewayRequest::try_from(frame) else { continue };
+                gateway_request_writer.write(msg).await;
+            }
+            SharedHeartbeat::FRAME_ID => {
+                let Ok(msg) = SharedHeartbeat::try_from(frame) else { continue };
+                shared_heartbeat_writer.write(msg).await;
+            }
+            Unassigned::FRAME_ID => {
+                let Ok(msg) = Unassigned::try_from(frame) else { continue };
+                unassigned_writer.write(msg).await;
+            }
+            _ => {}
+        }
+    }
+}
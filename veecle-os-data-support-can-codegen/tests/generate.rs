@@ -29,6 +29,8 @@ fn generate_test_case(source_path: &Utf8Path, input: String) -> datatest_stable:
         }),
         serde: syn::parse_str("::my_serde")?,
         message_frame_validations: Box::new(|_| None),
+        message_checksums: Box::new(|_| None),
+        byte_order: None,
     };
 
     let mut actual =
@@ -35,3 +35,15 @@ fn ccvs1() {
         Ccvs1::try_from(my_veecle_os_data_support_can::Frame::from(&ccvs1)).unwrap()
     );
 }
+
+#[test]
+fn raw() {
+    let engine_speed = EngineSpeed::from_raw(4).unwrap();
+    assert_eq!(engine_speed, EngineSpeed::try_from(0.5).unwrap());
+    assert_eq!(engine_speed.raw(), 4);
+    assert_eq!(
+        EngineSpeed::from_raw(EngineSpeed::MAX.raw()).unwrap(),
+        EngineSpeed::MAX
+    );
+    assert!(EngineSpeed::from_raw(EngineSpeed::MAX.raw() + 1).is_err());
+}
@@ -1,19 +1,138 @@
 use std::collections::HashMap;
 
 use quote::quote;
-use veecle_os_data_support_can_codegen::Generator;
+use veecle_os_data_support_can_codegen::{
+    ByteOrder, ChecksumAlgorithm, ChecksumOptions, CounterOptions, Generator,
+};
 
 pub struct Input {
     pub krate: syn::Path,
     pub module: syn::ItemMod,
     pub context: String,
     pub source: String,
+    pub byte_order: Option<ByteOrder>,
     pub extra: Vec<syn::Item>,
 }
 
+/// Parses the inner attributes following the `#![dbc = ...]` attribute, returns the byte order override if any.
+pub fn parse_attributes(attrs: &[syn::Attribute]) -> syn::Result<Option<ByteOrder>> {
+    let mut byte_order = None;
+
+    for attr in attrs {
+        if attr.path().is_ident("byte_order") {
+            let syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(value),
+                ..
+            }) = &attr.meta.require_name_value()?.value
+            else {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    r#"expected `#![byte_order = "big"]` or `#![byte_order = "little"]`"#,
+                ));
+            };
+
+            byte_order = Some(match value.value().as_str() {
+                "big" => ByteOrder::BigEndian,
+                "little" => ByteOrder::LittleEndian,
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        value,
+                        r#"unsupported byte order, expected `"big"` or `"little"`"#,
+                    ));
+                }
+            });
+        } else {
+            return Err(syn::Error::new_spanned(attr, "unsupported attribute"));
+        }
+    }
+
+    Ok(byte_order)
+}
+
 #[derive(Default)]
 struct Validation {
     message_frames: HashMap<syn::Ident, syn::Expr>,
+    message_checksums: HashMap<syn::Ident, ChecksumOptions>,
+    errors: Vec<syn::Error>,
+}
+
+/// Parses a `#[checksum(signal = "...", algorithm = "...", ...)]` attribute.
+fn parse_checksum(attr: &syn::Attribute) -> syn::Result<ChecksumOptions> {
+    let mut signal = None;
+    let mut algorithm = None;
+    let mut data_id = None;
+    let mut counter = None;
+    let mut counter_max = None;
+
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("signal") {
+            signal = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+        } else if meta.path.is_ident("algorithm") {
+            algorithm = Some(meta.value()?.parse::<syn::LitStr>()?);
+        } else if meta.path.is_ident("data_id") {
+            data_id = Some(
+                meta.value()?
+                    .parse::<syn::LitInt>()?
+                    .base10_parse::<u16>()?,
+            );
+        } else if meta.path.is_ident("counter") {
+            counter = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+        } else if meta.path.is_ident("counter_max") {
+            counter_max = Some(
+                meta.value()?
+                    .parse::<syn::LitInt>()?
+                    .base10_parse::<u64>()?,
+            );
+        } else {
+            return Err(meta.error("unsupported checksum property"));
+        }
+        Ok(())
+    })?;
+
+    let signal = signal.ok_or_else(|| syn::Error::new_spanned(attr, "missing `signal`"))?;
+    let algorithm_lit =
+        algorithm.ok_or_else(|| syn::Error::new_spanned(attr, "missing `algorithm`"))?;
+
+    let algorithm = match (algorithm_lit.value().as_str(), data_id) {
+        ("crc8_sae_j1850", None) => ChecksumAlgorithm::Crc8SaeJ1850,
+        ("crc8_autosar", None) => ChecksumAlgorithm::Crc8Autosar,
+        ("e2e_profile1", Some(data_id)) => ChecksumAlgorithm::E2eProfile1 { data_id },
+        ("e2e_profile1", None) => {
+            return Err(syn::Error::new_spanned(
+                algorithm_lit,
+                "`e2e_profile1` requires a `data_id`",
+            ));
+        }
+        ("crc8_sae_j1850" | "crc8_autosar", Some(_)) => {
+            return Err(syn::Error::new_spanned(
+                algorithm_lit,
+                "`data_id` is only supported with `e2e_profile1`",
+            ));
+        }
+        _ => {
+            return Err(syn::Error::new_spanned(
+                algorithm_lit,
+                "unsupported checksum algorithm, expected one of `crc8_sae_j1850`, `crc8_autosar` or `e2e_profile1`",
+            ));
+        }
+    };
+
+    let counter = match (counter, counter_max) {
+        (Some(signal), Some(max)) => Some(CounterOptions { signal, max }),
+        (None, None) => None,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`counter` and `counter_max` must be specified together",
+            ));
+        }
+    };
+
+    Ok(ChecksumOptions {
+        signal,
+        algorithm,
+        counter,
+    })
 }
 
 fn extract_validation_functions(items: &mut [syn::Item]) -> Validation {
@@ -32,6 +151,22 @@ fn extract_validation_functions(items: &mut [syn::Item]) -> Validation {
                 None
             };
 
+            item_impl.attrs.retain(|attr| {
+                if attr.path().is_ident("checksum") {
+                    match parse_checksum(attr) {
+                        Ok(checksum) => {
+                            self.validation
+                                .message_checksums
+                                .insert(ident.clone().unwrap(), checksum);
+                        }
+                        Err(error) => self.validation.errors.push(error),
+                    }
+                    false
+                } else {
+                    true
+                }
+            });
+
             let previous_message = std::mem::replace(&mut self.current_message, ident);
 
             syn::visit_mut::visit_item_impl_mut(self, item_impl);
@@ -79,10 +214,17 @@ impl Input {
             module,
             context,
             source,
+            byte_order,
             mut extra,
         } = self;
 
-        let validation = extract_validation_functions(&mut extra);
+        let Validation {
+            message_frames,
+            message_checksums,
+            errors,
+        } = extract_validation_functions(&mut extra);
+
+        let errors = Vec::from_iter(errors.iter().map(syn::Error::to_compile_error));
 
         let options = veecle_os_data_support_can_codegen::Options {
             veecle_os_runtime: syn::parse_quote!(#krate::reëxports::veecle_os_runtime),
@@ -94,9 +236,9 @@ impl Input {
             }),
             serde: syn::parse_quote!(#krate::reëxports::serde),
             veecle_os_data_support_can: krate,
-            message_frame_validations: Box::new(move |name| {
-                validation.message_frames.get(name).cloned()
-            }),
+            message_frame_validations: Box::new(move |name| message_frames.get(name).cloned()),
+            message_checksums: Box::new(move |name| message_checksums.get(name).cloned()),
+            byte_order,
         };
 
         let generated = Generator::new(&context, options, &source).into_token_stream();
@@ -117,6 +259,7 @@ impl Input {
             #(#attrs)* #vis #unsafety #mod_token #ident {
                 #generated
                 #(#extra)*
+                #(#errors)*
             }
         }
     }
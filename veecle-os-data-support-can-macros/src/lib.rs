@@ -31,12 +31,13 @@ pub fn from_file(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<expand::Input> {
         // We expect the input to look like
         //
-        // path::to::veecle_os_data_support_can ; mod foo ; "some.dbc" ; extra items
+        // path::to::veecle_os_data_support_can ; mod foo ; "some.dbc" ; #![extra attributes] extra items
         let krate = input.parse()?;
         input.parse::<syn::Token![;]>()?;
         let module = input.parse()?;
         let path = input.parse()?;
         input.parse::<syn::Token![;]>()?;
+        let byte_order = expand::parse_attributes(&input.call(syn::Attribute::parse_inner)?)?;
 
         let mut extra = Vec::new();
         while !input.is_empty() {
@@ -60,6 +61,7 @@ pub fn from_file(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
             module,
             context: path,
             source,
+            byte_order,
             extra,
         })
     }
@@ -72,12 +74,13 @@ pub fn from_str(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<expand::Input> {
         // We expect the input to look like
         //
-        // path::to::veecle_os_data_support_can ; mod foo ; r#"VERSION .... (dbc file content)"# ; extra items
+        // path::to::veecle_os_data_support_can ; mod foo ; r#"VERSION .... (dbc file content)"# ; #![extra attributes] extra items
         let krate = input.parse()?;
         input.parse::<syn::Token![;]>()?;
         let module = input.parse()?;
         let source: syn::LitStr = input.parse()?;
         input.parse::<syn::Token![;]>()?;
+        let byte_order = expand::parse_attributes(&input.call(syn::Attribute::parse_inner)?)?;
         let extra = {
             let mut extra = Vec::new();
             while !input.is_empty() {
@@ -96,6 +99,7 @@ pub fn from_str(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
             module,
             context: format!("{}:{line}:{col}", span.file()),
             source: source.value(),
+            byte_order,
             extra,
         })
     }
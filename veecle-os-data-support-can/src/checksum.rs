@@ -0,0 +1,64 @@
+//! Checksum algorithms commonly used to protect CAN frames.
+//!
+//! These are used by the generated decoders when a message is configured with a checksum, but can also be used to
+//! compute the checksum when encoding a frame. Other CRC algorithms are available in the [`crc`](crate::crc) module.
+
+use veecle_os_data_support_crc::{CRC8_AUTOSAR, CRC8_SAE_J1850};
+
+/// Computes the CRC-8 SAE J1850 checksum of `data` (polynomial `0x1D`, initial value `0xFF`, final XOR `0xFF`).
+///
+/// ```rust
+/// use veecle_os_data_support_can::checksum::crc8_sae_j1850;
+///
+/// assert_eq!(crc8_sae_j1850(*b"123456789"), 0x4B);
+/// ```
+pub fn crc8_sae_j1850(data: impl IntoIterator<Item = u8>) -> u8 {
+    CRC8_SAE_J1850.checksum_iter(data)
+}
+
+/// Computes the AUTOSAR CRC-8 `0x2F` (also known as CRC-8H2F) checksum of `data` (polynomial `0x2F`, initial value
+/// `0xFF`, final XOR `0xFF`).
+///
+/// ```rust
+/// use veecle_os_data_support_can::checksum::crc8_autosar;
+///
+/// assert_eq!(crc8_autosar(*b"123456789"), 0xDF);
+/// ```
+pub fn crc8_autosar(data: impl IntoIterator<Item = u8>) -> u8 {
+    CRC8_AUTOSAR.checksum_iter(data)
+}
+
+/// Computes the AUTOSAR E2E profile 1 checksum of `data` with data ID mode "both".
+///
+/// This is a [`crc8_sae_j1850`] over the two bytes of `data_id` (low byte first) followed by `data`, where `data`
+/// should be all bytes of the frame except the checksum byte.
+pub fn e2e_profile1(data_id: u16, data: impl IntoIterator<Item = u8>) -> u8 {
+    crc8_sae_j1850(data_id.to_le_bytes().into_iter().chain(data))
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use test_case::test_case;
+
+    #[test_case(b"", 0x00)]
+    #[test_case(b"\x00", 0x3B)]
+    #[test_case(b"123456789", 0x4B)]
+    fn test_crc8_sae_j1850(data: &[u8], expected: u8) {
+        assert_eq!(super::crc8_sae_j1850(data.iter().copied()), expected);
+    }
+
+    #[test_case(b"", 0x00)]
+    #[test_case(b"123456789", 0xDF)]
+    fn test_crc8_autosar(data: &[u8], expected: u8) {
+        assert_eq!(super::crc8_autosar(data.iter().copied()), expected);
+    }
+
+    #[test]
+    fn test_e2e_profile1() {
+        assert_eq!(
+            super::e2e_profile1(0x3231, *b"3456789"),
+            super::crc8_sae_j1850(*b"123456789")
+        );
+    }
+}
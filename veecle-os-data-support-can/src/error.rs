@@ -21,6 +21,14 @@ pub enum CanDecodeError {
         message: &'static str,
     },
 
+    /// The checksum stored in the frame does not match the checksum computed over its data.
+    ChecksumMismatch {
+        /// The checksum stored in the frame.
+        expected: u8,
+        /// The checksum computed over the frame's data.
+        actual: u8,
+    },
+
     /// Validation failure.
     Invalid {
         /// Additional details about what was invalid.
@@ -43,6 +51,12 @@ impl Display for CanDecodeError {
             CanDecodeError::OutOfRange { name, ty, message } => {
                 write!(f, "field {name}:{ty}: {message}")
             }
+            CanDecodeError::ChecksumMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "checksum mismatch: expected {expected:#04x}, computed {actual:#04x}"
+                )
+            }
             CanDecodeError::Invalid { message } => write!(f, "validation failure: {message}"),
         }
     }
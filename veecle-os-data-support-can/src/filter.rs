@@ -0,0 +1,211 @@
+//! Acceptance filters matching CAN ids by an id and a mask, like the filters of CAN controllers.
+
+use crate::id::Id;
+
+/// The mask selecting all bits of a standard id.
+const STANDARD_MASK: u32 = 0x7FF;
+
+/// The mask selecting all bits of an extended id.
+const EXTENDED_MASK: u32 = 0x1FFF_FFFF;
+
+/// Returns the value of `id` and whether it is extended.
+const fn to_raw(id: Id) -> (u32, bool) {
+    match id {
+        Id::Standard(id) => (id.to_raw() as u32, false),
+        Id::Extended(id) => (id.to_raw(), true),
+    }
+}
+
+/// An acceptance filter matching the ids whose bits selected by `mask` are equal to those of `id`.
+///
+/// Only ids of the same kind (standard or extended) as `id` are matched, bits of `mask` above the width of the id are
+/// ignored.
+///
+/// ```
+/// use veecle_os_data_support_can::{ExtendedId, Id, IdFilter};
+///
+/// let filter = IdFilter {
+///     id: Id::Extended(ExtendedId::new_unwrap(0x0CF0_0400)),
+///     mask: 0x00FF_FF00,
+/// };
+///
+/// assert!(filter.matches(Id::Extended(ExtendedId::new_unwrap(0x18F0_04FE))));
+/// assert!(!filter.matches(Id::Extended(ExtendedId::new_unwrap(0x0CF0_0500))));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct IdFilter {
+    /// The id to compare against.
+    pub id: Id,
+
+    /// The bits of the id to compare, a set bit must match while a cleared bit is ignored.
+    pub mask: u32,
+}
+
+impl IdFilter {
+    /// Creates a filter matching exactly `id`.
+    pub const fn exact(id: Id) -> Self {
+        let mask = match id {
+            Id::Standard(_) => STANDARD_MASK,
+            Id::Extended(_) => EXTENDED_MASK,
+        };
+        Self { id, mask }
+    }
+
+    /// Returns whether `id` passes this filter.
+    pub const fn matches(&self, id: Id) -> bool {
+        let (expected, expected_extended) = to_raw(self.id);
+        let (actual, actual_extended) = to_raw(id);
+        expected_extended == actual_extended && (expected ^ actual) & self.mask == 0
+    }
+}
+
+/// Computes the narrowest [`IdFilter`]s matching a set of ids, e.g. the `FRAME_ID`s of the messages generated from a
+/// DBC that a node receives.
+///
+/// A filter can only match one kind of id, so separate filters are computed for the standard and extended ids. The
+/// filters match all added ids, but may match additional ids as well, check received frames against the exact ids
+/// if that matters.
+///
+/// ```
+/// use veecle_os_data_support_can::{Id, IdFilterBuilder, StandardId};
+///
+/// const FILTERS: IdFilterBuilder = IdFilterBuilder::new()
+///     .id(Id::Standard(StandardId::new_unwrap(0x120)))
+///     .id(Id::Standard(StandardId::new_unwrap(0x124)));
+///
+/// let filter = FILTERS.standard().unwrap();
+/// assert_eq!(filter.mask, 0x7FB);
+/// assert!(filter.matches(Id::Standard(StandardId::new_unwrap(0x120))));
+/// assert!(filter.matches(Id::Standard(StandardId::new_unwrap(0x124))));
+/// assert!(!filter.matches(Id::Standard(StandardId::new_unwrap(0x122))));
+/// assert_eq!(FILTERS.extended(), None);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct IdFilterBuilder {
+    standard: Option<IdFilter>,
+    extended: Option<IdFilter>,
+}
+
+impl IdFilterBuilder {
+    /// Creates a builder without any ids.
+    pub const fn new() -> Self {
+        Self {
+            standard: None,
+            extended: None,
+        }
+    }
+
+    /// Widens the filter for the kind of `id` to also match `id`.
+    pub const fn id(mut self, id: Id) -> Self {
+        let filter = match id {
+            Id::Standard(_) => &mut self.standard,
+            Id::Extended(_) => &mut self.extended,
+        };
+
+        *filter = match *filter {
+            None => Some(IdFilter::exact(id)),
+            Some(IdFilter { id: first, mask }) => {
+                let (first_raw, _) = to_raw(first);
+                let (raw, _) = to_raw(id);
+                Some(IdFilter {
+                    id: first,
+                    mask: mask & !(first_raw ^ raw),
+                })
+            }
+        };
+
+        self
+    }
+
+    /// Returns the filter matching all added standard ids, if any were added.
+    pub const fn standard(&self) -> Option<IdFilter> {
+        self.standard
+    }
+
+    /// Returns the filter matching all added extended ids, if any were added.
+    pub const fn extended(&self) -> Option<IdFilter> {
+        self.extended
+    }
+}
+
+impl FromIterator<Id> for IdFilterBuilder {
+    fn from_iter<T: IntoIterator<Item = Id>>(iter: T) -> Self {
+        iter.into_iter().fold(Self::new(), Self::id)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::{IdFilter, IdFilterBuilder};
+    use crate::{ExtendedId, Id, StandardId};
+
+    fn standard(value: u16) -> Id {
+        Id::Standard(StandardId::new_unwrap(value))
+    }
+
+    fn extended(value: u32) -> Id {
+        Id::Extended(ExtendedId::new_unwrap(value))
+    }
+
+    #[test]
+    fn exact() {
+        let filter = IdFilter::exact(standard(0x123));
+        assert!(filter.matches(standard(0x123)));
+        assert!(!filter.matches(standard(0x122)));
+        assert!(!filter.matches(extended(0x123)));
+
+        let filter = IdFilter::exact(extended(0x1FFF_FFFF));
+        assert!(filter.matches(extended(0x1FFF_FFFF)));
+        assert!(!filter.matches(extended(0x0FFF_FFFF)));
+        assert!(!filter.matches(standard(0x7FF)));
+    }
+
+    #[test]
+    fn mask() {
+        let filter = IdFilter {
+            id: standard(0x100),
+            mask: 0x700,
+        };
+        assert!(filter.matches(standard(0x100)));
+        assert!(filter.matches(standard(0x1FF)));
+        assert!(!filter.matches(standard(0x200)));
+
+        let accept_all = IdFilter {
+            id: extended(0),
+            mask: 0,
+        };
+        assert!(accept_all.matches(extended(0x1FFF_FFFF)));
+        assert!(!accept_all.matches(standard(0)));
+    }
+
+    #[test]
+    fn builder() {
+        let builder = IdFilterBuilder::from_iter([
+            standard(0x120),
+            extended(0x0CF0_0400),
+            standard(0x130),
+            extended(0x0CF0_0401),
+        ]);
+
+        let standard_filter = builder.standard().unwrap();
+        assert_eq!(standard_filter.mask, 0x7EF);
+        assert!(standard_filter.matches(standard(0x120)));
+        assert!(standard_filter.matches(standard(0x130)));
+        assert!(!standard_filter.matches(standard(0x121)));
+
+        let extended_filter = builder.extended().unwrap();
+        assert_eq!(extended_filter.mask, 0x1FFF_FFFE);
+        assert!(extended_filter.matches(extended(0x0CF0_0400)));
+        assert!(extended_filter.matches(extended(0x0CF0_0401)));
+        assert!(!extended_filter.matches(extended(0x0CF0_0402)));
+    }
+
+    #[test]
+    fn empty_builder() {
+        let builder = IdFilterBuilder::new();
+        assert_eq!(builder.standard(), None);
+        assert_eq!(builder.extended(), None);
+        assert_eq!(builder, IdFilterBuilder::default());
+    }
+}
@@ -123,7 +123,7 @@ mod tests {
 
         assert_eq!(
             to_debug(Frame::new(crate::StandardId::new(0).unwrap(), [])),
-            "Frame { id: Standard(0x0), data: '' }"
+            "Frame { id: Standard(0x000), data: '' }"
         );
 
         assert_eq!(
@@ -131,7 +131,7 @@ mod tests {
                 crate::ExtendedId::new(0x153EAB12).unwrap(),
                 [0x04, 0xA2, 0xC2, 0xED, 0xCA, 0xE3, 0x88, 0x74]
             )),
-            "Frame { id: Extended(0x153eab12), data: '04a2c2edcae38874' }"
+            "Frame { id: Extended(0x153EAB12), data: '04a2c2edcae38874' }"
         );
 
         assert_eq!(
@@ -139,7 +139,7 @@ mod tests {
                 crate::ExtendedId::new(0x1B56C72D).unwrap(),
                 [0x40, 0x71, 0xEF, 0x61]
             )),
-            "Frame { id: Extended(0x1b56c72d), data: '4071ef61' }"
+            "Frame { id: Extended(0x1B56C72D), data: '4071ef61' }"
         );
     }
 }
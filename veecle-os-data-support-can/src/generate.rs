@@ -34,6 +34,61 @@
 ///     engine_speed: from_str::eec1::EngineSpeed::try_from(0.5).unwrap(),
 /// };
 /// ```
+///
+/// # Byte order override
+///
+/// Some tooling produces DBCs with incorrect `@0`/`@1` byte order markers. A `#![byte_order = "big"]` or
+/// `#![byte_order = "little"]` attribute following the `#![dbc]` attribute overrides the byte order of all signals,
+/// which is noted as a warning in the generated module documentation:
+///
+/// ```rust
+/// veecle_os_data_support_can::generate!(
+///     mod generated {
+///         #![dbc = r#"
+///             VERSION ""
+///
+///             NS_ :
+///
+///             BO_ 1 SomeMessage: 8 Vector__XXX
+///              SG_ Value : 7|16@1+ (1,0) [0|0] "" Vector__XXX
+///         "#]
+///         #![byte_order = "big"]
+///     }
+/// );
+/// ```
+///
+/// # Checksums
+///
+/// A message's checksum, and optionally its rolling counter, can be validated during decoding by adding a
+/// `#[checksum]` attribute to an `impl` block for the message type:
+///
+/// ```rust
+/// veecle_os_data_support_can::generate!(
+///     mod generated {
+///         #![dbc = r#"
+///             VERSION ""
+///
+///             NS_ :
+///
+///             BO_ 2 Protected: 8 Vector__XXX
+///              SG_ Data : 0|32@1+ (1,0) [0|0] "" Vector__XXX
+///              SG_ Counter : 48|4@1+ (1,0) [0|15] "" Vector__XXX
+///              SG_ CRC : 56|8@1+ (1,0) [0|0] "" Vector__XXX
+///         "#]
+///
+///         #[checksum(signal = "CRC", algorithm = "e2e_profile1", data_id = 0x123, counter = "Counter", counter_max = 14)]
+///         impl Protected {}
+///     }
+/// );
+/// ```
+///
+/// * `signal`: the 8 bit, byte aligned signal containing the checksum.
+/// * `algorithm`: one of `"crc8_sae_j1850"`, `"crc8_autosar"` or `"e2e_profile1"` (which requires a `data_id`), see
+///   the [`checksum`](crate::checksum) module for details.
+/// * `counter` and `counter_max` (optional): the signal containing a rolling counter and its maximum valid value.
+///
+/// A mismatching checksum fails decoding with [`CanDecodeError::ChecksumMismatch`](crate::CanDecodeError), a counter
+/// above its maximum with [`CanDecodeError::OutOfRange`](crate::CanDecodeError).
 #[macro_export]
 macro_rules! generate {
     ($vis:vis mod $name:ident { #![dbc = include_str!($file:literal)] $($extra:tt)* }) => {
@@ -26,8 +26,8 @@ impl StandardId {
     }
 
     /// Returns the CAN Identifier as a 16-bit integer.
-    pub fn to_raw(self) -> u16 {
-        self.into()
+    pub const fn to_raw(self) -> u16 {
+        self.0
     }
 }
 
@@ -53,9 +53,17 @@ impl From<StandardId> for u16 {
     }
 }
 
+/// Formats the id as `StandardId(0x123)`.
 impl core::fmt::Debug for StandardId {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{:#x}", self.0)
+        write!(f, "StandardId(0x{:03X})", self.0)
+    }
+}
+
+/// Formats the id as `0x123 (std)`, the canonical three hex digits followed by a marker for standard ids.
+impl core::fmt::Display for StandardId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "0x{:03X} (std)", self.0)
     }
 }
 
@@ -90,8 +98,8 @@ impl ExtendedId {
     }
 
     /// Returns the CAN Identifier as a 32-bit integer.
-    pub fn to_raw(self) -> u32 {
-        self.into()
+    pub const fn to_raw(self) -> u32 {
+        self.0
     }
 }
 
@@ -109,10 +117,17 @@ impl From<ExtendedId> for u32 {
     }
 }
 
+/// Formats the id as `ExtendedId(0x0CF00400)`.
 impl core::fmt::Debug for ExtendedId {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{:#x}", self.0)?;
-        Ok(())
+        write!(f, "ExtendedId(0x{:08X})", self.0)
+    }
+}
+
+/// Formats the id as `0x0CF00400 (ext)`, the canonical eight hex digits followed by a marker for extended ids.
+impl core::fmt::Display for ExtendedId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "0x{:08X} (ext)", self.0)
     }
 }
 
@@ -128,7 +143,7 @@ impl<'de> serde::Deserialize<'de> for ExtendedId {
 }
 
 /// Either a standard or extended CAN id.
-#[derive(Clone, Copy, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum Id {
     /// A standard CAN id.
     Standard(StandardId),
@@ -149,6 +164,26 @@ impl From<ExtendedId> for Id {
     }
 }
 
+/// Formats the id as `Standard(0x123)` or `Extended(0x0CF00400)`.
+impl core::fmt::Debug for Id {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Standard(StandardId(value)) => write!(f, "Standard(0x{value:03X})"),
+            Self::Extended(ExtendedId(value)) => write!(f, "Extended(0x{value:08X})"),
+        }
+    }
+}
+
+/// Formats the id like [`StandardId`] or [`ExtendedId`] do, e.g. `0x123 (std)` or `0x0CF00400 (ext)`.
+impl core::fmt::Display for Id {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Standard(id) => id.fmt(f),
+            Self::Extended(id) => id.fmt(f),
+        }
+    }
+}
+
 /// All `Id` values are <0x2000_0000 so we have the top three bits spare, this type packs the discriminant into the top
 /// bit and removes alignment to minimize the storage space required.
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -236,6 +271,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn formatting() {
+        let standard = StandardId::new(0x12).unwrap();
+        assert_eq!(format!("{standard}"), "0x012 (std)");
+        assert_eq!(format!("{standard:?}"), "StandardId(0x012)");
+        assert_eq!(format!("{}", Id::from(standard)), "0x012 (std)");
+        assert_eq!(format!("{:?}", Id::from(standard)), "Standard(0x012)");
+
+        let extended = ExtendedId::new(0xCF0_0400).unwrap();
+        assert_eq!(format!("{extended}"), "0x0CF00400 (ext)");
+        assert_eq!(format!("{extended:?}"), "ExtendedId(0x0CF00400)");
+        assert_eq!(format!("{}", Id::from(extended)), "0x0CF00400 (ext)");
+        assert_eq!(format!("{:?}", Id::from(extended)), "Extended(0x0CF00400)");
+
+        assert_eq!(
+            format!("{}", StandardId::new(0x7FF).unwrap()),
+            "0x7FF (std)"
+        );
+        assert_eq!(
+            format!("{}", ExtendedId::new(0x12).unwrap()),
+            "0x00000012 (ext)"
+        );
+    }
+
     #[test]
     fn test_deserialize_standard_id_valid() {
         for value in STANDARD_ID_VALIDS {
@@ -1,23 +1,35 @@
 //! Support for working with CAN messages within a runtime instance.
 #![no_std]
-#![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "socketcan"), forbid(unsafe_code))]
+#![cfg_attr(feature = "socketcan", deny(unsafe_code))]
 #![cfg_attr(coverage_nightly, feature(coverage_attribute))]
 
-#[cfg(test)]
+#[cfg(any(test, feature = "socketcan"))]
 extern crate std;
 
+pub mod checksum;
 mod error;
+mod filter;
 mod frame;
 mod generate;
 mod id;
+mod periodic;
+#[cfg(all(feature = "socketcan", target_os = "linux"))]
+pub mod socketcan;
+mod transport;
 
 #[doc(hidden)]
 /// Private API, do not use.
 pub mod bits;
 
 pub use self::error::CanDecodeError;
+pub use self::filter::{IdFilter, IdFilterBuilder};
 pub use self::frame::{Frame, FrameSize};
 pub use self::id::{ExtendedId, Id, StandardId};
+pub use self::periodic::{PeriodicMessage, PeriodicTransmitter, PeriodicTransmitterError};
+pub use self::transport::{CanTransport, ReceiveFrames, TransmitFrames};
+/// CRC algorithms, re-exported from `veecle-os-data-support-crc`.
+pub use veecle_os_data_support_crc as crc;
 
 #[doc(hidden)]
 /// Private API, do not use.
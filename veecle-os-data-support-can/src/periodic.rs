@@ -0,0 +1,139 @@
+//! Transmitting messages at the cycle times declared in the DBC.
+
+use veecle_os_runtime::single_writer::Reader;
+use veecle_os_runtime::{Never, Storable};
+use veecle_osal_api::time::{Duration, TimeAbstraction};
+
+use crate::frame::Frame;
+use crate::transport::CanTransport;
+
+/// A message that can be transmitted by a [`PeriodicTransmitter`], implemented by the generated messages.
+pub trait PeriodicMessage: Storable<DataType = Self> {
+    /// The cycle time in milliseconds, `None` if the message is only sent on change.
+    const CYCLE_TIME_MS: Option<u32>;
+
+    /// Encodes the message into a frame.
+    fn to_frame(&self) -> Frame;
+}
+
+/// An error of the [`PeriodicTransmitter`] actor.
+#[derive(Debug)]
+pub enum PeriodicTransmitterError<E> {
+    /// Sending a frame through the transport failed.
+    Transport(E),
+
+    /// Waiting for the next cycle failed.
+    Time(veecle_osal_api::Error),
+}
+
+impl<E> core::fmt::Display for PeriodicTransmitterError<E>
+where
+    E: core::fmt::Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Transport(error) => write!(f, "failed to send frame: {error}"),
+            Self::Time(error) => write!(f, "failed to wait for the next cycle: {error}"),
+        }
+    }
+}
+
+impl<E> core::error::Error for PeriodicTransmitterError<E> where E: core::error::Error {}
+
+/// An actor transmitting the message `M` through a [`CanTransport`] at its [cycle
+/// time](PeriodicMessage::CYCLE_TIME_MS).
+///
+/// Nothing is transmitted until the first value of `M` is written, then the latest value is transmitted once per
+/// cycle, whether or not it was updated in between. If transmitting falls behind, the missed cycles are skipped
+/// instead of being transmitted in a burst. Messages without a cycle time are transmitted every time they are
+/// written instead.
+///
+/// To transmit a set of messages, add one actor per message. Each actor needs its own transport, e.g. a shared
+/// reference to the same `SocketCan` of the `socketcan` feature.
+///
+/// ```rust
+/// # use veecle_os_data_support_can::{CanTransport, Frame};
+/// # #[derive(Debug, Clone, Copy)]
+/// # struct Bus;
+/// # impl CanTransport for Bus {
+/// #     type Error = core::convert::Infallible;
+/// #     async fn send(&mut self, _: &Frame) -> Result<(), Self::Error> { Ok(()) }
+/// #     async fn recv(&mut self) -> Result<Frame, Self::Error> { core::future::pending().await }
+/// # }
+/// use veecle_os_data_support_can::PeriodicTransmitter;
+/// use veecle_osal_std::time::Time;
+///
+/// veecle_os_data_support_can::generate!(
+///     mod messages {
+///         #![dbc = r#"
+///             VERSION ""
+///
+///             NS_ :
+///
+///             BO_ 1 Status: 1 Vector__XXX
+///              SG_ Active : 0|1@1+ (1,0) [0|1] "" Vector__XXX
+///
+///             BO_ 2 Alarm: 1 Vector__XXX
+///              SG_ Code : 0|8@1+ (1,0) [0|255] "" Vector__XXX
+///
+///             BA_DEF_ BO_ "GenMsgCycleTime" INT 0 65535;
+///             BA_ "GenMsgCycleTime" BO_ 1 100;
+///         "#]
+///     }
+/// );
+///
+/// assert_eq!(messages::Status::CYCLE_TIME_MS, Some(100));
+/// assert_eq!(messages::Alarm::CYCLE_TIME_MS, None);
+///
+/// # let _ = async {
+/// veecle_os_runtime::execute! {
+///     actors: [
+///         // Transmitted every 100 ms.
+///         PeriodicTransmitter<messages::Status, Bus, Time>: Bus,
+///         // Transmitted whenever it is written.
+///         PeriodicTransmitter<messages::Alarm, Bus, Time>: Bus,
+///         // ... the actors writing `messages::Status` and `messages::Alarm`.
+///     ],
+/// }
+/// .await;
+/// # };
+/// ```
+#[veecle_os_runtime::actor]
+pub async fn periodic_transmitter<M, T, Time>(
+    #[init_context] mut transport: T,
+    mut reader: Reader<'_, M>,
+) -> Result<Never, PeriodicTransmitterError<T::Error>>
+where
+    M: PeriodicMessage + 'static,
+    T: CanTransport,
+    Time: TimeAbstraction,
+{
+    let Some(cycle_time) = M::CYCLE_TIME_MS else {
+        loop {
+            let frame = reader.read_updated(M::to_frame).await;
+            transport
+                .send(&frame)
+                .await
+                .map_err(PeriodicTransmitterError::Transport)?;
+        }
+    };
+    let cycle_time = Duration::from_millis(u64::from(cycle_time));
+
+    reader.wait_for_update().await;
+    let mut deadline = Time::now();
+
+    loop {
+        if let Some(frame) = reader.read(|message| message.map(M::to_frame)) {
+            transport
+                .send(&frame)
+                .await
+                .map_err(PeriodicTransmitterError::Transport)?;
+        }
+
+        deadline = deadline + cycle_time;
+        deadline = deadline.max(Time::now());
+        Time::sleep_until(deadline)
+            .await
+            .map_err(PeriodicTransmitterError::Time)?;
+    }
+}
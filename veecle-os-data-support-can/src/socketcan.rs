@@ -0,0 +1,242 @@
+//! A [`CanTransport`] for Linux SocketCAN interfaces.
+
+// The socket API is only accessible via FFI, this is the only module of the crate allowed to use unsafe code.
+#![allow(unsafe_code)]
+
+use core::mem::size_of;
+use std::ffi::CString;
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+use tokio::io::Interest;
+use tokio::io::unix::AsyncFd;
+
+use crate::id::{ExtendedId, Id, StandardId};
+use crate::{CanTransport, Frame};
+
+/// A raw SocketCAN socket bound to a CAN interface, e.g. `can0` or `vcan0`.
+///
+/// The [`CanTransport`] is implemented for shared references as well, so a single socket can be used by both a
+/// [`ReceiveFrames`](crate::ReceiveFrames) and a [`TransmitFrames`](crate::TransmitFrames) actor. Frames sent through
+/// a socket are not received by the same socket, remote transmission request and error frames are skipped.
+///
+/// The socket has to be used within a `tokio` runtime with IO enabled.
+///
+/// ```rust,no_run
+/// use veecle_os_data_support_can::socketcan::SocketCan;
+/// use veecle_os_data_support_can::{ReceiveFrames, TransmitFrames};
+///
+/// # let _ = async {
+/// let socket = SocketCan::open("vcan0").expect("the interface should exist");
+///
+/// veecle_os_runtime::execute! {
+///     actors: [
+///         ReceiveFrames<&SocketCan>: &socket,
+///         TransmitFrames<&SocketCan>: &socket,
+///         // ... the generated `DeserializeFrames` actor and the actors using the messages.
+///     ],
+/// }
+/// .await;
+/// # };
+/// ```
+#[derive(Debug)]
+pub struct SocketCan {
+    socket: AsyncFd<OwnedFd>,
+}
+
+impl SocketCan {
+    /// Opens a socket bound to the CAN interface named `interface`.
+    pub fn open(interface: &str) -> io::Result<Self> {
+        let name = CString::new(interface)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+
+        // SAFETY: `name` is a nul-terminated string.
+        let index = unsafe { libc::if_nametoindex(name.as_ptr()) };
+        if index == 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        // SAFETY: Creating a socket has no preconditions.
+        let fd = unsafe {
+            libc::socket(
+                libc::PF_CAN,
+                libc::SOCK_RAW | libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC,
+                libc::CAN_RAW,
+            )
+        };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: `fd` is a newly created socket which is not owned by anything else.
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        // SAFETY: `sockaddr_can` is a plain C struct for which all zeroes is a valid value.
+        let mut address: libc::sockaddr_can = unsafe { core::mem::zeroed() };
+        address.can_family = libc::AF_CAN as libc::sa_family_t;
+        address.can_ifindex = index as libc::c_int;
+
+        // SAFETY: `address` is a `sockaddr_can` of the passed length.
+        let result = unsafe {
+            libc::bind(
+                fd.as_raw_fd(),
+                (&raw const address).cast(),
+                size_of::<libc::sockaddr_can>() as libc::socklen_t,
+            )
+        };
+        if result < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Self {
+            socket: AsyncFd::new(fd)?,
+        })
+    }
+}
+
+/// Reads a single frame from the non-blocking socket `fd`.
+fn read(fd: &OwnedFd) -> io::Result<libc::can_frame> {
+    // SAFETY: `can_frame` is a plain C struct for which all zeroes is a valid value.
+    let mut frame: libc::can_frame = unsafe { core::mem::zeroed() };
+
+    // SAFETY: `frame` is valid for writes of its size.
+    let read = unsafe {
+        libc::read(
+            fd.as_raw_fd(),
+            (&raw mut frame).cast(),
+            size_of::<libc::can_frame>(),
+        )
+    };
+    if read < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    if read as usize != size_of::<libc::can_frame>() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "received an incomplete CAN frame",
+        ));
+    }
+
+    Ok(frame)
+}
+
+/// Writes a single frame to the non-blocking socket `fd`.
+fn write(fd: &OwnedFd, frame: &libc::can_frame) -> io::Result<()> {
+    // SAFETY: `frame` is valid for reads of its size.
+    let written = unsafe {
+        libc::write(
+            fd.as_raw_fd(),
+            (&raw const *frame).cast(),
+            size_of::<libc::can_frame>(),
+        )
+    };
+    if written < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    if written as usize != size_of::<libc::can_frame>() {
+        return Err(io::Error::new(
+            io::ErrorKind::WriteZero,
+            "sent an incomplete CAN frame",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Converts a received frame, returns `None` for remote transmission request and error frames.
+fn from_raw(raw: &libc::can_frame) -> Option<Frame> {
+    if raw.can_id & (libc::CAN_RTR_FLAG | libc::CAN_ERR_FLAG) != 0 {
+        return None;
+    }
+
+    let id = if raw.can_id & libc::CAN_EFF_FLAG != 0 {
+        Id::Extended(ExtendedId::new(raw.can_id & libc::CAN_EFF_MASK)?)
+    } else {
+        Id::Standard(StandardId::new((raw.can_id & libc::CAN_SFF_MASK) as u16)?)
+    };
+
+    Frame::new_checked(id, raw.data.get(..usize::from(raw.can_dlc))?)
+}
+
+/// Converts a frame for sending.
+fn to_raw(frame: &Frame) -> libc::can_frame {
+    // SAFETY: `can_frame` is a plain C struct for which all zeroes is a valid value.
+    let mut raw: libc::can_frame = unsafe { core::mem::zeroed() };
+
+    raw.can_id = match frame.id() {
+        Id::Standard(id) => u32::from(id.to_raw()),
+        Id::Extended(id) => id.to_raw() | libc::CAN_EFF_FLAG,
+    };
+    // A `Frame` has at most 8 bytes of data.
+    raw.can_dlc = frame.data().len() as u8;
+    raw.data[..frame.data().len()].copy_from_slice(frame.data());
+
+    raw
+}
+
+impl CanTransport for &SocketCan {
+    type Error = io::Error;
+
+    async fn send(&mut self, frame: &Frame) -> Result<(), Self::Error> {
+        let raw = to_raw(frame);
+        self.socket
+            .async_io(Interest::WRITABLE, |fd| write(fd, &raw))
+            .await
+    }
+
+    async fn recv(&mut self) -> Result<Frame, Self::Error> {
+        loop {
+            let raw = self.socket.async_io(Interest::READABLE, read).await?;
+            if let Some(frame) = from_raw(&raw) {
+                return Ok(frame);
+            }
+        }
+    }
+}
+
+impl CanTransport for SocketCan {
+    type Error = io::Error;
+
+    async fn send(&mut self, frame: &Frame) -> Result<(), Self::Error> {
+        <&SocketCan as CanTransport>::send(&mut &*self, frame).await
+    }
+
+    async fn recv(&mut self) -> Result<Frame, Self::Error> {
+        <&SocketCan as CanTransport>::recv(&mut &*self).await
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::{from_raw, to_raw};
+    use crate::{ExtendedId, Frame, StandardId};
+
+    #[test]
+    fn conversion() {
+        let frame = Frame::new(StandardId::new_unwrap(0x123), [1, 2, 3]);
+        let raw = to_raw(&frame);
+        assert_eq!(raw.can_id, 0x123);
+        assert_eq!(raw.can_dlc, 3);
+        let converted = from_raw(&raw).unwrap();
+        assert_eq!(converted.id(), frame.id());
+        assert_eq!(converted.data(), frame.data());
+
+        let frame = Frame::new(ExtendedId::new_unwrap(0x0CF0_0400), [0; 8]);
+        let raw = to_raw(&frame);
+        assert_eq!(raw.can_id, 0x8CF0_0400);
+        assert_eq!(raw.can_dlc, 8);
+        let converted = from_raw(&raw).unwrap();
+        assert_eq!(converted.id(), frame.id());
+        assert_eq!(converted.data(), frame.data());
+    }
+
+    #[test]
+    fn skips_rtr_and_error_frames() {
+        let mut raw = to_raw(&Frame::new(StandardId::new_unwrap(0x123), []));
+        raw.can_id |= libc::CAN_RTR_FLAG;
+        assert!(from_raw(&raw).is_none());
+
+        raw.can_id = libc::CAN_ERR_FLAG | 0x4;
+        assert!(from_raw(&raw).is_none());
+    }
+}
@@ -0,0 +1,195 @@
+//! An async abstraction over CAN buses and actors connecting them to the runtime.
+
+use veecle_os_runtime::Never;
+use veecle_os_runtime::single_writer::{Reader, Writer};
+
+use crate::frame::Frame;
+
+/// An async transport sending and receiving [`Frame`]s, e.g. a CAN controller or a socket bound to a CAN interface.
+#[expect(async_fn_in_trait)]
+pub trait CanTransport {
+    /// The error returned if sending or receiving fails.
+    type Error: core::error::Error;
+
+    /// Sends `frame`, returning once it has been queued for transmission.
+    async fn send(&mut self, frame: &Frame) -> Result<(), Self::Error>;
+
+    /// Receives the next frame from the bus.
+    async fn recv(&mut self) -> Result<Frame, Self::Error>;
+}
+
+/// An actor receiving [`Frame`]s from a [`CanTransport`] and writing them to the store.
+///
+/// Together with the `deserialize_frames` actor generated for a DBC, which matches the ids of the frames against the
+/// `FRAME_ID`s of the messages, this decodes the frames received from a bus into the generated message types.
+///
+/// Fails with the error of the transport if receiving a frame fails.
+///
+/// ```rust
+/// # use veecle_os_data_support_can::{CanTransport, Frame};
+/// # #[derive(Debug)]
+/// # struct Bus;
+/// # impl CanTransport for Bus {
+/// #     type Error = core::convert::Infallible;
+/// #     async fn send(&mut self, _: &Frame) -> Result<(), Self::Error> { Ok(()) }
+/// #     async fn recv(&mut self) -> Result<Frame, Self::Error> { core::future::pending().await }
+/// # }
+/// use veecle_os_data_support_can::ReceiveFrames;
+///
+/// veecle_os_data_support_can::generate!(
+///     mod messages {
+///         #![dbc = r#"
+///             VERSION ""
+///
+///             NS_ :
+///
+///             BO_ 1 Status: 1 Vector__XXX
+///              SG_ Active : 0|1@1+ (1,0) [0|1] "" Vector__XXX
+///         "#]
+///     }
+/// );
+///
+/// # let _ = async {
+/// veecle_os_runtime::execute! {
+///     actors: [
+///         ReceiveFrames<Bus>: Bus,
+///         messages::DeserializeFrames,
+///         // ... the actors reading `messages::Status`.
+///     ],
+/// }
+/// .await;
+/// # };
+/// ```
+#[veecle_os_runtime::actor]
+pub async fn receive_frames<T>(
+    #[init_context] mut transport: T,
+    mut writer: Writer<'_, Frame>,
+) -> Result<Never, T::Error>
+where
+    T: CanTransport,
+{
+    loop {
+        let frame = transport.recv().await?;
+        writer.write(frame).await;
+    }
+}
+
+/// An actor sending every [`Frame`] written to the store through a [`CanTransport`].
+///
+/// Fails with the error of the transport if sending a frame fails.
+#[veecle_os_runtime::actor]
+pub async fn transmit_frames<T>(
+    #[init_context] mut transport: T,
+    mut reader: Reader<'_, Frame>,
+) -> Result<Never, T::Error>
+where
+    T: CanTransport,
+{
+    loop {
+        let frame = reader.read_updated_cloned().await;
+        transport.send(&frame).await?;
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+    use std::vec::Vec;
+
+    use veecle_os_runtime::single_writer::{Reader, Writer};
+
+    use super::{CanTransport, ReceiveFrames, TransmitFrames};
+    use crate::{ExtendedId, Frame, StandardId};
+
+    /// A transport receiving a fixed list of frames and recording the sent frames.
+    #[derive(Debug)]
+    struct MockTransport {
+        received: VecDeque<Frame>,
+        sent: &'static Mutex<Vec<Frame>>,
+    }
+
+    impl CanTransport for MockTransport {
+        type Error = core::convert::Infallible;
+
+        async fn send(&mut self, frame: &Frame) -> Result<(), Self::Error> {
+            self.sent.lock().unwrap().push(*frame);
+            Ok(())
+        }
+
+        async fn recv(&mut self) -> Result<Frame, Self::Error> {
+            match self.received.pop_front() {
+                Some(frame) => Ok(frame),
+                None => core::future::pending().await,
+            }
+        }
+    }
+
+    /// Yields to the executor once, letting the other actors make progress.
+    async fn yield_now() {
+        let mut yielded = false;
+        core::future::poll_fn(|context| {
+            if yielded {
+                core::task::Poll::Ready(())
+            } else {
+                yielded = true;
+                context.waker().wake_by_ref();
+                core::task::Poll::Pending
+            }
+        })
+        .await;
+    }
+
+    #[test]
+    fn receive() {
+        static SENT: Mutex<Vec<Frame>> = Mutex::new(Vec::new());
+
+        let transport = MockTransport {
+            received: VecDeque::from([Frame::new(ExtendedId::new_unwrap(0x0CF0_0400), [1, 2, 3])]),
+            sent: &SENT,
+        };
+
+        veecle_os_test::block_on_future(veecle_os_test::execute! {
+            actors: [
+                ReceiveFrames<MockTransport>: transport,
+            ],
+            validation: async |mut reader: Reader<'_, Frame>| {
+                let frame = reader.read_updated_cloned().await;
+                assert_eq!(frame.id(), ExtendedId::new_unwrap(0x0CF0_0400).into());
+                assert_eq!(frame.data(), [1, 2, 3]);
+            }
+        });
+
+        assert!(SENT.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn transmit() {
+        static SENT: Mutex<Vec<Frame>> = Mutex::new(Vec::new());
+
+        let transport = MockTransport {
+            received: VecDeque::new(),
+            sent: &SENT,
+        };
+
+        veecle_os_test::block_on_future(veecle_os_test::execute! {
+            actors: [
+                TransmitFrames<MockTransport>: transport,
+            ],
+            validation: async |mut writer: Writer<'_, Frame>| {
+                writer.write(Frame::new(StandardId::new_unwrap(0x123), [4, 5])).await;
+                while SENT.lock().unwrap().is_empty() {
+                    yield_now().await;
+                }
+            }
+        });
+
+        let sent = SENT.lock().unwrap();
+        let [frame] = sent.as_slice() else {
+            panic!("expected a single sent frame, got {sent:?}");
+        };
+        assert_eq!(frame.id(), StandardId::new_unwrap(0x123).into());
+        assert_eq!(frame.data(), [4, 5]);
+    }
+}
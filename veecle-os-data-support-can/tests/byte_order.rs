@@ -0,0 +1,27 @@
+#![expect(missing_docs)]
+
+use veecle_os_data_support_can::{Frame, generate};
+
+#[test]
+fn byte_order_override() {
+    generate!(
+        mod generated {
+            #![dbc = r#"
+                VERSION ""
+
+                NS_ :
+
+                BO_ 1 SomeMessage: 8 Vector__XXX
+                    SG_ Value : 7|16@1+ (1,0) [0|0] "" Vector__XXX
+            "#]
+            #![byte_order = "big"]
+        }
+    );
+
+    let bytes = [0x12, 0x34, 0, 0, 0, 0, 0, 0];
+    let frame = Frame::new(generated::SomeMessage::FRAME_ID, bytes);
+
+    let message = generated::SomeMessage::try_from(&frame).unwrap();
+    assert_eq!(message.value.value(), 0x1234);
+    assert_eq!(Frame::from(&message).data(), bytes);
+}
@@ -81,6 +81,8 @@ macro_rules! make_tests {
                                 }),
                                 serde: syn::parse_str("serde")?,
                                 message_frame_validations: Box::new(|_| None),
+                                message_checksums: Box::new(|_| None),
+                                byte_order: None,
                             };
 
                             Generator::new(stringify!($db_name), options, $dbc).into_string();
@@ -0,0 +1,103 @@
+#![expect(missing_docs)]
+
+use std::sync::Mutex;
+
+use veecle_os_data_support_can::{CanTransport, Frame, Id, PeriodicTransmitter, StandardId};
+use veecle_os_runtime::single_writer::Writer;
+use veecle_os_runtime::{Never, Runtime, RuntimeStore};
+use veecle_osal_std::time::{Duration, Time, TimeAbstraction};
+
+veecle_os_data_support_can::generate!(
+    mod messages {
+        #![dbc = r#"
+            VERSION ""
+
+            NS_ :
+
+            BO_ 100 EngineStatus: 2 Vector__XXX
+             SG_ Rpm : 0|16@1+ (1,0) [0|65535] "rpm" Vector__XXX
+
+            BO_ 200 EngineEvent: 1 Vector__XXX
+             SG_ Code : 0|8@1+ (1,0) [0|255] "" Vector__XXX
+
+            BA_DEF_ BO_ "GenMsgCycleTime" INT 0 65535;
+            BA_ "GenMsgCycleTime" BO_ 100 100;
+        "#]
+    }
+);
+
+use messages::{EngineEvent, EngineStatus};
+
+/// A transport recording the sent frames.
+#[derive(Debug)]
+struct Recorder(&'static Mutex<Vec<Frame>>);
+
+impl CanTransport for Recorder {
+    type Error = core::convert::Infallible;
+
+    async fn send(&mut self, frame: &Frame) -> Result<(), Self::Error> {
+        self.0.lock().unwrap().push(*frame);
+        Ok(())
+    }
+
+    async fn recv(&mut self) -> Result<Frame, Self::Error> {
+        core::future::pending().await
+    }
+}
+
+/// Writes the status once after 50 ms and an event after 270 ms and 280 ms.
+#[veecle_os_runtime::actor]
+async fn engine(mut status: Writer<'_, EngineStatus>, mut event: Writer<'_, EngineEvent>) -> Never {
+    Time::sleep(Duration::from_millis(50)).await.unwrap();
+    status
+        .write(EngineStatus {
+            rpm: messages::engine_status::Rpm::try_from(1000).unwrap(),
+        })
+        .await;
+
+    Time::sleep(Duration::from_millis(220)).await.unwrap();
+    event
+        .write(EngineEvent {
+            code: messages::engine_event::Code::try_from(1).unwrap(),
+        })
+        .await;
+
+    Time::sleep(Duration::from_millis(10)).await.unwrap();
+    event
+        .write(EngineEvent {
+            code: messages::engine_event::Code::try_from(2).unwrap(),
+        })
+        .await;
+
+    core::future::pending().await
+}
+
+#[test]
+fn cycle_times() {
+    assert_eq!(EngineStatus::CYCLE_TIME_MS, Some(100));
+    assert_eq!(EngineEvent::CYCLE_TIME_MS, None);
+}
+
+#[tokio::test(start_paused = true)]
+async fn transmits_at_cycle_time_or_on_change() {
+    static SENT: Mutex<Vec<Frame>> = Mutex::new(Vec::new());
+
+    let store = core::pin::pin!(RuntimeStore::new());
+    let runtime = Runtime::builder(store.as_ref())
+        .actor::<Engine>(())
+        .actor::<PeriodicTransmitter<EngineStatus, Recorder, Time>>(Recorder(&SENT))
+        .actor::<PeriodicTransmitter<EngineEvent, Recorder, Time>>(Recorder(&SENT))
+        .build();
+
+    let _ = tokio::time::timeout(std::time::Duration::from_millis(475), runtime).await;
+
+    let sent = SENT.lock().unwrap();
+    let ids = Vec::from_iter(sent.iter().map(|frame| frame.id()));
+    let status = Id::Standard(StandardId::new_unwrap(100));
+    let event = Id::Standard(StandardId::new_unwrap(200));
+
+    // The status is sent at 50, 150, 250, 350 and 450 ms, the events when they are written.
+    assert_eq!(ids, [status, status, status, event, event, status, status]);
+    assert_eq!(sent[3].data(), [1]);
+    assert_eq!(sent[4].data(), [2]);
+}
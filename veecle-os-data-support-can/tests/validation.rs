@@ -53,3 +53,66 @@ fn simple_validation() {
         ),
     );
 }
+
+#[test]
+fn checksum_validation() {
+    generate!(
+        mod generated {
+            #![dbc = r#"
+                VERSION ""
+
+                NS_ :
+
+                BO_ 2 Protected: 8 Vector__XXX
+                    SG_ Data : 0|32@1+ (1,0) [0|0] "" Vector__XXX
+                    SG_ Counter : 48|4@1+ (1,0) [0|15] "" Vector__XXX
+                    SG_ CRC : 56|8@1+ (1,0) [0|0] "" Vector__XXX
+            "#]
+
+            #[checksum(
+                signal = "CRC",
+                algorithm = "e2e_profile1",
+                data_id = 0x123,
+                counter = "Counter",
+                counter_max = 14
+            )]
+            impl Protected {}
+        }
+    );
+
+    fn frame(mut bytes: [u8; 8]) -> Frame {
+        bytes[7] =
+            veecle_os_data_support_can::checksum::e2e_profile1(0x123, bytes[..7].iter().copied());
+        Frame::new(generated::Protected::FRAME_ID, bytes)
+    }
+
+    let decoded =
+        generated::Protected::try_from(frame([0x78, 0x56, 0x34, 0x12, 0, 0, 3, 0])).unwrap();
+    assert_eq!(decoded.data.value(), 0x12345678);
+    assert_eq!(decoded.counter.value(), 3);
+
+    let mut bytes: [u8; 8] = frame([0x78, 0x56, 0x34, 0x12, 0, 0, 3, 0])
+        .data()
+        .try_into()
+        .unwrap();
+    bytes[0] ^= 1;
+    let error = generated::Protected::try_from(Frame::new(generated::Protected::FRAME_ID, bytes))
+        .unwrap_err();
+    assert!(
+        matches!(error, CanDecodeError::ChecksumMismatch { expected, .. } if expected == bytes[7]),
+        "unexpected error {error:?}",
+    );
+
+    let error =
+        generated::Protected::try_from(frame([0x78, 0x56, 0x34, 0x12, 0, 0, 15, 0])).unwrap_err();
+    assert!(
+        matches!(
+            error,
+            CanDecodeError::OutOfRange {
+                name: "Counter",
+                ..
+            }
+        ),
+        "unexpected error {error:?}",
+    );
+}
@@ -0,0 +1,231 @@
+//! CRC algorithms shared by the Veecle OS data support crates.
+//!
+//! [`Crc8`], [`Crc16`] and [`Crc32`] implement table-based CRCs with a configurable polynomial, initial value, bit
+//! order and final XOR. The lookup tables are computed by `const fn`s, so the predefined algorithms below are
+//! evaluated at compile time.
+//!
+//! ```rust
+//! use veecle_os_data_support_crc::{CRC8_SAE_J1850, CRC32};
+//!
+//! assert_eq!(CRC8_SAE_J1850.checksum(b"123456789"), 0x4B);
+//! assert_eq!(CRC32.checksum(b"123456789"), 0xCBF43926);
+//! ```
+
+#![no_std]
+#![forbid(unsafe_code)]
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+/// Creates a CRC algorithm type for the given register width.
+macro_rules! crc {
+    (
+        $(#[$($attributes:tt)*])*
+        pub struct $name:ident($width:ty);
+    ) => {
+        $(#[$($attributes)*])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $name {
+            table: [$width; 256],
+            init: $width,
+            reflected: bool,
+            xor_out: $width,
+        }
+
+        impl $name {
+            /// Creates a CRC algorithm from its parameters, computing the lookup table.
+            ///
+            /// `polynomial` is given in normal (MSB-first) notation without the leading term. If `reflected` is set,
+            /// both the input bytes and the result are reflected, i.e. the CRC is computed LSB-first.
+            pub const fn new(polynomial: $width, init: $width, reflected: bool, xor_out: $width) -> Self {
+                let mut table = [0; 256];
+
+                let mut index = 0;
+                while index < 256 {
+                    let mut crc;
+                    let mut bit = 0;
+
+                    if reflected {
+                        let polynomial = polynomial.reverse_bits();
+                        crc = index as $width;
+                        while bit < 8 {
+                            crc = if crc & 1 != 0 {
+                                (crc >> 1) ^ polynomial
+                            } else {
+                                crc >> 1
+                            };
+                            bit += 1;
+                        }
+                    } else {
+                        crc = (index as $width) << (<$width>::BITS - 8);
+                        while bit < 8 {
+                            crc = if crc & (1 << (<$width>::BITS - 1)) != 0 {
+                                (crc << 1) ^ polynomial
+                            } else {
+                                crc << 1
+                            };
+                            bit += 1;
+                        }
+                    }
+
+                    table[index] = crc;
+                    index += 1;
+                }
+
+                Self {
+                    table,
+                    init: if reflected { init.reverse_bits() } else { init },
+                    reflected,
+                    xor_out,
+                }
+            }
+
+            /// Computes the checksum of `data`.
+            pub const fn checksum(&self, data: &[u8]) -> $width {
+                let mut crc = self.init;
+
+                let mut index = 0;
+                while index < data.len() {
+                    crc = self.update(crc, data[index]);
+                    index += 1;
+                }
+
+                crc ^ self.xor_out
+            }
+
+            /// Computes the checksum of the bytes yielded by `data`.
+            ///
+            /// This allows computing the checksum over non-contiguous data, e.g. a data ID followed by a payload.
+            pub fn checksum_iter(&self, data: impl IntoIterator<Item = u8>) -> $width {
+                let crc = data
+                    .into_iter()
+                    .fold(self.init, |crc, byte| self.update(crc, byte));
+
+                crc ^ self.xor_out
+            }
+
+            /// Feeds a single byte into the CRC register.
+            const fn update(&self, crc: $width, byte: u8) -> $width {
+                // Shifting out the whole register (for 8 bit CRCs) has to result in zero instead of overflowing.
+                if self.reflected {
+                    let index = (crc as u8 ^ byte) as usize;
+                    let shifted = match crc.checked_shr(8) {
+                        Some(shifted) => shifted,
+                        None => 0,
+                    };
+                    shifted ^ self.table[index]
+                } else {
+                    let index = ((crc >> (<$width>::BITS - 8)) as u8 ^ byte) as usize;
+                    let shifted = match crc.checked_shl(8) {
+                        Some(shifted) => shifted,
+                        None => 0,
+                    };
+                    shifted ^ self.table[index]
+                }
+            }
+        }
+    };
+}
+
+crc! {
+    /// An 8 bit CRC algorithm.
+    pub struct Crc8(u8);
+}
+
+crc! {
+    /// A 16 bit CRC algorithm.
+    pub struct Crc16(u16);
+}
+
+crc! {
+    /// A 32 bit CRC algorithm.
+    pub struct Crc32(u32);
+}
+
+/// CRC-8 SAE J1850 (polynomial `0x1D`, initial value `0xFF`, final XOR `0xFF`).
+///
+/// This is the AUTOSAR `Crc_CalculateCRC8` algorithm used by E2E profiles 1, 2 and 11.
+pub const CRC8_SAE_J1850: Crc8 = Crc8::new(0x1D, 0xFF, false, 0xFF);
+
+/// AUTOSAR CRC-8H2F (polynomial `0x2F`, initial value `0xFF`, final XOR `0xFF`).
+///
+/// This is the AUTOSAR `Crc_CalculateCRC8H2F` algorithm.
+pub const CRC8_AUTOSAR: Crc8 = Crc8::new(0x2F, 0xFF, false, 0xFF);
+
+/// CRC-16-CCITT-FALSE (polynomial `0x1021`, initial value `0xFFFF`, no final XOR).
+///
+/// This is the AUTOSAR `Crc_CalculateCRC16` algorithm used by E2E profiles 5 and 6.
+pub const CRC16_CCITT_FALSE: Crc16 = Crc16::new(0x1021, 0xFFFF, false, 0x0000);
+
+/// CRC-32 as used by IEEE 802.3 (polynomial `0x04C11DB7` reflected, initial value and final XOR `0xFFFFFFFF`).
+///
+/// This is the AUTOSAR `Crc_CalculateCRC32` algorithm.
+pub const CRC32: Crc32 = Crc32::new(0x04C1_1DB7, 0xFFFF_FFFF, true, 0xFFFF_FFFF);
+
+/// AUTOSAR CRC-32P4 (polynomial `0xF4ACFB13` reflected, initial value and final XOR `0xFFFFFFFF`).
+///
+/// This is the AUTOSAR `Crc_CalculateCRC32P4` algorithm used by E2E profile 4.
+pub const CRC32_AUTOSAR: Crc32 = Crc32::new(0xF4AC_FB13, 0xFFFF_FFFF, true, 0xFFFF_FFFF);
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use test_case::test_case;
+
+    use super::{CRC8_AUTOSAR, CRC8_SAE_J1850, CRC16_CCITT_FALSE, CRC32, CRC32_AUTOSAR, Crc8};
+
+    // Check values from the AUTOSAR CRC library specification.
+    #[test_case(&[0x00, 0x00, 0x00, 0x00], 0x59)]
+    #[test_case(&[0xF2, 0x01, 0x83], 0x37)]
+    #[test_case(&[0x0F, 0xAA, 0x00, 0x55], 0x79)]
+    #[test_case(&[0xFF, 0xFF, 0xFF, 0xFF], 0x74)]
+    #[test_case(b"", 0x00)]
+    #[test_case(b"123456789", 0x4B)]
+    fn crc8_sae_j1850(data: &[u8], expected: u8) {
+        assert_eq!(CRC8_SAE_J1850.checksum(data), expected);
+        assert_eq!(CRC8_SAE_J1850.checksum_iter(data.iter().copied()), expected);
+    }
+
+    #[test_case(&[0x00, 0x00, 0x00, 0x00], 0x12)]
+    #[test_case(&[0xF2, 0x01, 0x83], 0xC2)]
+    #[test_case(&[0xFF, 0xFF, 0xFF, 0xFF], 0x6C)]
+    #[test_case(b"123456789", 0xDF)]
+    fn crc8_autosar(data: &[u8], expected: u8) {
+        assert_eq!(CRC8_AUTOSAR.checksum(data), expected);
+    }
+
+    #[test_case(&[0x00, 0x00, 0x00, 0x00], 0x84C0)]
+    #[test_case(&[0xF2, 0x01, 0x83], 0xD374)]
+    #[test_case(&[0xFF, 0xFF, 0xFF, 0xFF], 0x1D0F)]
+    #[test_case(b"123456789", 0x29B1)]
+    fn crc16_ccitt_false(data: &[u8], expected: u16) {
+        assert_eq!(CRC16_CCITT_FALSE.checksum(data), expected);
+    }
+
+    #[test_case(&[0x00, 0x00, 0x00, 0x00], 0x2144DF1C)]
+    #[test_case(&[0xF2, 0x01, 0x83], 0x24AB9D77)]
+    #[test_case(&[0xFF, 0xFF, 0xFF, 0xFF], 0xFFFFFFFF)]
+    #[test_case(b"123456789", 0xCBF43926)]
+    fn crc32(data: &[u8], expected: u32) {
+        assert_eq!(CRC32.checksum(data), expected);
+        assert_eq!(CRC32.checksum_iter(data.iter().copied()), expected);
+    }
+
+    #[test_case(&[0x00, 0x00, 0x00, 0x00], 0x6FB32240)]
+    #[test_case(&[0xF2, 0x01, 0x83], 0x4F721A25)]
+    #[test_case(&[0xFF, 0xFF, 0xFF, 0xFF], 0xFFFFFFFF)]
+    #[test_case(b"123456789", 0x1697D06A)]
+    fn crc32_autosar(data: &[u8], expected: u32) {
+        assert_eq!(CRC32_AUTOSAR.checksum(data), expected);
+    }
+
+    #[test]
+    fn const_evaluation() {
+        const CHECKSUM: u8 = CRC8_SAE_J1850.checksum(b"123456789");
+        assert_eq!(CHECKSUM, 0x4B);
+
+        // Any polynomial can be used, e.g. the reflected CRC-8/MAXIM-DOW.
+        assert_eq!(
+            Crc8::new(0x31, 0x00, true, 0x00).checksum(b"123456789"),
+            0xA1
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use proc_macro2::Ident;
+use syn::{Attribute, Field, GenericArgument, Lifetime, LitInt, LitStr, PathArguments, Type};
+
+/// Attributes of a field, set with `#[someip(...)]`.
+#[derive(Default)]
+pub struct FieldAttributes {
+    /// The struct lifetime the field is parsed with, set with `lifetime = 'a`.
+    pub lifetime: Option<Lifetime>,
+    /// The bit of the presence bitmask for an optional field, set with `optional, present_bit = N`.
+    pub present_bit: Option<u32>,
+    /// The number of reserved bytes in front of the field, set with `reserved_before = N`.
+    pub reserved_before: usize,
+    /// The number of reserved bytes after the field, set with `reserved_after = N`.
+    pub reserved_after: usize,
+}
+
+impl FieldAttributes {
+    /// Parses the `#[someip(...)]` attributes of `field`.
+    pub fn from_field(field: &Field) -> syn::Result<Self> {
+        let mut attributes = Self::default();
+        let mut optional = false;
+
+        for attribute in field
+            .attrs
+            .iter()
+            .filter(|attribute| attribute.path().is_ident("someip"))
+        {
+            attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("lifetime") {
+                    attributes.lifetime = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("optional") {
+                    optional = true;
+                    Ok(())
+                } else if meta.path.is_ident("present_bit") {
+                    let bit: LitInt = meta.value()?.parse()?;
+                    attributes.present_bit = Some(bit.base10_parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("reserved_before") {
+                    let length: LitInt = meta.value()?.parse()?;
+                    attributes.reserved_before = length.base10_parse()?;
+                    Ok(())
+                } else if meta.path.is_ident("reserved_after") {
+                    let length: LitInt = meta.value()?.parse()?;
+                    attributes.reserved_after = length.base10_parse()?;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported someip attribute"))
+                }
+            })?;
+        }
+
+        if optional != attributes.present_bit.is_some() {
+            return Err(syn::Error::new_spanned(
+                field,
+                "optional fields require both `optional` and `present_bit = N`",
+            ));
+        }
+
+        if optional && (attributes.reserved_before != 0 || attributes.reserved_after != 0) {
+            return Err(syn::Error::new_spanned(
+                field,
+                "reserved bytes must be placed next to a field that is always present",
+            ));
+        }
+
+        Ok(attributes)
+    }
+}
+
+/// The presence bitmask of a struct with optional fields, set with `#[someip(optional_bitmask = "u16")]`.
+pub struct Bitmask {
+    /// The unsigned integer type of the bitmask.
+    pub ty: Ident,
+    /// The number of bits of `ty`.
+    pub bits: u32,
+}
+
+impl Bitmask {
+    /// Parses the `#[someip(...)]` attributes of the struct.
+    pub fn from_attributes(attributes: &[Attribute]) -> syn::Result<Option<Self>> {
+        let mut bitmask = None;
+
+        for attribute in attributes
+            .iter()
+            .filter(|attribute| attribute.path().is_ident("someip"))
+        {
+            attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("optional_bitmask") {
+                    let ty: LitStr = meta.value()?.parse()?;
+                    let bits = match ty.value().as_str() {
+                        "u8" => 8,
+                        "u16" => 16,
+                        "u32" => 32,
+                        "u64" => 64,
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                &ty,
+                                "the bitmask must be one of `u8`, `u16`, `u32` or `u64`",
+                            ));
+                        }
+                    };
+                    bitmask = Some(Self {
+                        ty: Ident::new(&ty.value(), ty.span()),
+                        bits,
+                    });
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported someip attribute"))
+                }
+            })?;
+        }
+
+        Ok(bitmask)
+    }
+}
+
+/// A field of the struct being derived.
+pub struct DerivedField<'a> {
+    pub field: &'a Field,
+    pub attributes: FieldAttributes,
+    /// For optional fields, the present bit and the type `T` of the `Option<T>` field.
+    pub optional: Option<(u32, &'a Type)>,
+}
+
+/// Parses the attributes of all fields and validates optional fields against the struct `bitmask`.
+pub fn derived_fields<'a>(
+    fields: &'a syn::Fields,
+    bitmask: Option<&Bitmask>,
+) -> syn::Result<Vec<DerivedField<'a>>> {
+    let mut used_bits = Vec::new();
+
+    let derived_fields = fields
+        .iter()
+        .map(|field| {
+            let attributes = FieldAttributes::from_field(field)?;
+
+            let optional = match attributes.present_bit {
+                Some(bit) => {
+                    let Some(bitmask) = bitmask else {
+                        return Err(syn::Error::new_spanned(
+                            field,
+                            "optional fields require a `#[someip(optional_bitmask = \"...\")]` struct attribute",
+                        ));
+                    };
+                    if bit >= bitmask.bits {
+                        return Err(syn::Error::new_spanned(
+                            field,
+                            format!("the present bit must be less than {}", bitmask.bits),
+                        ));
+                    }
+                    if used_bits.contains(&bit) {
+                        return Err(syn::Error::new_spanned(
+                            field,
+                            "the present bit is already used by another field",
+                        ));
+                    }
+                    used_bits.push(bit);
+
+                    let inner = option_inner_type(&field.ty).ok_or_else(|| {
+                        syn::Error::new_spanned(&field.ty, "optional fields must be of type `Option<T>`")
+                    })?;
+                    Some((bit, inner))
+                }
+                None => None,
+            };
+
+            Ok(DerivedField {
+                field,
+                attributes,
+                optional,
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    if let Some(bitmask) = bitmask
+        && used_bits.is_empty()
+    {
+        return Err(syn::Error::new_spanned(
+            &bitmask.ty,
+            "a struct with an optional bitmask requires at least one optional field",
+        ));
+    }
+
+    Ok(derived_fields)
+}
+
+/// Returns `T` if `ty` is `Option<T>`.
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    match arguments.args.first()? {
+        GenericArgument::Type(inner) if arguments.args.len() == 1 => Some(inner),
+        _ => None,
+    }
+}
@@ -3,6 +3,7 @@
 use proc_macro::TokenStream;
 use syn::{DeriveInput, parse_macro_input};
 
+mod attributes;
 mod parse;
 mod serialize;
 
@@ -49,6 +50,26 @@ mod serialize;
 /// assert!(WithLifetimeDerived::parse(&[]).is_ok());
 /// ```
 ///
+/// Fields can be fixed size arrays of any type implementing `Parse`, which are parsed element by element.
+///
+/// ```rust
+/// use veecle_os_data_support_someip::parse::{Parse, ParseError, ParseExt};
+///
+/// #[derive(Debug, PartialEq, Parse)]
+/// struct Device {
+///     mac_address: [u8; 6],
+///     ports: [u16; 2],
+/// }
+///
+/// let bytes = &[0x2, 0x0, 0x5E, 0x10, 0x0, 0x1, 0x7, 0xD0, 0x7, 0xD1];
+///
+/// let device = Device::parse(bytes).unwrap();
+/// assert_eq!(device.mac_address, [0x2, 0x0, 0x5E, 0x10, 0x0, 0x1]);
+/// assert_eq!(device.ports, [2000, 2001]);
+///
+/// assert_eq!(Device::parse(&bytes[..8]), Err(ParseError::PayloadTooShort));
+/// ```
+///
 /// Zero sized types and tuple structs can be derived as well.
 ///
 /// ```rust
@@ -61,7 +82,103 @@ mod serialize;
 /// struct TupleStruct(u32, u16);
 /// ```
 ///
-/// It cannot be derived for enums, unions, or structs with more than one lifetime.
+/// Parsing the fields of a struct increases the nesting depth of the reader, parsing fails with
+/// `ParseError::MaxDepthExceeded` when structs are nested deeper than the maximum depth of the reader.
+///
+/// ```rust
+/// use veecle_os_data_support_someip::parse::{Parse, ParseError, ParseExt};
+///
+/// #[derive(Debug, Parse)]
+/// struct Inner(u8);
+///
+/// #[derive(Debug, Parse)]
+/// struct Outer(Inner);
+///
+/// assert!(Outer::parse_with_max_depth(&[1], 2).is_ok());
+/// assert_eq!(
+///     Outer::parse_with_max_depth(&[1], 1).unwrap_err(),
+///     ParseError::MaxDepthExceeded { max_depth: 1 }
+/// );
+/// ```
+///
+/// Structs with multiple lifetimes are supported as long as every field borrows from at most one of them.
+/// The lifetime a field is parsed with is inferred from its type, use `#[someip(lifetime = 'a)]` to specify it when the
+/// type mentions multiple lifetimes of the struct.
+///
+/// ```rust
+/// use veecle_os_data_support_someip::parse::{ByteReader, Parse, ParseError, ParseExt};
+///
+/// #[derive(Debug)]
+/// struct Foo;
+///
+/// impl<'a> Parse<'a> for &'a Foo {
+///     fn parse_partial(reader: &mut ByteReader<'a>) -> Result<Self, ParseError> {
+///         Ok(&Foo)
+///     }
+/// }
+///
+/// #[derive(Debug, Parse)]
+/// struct WithLifetimesDerived<'header, 'payload> {
+///     header: &'header Foo,
+///     payload: &'payload Foo,
+///     #[someip(lifetime = 'payload)]
+///     explicit: &'payload Foo,
+///     value: u8,
+/// }
+///
+/// assert!(WithLifetimesDerived::parse(&[1]).is_ok());
+/// ```
+///
+/// Optional fields are supported with a leading presence bitmask. The bitmask type is set with
+/// `#[someip(optional_bitmask = "u16")]` on the struct (one of `u8`, `u16`, `u32` or `u64`), optional fields are
+/// typed `Option<T>` and marked with `#[someip(optional, present_bit = N)]`. A field is only parsed if bit `N` (counted
+/// from the least significant bit) is set, otherwise it is `None`.
+///
+/// ```rust
+/// use veecle_os_data_support_someip::parse::{Parse, ParseExt};
+///
+/// #[derive(Debug, PartialEq, Parse)]
+/// #[someip(optional_bitmask = "u8")]
+/// struct Status {
+///     speed: u16,
+///     #[someip(optional, present_bit = 0)]
+///     temperature: Option<u8>,
+///     #[someip(optional, present_bit = 1)]
+///     pressure: Option<u16>,
+/// }
+///
+/// let bytes = &[0b10, 0x0, 0x32, 0x3, 0xE8];
+///
+/// assert_eq!(
+///     Status::parse(bytes).unwrap(),
+///     Status {
+///         speed: 50,
+///         temperature: None,
+///         pressure: Some(1000),
+///     }
+/// );
+/// ```
+///
+/// Reserved or padding bytes of the wire layout are declared with `#[someip(reserved_before = N)]` and
+/// `#[someip(reserved_after = N)]` on the neighboring field instead of a dummy field. They are skipped without checking
+/// their value when parsing and written as zeros when serializing.
+///
+/// ```rust
+/// use veecle_os_data_support_someip::parse::{Parse, ParseExt};
+///
+/// #[derive(Debug, PartialEq, Parse)]
+/// struct Frame {
+///     kind: u8,
+///     #[someip(reserved_before = 3)]
+///     id: u32,
+/// }
+///
+/// let bytes = &[0x1, 0xFF, 0xFF, 0xFF, 0x0, 0x0, 0x0, 0x2A];
+///
+/// assert_eq!(Frame::parse(bytes).unwrap(), Frame { kind: 1, id: 42 });
+/// ```
+///
+/// It cannot be derived for enums, unions, or structs with fields using multiple lifetimes without an explicit lifetime.
 ///
 /// ```compile_fail
 /// use veecle_os_data_support_someip::parse::{Parse};
@@ -80,7 +197,7 @@ mod serialize;
 ///   foo: PhantomData<(&'a (), &'b ())>,
 /// }
 /// ```
-#[proc_macro_derive(Parse)]
+#[proc_macro_derive(Parse, attributes(someip))]
 pub fn someip_parse(input: TokenStream) -> TokenStream {
     let derive_input = parse_macro_input!(input as DeriveInput);
     parse::impl_derive_parse(derive_input).unwrap_or_else(|error| error.into_compile_error().into())
@@ -109,6 +226,31 @@ pub fn someip_parse(input: TokenStream) -> TokenStream {
 /// assert_eq!(&buffer[..written], bytes);
 /// ```
 ///
+/// Fields can be fixed size arrays of any type implementing `Serialize`, which are serialized element by element.
+///
+/// ```rust
+/// use veecle_os_data_support_someip::serialize::{Serialize, SerializeExt};
+///
+/// #[derive(Serialize)]
+/// struct Device {
+///     mac_address: [u8; 6],
+///     ports: [u16; 2],
+/// }
+///
+/// let device = Device {
+///     mac_address: [0x2, 0x0, 0x5E, 0x10, 0x0, 0x1],
+///     ports: [2000, 2001],
+/// };
+///
+/// let mut buffer = [0u8; 16];
+/// let written = device.serialize(&mut buffer).unwrap();
+///
+/// assert_eq!(
+///     &buffer[..written],
+///     &[0x2, 0x0, 0x5E, 0x10, 0x0, 0x1, 0x7, 0xD0, 0x7, 0xD1]
+/// );
+/// ```
+///
 /// Zero sized types and tuple structs can be derived as well.
 ///
 /// ```rust
@@ -121,6 +263,71 @@ pub fn someip_parse(input: TokenStream) -> TokenStream {
 /// struct TupleStruct(u32, u16);
 /// ```
 ///
+/// Optional fields are serialized after a leading presence bitmask, see the `Parse` derive macro.
+///
+/// ```rust
+/// use veecle_os_data_support_someip::serialize::{Serialize, SerializeExt};
+///
+/// #[derive(Serialize)]
+/// #[someip(optional_bitmask = "u8")]
+/// struct Status {
+///     speed: u16,
+///     #[someip(optional, present_bit = 0)]
+///     temperature: Option<u8>,
+///     #[someip(optional, present_bit = 1)]
+///     pressure: Option<u16>,
+/// }
+///
+/// let status = Status {
+///     speed: 50,
+///     temperature: None,
+///     pressure: Some(1000),
+/// };
+///
+/// let mut buffer = [0u8; 16];
+/// let written = status.serialize(&mut buffer).unwrap();
+///
+/// assert_eq!(&buffer[..written], &[0b10, 0x0, 0x32, 0x3, 0xE8]);
+/// ```
+///
+/// Reserved bytes declared with `#[someip(reserved_before = N)]` or `#[someip(reserved_after = N)]` are written as
+/// zeros, see the `Parse` derive macro.
+///
+/// ```rust
+/// use veecle_os_data_support_someip::serialize::{Serialize, SerializeExt};
+///
+/// #[derive(Serialize)]
+/// struct Frame {
+///     kind: u8,
+///     #[someip(reserved_before = 3)]
+///     id: u32,
+/// }
+///
+/// let mut buffer = [0xFFu8; 16];
+/// let written = Frame { kind: 1, id: 42 }.serialize(&mut buffer).unwrap();
+///
+/// assert_eq!(&buffer[..written], &[0x1, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x2A]);
+/// ```
+///
+/// Optional fields must be of type `Option<T>` and require the bitmask attribute on the struct.
+///
+/// ```compile_fail
+/// use veecle_os_data_support_someip::serialize::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct MissingBitmask {
+///     #[someip(optional, present_bit = 0)]
+///     value: Option<u8>,
+/// }
+///
+/// #[derive(Serialize)]
+/// #[someip(optional_bitmask = "u8")]
+/// struct NotAnOption {
+///     #[someip(optional, present_bit = 0)]
+///     value: u8,
+/// }
+/// ```
+///
 /// It cannot be derived for enums or unions.
 ///
 /// ```compile_fail
@@ -135,7 +342,7 @@ pub fn someip_parse(input: TokenStream) -> TokenStream {
 ///   bar: u8,
 /// }
 /// ```
-#[proc_macro_derive(Serialize)]
+#[proc_macro_derive(Serialize, attributes(someip))]
 pub fn someip_serialize(input: TokenStream) -> TokenStream {
     let derive_input = parse_macro_input!(input as DeriveInput);
     serialize::impl_derive_serialize(derive_input)
@@ -1,8 +1,11 @@
 use proc_macro::TokenStream;
 use proc_macro2::Span;
 use quote::quote_spanned;
+use syn::visit::Visit;
 use syn::{DeriveInput, GenericParam, Lifetime, LifetimeParam};
 
+use crate::attributes::{Bitmask, DerivedField, derived_fields};
+
 /// Implementation of the `Parse` derive macro.
 pub fn impl_derive_parse(derive_input: DeriveInput) -> syn::Result<TokenStream> {
     let syn::Data::Struct(data_struct) = derive_input.data else {
@@ -16,7 +19,13 @@ pub fn impl_derive_parse(derive_input: DeriveInput) -> syn::Result<TokenStream>
 
     let struct_name = &derive_input.ident;
 
-    let padded_generics = match derive_input.generics.lifetimes().count() {
+    let lifetimes = derive_input
+        .generics
+        .lifetimes()
+        .map(|param| param.lifetime.clone())
+        .collect::<Vec<_>>();
+
+    let padded_generics = match lifetimes.len() {
         0 => {
             let mut generics = derive_input.generics.clone();
 
@@ -31,10 +40,19 @@ pub fn impl_derive_parse(derive_input: DeriveInput) -> syn::Result<TokenStream>
         }
         1 => derive_input.generics.clone(),
         _ => {
-            return Err(syn::Error::new_spanned(
-                &derive_input.generics,
-                "Parse can only be derived for structs with no lifetime or a single lifetime",
-            ));
+            // The reader's lifetime has to outlive every lifetime of the struct, fields are then parsed with a reader
+            // shortened to their own lifetime.
+            let mut generics = derive_input.generics.clone();
+
+            let mut parse_lifetime =
+                LifetimeParam::new(Lifetime::new("'__parse", Span::mixed_site()));
+            parse_lifetime.bounds.extend(lifetimes.iter().cloned());
+
+            generics
+                .params
+                .insert(0, GenericParam::Lifetime(parse_lifetime));
+
+            generics
         }
     };
 
@@ -42,11 +60,63 @@ pub fn impl_derive_parse(derive_input: DeriveInput) -> syn::Result<TokenStream>
     let (_, ty_generics, where_clause) = derive_input.generics.split_for_impl();
     let (impl_generics, _, _) = padded_generics.split_for_impl();
 
-    let field_types = data_struct
-        .fields
+    let bitmask = Bitmask::from_attributes(&derive_input.attrs)?;
+    let derived_fields = derived_fields(&data_struct.fields, bitmask.as_ref())?;
+
+    let field_parsers = derived_fields
         .iter()
-        .map(|field| &field.ty)
-        .collect::<Vec<_>>();
+        .map(|derived_field| {
+            let field_type = match derived_field.optional {
+                Some((_, inner)) => inner,
+                None => &derived_field.field.ty,
+            };
+            let parse = quote_spanned! { Span::mixed_site() =>
+                <#field_type as #veecle_os_data_support_someip::parse::Parse>::parse_partial
+            };
+
+            let lifetime = match lifetimes.len() {
+                0 | 1 => None,
+                _ => field_lifetime(derived_field, &lifetimes)?,
+            };
+            let parse = match lifetime {
+                Some(lifetime) => quote_spanned! { Span::mixed_site() =>
+                    reader.with_lifetime::< #lifetime, _>(|reader| #parse(reader))?
+                },
+                None => quote_spanned! { Span::mixed_site() => #parse(reader)? },
+            };
+
+            // Reserved bytes are skipped without checking their value, as receivers should ignore them.
+            let (reserved_before, reserved_after) = (
+                derived_field.attributes.reserved_before,
+                derived_field.attributes.reserved_after,
+            );
+            let parse = if reserved_before != 0 || reserved_after != 0 {
+                quote_spanned! { Span::mixed_site() =>
+                    {
+                        reader.skip(#reserved_before)?;
+                        let value = #parse;
+                        reader.skip(#reserved_after)?;
+                        value
+                    }
+                }
+            } else {
+                parse
+            };
+
+            match derived_field.optional {
+                Some((bit, _)) => Ok(quote_spanned! { Span::mixed_site() =>
+                    if bitmask & (1 << #bit) != 0 { Some(#parse) } else { None }
+                }),
+                None => Ok(parse),
+            }
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let parse_bitmask = bitmask.map(|Bitmask { ty, .. }| {
+        quote_spanned! { Span::mixed_site() =>
+            let bitmask = <#ty as #veecle_os_data_support_someip::parse::Parse>::parse_partial(reader)?;
+        }
+    });
 
     match &data_struct.fields {
         syn::Fields::Named(..) => {
@@ -55,11 +125,15 @@ pub fn impl_derive_parse(derive_input: DeriveInput) -> syn::Result<TokenStream>
             Ok(quote_spanned! { Span::mixed_site() =>
                 impl #impl_generics #veecle_os_data_support_someip::parse::Parse< #trait_lifetime > for #struct_name #ty_generics #where_clause {
                     fn parse_partial(reader: &mut #veecle_os_data_support_someip::parse::ByteReader< #trait_lifetime >) -> Result<Self, #veecle_os_data_support_someip::parse::ParseError> {
-                        #(
-                            let #field_names = <#field_types as #veecle_os_data_support_someip::parse::Parse>::parse_partial(reader)?;
-                        )*
+                        reader.nested(|reader| {
+                            #parse_bitmask
+
+                            #(
+                                let #field_names = #field_parsers;
+                            )*
 
-                        Ok(Self { #(#field_names),* })
+                            Ok(Self { #(#field_names),* })
+                        })
                     }
                 }
             }
@@ -69,9 +143,13 @@ pub fn impl_derive_parse(derive_input: DeriveInput) -> syn::Result<TokenStream>
             Ok(quote_spanned! { Span::mixed_site() =>
                 impl #impl_generics #veecle_os_data_support_someip::parse::Parse< #trait_lifetime > for #struct_name #ty_generics #where_clause {
                     fn parse_partial(reader: &mut #veecle_os_data_support_someip::parse::ByteReader< #trait_lifetime >) -> Result<Self, #veecle_os_data_support_someip::parse::ParseError> {
-                        Ok(Self (#(
-                            <#field_types as #veecle_os_data_support_someip::parse::Parse>::parse_partial(reader)?,
-                        )*))
+                        reader.nested(|reader| {
+                            #parse_bitmask
+
+                            Ok(Self (#(
+                                #field_parsers,
+                            )*))
+                        })
                     }
                 }
             }
@@ -87,3 +165,52 @@ pub fn impl_derive_parse(derive_input: DeriveInput) -> syn::Result<TokenStream>
         .into()),
     }
 }
+
+/// Returns the struct lifetime a field borrows from, or `None` if it does not borrow from any of them.
+///
+/// The lifetime is taken from a `#[someip(lifetime = 'a)]` attribute if present, otherwise it is inferred from the
+/// struct lifetimes used in the field type.
+fn field_lifetime(
+    derived_field: &DerivedField,
+    lifetimes: &[Lifetime],
+) -> syn::Result<Option<Lifetime>> {
+    let field = derived_field.field;
+
+    if let Some(lifetime) = &derived_field.attributes.lifetime {
+        if !lifetimes.contains(lifetime) {
+            return Err(syn::Error::new_spanned(
+                lifetime,
+                "the lifetime must be one of the lifetimes of the struct",
+            ));
+        }
+        return Ok(Some(lifetime.clone()));
+    }
+
+    struct Collector<'a> {
+        lifetimes: &'a [Lifetime],
+        used: Vec<Lifetime>,
+    }
+
+    impl<'ast> Visit<'ast> for Collector<'_> {
+        fn visit_lifetime(&mut self, lifetime: &'ast Lifetime) {
+            if self.lifetimes.contains(lifetime) && !self.used.contains(lifetime) {
+                self.used.push(lifetime.clone());
+            }
+        }
+    }
+
+    let mut collector = Collector {
+        lifetimes,
+        used: Vec::new(),
+    };
+    collector.visit_type(&field.ty);
+
+    match collector.used.len() {
+        0 => Ok(None),
+        1 => Ok(collector.used.pop()),
+        _ => Err(syn::Error::new_spanned(
+            &field.ty,
+            "the field uses multiple lifetimes of the struct, specify the one it is parsed with using `#[someip(lifetime = 'a)]`",
+        )),
+    }
+}
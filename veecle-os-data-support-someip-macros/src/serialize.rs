@@ -2,6 +2,9 @@ use proc_macro::TokenStream;
 use proc_macro2::Span;
 use quote::quote_spanned;
 use syn::DeriveInput;
+use syn::ext::IdentExt;
+
+use crate::attributes::{Bitmask, FieldAttributes, derived_fields};
 
 /// Implementation of the `Serialize` derive macro.
 pub fn impl_derive_serialize(derive_input: DeriveInput) -> syn::Result<TokenStream> {
@@ -17,6 +20,9 @@ pub fn impl_derive_serialize(derive_input: DeriveInput) -> syn::Result<TokenStre
     let struct_name = &derive_input.ident;
     let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
 
+    let bitmask = Bitmask::from_attributes(&derive_input.attrs)?;
+    let derived_fields = derived_fields(&data_struct.fields, bitmask.as_ref())?;
+
     // ZST
     if data_struct.fields.is_empty() {
         return Ok(quote_spanned! { Span::mixed_site() =>
@@ -50,23 +56,103 @@ pub fn impl_derive_serialize(derive_input: DeriveInput) -> syn::Result<TokenStre
                 })
         })
         .collect::<Vec<_>>();
-    let field_types = data_struct
-        .fields
+
+    let (field_lengths, field_serializers): (Vec<_>, Vec<_>) = derived_fields
         .iter()
-        .map(|field| &field.ty)
-        .collect::<Vec<_>>();
+        .zip(&field_names)
+        .map(|(derived_field, field_name)| {
+            // Names the field in `SerializeError::FieldTooLong`.
+            let field_name_string = match field_name {
+                syn::Member::Named(identifier) => identifier.unraw().to_string(),
+                syn::Member::Unnamed(index) => index.index.to_string(),
+            };
+            match derived_field.optional {
+                Some((_, inner)) => (
+                    quote_spanned! { Span::mixed_site() =>
+                        self.#field_name.as_ref().map_or(0, <#inner as #veecle_os_data_support_someip::serialize::Serialize>::required_length)
+                    },
+                    quote_spanned! { Span::mixed_site() =>
+                        if let Some(value) = &self.#field_name {
+                            <#inner as #veecle_os_data_support_someip::serialize::Serialize>::serialize_partial(value, writer)
+                                .map_err(|error| error.in_field(#field_name_string))?;
+                        }
+                    },
+                ),
+                None => {
+                    let field_type = &derived_field.field.ty;
+                    let FieldAttributes {
+                        reserved_before,
+                        reserved_after,
+                        ..
+                    } = derived_field.attributes;
+                    let write_reserved = |length: usize| {
+                        (length != 0).then(|| {
+                            quote_spanned! { Span::mixed_site() =>
+                                writer.write_slice(&[0; #length])?;
+                            }
+                        })
+                    };
+                    let (write_before, write_after) =
+                        (write_reserved(reserved_before), write_reserved(reserved_after));
+                    (
+                        quote_spanned! { Span::mixed_site() =>
+                            #reserved_before
+                                + <#field_type as #veecle_os_data_support_someip::serialize::Serialize>::required_length(&self.#field_name)
+                                + #reserved_after
+                        },
+                        quote_spanned! { Span::mixed_site() =>
+                            #write_before
+                            <#field_type as #veecle_os_data_support_someip::serialize::Serialize>::serialize_partial(&self.#field_name, writer)
+                                .map_err(|error| error.in_field(#field_name_string))?;
+                            #write_after
+                        },
+                    )
+                }
+            }
+        })
+        .unzip();
+
+    let (bitmask_length, serialize_bitmask) = match bitmask {
+        Some(Bitmask { ty, .. }) => {
+            let present_bits = derived_fields.iter().zip(&field_names).filter_map(
+                |(derived_field, field_name)| {
+                    let (bit, _) = derived_field.optional?;
+                    Some(quote_spanned! { Span::mixed_site() =>
+                        if self.#field_name.is_some() {
+                            bitmask |= 1 << #bit;
+                        }
+                    })
+                },
+            );
+
+            (
+                quote_spanned! { Span::mixed_site() =>
+                    <#ty as #veecle_os_data_support_someip::serialize::Serialize>::required_length(&0),
+                },
+                quote_spanned! { Span::mixed_site() =>
+                    let mut bitmask: #ty = 0;
+                    #(#present_bits)*
+                    <#ty as #veecle_os_data_support_someip::serialize::Serialize>::serialize_partial(&bitmask, writer)?;
+                },
+            )
+        }
+        None => Default::default(),
+    };
 
     Ok(quote_spanned! { Span::mixed_site() =>
         impl #impl_generics #veecle_os_data_support_someip::serialize::Serialize for #struct_name #ty_generics #where_clause {
             fn required_length(&self) -> usize {
-                [#(
-                    <#field_types as #veecle_os_data_support_someip::serialize::Serialize>::required_length(&self.#field_names),
-                )*].into_iter().sum()
+                [
+                    #bitmask_length
+                    #(#field_lengths,)*
+                ].into_iter().sum()
             }
 
             fn serialize_partial(&self, writer: &mut #veecle_os_data_support_someip::serialize::ByteWriter) -> Result<(), #veecle_os_data_support_someip::serialize::SerializeError> {
+                #serialize_bitmask
+
                 #(
-                    <#field_types as #veecle_os_data_support_someip::serialize::Serialize>::serialize_partial(&self.#field_names, writer)?;
+                    #field_serializers
                 )*
 
                 Ok(())
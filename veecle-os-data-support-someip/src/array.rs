@@ -117,6 +117,38 @@ where
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a, T, L, const MAX_ELEMENTS: usize> arbitrary::Arbitrary<'a>
+    for DynamicLengthArray<'a, T, L, MAX_ELEMENTS>
+where
+    T: Parse<'a>,
+    L: LengthField,
+{
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let bytes = <&'a [u8] as arbitrary::Arbitrary<'a>>::arbitrary(u)?;
+
+        // Keep only the bytes making up complete elements, so the array parses back to the same elements.
+        let mut element_reader = ByteReader::new(bytes);
+        let mut element_count = 0;
+        let mut length = 0;
+
+        while !element_reader.is_empty()
+            && element_count < MAX_ELEMENTS
+            && T::parse_partial(&mut element_reader).is_ok()
+        {
+            element_count += 1;
+            length = bytes.len() - element_reader.len();
+        }
+
+        L::from_length(length).map_err(|_| arbitrary::Error::IncorrectFormat)?;
+
+        Ok(Self {
+            reader: ByteReader::new(&bytes[..length]),
+            _marker: PhantomData,
+        })
+    }
+}
+
 impl<T, L, const MAX_ELEMENTS: usize> Serialize for DynamicLengthArray<'_, T, L, MAX_ELEMENTS>
 where
     L: LengthField + Serialize,
@@ -131,7 +163,7 @@ where
         let length = byte_writer
             .write_counted(|byte_writer| byte_writer.write_slice(self.reader.remaining_slice()))?;
 
-        byte_writer.write_length(reserved_length, &L::from_length(length)?)
+        byte_writer.write_length(reserved_length, &L::from_field_length(length)?)
     }
 }
 
@@ -282,6 +314,31 @@ where
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a, T, L, const ELEMENT_COUNT: usize> arbitrary::Arbitrary<'a>
+    for FixedLengthArray<'a, T, L, ELEMENT_COUNT>
+where
+    T: Parse<'a>,
+{
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let bytes = <&'a [u8] as arbitrary::Arbitrary<'a>>::arbitrary(u)?;
+
+        // Keep only the bytes making up the elements, so the array parses back to the same elements.
+        let mut element_reader = ByteReader::new(bytes);
+
+        for _ in 0..ELEMENT_COUNT {
+            T::parse_partial(&mut element_reader).map_err(|_| arbitrary::Error::IncorrectFormat)?;
+        }
+
+        let length = bytes.len() - element_reader.len();
+
+        Ok(Self {
+            reader: ByteReader::new(&bytes[..length]),
+            _marker: PhantomData,
+        })
+    }
+}
+
 impl<T, L, const ELEMENT_COUNT: usize> Serialize for FixedLengthArray<'_, T, L, ELEMENT_COUNT>
 where
     L: LengthField + Serialize,
@@ -291,7 +348,7 @@ where
     }
 
     fn serialize_partial(&self, byte_writer: &mut ByteWriter) -> Result<(), SerializeError> {
-        L::from_length(ELEMENT_COUNT)?.serialize_partial(byte_writer)?;
+        L::from_field_length(ELEMENT_COUNT)?.serialize_partial(byte_writer)?;
         byte_writer.write_slice(self.reader.remaining_slice())
     }
 }
@@ -441,10 +498,10 @@ mod dynamic_length_array {
             DynamicLengthArray::<'_, u8, u8, 512>::create(TEST_ELEMENTS.iter(), &mut buffer)
                 .unwrap();
 
-        assert_eq!(
+        assert!(matches!(
             array.serialize(&mut [0; 512]),
-            Err(SerializeError::LengthOverflow)
-        );
+            Err(SerializeError::FieldTooLong { max: 255, .. })
+        ));
     }
 
     #[test]
@@ -617,10 +674,10 @@ mod fixed_length_array {
         let array =
             FixedLengthArray::<'_, u8, u8, 256>::create(TEST_ELEMENTS.iter(), &mut buffer).unwrap();
 
-        assert_eq!(
+        assert!(matches!(
             array.serialize(&mut [0; 512]),
-            Err(SerializeError::LengthOverflow)
-        );
+            Err(SerializeError::FieldTooLong { max: 255, .. })
+        ));
     }
 
     #[test]
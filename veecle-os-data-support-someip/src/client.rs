@@ -0,0 +1,180 @@
+//! Client side helpers for SOME/IP request/response communication.
+
+use crate::header::{ClientId, Header, InterfaceVersion, MessageId, RequestId, SessionId};
+
+/// Generates the [`SessionId`]s of consecutive requests.
+///
+/// Session IDs start at `1` and wrap from `0xFFFF` back to `1`. `0` signals that session handling is disabled, so it
+/// is skipped when wrapping; a generator [seeded](Self::starting_at) with `0` is disabled and always returns `0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionIdGenerator {
+    next: SessionId,
+}
+
+impl Default for SessionIdGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionIdGenerator {
+    /// Creates a new generator starting at session ID `1`.
+    pub fn new() -> Self {
+        Self::starting_at(SessionId::from(1))
+    }
+
+    /// Creates a new generator starting at `session_id`, e.g. to continue a sequence after a restart.
+    pub fn starting_at(session_id: SessionId) -> Self {
+        Self { next: session_id }
+    }
+
+    /// Creates a new generator with session handling disabled, always returning session ID `0`.
+    pub fn disabled() -> Self {
+        Self::starting_at(SessionId::from(0))
+    }
+
+    /// Returns whether session handling is enabled, i.e. the generated session IDs are not `0`.
+    pub fn is_enabled(&self) -> bool {
+        u16::from(self.next) != 0
+    }
+
+    /// Returns the session ID the next request will use, without advancing the generator.
+    pub fn peek(&self) -> SessionId {
+        self.next
+    }
+
+    /// Returns the session ID for the next request and advances the generator.
+    pub fn next_session_id(&mut self) -> SessionId {
+        let session_id = self.next;
+        self.next = session_id.next();
+        session_id
+    }
+}
+
+/// A SOME/IP client, creating the headers of its requests with consecutive session IDs.
+///
+/// A response belongs to a request if their [`RequestId`]s are equal, so keeping the [`Header::request_id`] of a
+/// sent request allows correlating it with its response.
+#[derive(Debug, Clone)]
+pub struct Client {
+    client_id: ClientId,
+    session_ids: SessionIdGenerator,
+}
+
+impl Client {
+    /// Creates a new client with the given ID, starting at session ID `1`.
+    pub fn new(client_id: ClientId) -> Self {
+        Self::with_session_ids(client_id, SessionIdGenerator::new())
+    }
+
+    /// Creates a new client with the given ID, taking session IDs from `session_ids`.
+    pub fn with_session_ids(client_id: ClientId, session_ids: SessionIdGenerator) -> Self {
+        Self {
+            client_id,
+            session_ids,
+        }
+    }
+
+    /// Returns the [`ClientId`].
+    pub fn client_id(&self) -> ClientId {
+        self.client_id
+    }
+
+    /// Returns the [`SessionIdGenerator`] used for requests.
+    pub fn session_ids(&self) -> &SessionIdGenerator {
+        &self.session_ids
+    }
+
+    /// Returns the [`SessionIdGenerator`] used for requests mutably, e.g. to re-seed it.
+    pub fn session_ids_mut(&mut self) -> &mut SessionIdGenerator {
+        &mut self.session_ids
+    }
+
+    /// Returns the [`RequestId`] for the next request, advancing the session ID.
+    pub fn next_request_id(&mut self) -> RequestId {
+        RequestId::new(self.client_id, self.session_ids.next_session_id())
+    }
+
+    /// Creates the [`Header`] for a new request expecting a response, see [`Header::request`].
+    pub fn request(
+        &mut self,
+        message_id: MessageId,
+        interface_version: InterfaceVersion,
+    ) -> Header {
+        Header::request(message_id, self.next_request_id(), interface_version)
+    }
+
+    /// Creates the [`Header`] for a new fire&forget request, see [`Header::request_no_return`].
+    pub fn request_no_return(
+        &mut self,
+        message_id: MessageId,
+        interface_version: InterfaceVersion,
+    ) -> Header {
+        Header::request_no_return(message_id, self.next_request_id(), interface_version)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::{Client, SessionIdGenerator};
+    use crate::header::{
+        ClientId, InterfaceVersion, MessageId, MethodId, ReturnCode, ServiceId, SessionId,
+    };
+
+    #[test]
+    fn session_ids() {
+        let mut generator = SessionIdGenerator::new();
+        assert!(generator.is_enabled());
+        assert_eq!(generator.peek(), SessionId::from(1));
+        assert_eq!(generator.next_session_id(), SessionId::from(1));
+        assert_eq!(generator.next_session_id(), SessionId::from(2));
+        assert_eq!(generator.peek(), SessionId::from(3));
+    }
+
+    #[test]
+    fn session_ids_skip_zero_on_wrap() {
+        let mut generator = SessionIdGenerator::starting_at(SessionId::from(0xFFFE));
+        assert_eq!(generator.next_session_id(), SessionId::from(0xFFFE));
+        assert_eq!(generator.next_session_id(), SessionId::from(0xFFFF));
+        assert_eq!(generator.next_session_id(), SessionId::from(1));
+    }
+
+    #[test]
+    fn session_ids_disabled() {
+        let mut generator = SessionIdGenerator::disabled();
+        assert!(!generator.is_enabled());
+        assert_eq!(generator.next_session_id(), SessionId::from(0));
+        assert_eq!(generator.next_session_id(), SessionId::from(0));
+        assert_eq!(
+            generator,
+            SessionIdGenerator::starting_at(SessionId::from(0))
+        );
+    }
+
+    #[test]
+    fn client_requests() {
+        let client_id = ClientId::new(1.into(), 2.into());
+        let message_id = MessageId::new(ServiceId::from(3), MethodId::from(4));
+        let mut client = Client::new(client_id);
+
+        let first = client.request(message_id, InterfaceVersion::from(1));
+        let second = client.request_no_return(message_id, InterfaceVersion::from(1));
+
+        assert_eq!(first.request_id().client_id(), client_id);
+        assert_eq!(first.request_id().session_id(), SessionId::from(1));
+        assert_eq!(second.request_id().session_id(), SessionId::from(2));
+        assert_eq!(client.session_ids().peek(), SessionId::from(3));
+
+        let response = first.response(ReturnCode::Ok).unwrap();
+        assert_eq!(response.request_id(), first.request_id());
+        assert_ne!(response.request_id(), second.request_id());
+
+        *client.session_ids_mut() = SessionIdGenerator::starting_at(SessionId::from(0xFFFF));
+        assert_eq!(
+            client.next_request_id().session_id(),
+            SessionId::from(0xFFFF)
+        );
+        assert_eq!(client.next_request_id().session_id(), SessionId::from(1));
+    }
+}
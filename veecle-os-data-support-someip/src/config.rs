@@ -0,0 +1,345 @@
+//! Parsing of the services, instances and endpoints from a [vsomeip configuration].
+//!
+//! Only the subset needed to set up service discovery and routing is read, other settings (e.g. `logging` or
+//! `routing`) are ignored.
+//! Like vsomeip, IDs are parsed as hexadecimal if they start with `0x` and as decimal otherwise.
+//!
+//! [vsomeip configuration]: https://github.com/COVESA/vsomeip/blob/master/documentation/vsomeipConfiguration.md
+//!
+//! # Examples
+//!
+//! ```rust
+//! use veecle_os_data_support_someip::config::{Protocol, ServiceConfig};
+//! use veecle_os_data_support_someip::header::ServiceId;
+//!
+//! let config = ServiceConfig::from_json(
+//!     r#"{
+//!         "unicast": "10.0.2.15",
+//!         "services": [
+//!             { "service": "0x1234", "instance": "0x5678", "unreliable": "30509" }
+//!         ],
+//!         "service-discovery": { "enable": "true", "multicast": "224.244.224.245" }
+//!     }"#,
+//! )?;
+//!
+//! assert_eq!(config.services[0].service_id, ServiceId::from(0x1234));
+//! assert_eq!(config.services[0].unreliable_port, Some(30509));
+//! assert_eq!(config.service_discovery.port, 30490);
+//! assert_eq!(config.service_discovery.protocol, Protocol::Udp);
+//! # Ok::<(), veecle_os_data_support_someip::config::ConfigError>(())
+//! ```
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::net::{IpAddr, Ipv4Addr};
+use core::str::FromStr;
+
+use serde::Deserialize;
+
+use crate::header::ServiceId;
+
+/// An error while parsing a vsomeip configuration.
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    /// The configuration is not valid JSON or has an unexpected structure.
+    #[error("invalid vsomeip configuration: {0}")]
+    Json(serde_json::Error),
+
+    /// A setting has a value that cannot be parsed.
+    #[error("invalid value `{value}` for `{setting}`")]
+    InvalidValue {
+        /// The name of the setting, e.g. `service`.
+        setting: &'static str,
+        /// The value that failed to parse.
+        value: String,
+    },
+}
+
+/// The services and service discovery settings of a vsomeip configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceConfig {
+    /// The unicast address of this host, if configured.
+    pub unicast: Option<IpAddr>,
+
+    /// The offered service instances.
+    pub services: Vec<Service>,
+
+    /// The service discovery settings, with the vsomeip defaults for settings that are not configured.
+    pub service_discovery: ServiceDiscovery,
+}
+
+/// A service instance and the ports it is offered on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Service {
+    /// The ID of the service.
+    pub service_id: ServiceId,
+
+    /// The ID of the service instance.
+    pub instance_id: u16,
+
+    /// The TCP port of the instance, if it is offered reliably.
+    pub reliable_port: Option<u16>,
+
+    /// The UDP port of the instance, if it is offered unreliably.
+    pub unreliable_port: Option<u16>,
+}
+
+/// Service discovery settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServiceDiscovery {
+    /// Whether service discovery is enabled, defaults to `true`.
+    pub enabled: bool,
+
+    /// The multicast address service discovery messages are sent to, defaults to `224.224.224.0`.
+    pub multicast: IpAddr,
+
+    /// The port service discovery messages are sent to, defaults to `30490`.
+    pub port: u16,
+
+    /// The transport protocol used for service discovery, defaults to [`Protocol::Udp`].
+    pub protocol: Protocol,
+}
+
+impl Default for ServiceDiscovery {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            multicast: IpAddr::V4(Ipv4Addr::new(224, 224, 224, 0)),
+            port: 30490,
+            protocol: Protocol::Udp,
+        }
+    }
+}
+
+/// A transport protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    /// User Datagram Protocol.
+    Udp,
+    /// Transmission Control Protocol.
+    Tcp,
+}
+
+impl ServiceConfig {
+    /// Parses the services and service discovery settings from a vsomeip JSON configuration.
+    pub fn from_json(json: &str) -> Result<Self, ConfigError> {
+        let raw: RawConfig = serde_json::from_str(json).map_err(ConfigError::Json)?;
+
+        let unicast = raw
+            .unicast
+            .map(|unicast| parse("unicast", &unicast, IpAddr::from_str))
+            .transpose()?;
+
+        let services = raw
+            .services
+            .into_iter()
+            .map(|service| {
+                Ok(Service {
+                    service_id: ServiceId::from(parse("service", &service.service, parse_id)?),
+                    instance_id: parse("instance", &service.instance, parse_id)?,
+                    reliable_port: service
+                        .reliable
+                        .map(|reliable| parse("reliable", reliable.port(), u16::from_str))
+                        .transpose()?,
+                    unreliable_port: service
+                        .unreliable
+                        .map(|unreliable| parse("unreliable", &unreliable, u16::from_str))
+                        .transpose()?,
+                })
+            })
+            .collect::<Result<_, ConfigError>>()?;
+
+        let mut service_discovery = ServiceDiscovery::default();
+        if let Some(raw) = raw.service_discovery {
+            if let Some(enable) = raw.enable {
+                service_discovery.enabled = parse("enable", &enable, bool::from_str)?;
+            }
+            if let Some(multicast) = raw.multicast {
+                service_discovery.multicast = parse("multicast", &multicast, IpAddr::from_str)?;
+            }
+            if let Some(port) = raw.port {
+                service_discovery.port = parse("port", &port, u16::from_str)?;
+            }
+            if let Some(protocol) = raw.protocol {
+                service_discovery.protocol = match protocol.as_str() {
+                    "udp" => Protocol::Udp,
+                    "tcp" => Protocol::Tcp,
+                    _ => {
+                        return Err(ConfigError::InvalidValue {
+                            setting: "protocol",
+                            value: protocol,
+                        });
+                    }
+                };
+            }
+        }
+
+        Ok(Self {
+            unicast,
+            services,
+            service_discovery,
+        })
+    }
+}
+
+/// Parses `value` of `setting` with `f`, reporting failures as [`ConfigError::InvalidValue`].
+fn parse<T, E>(
+    setting: &'static str,
+    value: &str,
+    f: impl FnOnce(&str) -> Result<T, E>,
+) -> Result<T, ConfigError> {
+    f(value).map_err(|_| ConfigError::InvalidValue {
+        setting,
+        value: value.to_string(),
+    })
+}
+
+/// Parses an ID as hexadecimal if it starts with `0x`, otherwise as decimal.
+fn parse_id(value: &str) -> Result<u16, core::num::ParseIntError> {
+    match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => u16::from_str_radix(hex, 16),
+        None => value.parse(),
+    }
+}
+
+/// The subset of the vsomeip configuration that is read, all values are strings in vsomeip configurations.
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RawConfig {
+    unicast: Option<String>,
+    #[serde(default)]
+    services: Vec<RawService>,
+    service_discovery: Option<RawServiceDiscovery>,
+}
+
+#[derive(Deserialize)]
+struct RawService {
+    service: String,
+    instance: String,
+    reliable: Option<RawReliable>,
+    unreliable: Option<String>,
+}
+
+/// The reliable endpoint is either just the port or an object with further TCP settings.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawReliable {
+    Port(String),
+    Endpoint { port: String },
+}
+
+impl RawReliable {
+    fn port(&self) -> &str {
+        match self {
+            Self::Port(port) | Self::Endpoint { port } => port,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RawServiceDiscovery {
+    enable: Option<String>,
+    multicast: Option<String>,
+    port: Option<String>,
+    protocol: Option<String>,
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use core::net::{IpAddr, Ipv4Addr};
+
+    use super::{ConfigError, Protocol, Service, ServiceConfig, ServiceDiscovery};
+    use crate::header::ServiceId;
+
+    #[test]
+    fn parses_services_and_service_discovery() {
+        let config = ServiceConfig::from_json(
+            r#"{
+                "unicast": "10.0.2.15",
+                "network": "vsomeip",
+                "logging": { "level": "trace" },
+                "services": [
+                    { "service": "1234", "instance": "5678", "unreliable": "30509" },
+                    {
+                        "service": "0x1235",
+                        "instance": "0x0001",
+                        "reliable": { "port": "30510", "enable-magic-cookies": "false" }
+                    }
+                ],
+                "service-discovery": {
+                    "enable": "false",
+                    "multicast": "224.244.224.245",
+                    "port": "30491",
+                    "protocol": "tcp"
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config,
+            ServiceConfig {
+                unicast: Some(IpAddr::V4(Ipv4Addr::new(10, 0, 2, 15))),
+                services: alloc::vec![
+                    Service {
+                        service_id: ServiceId::from(1234),
+                        instance_id: 5678,
+                        reliable_port: None,
+                        unreliable_port: Some(30509),
+                    },
+                    Service {
+                        service_id: ServiceId::from(0x1235),
+                        instance_id: 1,
+                        reliable_port: Some(30510),
+                        unreliable_port: None,
+                    },
+                ],
+                service_discovery: ServiceDiscovery {
+                    enabled: false,
+                    multicast: IpAddr::V4(Ipv4Addr::new(224, 244, 224, 245)),
+                    port: 30491,
+                    protocol: Protocol::Tcp,
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn defaults() {
+        let config = ServiceConfig::from_json("{}").unwrap();
+
+        assert_eq!(config.unicast, None);
+        assert!(config.services.is_empty());
+        assert_eq!(config.service_discovery, ServiceDiscovery::default());
+    }
+
+    #[test]
+    fn invalid_values() {
+        let error =
+            ServiceConfig::from_json(r#"{ "services": [{ "service": "0xZZ", "instance": "1" }] }"#)
+                .unwrap_err();
+        assert!(matches!(
+            error,
+            ConfigError::InvalidValue { setting: "service", ref value } if value == "0xZZ"
+        ));
+
+        let error = ServiceConfig::from_json(r#"{ "service-discovery": { "protocol": "sctp" } }"#)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            ConfigError::InvalidValue {
+                setting: "protocol",
+                ..
+            }
+        ));
+
+        assert!(matches!(
+            ServiceConfig::from_json("[]"),
+            Err(ConfigError::Json(_))
+        ));
+    }
+}
@@ -0,0 +1,77 @@
+//! Round-trip fuzzing of SOME/IP payload types.
+//!
+//! With the `arbitrary` feature enabled, the SOME/IP types of this crate implement [`arbitrary::Arbitrary`]. Types
+//! using the [`Parse`] and [`Serialize`] derives can additionally derive [`arbitrary::Arbitrary`] (requires a
+//! dependency on `arbitrary` with the `derive` feature), which allows checking the whole message definition for
+//! panics and mismatches between serializing and parsing with [`assert_round_trip`]:
+//!
+//! ```rust
+//! use veecle_os_data_support_someip::fuzz::assert_round_trip;
+//! use veecle_os_data_support_someip::parse::Parse;
+//! use veecle_os_data_support_someip::serialize::Serialize;
+//! use veecle_os_data_support_someip::string::DynamicLengthString;
+//!
+//! #[derive(Debug, PartialEq, Parse, Serialize, arbitrary::Arbitrary)]
+//! struct Message<'a> {
+//!     id: u16,
+//!     name: DynamicLengthString<'a, u32>,
+//! }
+//!
+//! let data = [0x12, 0x34, 0x00, b'a', b'b', b'c'];
+//! let mut buffer = [0; 64];
+//!
+//! assert_round_trip::<Message>(&data, &mut buffer).unwrap();
+//! ```
+//!
+//! The same function can be used as the body of a fuzz target, e.g. with `cargo fuzz`:
+//!
+//! ```rust,ignore
+//! libfuzzer_sys::fuzz_target!(|data: &[u8]| {
+//!     let mut buffer = [0; 2048];
+//!     let _ = assert_round_trip::<Message>(data, &mut buffer);
+//! });
+//! ```
+
+use core::fmt::Debug;
+
+use arbitrary::{Arbitrary, Unstructured};
+
+use crate::parse::{Parse, ParseExt};
+use crate::serialize::{Serialize, SerializeError, SerializeExt};
+
+/// Generates a `T` from `data`, serializes it into `buffer`, parses it back and asserts that the result is equal.
+///
+/// Returns an error if no `T` could be generated from `data`. Values that don't fit into `buffer` are skipped.
+///
+/// # Panics
+///
+/// If serializing the generated value fails for any reason other than the buffer being too small, if the number of
+/// written bytes doesn't match [`Serialize::required_length`], if parsing the serialized bytes fails or if the
+/// parsed value differs from the generated one.
+pub fn assert_round_trip<'a, T>(data: &'a [u8], buffer: &'a mut [u8]) -> arbitrary::Result<()>
+where
+    T: Arbitrary<'a> + Parse<'a> + Serialize + PartialEq + Debug,
+{
+    let value = T::arbitrary_take_rest(Unstructured::new(data))?;
+
+    let written = match value.serialize(buffer) {
+        Ok(written) => written,
+        Err(SerializeError::BufferTooSmall) => return Ok(()),
+        Err(error) => panic!("failed to serialize {value:?}: {error}"),
+    };
+
+    assert_eq!(
+        written,
+        value.required_length(),
+        "required length does not match the serialized length of {value:?}"
+    );
+
+    let serialized: &'a [u8] = &buffer[..written];
+
+    match T::parse(serialized) {
+        Ok(parsed) => assert_eq!(parsed, value),
+        Err(error) => panic!("failed to parse {value:?} from {serialized:02x?}: {error}"),
+    }
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 //! SOME/IP header de-/serialization.
 
-use crate::parse::{ByteReader, Parse, ParseError};
+use crate::parse::{ByteReader, Parse, ParseError, ParseExt};
 use crate::serialize::{ByteWriter, Serialize, SerializeError};
 
 /// Creates a new type wrapping a primitive. The new type implements conversion from and to the primitive as well as
@@ -12,6 +12,8 @@ macro_rules! create_new_type {
     ) => {
         $(#[$($attributes)*])*
         #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        #[cfg_attr(feature = "serde", serde(transparent))]
         pub struct $name($inner);
 
         impl From<$name> for $inner {
@@ -26,6 +28,13 @@ macro_rules! create_new_type {
             }
         }
 
+        #[cfg(feature = "arbitrary")]
+        impl<'a> arbitrary::Arbitrary<'a> for $name {
+            fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+                u.arbitrary().map($name)
+            }
+        }
+
         impl<'a> Parse<'a> for $name {
             fn parse_partial(reader: &mut ByteReader<'a>) -> Result<Self, ParseError> {
                 Parse::parse_partial(reader).map($name)
@@ -56,11 +65,19 @@ create_new_type! {
 
 /// SOME/IP message ID.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Parse, Serialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MessageId {
     service_id: ServiceId,
     method_id: MethodId,
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for MessageId {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self::new(u.arbitrary()?, u.arbitrary()?))
+    }
+}
+
 impl MessageId {
     /// Creates a new message ID.
     pub fn new(service_id: ServiceId, method_id: MethodId) -> Self {
@@ -100,11 +117,41 @@ impl Length {
     // Header fields included in the length.
     const REMAINING_HEADER_SIZE: u32 = 8;
 
-    /// Calculates the length of the payload, not including any of the header.
+    // The length of a message without payload.
+    const EMPTY_PAYLOAD: Self = Self(Self::REMAINING_HEADER_SIZE);
+
+    /// Calculates the length field for a payload of `length` bytes, not including any of the header.
+    ///
+    /// Returns [`SerializeError::LengthOverflow`] if the payload is too long for the length field.
+    ///
+    /// This does not take E2E protection into account.
+    pub fn from_payload_length(length: u32) -> Result<Self, SerializeError> {
+        Self::REMAINING_HEADER_SIZE
+            .checked_add(length)
+            .map(Self)
+            .ok_or(SerializeError::LengthOverflow)
+    }
+
+    /// Calculates the length field for a payload of `length` bytes.
+    ///
+    /// Returns [`SerializeError::LengthOverflow`] instead of wrapping around if the payload is too long for the
+    /// length field, e.g. on 64-bit targets or if a payload length was computed from malformed input.
     ///
     /// This does not take E2E protection into account.
-    pub fn from_payload_length(length: u32) -> Self {
-        Self(length + Self::REMAINING_HEADER_SIZE)
+    pub fn try_from_usize(length: usize) -> Result<Self, SerializeError> {
+        Self::EMPTY_PAYLOAD.checked_add(length)
+    }
+
+    /// Adds `length` bytes of payload to the length.
+    ///
+    /// This allows accumulating the length of a payload serialized in parts. Returns
+    /// [`SerializeError::LengthOverflow`] if the sum doesn't fit the length field.
+    pub fn checked_add(self, length: usize) -> Result<Self, SerializeError> {
+        u32::try_from(length)
+            .ok()
+            .and_then(|length| self.0.checked_add(length))
+            .map(Self)
+            .ok_or(SerializeError::LengthOverflow)
     }
 
     /// Calculates the length of the payload, not including any of the header.
@@ -127,11 +174,19 @@ create_new_type! {
 
 /// SOME/IP client ID.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Parse, Serialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ClientId {
     prefix: Prefix,
     id: ClientIdInner,
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for ClientId {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self::new(u.arbitrary()?, u.arbitrary()?))
+    }
+}
+
 impl ClientId {
     /// Creates a new client ID.
     pub fn new(prefix: Prefix, id: ClientIdInner) -> Self {
@@ -181,11 +236,19 @@ impl SessionId {
 
 /// SOME/IP request ID.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Parse, Serialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RequestId {
     client_id: ClientId,
     session_id: SessionId,
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for RequestId {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self::new(u.arbitrary()?, u.arbitrary()?))
+    }
+}
+
 impl RequestId {
     /// Creates a new message ID.
     pub fn new(client_id: ClientId, session_id: SessionId) -> Self {
@@ -221,6 +284,11 @@ create_new_type! {
     pub struct ProtocolVersion(u8);
 }
 
+impl ProtocolVersion {
+    /// The protocol version implemented by this crate.
+    pub const CURRENT: Self = Self(0x01);
+}
+
 create_new_type! {
     /// SOME/IP interface version.
     pub struct InterfaceVersion(u8);
@@ -228,6 +296,7 @@ create_new_type! {
 
 /// SOME/IP message type version.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MessageType {
     /// A request expecting a response (even void).
     Request,
@@ -251,6 +320,55 @@ pub enum MessageType {
     TpError,
 }
 
+impl MessageType {
+    /// Returns `true` for requests the receiver must answer with a response or error.
+    ///
+    /// Fire&forget requests ([`MessageType::RequestNoReturn`]) and notifications are never answered, waiting for a
+    /// response to them would never finish.
+    pub fn is_response_expected(&self) -> bool {
+        matches!(self, Self::Request | Self::TpRequest)
+    }
+
+    /// Returns `true` for responses and errors.
+    pub fn is_response(&self) -> bool {
+        matches!(
+            self,
+            Self::Response | Self::Error | Self::TpResponse | Self::TpError
+        )
+    }
+
+    /// Returns `true` for messages segmented with SOME/IP-TP.
+    pub fn is_tp(&self) -> bool {
+        matches!(
+            self,
+            Self::TpRequest
+                | Self::TpRequestNoReturn
+                | Self::TpNotification
+                | Self::TpResponse
+                | Self::TpError
+        )
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for MessageType {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.choose(&[
+            Self::Request,
+            Self::RequestNoReturn,
+            Self::Notification,
+            Self::Response,
+            Self::Error,
+            Self::TpRequest,
+            Self::TpRequestNoReturn,
+            Self::TpNotification,
+            Self::TpResponse,
+            Self::TpError,
+        ])
+        .copied()
+    }
+}
+
 impl<'a> Parse<'a> for MessageType {
     fn parse_partial(reader: &mut ByteReader<'a>) -> Result<Self, ParseError> {
         let byte = reader.read_byte()?;
@@ -300,6 +418,7 @@ impl Serialize for MessageType {
 
 /// SOME/IP return code.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReturnCode {
     /// No error occurred.
     Ok,
@@ -339,10 +458,58 @@ pub enum ReturnCode {
     Reserved1(u8),
 }
 
-impl<'a> Parse<'a> for ReturnCode {
-    fn parse_partial(reader: &mut ByteReader<'a>) -> Result<Self, ParseError> {
-        let byte = reader.read_byte()?;
+impl ReturnCode {
+    /// Returns `true` if this is [`ReturnCode::Ok`].
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Self::Ok)
+    }
+
+    /// Converts this return code into a [`Result`], returning `Err(self)` for any code other than [`ReturnCode::Ok`].
+    ///
+    /// ```rust
+    /// use veecle_os_data_support_someip::header::ReturnCode;
+    ///
+    /// assert_eq!(ReturnCode::Ok.into_result(), Ok(()));
+    /// assert_eq!(ReturnCode::UnknownMethod.into_result(), Err(ReturnCode::UnknownMethod));
+    /// ```
+    pub fn into_result(self) -> Result<(), ReturnCode> {
+        match self {
+            Self::Ok => Ok(()),
+            error => Err(error),
+        }
+    }
+}
+
+impl core::fmt::Display for ReturnCode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Ok => f.write_str("E_OK"),
+            Self::NotOk => f.write_str("E_NOT_OK"),
+            Self::UnknownService => f.write_str("E_UNKNOWN_SERVICE"),
+            Self::UnknownMethod => f.write_str("E_UNKNOWN_METHOD"),
+            Self::NotReady => f.write_str("E_NOT_READY"),
+            Self::NotReachable => f.write_str("E_NOT_REACHABLE"),
+            Self::Timeout => f.write_str("E_TIMEOUT"),
+            Self::WrongProtocolVersion => f.write_str("E_WRONG_PROTOCOL_VERSION"),
+            Self::WrongInterfaceVersion => f.write_str("E_WRONG_INTERFACE_VERSION"),
+            Self::MalformedMessage => f.write_str("E_MALFORMED_MESSAGE"),
+            Self::WrongMessageType => f.write_str("E_WRONG_MESSAGE_TYPE"),
+            Self::E2ERepeated => f.write_str("E_E2E_REPEATED"),
+            Self::E2EWrongSequence => f.write_str("E_E2E_WRONG_SEQUENCE"),
+            Self::E2E => f.write_str("E_E2E"),
+            Self::E2ENotAvailable => f.write_str("E_E2E_NOT_AVAILABLE"),
+            Self::E2ENoNewData => f.write_str("E_E2E_NO_NEW_DATA"),
+            Self::Reserved0(byte) | Self::Reserved1(byte) => write!(f, "reserved ({byte:#04x})"),
+        }
+    }
+}
+
+impl core::error::Error for ReturnCode {}
 
+impl TryFrom<u8> for ReturnCode {
+    type Error = ParseError;
+
+    fn try_from(byte: u8) -> Result<Self, Self::Error> {
         let return_code = match byte {
             0x00 => Self::Ok,
             0x01 => Self::NotOk,
@@ -373,37 +540,56 @@ impl<'a> Parse<'a> for ReturnCode {
     }
 }
 
+impl From<ReturnCode> for u8 {
+    fn from(return_code: ReturnCode) -> u8 {
+        match return_code {
+            ReturnCode::Ok => 0x00,
+            ReturnCode::NotOk => 0x01,
+            ReturnCode::UnknownService => 0x02,
+            ReturnCode::UnknownMethod => 0x03,
+            ReturnCode::NotReady => 0x04,
+            ReturnCode::NotReachable => 0x05,
+            ReturnCode::Timeout => 0x06,
+            ReturnCode::WrongProtocolVersion => 0x07,
+            ReturnCode::WrongInterfaceVersion => 0x08,
+            ReturnCode::MalformedMessage => 0x09,
+            ReturnCode::WrongMessageType => 0x0A,
+            ReturnCode::E2ERepeated => 0x0B,
+            ReturnCode::E2EWrongSequence => 0x0C,
+            ReturnCode::E2E => 0x0D,
+            ReturnCode::E2ENotAvailable => 0x0E,
+            ReturnCode::E2ENoNewData => 0x0F,
+            ReturnCode::Reserved0(byte) => byte,
+            ReturnCode::Reserved1(byte) => byte,
+        }
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for ReturnCode {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Self::try_from(u.int_in_range(0x00..=0x5E)?).map_err(|_| arbitrary::Error::IncorrectFormat)
+    }
+}
+
+impl<'a> Parse<'a> for ReturnCode {
+    fn parse_partial(reader: &mut ByteReader<'a>) -> Result<Self, ParseError> {
+        Self::try_from(reader.read_byte()?)
+    }
+}
+
 impl Serialize for ReturnCode {
     fn required_length(&self) -> usize {
         1
     }
 
     fn serialize_partial(&self, byte_writer: &mut ByteWriter) -> Result<(), SerializeError> {
-        match self {
-            ReturnCode::Ok => byte_writer.write_byte(0x00),
-            ReturnCode::NotOk => byte_writer.write_byte(0x01),
-            ReturnCode::UnknownService => byte_writer.write_byte(0x02),
-            ReturnCode::UnknownMethod => byte_writer.write_byte(0x03),
-            ReturnCode::NotReady => byte_writer.write_byte(0x04),
-            ReturnCode::NotReachable => byte_writer.write_byte(0x05),
-            ReturnCode::Timeout => byte_writer.write_byte(0x06),
-            ReturnCode::WrongProtocolVersion => byte_writer.write_byte(0x07),
-            ReturnCode::WrongInterfaceVersion => byte_writer.write_byte(0x08),
-            ReturnCode::MalformedMessage => byte_writer.write_byte(0x09),
-            ReturnCode::WrongMessageType => byte_writer.write_byte(0x0A),
-            ReturnCode::E2ERepeated => byte_writer.write_byte(0x0B),
-            ReturnCode::E2EWrongSequence => byte_writer.write_byte(0x0C),
-            ReturnCode::E2E => byte_writer.write_byte(0x0D),
-            ReturnCode::E2ENotAvailable => byte_writer.write_byte(0x0E),
-            ReturnCode::E2ENoNewData => byte_writer.write_byte(0x0F),
-            ReturnCode::Reserved0(byte) => byte_writer.write_byte(*byte),
-            ReturnCode::Reserved1(byte) => byte_writer.write_byte(*byte),
-        }
+        byte_writer.write_byte(u8::from(*self))
     }
 }
 
 /// SOME/IP packet payload.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Payload<'a>(&'a [u8]);
 
 impl<'a> From<&'a [u8]> for Payload<'a> {
@@ -432,6 +618,7 @@ impl<'a> Payload<'a> {
 
 /// SOME/IP header.
 #[derive(Debug, Clone, PartialEq, Eq, Parse, Serialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Header {
     message_id: MessageId,
     length: Length,
@@ -442,6 +629,21 @@ pub struct Header {
     return_code: ReturnCode,
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Header {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self::new(
+            u.arbitrary()?,
+            u.arbitrary()?,
+            u.arbitrary()?,
+            u.arbitrary()?,
+            u.arbitrary()?,
+            u.arbitrary()?,
+            u.arbitrary()?,
+        ))
+    }
+}
+
 impl Header {
     /// Creates a new [`Header`].
     pub fn new(
@@ -464,6 +666,97 @@ impl Header {
         }
     }
 
+    /// Creates a new [`Header`] for a request expecting a response.
+    ///
+    /// The [`Length`] is set when serializing the header with a payload.
+    pub fn request(
+        message_id: MessageId,
+        request_id: RequestId,
+        interface_version: InterfaceVersion,
+    ) -> Self {
+        Self::with_message_type(
+            message_id,
+            request_id,
+            interface_version,
+            MessageType::Request,
+        )
+    }
+
+    /// Creates a new [`Header`] for a fire&forget request that is not answered.
+    ///
+    /// The [`Length`] is set when serializing the header with a payload.
+    pub fn request_no_return(
+        message_id: MessageId,
+        request_id: RequestId,
+        interface_version: InterfaceVersion,
+    ) -> Self {
+        Self::with_message_type(
+            message_id,
+            request_id,
+            interface_version,
+            MessageType::RequestNoReturn,
+        )
+    }
+
+    /// Creates a new [`Header`] for a notification.
+    ///
+    /// The [`Length`] is set when serializing the header with a payload.
+    pub fn notification(
+        message_id: MessageId,
+        request_id: RequestId,
+        interface_version: InterfaceVersion,
+    ) -> Self {
+        Self::with_message_type(
+            message_id,
+            request_id,
+            interface_version,
+            MessageType::Notification,
+        )
+    }
+
+    /// Creates the [`Header`] of a response to this request with the given [`ReturnCode`].
+    ///
+    /// Returns `None` if this is not a request expecting a response, see [`MessageType::is_response_expected`].
+    /// A [`ReturnCode`] other than [`ReturnCode::Ok`] results in an error response.
+    pub fn response(&self, return_code: ReturnCode) -> Option<Self> {
+        let message_type = match (self.message_type, return_code.is_ok()) {
+            (MessageType::Request, true) => MessageType::Response,
+            (MessageType::Request, false) => MessageType::Error,
+            (MessageType::TpRequest, true) => MessageType::TpResponse,
+            (MessageType::TpRequest, false) => MessageType::TpError,
+            _ => return None,
+        };
+
+        Some(Self {
+            length: Length::EMPTY_PAYLOAD,
+            message_type,
+            return_code,
+            ..self.clone()
+        })
+    }
+
+    fn with_message_type(
+        message_id: MessageId,
+        request_id: RequestId,
+        interface_version: InterfaceVersion,
+        message_type: MessageType,
+    ) -> Self {
+        Self::new(
+            message_id,
+            Length::EMPTY_PAYLOAD,
+            request_id,
+            ProtocolVersion::CURRENT,
+            interface_version,
+            message_type,
+            ReturnCode::Ok,
+        )
+    }
+
+    /// Returns `true` if the receiver must answer this message, see [`MessageType::is_response_expected`].
+    pub fn is_response_expected(&self) -> bool {
+        self.message_type.is_response_expected()
+    }
+
     /// Returns the [`MessageId`].
     pub fn message_id(&self) -> MessageId {
         self.message_id
@@ -499,6 +792,11 @@ impl Header {
         self.return_code
     }
 
+    /// Returns `Err` with the [`ReturnCode`] if it is not [`ReturnCode::Ok`], e.g. for an error response.
+    pub fn check_return_code(&self) -> Result<(), ReturnCode> {
+        self.return_code.into_result()
+    }
+
     /// Returns the [`MessageId`].
     pub fn set_message_id(&mut self, message_id: MessageId) {
         self.message_id = message_id;
@@ -544,6 +842,34 @@ impl Header {
         Ok((header, payload))
     }
 
+    /// Parses a response to a request, separating errors returned by the remote service from parsing errors.
+    ///
+    /// Error messages ([`MessageType::Error`] and [`MessageType::TpError`]) and responses with a [`ReturnCode`] other
+    /// than [`ReturnCode::Ok`] return [`ResponseError::Service`] with the return code and the unparsed payload, which
+    /// may contain application specific error data. Otherwise, the payload is parsed as `T`.
+    pub fn parse_response<'a, T>(bytes: &'a [u8]) -> Result<(Header, T), ResponseError<'a>>
+    where
+        T: Parse<'a>,
+    {
+        let (header, payload) = Self::parse_with_payload(bytes)?;
+
+        let is_error = matches!(
+            header.message_type,
+            MessageType::Error | MessageType::TpError
+        );
+
+        if is_error || !header.return_code.is_ok() {
+            return Err(ResponseError::Service {
+                return_code: header.return_code,
+                payload,
+            });
+        }
+
+        let value = T::parse(payload.into_inner())?;
+
+        Ok((header, value))
+    }
+
     /// Serializes the header and the payload into one packet.
     pub fn serialize_with_payload<'a>(
         &mut self,
@@ -552,7 +878,7 @@ impl Header {
     ) -> Result<&'a [u8], SerializeError> {
         let mut byte_writer = ByteWriter::new(buffer);
 
-        self.length = Length::from_payload_length(payload.as_ref().len() as u32);
+        self.length = Length::try_from_usize(payload.as_ref().len())?;
 
         let written = byte_writer.write_counted(|byte_writer| {
             self.serialize_partial(byte_writer)?;
@@ -570,7 +896,7 @@ impl Header {
     ) -> Result<&'a [u8], SerializeError> {
         let mut byte_writer = ByteWriter::new(buffer);
 
-        self.length = Length::from_payload_length(payload.required_length() as u32);
+        self.length = Length::try_from_usize(payload.required_length())?;
 
         let written = byte_writer.write_counted(|byte_writer| {
             self.serialize_partial(byte_writer)?;
@@ -581,6 +907,34 @@ impl Header {
     }
 }
 
+/// An error receiving a response, see [`Header::parse_response`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum ResponseError<'a> {
+    /// The response could not be parsed.
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    /// The remote service answered with an error.
+    #[error("the service returned {return_code}")]
+    Service {
+        /// The [`ReturnCode`] of the error message.
+        return_code: ReturnCode,
+        /// The application specific error payload, may be empty.
+        payload: Payload<'a>,
+    },
+}
+
+impl ResponseError<'_> {
+    /// Returns the [`ReturnCode`] describing this error.
+    ///
+    /// Parsing errors are reported as [`ReturnCode::MalformedMessage`].
+    pub fn return_code(&self) -> ReturnCode {
+        match self {
+            Self::Parse(error) => error.return_code(),
+            Self::Service { return_code, .. } => *return_code,
+        }
+    }
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -589,7 +943,7 @@ mod tests {
 
     use super::{
         ClientId, Header, InterfaceVersion, Length, MessageId, MessageType, MethodId, Payload,
-        ProtocolVersion, RequestId, ReturnCode, ServiceId, SessionId,
+        ProtocolVersion, RequestId, ResponseError, ReturnCode, ServiceId, SessionId,
     };
     use crate::header::{ClientIdInner, Prefix};
     use crate::parse::{Parse, ParseError, ParseExt};
@@ -635,6 +989,39 @@ mod tests {
         test_round_trip!(Header, header, EXPECTED_DATA);
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_json_round_trip() {
+        let header = Header {
+            message_id: MessageId::new(ServiceId(0x1234), MethodId(0x5678)),
+            length: Length(8),
+            request_id: RequestId::new(ClientId::new(1.into(), 2.into()), SessionId(3)),
+            protocol_version: ProtocolVersion(1),
+            interface_version: InterfaceVersion(2),
+            message_type: MessageType::Response,
+            return_code: ReturnCode::Reserved0(0x10),
+        };
+
+        let json = serde_json::to_value(&header).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "message_id": { "service_id": 0x1234, "method_id": 0x5678 },
+                "length": 8,
+                "request_id": {
+                    "client_id": { "prefix": 1, "id": 2 },
+                    "session_id": 3,
+                },
+                "protocol_version": 1,
+                "interface_version": 2,
+                "message_type": "Response",
+                "return_code": { "Reserved0": 16 },
+            })
+        );
+
+        assert_eq!(serde_json::from_value::<Header>(json).unwrap(), header);
+    }
+
     #[test]
     fn parse_with_payload_cut_off() {
         for cut_off in 0..16 {
@@ -675,6 +1062,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn length_checked_arithmetic() {
+        assert_eq!(Length::try_from_usize(5), Length::from_payload_length(5));
+        assert_eq!(
+            Length::try_from_usize(5).and_then(|length| length.checked_add(3)),
+            Length::from_payload_length(8)
+        );
+
+        let max_payload_length = (u32::MAX - 8) as usize;
+        assert_eq!(
+            Length::try_from_usize(max_payload_length).map(|length| length.payload_length()),
+            Ok(u32::MAX - 8)
+        );
+        assert_eq!(
+            Length::try_from_usize(max_payload_length + 1),
+            Err(SerializeError::LengthOverflow)
+        );
+        assert_eq!(
+            Length::from_payload_length(u32::MAX - 8).map(|length| length.payload_length()),
+            Ok(u32::MAX - 8)
+        );
+        assert_eq!(
+            Length::from_payload_length(u32::MAX - 7),
+            Err(SerializeError::LengthOverflow)
+        );
+        assert_eq!(
+            Length::EMPTY_PAYLOAD.checked_add(usize::MAX),
+            Err(SerializeError::LengthOverflow)
+        );
+    }
+
     #[test]
     fn set_header_length_field() {
         let mut header = Header {
@@ -924,6 +1342,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn return_code_conversion() {
+        for byte in 0x00..0x5F {
+            let return_code = ReturnCode::try_from(byte).unwrap();
+            assert_eq!(u8::from(return_code), byte);
+            assert_eq!(return_code.is_ok(), byte == 0x00);
+        }
+
+        assert!(ReturnCode::try_from(0x5F).is_err());
+    }
+
+    #[test]
+    fn check_return_code() {
+        let mut header = Header {
+            message_id: MessageId::new(ServiceId(1), MethodId(2)),
+            length: Length(8),
+            request_id: RequestId::new(ClientId::new(4.into(), 5.into()), SessionId(6)),
+            protocol_version: ProtocolVersion(1),
+            interface_version: InterfaceVersion(1),
+            message_type: MessageType::Response,
+            return_code: ReturnCode::Ok,
+        };
+        assert_eq!(header.check_return_code(), Ok(()));
+
+        header.set_message_type(MessageType::Error);
+        header.set_return_code(ReturnCode::UnknownService);
+        assert_eq!(header.check_return_code(), Err(ReturnCode::UnknownService));
+    }
+
     #[test]
     fn serialize_with_serializable() {
         #[derive(Debug, Parse, Serialize, Eq, PartialEq)]
@@ -996,4 +1443,101 @@ mod tests {
             Err(SerializeError::BufferTooSmall)
         );
     }
+
+    #[test]
+    fn message_type_predicates() {
+        assert!(MessageType::Request.is_response_expected());
+        assert!(MessageType::TpRequest.is_response_expected());
+        assert!(!MessageType::RequestNoReturn.is_response_expected());
+        assert!(!MessageType::TpRequestNoReturn.is_response_expected());
+        assert!(!MessageType::Notification.is_response_expected());
+        assert!(!MessageType::Response.is_response_expected());
+
+        assert!(MessageType::Error.is_response());
+        assert!(MessageType::TpResponse.is_response());
+        assert!(!MessageType::RequestNoReturn.is_response());
+
+        assert!(MessageType::TpNotification.is_tp());
+        assert!(!MessageType::Notification.is_tp());
+    }
+
+    #[test]
+    fn request_and_response() {
+        let message_id = MessageId::new(ServiceId(1), MethodId(2));
+        let request_id = RequestId::new(ClientId::new(3.into(), 4.into()), SessionId(5));
+
+        let request = Header::request(message_id, request_id, InterfaceVersion(6));
+        assert_eq!(request.message_type(), MessageType::Request);
+        assert_eq!(request.protocol_version(), ProtocolVersion::CURRENT);
+        assert!(request.is_response_expected());
+
+        let response = request.response(ReturnCode::Ok).unwrap();
+        assert_eq!(response.message_id(), message_id);
+        assert_eq!(response.request_id(), request_id);
+        assert_eq!(response.interface_version(), InterfaceVersion(6));
+        assert_eq!(response.message_type(), MessageType::Response);
+        assert!(!response.is_response_expected());
+
+        let error = request.response(ReturnCode::UnknownMethod).unwrap();
+        assert_eq!(error.message_type(), MessageType::Error);
+        assert_eq!(error.return_code(), ReturnCode::UnknownMethod);
+    }
+
+    #[test]
+    fn fire_and_forget_has_no_response() {
+        let message_id = MessageId::new(ServiceId(1), MethodId(2));
+        let request_id = RequestId::new(ClientId::new(3.into(), 4.into()), SessionId(5));
+
+        let request = Header::request_no_return(message_id, request_id, InterfaceVersion(6));
+        assert_eq!(request.message_type(), MessageType::RequestNoReturn);
+        assert!(!request.is_response_expected());
+        assert_eq!(request.response(ReturnCode::Ok), None);
+
+        let notification = Header::notification(message_id, request_id, InterfaceVersion(6));
+        assert_eq!(notification.message_type(), MessageType::Notification);
+        assert_eq!(notification.response(ReturnCode::Ok), None);
+    }
+
+    #[test]
+    fn parse_response() {
+        let message_id = MessageId::new(ServiceId(1), MethodId(2));
+        let request_id = RequestId::new(ClientId::new(3.into(), 4.into()), SessionId(5));
+        let request = Header::request(message_id, request_id, InterfaceVersion(6));
+
+        let mut buffer = [0u8; 64];
+
+        let mut response = request.response(ReturnCode::Ok).unwrap();
+        let bytes = response
+            .serialize_with_serializable(&0x1234u16, &mut buffer)
+            .unwrap();
+        assert_eq!(Header::parse_response::<u16>(bytes), Ok((response, 0x1234)));
+
+        let mut error = request.response(ReturnCode::NotReady).unwrap();
+        let bytes = error
+            .serialize_with_payload(Payload::new(&[0xAB]), &mut buffer)
+            .unwrap();
+        let result = Header::parse_response::<u16>(bytes);
+        assert_eq!(
+            result,
+            Err(ResponseError::Service {
+                return_code: ReturnCode::NotReady,
+                payload: Payload::new(&[0xAB]),
+            })
+        );
+        assert_eq!(result.unwrap_err().return_code(), ReturnCode::NotReady);
+
+        let mut response = request.response(ReturnCode::Ok).unwrap();
+        let bytes = response
+            .serialize_with_payload(Payload::new(&[0xAB]), &mut buffer)
+            .unwrap();
+        let result = Header::parse_response::<u16>(bytes);
+        assert_eq!(
+            result,
+            Err(ResponseError::Parse(ParseError::PayloadTooShort))
+        );
+        assert_eq!(
+            result.unwrap_err().return_code(),
+            ReturnCode::MalformedMessage
+        );
+    }
 }
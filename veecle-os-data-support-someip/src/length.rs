@@ -13,14 +13,30 @@ mod private {
 
 /// A SOME/IP length field. Can be either [`u8`], [`u16`], or [`u32`].
 pub trait LengthField: private::Sealed + Sized {
+    /// The maximum length the length field can encode.
+    const MAX_LENGTH: usize;
+
     /// Parses the length field and returns the length as a [`usize`].
     fn get_length(reader: &mut ByteReader) -> Result<usize, ParseError>;
 
     /// Creates a length field from the length as [`usize`].
     fn from_length(length: usize) -> Result<Self, SerializeError>;
+
+    /// Creates a length field from the length of a field as [`usize`].
+    ///
+    /// Returns [`SerializeError::FieldTooLong`] if the length exceeds [`Self::MAX_LENGTH`], see
+    /// [`SerializeError::in_field`] to name the field.
+    fn from_field_length(length: usize) -> Result<Self, SerializeError> {
+        Self::from_length(length).map_err(|_| SerializeError::FieldTooLong {
+            field: None,
+            max: Self::MAX_LENGTH,
+        })
+    }
 }
 
 impl LengthField for u8 {
+    const MAX_LENGTH: usize = u8::MAX as usize;
+
     fn get_length(reader: &mut ByteReader) -> Result<usize, ParseError> {
         Self::parse_partial(reader).map(|length| length as usize)
     }
@@ -33,6 +49,8 @@ impl LengthField for u8 {
 }
 
 impl LengthField for u16 {
+    const MAX_LENGTH: usize = u16::MAX as usize;
+
     fn get_length(reader: &mut ByteReader) -> Result<usize, ParseError> {
         Self::parse_partial(reader).map(|length| length as usize)
     }
@@ -45,6 +63,8 @@ impl LengthField for u16 {
 }
 
 impl LengthField for u32 {
+    const MAX_LENGTH: usize = u32::MAX as usize;
+
     fn get_length(reader: &mut ByteReader) -> Result<usize, ParseError> {
         Self::parse_partial(reader).map(|length| length as usize)
     }
@@ -4,6 +4,9 @@
 #![forbid(unsafe_code)]
 #![cfg_attr(coverage_nightly, feature(coverage_attribute))]
 
+#[cfg(feature = "config")]
+extern crate alloc;
+
 #[cfg(test)]
 macro_rules! test_round_trip {
     ($type:ty, $value:expr, $expected:expr) => {
@@ -59,6 +62,11 @@ macro_rules! test_round_trip {
 }
 
 pub mod array;
+pub mod client;
+#[cfg(feature = "config")]
+pub mod config;
+#[cfg(feature = "arbitrary")]
+pub mod fuzz;
 pub mod header;
 pub mod length;
 pub mod parse;
@@ -68,6 +76,9 @@ pub mod serialize_impl;
 pub mod service_discovery;
 pub mod string;
 
+/// CRC algorithms, re-exported from `veecle-os-data-support-crc`.
+pub use veecle_os_data_support_crc as crc;
+
 // Make `Parse` derive macro work inside this crate.
 // This is required because the macro expects the `veecle_os_data_support_someip` crate to be imported.
 extern crate self as veecle_os_data_support_someip;
@@ -3,6 +3,8 @@
 // Re-export the derive macro.
 pub use veecle_os_data_support_someip_macros::Parse;
 
+use crate::header::ReturnCode;
+
 /// An error while parsing a SOME/IP payload type.
 #[derive(Debug, Clone, Copy, thiserror::Error, PartialEq, Eq)]
 pub enum ParseError {
@@ -23,21 +25,97 @@ pub enum ParseError {
         /// Name of the type that was malformed.
         failed_at: &'static str,
     },
+    /// The payload nests types deeper than the [maximum depth](ByteReader::with_max_depth) of the reader.
+    #[error("the payload nests types deeper than the maximum depth of {max_depth}")]
+    MaxDepthExceeded {
+        /// Maximum depth of the reader.
+        max_depth: usize,
+    },
 }
 
+impl ParseError {
+    /// Returns the [`ReturnCode`] to answer a request with if its payload failed to parse.
+    pub fn return_code(&self) -> ReturnCode {
+        match self {
+            Self::PayloadTooShort
+            | Self::PayloadTooLong { .. }
+            | Self::MalformedMessage { .. }
+            | Self::MaxDepthExceeded { .. } => ReturnCode::MalformedMessage,
+        }
+    }
+}
+
+/// Default maximum nesting depth of a [`ByteReader`], see [`ByteReader::with_max_depth`].
+pub const DEFAULT_MAX_DEPTH: usize = 32;
+
 /// Reads bytes from an underlying byte-slice.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct ByteReader<'a> {
     /// Slice of bytes the reader reads from.
     data: &'a [u8],
     /// Reader offset into the slice.
     offset: usize,
+    /// Current nesting depth.
+    depth: usize,
+    /// Maximum nesting depth.
+    max_depth: usize,
+}
+
+/// Readers are equal if they read the same bytes from the same offset, regardless of their nesting depth.
+impl PartialEq for ByteReader<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data && self.offset == other.offset
+    }
 }
 
+impl Eq for ByteReader<'_> {}
+
 impl<'a> ByteReader<'a> {
     /// Creates a new reader for a slice of bytes.
+    ///
+    /// The reader uses [`DEFAULT_MAX_DEPTH`] as maximum nesting depth.
     pub fn new(data: &'a [u8]) -> Self {
-        Self { data, offset: 0 }
+        Self {
+            data,
+            offset: 0,
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// Sets the maximum nesting depth of this reader and all readers created from it.
+    ///
+    /// Every derived [`Parse`] implementation increases the depth while parsing its fields, parsing a payload that
+    /// nests deeper fails with [`ParseError::MaxDepthExceeded`]. This limits the stack usage when parsing malformed or
+    /// malicious payloads, e.g. with dynamic arrays of structs.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Returns the current nesting depth.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Runs `f` with the nesting depth increased by one.
+    ///
+    /// Returns [`ParseError::MaxDepthExceeded`] without running `f` if this would exceed the maximum depth.
+    pub fn nested<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        if self.depth >= self.max_depth {
+            return Err(ParseError::MaxDepthExceeded {
+                max_depth: self.max_depth,
+            });
+        }
+
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+
+        result
     }
 
     /// Creates a second reader for a sub-slice of this reader. The slice of the second reader starts at the current
@@ -59,7 +137,11 @@ impl<'a> ByteReader<'a> {
 
         let data = &self.data[current_offset..self.offset];
 
-        Ok(Self { offset: 0, data })
+        Ok(Self {
+            offset: 0,
+            data,
+            ..*self
+        })
     }
 
     /// Returns a new sub-reader with the remaining slice and advances the reader.
@@ -68,7 +150,33 @@ impl<'a> ByteReader<'a> {
 
         self.offset = self.data.len();
 
-        Self { data, offset: 0 }
+        Self {
+            data,
+            offset: 0,
+            ..*self
+        }
+    }
+
+    /// Runs `f` with a reader restricted to the shorter lifetime `'b` and advances this reader by the bytes `f` consumed.
+    ///
+    /// This allows parsing types that borrow for a shorter lifetime than the underlying slice, e.g. fields of a struct
+    /// with multiple lifetimes.
+    pub fn with_lifetime<'b, T>(&mut self, f: impl FnOnce(&mut ByteReader<'b>) -> T) -> T
+    where
+        'a: 'b,
+    {
+        let mut reader = ByteReader {
+            data: self.data,
+            offset: self.offset,
+            depth: self.depth,
+            max_depth: self.max_depth,
+        };
+
+        let result = f(&mut reader);
+
+        self.offset = reader.offset;
+
+        result
     }
 
     /// Reads a single byte and advances the reader.
@@ -85,6 +193,8 @@ impl<'a> ByteReader<'a> {
     }
 
     /// Returns a slice of bytes with the given length and advances the reader.
+    ///
+    /// Returns [`ParseError::PayloadTooShort`] without advancing the reader if fewer than `length` bytes remain.
     pub fn read_slice(&mut self, length: usize) -> Result<&'a [u8], ParseError> {
         let Some(new_offset) = self.offset.checked_add(length) else {
             return Err(ParseError::PayloadTooShort);
@@ -102,22 +212,45 @@ impl<'a> ByteReader<'a> {
     }
 
     /// Reads an array of `N` bytes and advances the reader.
+    ///
+    /// Returns [`ParseError::PayloadTooShort`] without advancing the reader if fewer than `N` bytes remain.
+    ///
+    /// ```
+    /// use veecle_os_data_support_someip::parse::{ByteReader, ParseError};
+    ///
+    /// let mut reader = ByteReader::new(&[0x02, 0x42, 0xac, 0x11, 0x00, 0x02, 0xff]);
+    ///
+    /// let mac: [u8; 6] = reader.read_array()?;
+    /// assert_eq!(mac, [0x02, 0x42, 0xac, 0x11, 0x00, 0x02]);
+    /// assert_eq!(reader.read_array::<2>(), Err(ParseError::PayloadTooShort));
+    /// # Ok::<(), ParseError>(())
+    /// ```
     pub fn read_array<const N: usize>(&mut self) -> Result<[u8; N], ParseError> {
-        let Some(new_offset) = self.offset.checked_add(N) else {
-            return Err(ParseError::PayloadTooShort);
-        };
+        let slice = self.read_slice(N)?;
 
-        if new_offset > self.data.len() {
-            return Err(ParseError::PayloadTooShort);
-        }
-
-        let result = self.data[self.offset..self.offset + N]
+        Ok(slice
             .try_into()
-            .expect("returned slice should always be N bytes long");
+            .expect("returned slice should always be N bytes long"))
+    }
+
+    /// Returns the next byte without advancing the reader.
+    pub fn peek_u8(&self) -> Result<u8, ParseError> {
+        self.data
+            .get(self.offset)
+            .copied()
+            .ok_or(ParseError::PayloadTooShort)
+    }
 
-        self.offset += N;
+    /// Returns a slice of the next `length` bytes without advancing the reader.
+    pub fn peek_slice(&self, length: usize) -> Result<&'a [u8], ParseError> {
+        self.remaining_slice()
+            .get(..length)
+            .ok_or(ParseError::PayloadTooShort)
+    }
 
-        Ok(result)
+    /// Advances the reader by `length` bytes without reading them, e.g. to skip reserved or padding bytes.
+    pub fn skip(&mut self, length: usize) -> Result<(), ParseError> {
+        self.read_slice(length).map(|_| ())
     }
 
     /// Returns the remaining slice without advancing the offset.
@@ -167,15 +300,20 @@ pub trait Parse<'a>: Sized {
 pub trait ParseExt<'a>: Sized {
     /// Parses a SOME/IP payload type from a given slice of bytes using [`Parse`] and
     /// validates all the bytes of the slice were used during parsing.
-    fn parse(slice: &'a [u8]) -> Result<Self, ParseError>;
+    fn parse(slice: &'a [u8]) -> Result<Self, ParseError> {
+        Self::parse_with_max_depth(slice, DEFAULT_MAX_DEPTH)
+    }
+
+    /// Like [`parse`](Self::parse), but with the given maximum nesting depth, see [`ByteReader::with_max_depth`].
+    fn parse_with_max_depth(slice: &'a [u8], max_depth: usize) -> Result<Self, ParseError>;
 }
 
 impl<'a, T> ParseExt<'a> for T
 where
     T: Parse<'a>,
 {
-    fn parse(slice: &'a [u8]) -> Result<Self, ParseError> {
-        let mut reader = ByteReader::new(slice);
+    fn parse_with_max_depth(slice: &'a [u8], max_depth: usize) -> Result<Self, ParseError> {
+        let mut reader = ByteReader::new(slice).with_max_depth(max_depth);
         let this = Self::parse_partial(&mut reader)?;
 
         match reader.is_empty() {
@@ -206,3 +344,63 @@ mod parse_ext {
         );
     }
 }
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod byte_reader {
+    use pretty_assertions::assert_eq;
+
+    use super::{ByteReader, ParseError};
+
+    #[test]
+    fn nested_limits_depth() {
+        let mut reader = ByteReader::new(&[1, 2]).with_max_depth(1);
+
+        let result = reader.nested(|reader| {
+            assert_eq!(reader.depth(), 1);
+
+            let mut sub_reader = reader.sub_reader(1)?;
+            assert_eq!(sub_reader.depth(), 1);
+
+            sub_reader.nested(|reader| reader.read_byte())
+        });
+
+        assert_eq!(result, Err(ParseError::MaxDepthExceeded { max_depth: 1 }));
+        assert_eq!(reader.depth(), 0);
+        assert_eq!(reader.nested(|reader| reader.read_byte()), Ok(2));
+    }
+
+    #[test]
+    fn peek_and_skip() {
+        let mut reader = ByteReader::new(&[1, 2, 3, 4]);
+
+        assert_eq!(reader.peek_u8(), Ok(1));
+        assert_eq!(reader.peek_slice(2), Ok(&[1, 2][..]));
+        assert_eq!(reader.peek_slice(5), Err(ParseError::PayloadTooShort));
+        assert_eq!(reader.len(), 4);
+
+        assert_eq!(reader.skip(3), Ok(()));
+        assert_eq!(reader.peek_u8(), Ok(4));
+        assert_eq!(reader.skip(2), Err(ParseError::PayloadTooShort));
+        assert_eq!(reader.read_byte(), Ok(4));
+
+        assert_eq!(reader.peek_u8(), Err(ParseError::PayloadTooShort));
+        assert_eq!(reader.peek_slice(0), Ok(&[][..]));
+        assert_eq!(reader.skip(0), Ok(()));
+    }
+
+    #[test]
+    fn read_array_and_slice() {
+        let mut reader = ByteReader::new(&[1, 2, 3, 4, 5]);
+
+        assert_eq!(reader.read_array::<2>(), Ok([1, 2]));
+        assert_eq!(reader.read_slice(1), Ok(&[3][..]));
+        assert_eq!(reader.read_array::<3>(), Err(ParseError::PayloadTooShort));
+        assert_eq!(reader.read_slice(3), Err(ParseError::PayloadTooShort));
+        assert_eq!(reader.len(), 2);
+
+        assert_eq!(reader.read_array::<0>(), Ok([]));
+        assert_eq!(reader.read_array::<2>(), Ok([4, 5]));
+        assert!(reader.is_empty());
+    }
+}
@@ -1,6 +1,8 @@
 //! Implementation of [`Parse`] for various data types.
 
 use super::parse::{Parse, ParseError};
+#[cfg(feature = "heapless")]
+use crate::length::LengthField;
 use crate::parse::ByteReader;
 
 impl<'a> Parse<'a> for bool {
@@ -41,6 +43,59 @@ impl_for_numeric!(i64);
 impl_for_numeric!(f32);
 impl_for_numeric!(f64);
 
+impl<'a, T, const N: usize> Parse<'a> for [T; N]
+where
+    T: Parse<'a>,
+{
+    fn parse_partial(reader: &mut ByteReader<'a>) -> Result<Self, ParseError> {
+        let mut error = None;
+
+        // Elements are parsed in order, after the first error no more bytes are consumed.
+        let elements: [Option<T>; N] = core::array::from_fn(|_| {
+            if error.is_some() {
+                return None;
+            }
+
+            T::parse_partial(reader)
+                .map_err(|parse_error| error = Some(parse_error))
+                .ok()
+        });
+
+        if let Some(error) = error {
+            return Err(error);
+        }
+
+        Ok(elements.map(|element| element.expect("all elements were parsed successfully")))
+    }
+}
+
+/// Parses a SOME/IP dynamic length array with a [`u32`] length field.
+///
+/// Unlike [`DynamicLengthArray`](crate::array::DynamicLengthArray), which ignores any elements past its maximum,
+/// parsing fails if the array contains more than `N` elements.
+#[cfg(feature = "heapless")]
+impl<'a, T, const N: usize> Parse<'a> for heapless::Vec<T, N>
+where
+    T: Parse<'a>,
+{
+    fn parse_partial(reader: &mut ByteReader<'a>) -> Result<Self, ParseError> {
+        let length = u32::get_length(reader)?;
+        let mut element_reader = reader.sub_reader(length)?;
+
+        let mut elements = heapless::Vec::new();
+        while !element_reader.is_empty() {
+            let element = T::parse_partial(&mut element_reader)?;
+            elements
+                .push(element)
+                .map_err(|_| ParseError::MalformedMessage {
+                    failed_at: core::any::type_name::<Self>(),
+                })?;
+        }
+
+        Ok(elements)
+    }
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod parse {
@@ -121,4 +176,62 @@ mod parse {
             &[0x0, 0xA, 0x8, 0x34, 0x10, 0x9, 0x8, 0x7]
         );
     }
+
+    #[test]
+    fn array() {
+        test_round_trip!(
+            [u16; 3],
+            [0x40A, 0x834, 0x1],
+            &[0x4, 0xA, 0x8, 0x34, 0x0, 0x1]
+        );
+        test_round_trip!([[u8; 2]; 2], [[1, 2], [3, 4]], &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn empty_array() {
+        assert_eq!(<[u32; 0]>::parse(&[]), Ok([]));
+    }
+
+    #[test]
+    fn malformed_array() {
+        assert_eq!(
+            <[bool; 2]>::parse(&[1, 2]),
+            Err(ParseError::MalformedMessage { failed_at: "bool" })
+        );
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn heapless_vec() {
+        test_round_trip!(
+            heapless::Vec<u16, 3>,
+            heapless::Vec::<u16, 3>::from_slice(&[0x40A, 0x834]).unwrap(),
+            &[0, 0, 0, 4, 0x4, 0xA, 0x8, 0x34]
+        );
+        test_round_trip!(
+            heapless::Vec<u16, 3>,
+            heapless::Vec::<u16, 3>::new(),
+            &[0, 0, 0, 0]
+        );
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn heapless_vec_too_many_elements() {
+        assert_eq!(
+            <heapless::Vec<u8, 2>>::parse(&[0, 0, 0, 3, 1, 2, 3]),
+            Err(ParseError::MalformedMessage {
+                failed_at: "heapless::vec::Vec<u8, 2>"
+            })
+        );
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn malformed_heapless_vec() {
+        assert_eq!(
+            <heapless::Vec<bool, 2>>::parse(&[0, 0, 0, 2, 1, 2]),
+            Err(ParseError::MalformedMessage { failed_at: "bool" })
+        );
+    }
 }
@@ -19,6 +19,14 @@ pub enum SerializeError {
     /// The length of a dynamic structure is bigger than the length field can encode.
     #[error("a length value is too big for the length field")]
     LengthOverflow,
+    /// A length-prefixed or fixed length field is longer than its length allows.
+    #[error("the {} field is too long. Maximum length is {max}", .field.unwrap_or("unnamed"))]
+    FieldTooLong {
+        /// Name of the innermost struct field that was too long, set for fields of structs deriving [`Serialize`].
+        field: Option<&'static str>,
+        /// Maximum length of the field.
+        max: usize,
+    },
     /// The minimum capacity of a dynamic type is subceeded.
     #[error("the dynamic data type expected more data")]
     DynamicTypeUnderflow,
@@ -27,6 +35,21 @@ pub enum SerializeError {
     DynamicTypeOverflow,
 }
 
+impl SerializeError {
+    /// Names the struct `field` a [`SerializeError::FieldTooLong`] occurred in, unless it is already named.
+    ///
+    /// The `Serialize` derive macro calls this for every field, so the innermost field is named.
+    pub fn in_field(self, field: &'static str) -> Self {
+        match self {
+            Self::FieldTooLong { field: None, max } => Self::FieldTooLong {
+                field: Some(field),
+                max,
+            },
+            error => error,
+        }
+    }
+}
+
 /// Writes bytes to an underlying byte-slice.
 #[derive(Debug)]
 pub struct ByteWriter<'a> {
@@ -139,6 +162,18 @@ pub trait SerializeExt: Sized {
     /// Serializes a SOME/IP payload type to a given slice of bytes using [`Serialize`] and returns the number of
     /// bytes written to the buffer.
     fn serialize(&self, buffer: &mut [u8]) -> Result<usize, SerializeError>;
+
+    /// Serializes a SOME/IP payload type to a given slice of bytes starting at `offset` using [`Serialize`] and
+    /// returns the number of bytes written to the buffer.
+    ///
+    /// The bytes before `offset` are left untouched, which allows assembling a message from multiple parts, e.g. a
+    /// header and its payload, in one buffer.
+    fn serialize_at(&self, buffer: &mut [u8], offset: usize) -> Result<usize, SerializeError> {
+        let buffer = buffer
+            .get_mut(offset..)
+            .ok_or(SerializeError::BufferTooSmall)?;
+        self.serialize(buffer)
+    }
 }
 
 impl<T> SerializeExt for T
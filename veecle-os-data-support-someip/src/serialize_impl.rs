@@ -1,5 +1,7 @@
 //! Implementation of [`Serialize`] for various data types.
 
+#[cfg(feature = "heapless")]
+use crate::length::LengthField;
 use crate::serialize::{ByteWriter, Serialize, SerializeError};
 
 impl Serialize for bool {
@@ -44,3 +46,39 @@ impl_for_numeric!(i64);
 
 impl_for_numeric!(f32);
 impl_for_numeric!(f64);
+
+impl<T, const N: usize> Serialize for [T; N]
+where
+    T: Serialize,
+{
+    fn required_length(&self) -> usize {
+        self.iter().map(Serialize::required_length).sum()
+    }
+
+    fn serialize_partial(&self, byte_writer: &mut ByteWriter) -> Result<(), SerializeError> {
+        self.iter()
+            .try_for_each(|element| element.serialize_partial(byte_writer))
+    }
+}
+
+/// Serializes a SOME/IP dynamic length array with a [`u32`] length field.
+#[cfg(feature = "heapless")]
+impl<T, const N: usize> Serialize for heapless::Vec<T, N>
+where
+    T: Serialize,
+{
+    fn required_length(&self) -> usize {
+        core::mem::size_of::<u32>() + self.iter().map(Serialize::required_length).sum::<usize>()
+    }
+
+    fn serialize_partial(&self, byte_writer: &mut ByteWriter) -> Result<(), SerializeError> {
+        let reserved_length = byte_writer.reserve_length::<u32>()?;
+
+        let length = byte_writer.write_counted(|byte_writer| {
+            self.iter()
+                .try_for_each(|element| element.serialize_partial(byte_writer))
+        })?;
+
+        byte_writer.write_length(reserved_length, &u32::from_field_length(length)?)
+    }
+}
@@ -3,6 +3,7 @@
 use bitflags::bitflags;
 
 use crate::array::DynamicLengthArray;
+use crate::length::LengthField;
 use crate::parse::{ByteReader, Parse, ParseError};
 use crate::serialize::{ByteWriter, Serialize, SerializeError};
 
@@ -384,7 +385,7 @@ impl Serialize for Option<'_> {
             }
         };
 
-        byte_writer.write_length(reserved_length, &(length as u16))
+        byte_writer.write_length(reserved_length, &u16::from_length(length)?)
     }
 }
 
@@ -601,7 +602,7 @@ impl Serialize for ConfigurationString<'_> {
             }
         })?;
 
-        let length = u8::try_from(length).map_err(|_| SerializeError::LengthOverflow)?;
+        let length = u8::from_field_length(length)?;
         byte_writer.write_length(reserved_length, &length)
     }
 }
@@ -862,10 +863,10 @@ mod configuration_string {
             value: ConfigurationStringValue::None,
         };
 
-        assert_eq!(
+        assert!(matches!(
             string.serialize(&mut [0; 512]),
-            Err(SerializeError::LengthOverflow)
-        );
+            Err(SerializeError::FieldTooLong { max: 255, .. })
+        ));
     }
 }
 
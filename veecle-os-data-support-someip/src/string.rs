@@ -24,6 +24,18 @@ impl<'a, const LENGTH: usize> FixedLengthString<'a, LENGTH> {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a, const LENGTH: usize> arbitrary::Arbitrary<'a> for FixedLengthString<'a, LENGTH> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        // Both the UTF-8 and UTF-16 encodings add four bytes of byte order mark and terminator to the content.
+        let content_length = LENGTH
+            .checked_sub(4)
+            .ok_or(arbitrary::Error::IncorrectFormat)?;
+
+        EncodedString::arbitrary_with_length(u, Some(content_length)).map(Self::new)
+    }
+}
+
 impl<'a, const LENGTH: usize> Parse<'a> for FixedLengthString<'a, LENGTH> {
     fn parse_partial(reader: &mut ByteReader<'a>) -> Result<Self, ParseError> {
         // TODO: Parse an optional string length. We don't know how this is determined yet.
@@ -41,6 +53,13 @@ impl<const LENGTH: usize> Serialize for FixedLengthString<'_, LENGTH> {
     }
 
     fn serialize_partial(&self, byte_writer: &mut ByteWriter) -> Result<(), SerializeError> {
+        if self.encoded_string.required_length() > LENGTH {
+            return Err(SerializeError::FieldTooLong {
+                field: None,
+                max: LENGTH,
+            });
+        }
+
         self.encoded_string.serialize_partial(byte_writer)
     }
 }
@@ -67,6 +86,21 @@ impl<'a, L> DynamicLengthString<'a, L> {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a, L> arbitrary::Arbitrary<'a> for DynamicLengthString<'a, L>
+where
+    L: LengthField,
+{
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let encoded_string = EncodedString::arbitrary(u)?;
+
+        L::from_length(encoded_string.required_length())
+            .map_err(|_| arbitrary::Error::IncorrectFormat)?;
+
+        Ok(Self::new(encoded_string))
+    }
+}
+
 impl<'a, L> Parse<'a> for DynamicLengthString<'a, L>
 where
     L: LengthField,
@@ -98,7 +132,7 @@ where
         let reserved_length = byte_writer.reserve_length()?;
 
         let length =
-            L::from_length(byte_writer.write_counted(|byte_writer| {
+            L::from_field_length(byte_writer.write_counted(|byte_writer| {
                 self.encoded_string.serialize_partial(byte_writer)
             })?)?;
 
@@ -281,6 +315,66 @@ impl<'a> EncodedString<'a> {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> EncodedString<'a> {
+    /// Generates an arbitrary string, with exactly `content_length` bytes between the byte order mark and the
+    /// terminator if specified.
+    fn arbitrary_with_length(
+        u: &mut arbitrary::Unstructured<'a>,
+        content_length: Option<usize>,
+    ) -> arbitrary::Result<Self> {
+        let encoding = u.int_in_range(0..=2)?;
+
+        // UTF-16 strings consist of two byte units, so an odd length can only be filled with UTF-8.
+        if encoding == 0 || content_length.is_some_and(|length| !length.is_multiple_of(2)) {
+            let text = match content_length {
+                Some(length) => core::str::from_utf8(u.bytes(length)?)
+                    .map_err(|_| arbitrary::Error::IncorrectFormat)?,
+                None => <&'a str as arbitrary::Arbitrary<'a>>::arbitrary(u)?,
+            };
+
+            // UTF-8 strings are null-terminated, so they can't contain a null byte.
+            return match (text.find('\0'), content_length) {
+                (None, _) => Ok(Self::Utf8(text)),
+                (Some(position), None) => Ok(Self::Utf8(&text[..position])),
+                (Some(_), Some(_)) => Err(arbitrary::Error::IncorrectFormat),
+            };
+        }
+
+        let bytes = match content_length {
+            Some(length) => u.bytes(length)?,
+            None => {
+                let units = u.arbitrary_len::<u16>()?;
+                u.bytes(units * 2)?
+            }
+        };
+
+        // UTF-16 strings are terminated by a 0x0000 unit, so they can't contain one.
+        let bytes = match (
+            bytes.chunks_exact(2).position(|unit| unit == [0, 0]),
+            content_length,
+        ) {
+            (None, _) => bytes,
+            (Some(position), None) => &bytes[..position * 2],
+            (Some(_), Some(_)) => return Err(arbitrary::Error::IncorrectFormat),
+        };
+
+        let reader = ByteReader::new(bytes);
+
+        Ok(match encoding {
+            1 => Self::Utf16Be(Utf16BeStr { reader }),
+            _ => Self::Utf16Le(Utf16LeStr { reader }),
+        })
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for EncodedString<'a> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Self::arbitrary_with_length(u, None)
+    }
+}
+
 // Byte order mark for UTF-8 and UTF-16.
 //
 // See: https://en.wikipedia.org/wiki/Byte_order_mark
@@ -394,6 +488,7 @@ impl Serialize for EncodedString<'_> {
 mod fixed_length_string {
 
     use crate::parse::ParseExt;
+    use crate::serialize::{SerializeError, SerializeExt};
     use crate::string::{EncodedString, FixedLengthString};
 
     #[test]
@@ -422,6 +517,16 @@ mod fixed_length_string {
             Err(crate::parse::ParseError::MalformedMessage { .. })
         ));
     }
+
+    #[test]
+    fn serialize_too_long() {
+        let string = FixedLengthString::<'_, 8>::new(EncodedString::create("TOO LONG"));
+
+        assert!(matches!(
+            string.serialize(&mut [0; 512]),
+            Err(SerializeError::FieldTooLong { max: 8, .. })
+        ));
+    }
 }
 
 #[cfg(test)]
@@ -467,10 +572,10 @@ mod dynamic_length_string {
 
         let string = DynamicLengthString::<'_, u8>::new(EncodedString::create(long_str));
 
-        assert_eq!(
+        assert!(matches!(
             string.serialize(&mut [0; 512]),
-            Err(SerializeError::LengthOverflow)
-        );
+            Err(SerializeError::FieldTooLong { max: 255, .. })
+        ));
     }
 }
 
@@ -0,0 +1,64 @@
+#![expect(missing_docs)]
+
+use veecle_os_data_support_someip::array::{DynamicLengthArray, FixedLengthArray};
+use veecle_os_data_support_someip::fuzz::assert_round_trip;
+use veecle_os_data_support_someip::header::Header;
+use veecle_os_data_support_someip::length::NoLengthField;
+use veecle_os_data_support_someip::parse::Parse;
+use veecle_os_data_support_someip::serialize::Serialize;
+use veecle_os_data_support_someip::string::{DynamicLengthString, FixedLengthString};
+
+#[derive(Debug, PartialEq, Parse, Serialize, arbitrary::Arbitrary)]
+struct Inner<'a> {
+    flag: bool,
+    name: DynamicLengthString<'a, u8>,
+}
+
+#[derive(Debug, PartialEq, Parse, Serialize, arbitrary::Arbitrary)]
+struct Message<'a> {
+    header: Header,
+    fixed_string: FixedLengthString<'a, 8>,
+    values: DynamicLengthArray<'a, u32, u16, 4>,
+    inner: DynamicLengthArray<'a, Inner<'a>, u32, 3>,
+    fixed_values: FixedLengthArray<'a, u16, NoLengthField, 2>,
+    trailer: u64,
+}
+
+/// Deterministic pseudo-random inputs, so the test covers a variety of generated values without depending on a
+/// fuzzing engine.
+fn inputs() -> impl Iterator<Item = Vec<u8>> {
+    let mut state = 0x2545_F491_4F6C_DD1Du64;
+
+    (0..2000).map(move |index| {
+        (0..index % 256)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            })
+            .collect()
+    })
+}
+
+#[test]
+fn header_round_trip() {
+    for data in inputs() {
+        let mut buffer = [0; 2048];
+        let _ = assert_round_trip::<Header>(&data, &mut buffer);
+    }
+}
+
+#[test]
+fn message_round_trip() {
+    let mut generated = 0;
+
+    for data in inputs() {
+        let mut buffer = [0; 2048];
+        if assert_round_trip::<Message>(&data, &mut buffer).is_ok() {
+            generated += 1;
+        }
+    }
+
+    assert!(generated > 0);
+}
@@ -0,0 +1,56 @@
+#![expect(missing_docs)]
+
+use pretty_assertions::assert_eq;
+use veecle_os_data_support_someip::parse::{Parse, ParseError, ParseExt};
+use veecle_os_data_support_someip::serialize::{Serialize, SerializeExt};
+
+#[derive(Debug, PartialEq, Parse, Serialize)]
+#[someip(optional_bitmask = "u16")]
+struct Evolved {
+    #[someip(optional, present_bit = 9)]
+    name: Option<[u8; 2]>,
+    id: u32,
+    #[someip(optional, present_bit = 0)]
+    flags: Option<u8>,
+}
+
+fn round_trip(value: &Evolved, expected: &[u8]) {
+    let mut buffer = [0u8; 32];
+    let written = value.serialize(&mut buffer).unwrap();
+    assert_eq!(&buffer[..written], expected);
+    assert_eq!(value.required_length(), expected.len());
+    assert_eq!(&Evolved::parse(expected).unwrap(), value);
+}
+
+#[test]
+fn absent_fields() {
+    let value = Evolved {
+        name: None,
+        id: 7,
+        flags: None,
+    };
+
+    round_trip(&value, &[0x00, 0x00, 0x00, 0x00, 0x00, 0x07]);
+}
+
+#[test]
+fn present_fields() {
+    let value = Evolved {
+        name: Some(*b"ab"),
+        id: 7,
+        flags: Some(0xAA),
+    };
+
+    round_trip(
+        &value,
+        &[0x02, 0x01, b'a', b'b', 0x00, 0x00, 0x00, 0x07, 0xAA],
+    );
+}
+
+#[test]
+fn missing_present_field() {
+    assert_eq!(
+        Evolved::parse(&[0x00, 0x01, 0x00, 0x00, 0x00, 0x07]),
+        Err(ParseError::PayloadTooShort)
+    );
+}
@@ -0,0 +1,67 @@
+#![expect(missing_docs)]
+
+use pretty_assertions::assert_eq;
+use veecle_os_data_support_someip::parse::{Parse, ParseError, ParseExt};
+use veecle_os_data_support_someip::serialize::{Serialize, SerializeExt};
+
+#[derive(Debug, PartialEq, Parse, Serialize)]
+struct Padded {
+    kind: u8,
+    #[someip(reserved_before = 3)]
+    id: u32,
+    #[someip(reserved_after = 2)]
+    flags: u16,
+}
+
+#[derive(Debug, PartialEq, Parse, Serialize)]
+struct PaddedTuple(#[someip(reserved_before = 1, reserved_after = 1)] u8);
+
+#[test]
+fn round_trip() {
+    let value = Padded {
+        kind: 1,
+        id: 42,
+        flags: 0xABCD,
+    };
+    let expected = &[
+        0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x2A, 0xAB, 0xCD, 0x00, 0x00,
+    ];
+
+    let mut buffer = [0xFFu8; 32];
+    let written = value.serialize(&mut buffer).unwrap();
+    assert_eq!(&buffer[..written], expected);
+    assert_eq!(value.required_length(), expected.len());
+    assert_eq!(Padded::parse(expected).unwrap(), value);
+}
+
+#[test]
+fn tuple_struct() {
+    let mut buffer = [0xFFu8; 8];
+    let written = PaddedTuple(7).serialize(&mut buffer).unwrap();
+    assert_eq!(&buffer[..written], &[0x00, 0x07, 0x00]);
+    assert_eq!(PaddedTuple::parse(&[0x00, 0x07, 0x00]), Ok(PaddedTuple(7)));
+}
+
+#[test]
+fn reserved_bytes_are_ignored() {
+    assert_eq!(
+        Padded::parse(&[
+            0x01, 0xAA, 0xBB, 0xCC, 0x00, 0x00, 0x00, 0x2A, 0xAB, 0xCD, 0xDD, 0xEE,
+        ]),
+        Ok(Padded {
+            kind: 1,
+            id: 42,
+            flags: 0xABCD,
+        })
+    );
+}
+
+#[test]
+fn missing_reserved_bytes() {
+    assert_eq!(
+        Padded::parse(&[
+            0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x2A, 0xAB, 0xCD, 0x00,
+        ]),
+        Err(ParseError::PayloadTooShort)
+    );
+}
@@ -127,3 +127,83 @@ fn service_discovery_header() {
         .for_each(|option| assert_eq!(option, option_iter.next().unwrap()));
     assert_eq!(option_iter.next(), None);
 }
+
+/// Test that a header and its payload can be serialized one after the other into one buffer.
+#[test]
+fn serialize_at_offset() {
+    use veecle_os_data_support_someip::serialize::{SerializeError, SerializeExt};
+
+    let payload = 0x1234_5678u32;
+    let mut header = Header::notification(
+        MessageId::new(ServiceId::from(0x0566), MethodId::from(0x8002)),
+        RequestId::new(
+            ClientId::new(Prefix::from(0x00), ClientIdInner::from(0x00)),
+            SessionId::from(0x6837),
+        ),
+        InterfaceVersion::from(0x00),
+    );
+    header.set_length(Length::from_payload_length(4).unwrap());
+
+    let mut buffer = [0u8; 20];
+    let header_length = header.serialize_at(&mut buffer, 0).unwrap();
+    let payload_length = payload.serialize_at(&mut buffer, header_length).unwrap();
+    assert_eq!((header_length, payload_length), (16, 4));
+
+    let (parsed_header, parsed_payload) = Header::parse_with_payload(&buffer).unwrap();
+    assert_eq!(parsed_header, header);
+    assert_eq!(u32::parse(parsed_payload.as_ref()).unwrap(), payload);
+
+    assert_eq!(
+        payload.serialize_at(&mut buffer, 17),
+        Err(SerializeError::BufferTooSmall)
+    );
+    assert_eq!(
+        payload.serialize_at(&mut buffer, 21),
+        Err(SerializeError::BufferTooSmall)
+    );
+}
+
+#[derive(Debug, veecle_os_data_support_someip::serialize::Serialize)]
+struct Person<'a> {
+    id: u8,
+    name: veecle_os_data_support_someip::string::DynamicLengthString<'a, u8>,
+}
+
+#[derive(Debug, veecle_os_data_support_someip::serialize::Serialize)]
+struct Team<'a>(u8, Person<'a>);
+
+/// Test that a too long field is reported with the name of the innermost struct field.
+#[test]
+fn field_too_long_names_field() {
+    use veecle_os_data_support_someip::serialize::{SerializeError, SerializeExt};
+    use veecle_os_data_support_someip::string::{DynamicLengthString, EncodedString};
+
+    let long_name = core::str::from_utf8(&[b'a'; 256]).unwrap();
+    let person = Person {
+        id: 1,
+        name: DynamicLengthString::new(EncodedString::create(long_name)),
+    };
+
+    let mut buffer = [0; 1024];
+    let error = person.serialize(&mut buffer).unwrap_err();
+    assert_eq!(
+        error,
+        SerializeError::FieldTooLong {
+            field: Some("name"),
+            max: 255
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "the name field is too long. Maximum length is 255"
+    );
+
+    let team = Team(2, person);
+    assert_eq!(
+        team.serialize(&mut buffer),
+        Err(SerializeError::FieldTooLong {
+            field: Some("name"),
+            max: 255
+        })
+    );
+}
@@ -83,8 +83,10 @@ fn actor2(
 /// # Attributes
 ///
 /// * `crate = ::veecle_os_runtime`: Overrides the path to the `veecle-os-runtime` crate in case the import was renamed.
+/// * `schema_version = 1`: Sets [`Storable::SCHEMA_VERSION`], defaults to `0`.
 ///
 /// [`Storable`]: https://docs.rs/veecle-os/latest/veecle_os/runtime/trait.Storable.html
+/// [`Storable::SCHEMA_VERSION`]: https://docs.rs/veecle-os/latest/veecle_os/runtime/trait.Storable.html#associatedconstant.SCHEMA_VERSION
 ///
 /// ```
 /// use core::fmt::Debug;
@@ -106,6 +108,12 @@ fn actor2(
 /// }
 ///
 /// #[derive(Debug, Storable)]
+/// #[storable(schema_version = 2)]
+/// pub struct Brake {
+///     test: u8,
+/// }
+///
+/// #[derive(Debug, Storable)]
 /// pub enum Actuator {
 ///     Variant1,
 ///     Variant2(u8),
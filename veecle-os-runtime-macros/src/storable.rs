@@ -10,6 +10,8 @@ pub struct StorableDerive {
     generics: Generics,
     /// The name of the Veecle OS crate for renaming.
     veecle_os_runtime: Option<Path>,
+    /// The schema version of the data type, if set.
+    schema_version: Option<u32>,
 }
 
 impl StorableDerive {
@@ -19,6 +21,7 @@ impl StorableDerive {
         let generics = input.generics;
 
         let mut veecle_os_runtime = None;
+        let mut schema_version = None;
 
         // Iterate through attributes to find #[storable(...)]
         for attr in input.attrs {
@@ -65,6 +68,14 @@ impl StorableDerive {
 
                         veecle_os_runtime = Some(parsed);
                     }
+                    Some("schema_version") => {
+                        if schema_version.is_some() {
+                            return Err(meta.error("setting `schema_version` argument multiple times"));
+                        }
+
+                        let literal = meta.value()?.parse::<syn::LitInt>()?;
+                        schema_version = Some(literal.base10_parse::<u32>()?);
+                    }
                     _ => return Err(meta.error("unknown attribute argument")),
                 }
 
@@ -76,6 +87,7 @@ impl StorableDerive {
             ident,
             generics,
             veecle_os_runtime,
+            schema_version,
         })
     }
 
@@ -94,6 +106,7 @@ impl StorableDerive {
                     where_clause,
                 },
             veecle_os_runtime,
+            schema_version,
         } = self;
 
         let veecle_os_runtime = veecle_os_runtime
@@ -101,6 +114,9 @@ impl StorableDerive {
             .map(Ok)
             .unwrap_or_else(crate::veecle_os_runtime_path)?;
 
+        let schema_version = schema_version
+            .map(|schema_version| quote!(const SCHEMA_VERSION: u32 = #schema_version;));
+
         Ok(quote!(
             #[automatically_derived]
             impl
@@ -110,6 +126,7 @@ impl StorableDerive {
             #where_clause
             {
                 type DataType = Self;
+                #schema_version
             }
         ))
     }
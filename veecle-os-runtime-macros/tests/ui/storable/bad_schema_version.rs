@@ -0,0 +1,13 @@
+#[derive(Debug, veecle_os_runtime_macros::Storable)]
+#[storable(schema_version = "1")]
+pub struct Sensor0 {
+    test: u8,
+}
+
+#[derive(Debug, veecle_os_runtime_macros::Storable)]
+#[storable(schema_version = 1, schema_version = 2)]
+pub struct Sensor1 {
+    test: u8,
+}
+
+fn main() {}
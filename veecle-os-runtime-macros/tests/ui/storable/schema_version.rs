@@ -0,0 +1,17 @@
+use veecle_os_runtime::Storable;
+
+#[derive(Debug, veecle_os_runtime_macros::Storable)]
+pub struct Sensor0 {
+    test: u8,
+}
+
+#[derive(Debug, veecle_os_runtime_macros::Storable)]
+#[storable(schema_version = 3)]
+pub struct Sensor1 {
+    test: u8,
+}
+
+const _: () = assert!(Sensor0::SCHEMA_VERSION == 0);
+const _: () = assert!(Sensor1::SCHEMA_VERSION == 3);
+
+fn main() {}
@@ -0,0 +1,421 @@
+//! Function based alternative to the [`execute!`](crate::execute!) macro.
+
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use alloc::task::Wake;
+use alloc::vec::Vec;
+use core::any::{Any, TypeId};
+use core::cell::OnceCell;
+use core::future::Future;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::task::{Context, Poll, Waker};
+
+use futures::task::AtomicWaker;
+use pin_project::pin_project;
+
+use crate::Never;
+use crate::actor::Actor;
+use crate::cons::{Cons, Nil, TupleConsToCons};
+use crate::datastore::sync::generational;
+use crate::datastore::{Datastore, SlotTrait};
+use crate::execute::{AccessCount, execute_actor};
+use crate::panic::PanicPolicy;
+
+/// Entry point for assembling a runtime instance without the [`execute!`](crate::execute!) macro.
+///
+/// See [`Runtime::builder`].
+#[derive(Debug)]
+pub struct Runtime;
+
+impl Runtime {
+    /// Returns a [`RuntimeBuilder`] without any actors, creating their slots in `store`.
+    ///
+    /// Actors are added one at a time with [`RuntimeBuilder::actor`], which allows assembling the actor set in
+    /// ordinary code, e.g. behind conditionals, from helper functions or in a loop over configuration loaded at
+    /// runtime.
+    /// The slots of the store are derived from the actors' store requests, exactly like with
+    /// [`execute!`](crate::execute!).
+    ///
+    /// ```rust
+    /// use veecle_os_runtime::single_writer::{Reader, Writer};
+    /// use veecle_os_runtime::{Never, Runtime, RuntimeStore, Storable};
+    ///
+    /// #[derive(Debug, Clone, PartialEq, Eq, Default, Storable)]
+    /// pub struct Ping(u32);
+    ///
+    /// #[veecle_os_runtime::actor]
+    /// async fn ping_actor(mut ping: Writer<'_, Ping>) -> Never {
+    ///     let mut value = 0;
+    ///     loop {
+    ///         ping.write(Ping(value)).await;
+    ///         value += 1;
+    ///     }
+    /// }
+    ///
+    /// #[veecle_os_runtime::actor]
+    /// async fn pong_actor(mut ping: Reader<'_, Ping>, #[init_context] limit: u32) -> Never {
+    ///     loop {
+    ///         let ping = ping.read_updated_cloned().await;
+    ///         println!("Ping {limit}: {}", ping.0);
+    /// #       // Exit the application to allow doc-tests to complete.
+    /// #       if ping.0 == limit { std::process::exit(0); }
+    ///     }
+    /// }
+    ///
+    /// let store = core::pin::pin!(RuntimeStore::new());
+    ///
+    /// let mut runtime = Runtime::builder(store.as_ref()).actor::<PingActor>(());
+    /// for limit in [10, 20, 30] {
+    ///     runtime = runtime.actor::<PongActor>(limit);
+    /// }
+    ///
+    /// futures::executor::block_on(runtime.build());
+    /// ```
+    ///
+    /// # Trade-offs compared to `execute!`
+    ///
+    /// * The builder is only available with the `alloc` feature, every actor future and every slot of the store is
+    ///   allocated separately on the heap.
+    ///   [`execute!`](crate::execute!) runs without heap allocation and remains the choice for constrained targets.
+    ///   For that reason it doesn't desugar to the builder but keeps its own statically typed store and actor list.
+    /// * The store has to be created and pinned by the caller before adding actors, see [`RuntimeStore`].
+    /// * The access patterns (e.g. one writer per type, at least one reader) are validated for the complete actor set
+    ///   in [`RuntimeBuilder::build`] and violations panic, the same checks [`execute!`](crate::execute!) does when
+    ///   its future is first polled.
+    /// * Like with [`execute!`](crate::execute!), only the actors that were woken are polled, in the order they were
+    ///   added, so the ordering guarantees between writers and readers are the same.
+    pub fn builder(store: Pin<&RuntimeStore>) -> RuntimeBuilder<'_> {
+        RuntimeBuilder {
+            store,
+            actors: Vec::new(),
+            panic_policy: PanicPolicy::default(),
+        }
+    }
+}
+
+/// Creates the future of an added actor once the store is available.
+type SpawnActor<'a> = Box<
+    dyn FnOnce(Pin<&'a RuntimeStore>, PanicPolicy) -> Pin<Box<dyn Future<Output = Never> + 'a>>
+        + 'a,
+>;
+
+/// An actor added to a [`RuntimeBuilder`].
+struct ActorEntry<'a> {
+    /// The type name of the actor, used in validation errors.
+    name: &'static str,
+
+    /// Returns how many writers for the given type the actor has.
+    writers: fn(TypeId) -> usize,
+
+    /// Returns how many readers for the given type the actor has (both exclusive and non-exclusive).
+    readers: fn(TypeId) -> usize,
+
+    /// Returns how many exclusive readers for the given type the actor has.
+    exclusive_readers: fn(TypeId) -> usize,
+
+    /// Adds the slots the actor requires to a store, see [`SlotList::add_slots`].
+    add_slots: fn(&mut Vec<Pin<Box<dyn ErasedSlot>>>, &[ActorEntry<'_>]),
+
+    spawn: SpawnActor<'a>,
+}
+
+impl core::fmt::Debug for ActorEntry<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+/// Builder for a runtime instance, created with [`Runtime::builder`].
+#[derive(Debug)]
+pub struct RuntimeBuilder<'a> {
+    store: Pin<&'a RuntimeStore>,
+    actors: Vec<ActorEntry<'a>>,
+    panic_policy: PanicPolicy,
+}
+
+#[expect(
+    private_bounds,
+    reason = "the bounds are internal helpers implemented for all valid actors"
+)]
+impl<'a> RuntimeBuilder<'a> {
+    /// Adds an actor with its init-context.
+    ///
+    /// Actors are polled in the order they were added.
+    /// The same actor type can be added multiple times, e.g. with different init-contexts, as long as the resulting
+    /// access patterns are valid.
+    pub fn actor<T>(mut self, init_context: T::InitContext) -> Self
+    where
+        T: Actor<'a, StoreRequest: TupleConsToCons, Slots: SlotList> + 'a,
+        <T::StoreRequest as TupleConsToCons>::Cons: AccessCount,
+        T::InitContext: 'a,
+    {
+        self.actors.push(ActorEntry {
+            name: core::any::type_name::<T>(),
+            writers: <T::StoreRequest as TupleConsToCons>::Cons::writers,
+            readers: <T::StoreRequest as TupleConsToCons>::Cons::readers,
+            exclusive_readers: <T::StoreRequest as TupleConsToCons>::Cons::exclusive_readers,
+            add_slots: T::Slots::add_slots,
+            spawn: Box::new(move |store, panic_policy| {
+                Box::pin(execute_actor::<T>(store, init_context, panic_policy))
+            }),
+        });
+        self
+    }
+
+    /// Sets what happens when an actor panics, defaults to [`PanicPolicy::Abort`].
+    pub fn panic_policy(mut self, panic_policy: PanicPolicy) -> Self {
+        self.panic_policy = panic_policy;
+        self
+    }
+
+    /// Creates the slots for the added actors in the store, validates their access patterns and returns the runtime
+    /// future running all actors.
+    ///
+    /// # Panics
+    ///
+    /// If the actors' access patterns are invalid, e.g. multiple writers for the same type, or if the store was
+    /// already used by another runtime.
+    pub fn build(self) -> impl Future<Output = Never> + 'a {
+        let Self {
+            store,
+            actors,
+            panic_policy,
+        } = self;
+
+        let mut slots = Vec::new();
+        for actor in &actors {
+            (actor.add_slots)(&mut slots, &actors);
+        }
+        if store.project_ref().slots.set(slots).is_err() {
+            panic!("the store is already used by another runtime");
+        }
+
+        let futures = Vec::from_iter(
+            actors
+                .into_iter()
+                .map(|actor| (actor.spawn)(store, panic_policy)),
+        );
+
+        run(store, futures)
+    }
+}
+
+/// Polls the woken actor `futures` in order and starts a new generation of `store` after every round.
+async fn run<'a>(
+    store: Pin<&'a RuntimeStore>,
+    mut futures: Vec<Pin<Box<dyn Future<Output = Never> + 'a>>>,
+) -> Never {
+    let shared = Arc::new(WakeShared {
+        waker: AtomicWaker::new(),
+        // Every actor is considered woken so that each is polled in the first round.
+        woken: Vec::from_iter(futures.iter().map(|_| AtomicBool::new(true))),
+    });
+    let wakers = Vec::from_iter((0..futures.len()).map(|index| {
+        Waker::from(Arc::new(ActorWaker {
+            index,
+            shared: shared.clone(),
+        }))
+    }));
+
+    core::future::poll_fn(|context| {
+        shared.waker.register(context.waker());
+
+        // Like the executor, only poll the woken actors in order. An actor woken by a later one is polled in the next
+        // round, as the outer waker was woken with it.
+        for (index, future) in futures.iter_mut().enumerate() {
+            if shared.woken[index].swap(false, Ordering::Relaxed) {
+                let mut context = Context::from_waker(&wakers[index]);
+                let Poll::Pending = future.as_mut().poll(&mut context);
+            }
+        }
+
+        // Start a new generation after every round so writers can write again once every reader has been polled.
+        store.source().increment_generation();
+
+        Poll::Pending
+    })
+    .await
+}
+
+/// The store of a runtime assembled with [`Runtime::builder`].
+///
+/// The store is created empty and has to be pinned before passing it to [`Runtime::builder`], its slots are created
+/// by [`RuntimeBuilder::build`].
+/// It can only be used by a single runtime.
+#[pin_project]
+#[derive(Default)]
+pub struct RuntimeStore {
+    #[pin]
+    source: generational::Source,
+    slots: OnceCell<Vec<Pin<Box<dyn ErasedSlot>>>>,
+}
+
+impl RuntimeStore {
+    /// Creates a new store without any slots.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl core::fmt::Debug for RuntimeStore {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RuntimeStore")
+            .field("source", &self.source)
+            .field("slots", &self.slots.get().map(Vec::len))
+            .finish()
+    }
+}
+
+impl Datastore for RuntimeStore {
+    fn source(self: Pin<&Self>) -> Pin<&generational::Source> {
+        self.project_ref().source
+    }
+
+    fn slot<S>(self: Pin<&Self>, requestor: &'static str) -> Pin<&S>
+    where
+        S: SlotTrait,
+    {
+        let slot = self
+            .get_ref()
+            .slots
+            .get()
+            .into_iter()
+            .flatten()
+            .find(|slot| slot.as_any().is::<S>())
+            .unwrap_or_else(|| {
+                panic!(
+                    "no slot available for `{}`, required by `{requestor}`",
+                    S::data_type_name()
+                )
+            });
+
+        // SAFETY:
+        // `Pin::map_unchecked`: The slot is pinned in its box, we're only transforming the type.
+        // `unwrap`: We verified above that the slot is of type `S`.
+        unsafe {
+            slot.as_ref()
+                .map_unchecked(|slot| slot.as_any().downcast_ref::<S>().unwrap())
+        }
+    }
+
+    #[cfg(feature = "introspection")]
+    fn for_each_slot(self: Pin<&Self>, f: &mut dyn FnMut(crate::introspection::SlotInfo<'_>)) {
+        for slot in self.get_ref().slots.get().into_iter().flatten() {
+            slot.inspect(f);
+        }
+    }
+}
+
+/// Object safe subset of [`SlotTrait`] to store slots of different types in a [`RuntimeStore`].
+trait ErasedSlot: Any {
+    /// Returns the slot for downcasting.
+    fn as_any(&self) -> &dyn Any;
+
+    /// Calls `f` with this slot.
+    #[cfg(feature = "introspection")]
+    fn inspect(&self, f: &mut dyn FnMut(crate::introspection::SlotInfo<'_>));
+}
+
+impl<S> ErasedSlot for S
+where
+    S: SlotTrait,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    #[cfg(feature = "introspection")]
+    fn inspect(&self, f: &mut dyn FnMut(crate::introspection::SlotInfo<'_>)) {
+        SlotTrait::inspect(self, &mut |value| {
+            f(crate::introspection::SlotInfo::new(
+                S::data_type_name(),
+                value,
+            ));
+        });
+    }
+}
+
+/// Internal helper to add the slots of an actor's cons-list of slot types to a [`RuntimeStore`].
+trait SlotList {
+    /// Adds every slot of this list that is not in `slots` yet, validating it against the access patterns of
+    /// `actors`.
+    fn add_slots(slots: &mut Vec<Pin<Box<dyn ErasedSlot>>>, actors: &[ActorEntry<'_>]);
+}
+
+impl SlotList for Nil {
+    fn add_slots(_slots: &mut Vec<Pin<Box<dyn ErasedSlot>>>, _actors: &[ActorEntry<'_>]) {}
+}
+
+impl<S> SlotList for S
+where
+    S: SlotTrait,
+{
+    fn add_slots(slots: &mut Vec<Pin<Box<dyn ErasedSlot>>>, actors: &[ActorEntry<'_>]) {
+        if slots.iter().any(|slot| slot.as_any().is::<S>()) {
+            return;
+        }
+
+        let type_id = S::data_type_id();
+        S::validate_access_pattern(
+            access(actors, |actor| (actor.writers)(type_id)),
+            access(actors, |actor| (actor.exclusive_readers)(type_id)),
+            access(actors, |actor| {
+                (actor.readers)(type_id) - (actor.exclusive_readers)(type_id)
+            }),
+        );
+
+        slots.push(Box::pin(S::new()));
+    }
+}
+
+impl<S, R> SlotList for Cons<S, R>
+where
+    S: SlotList,
+    R: SlotList,
+{
+    fn add_slots(slots: &mut Vec<Pin<Box<dyn ErasedSlot>>>, actors: &[ActorEntry<'_>]) {
+        S::add_slots(slots, actors);
+        R::add_slots(slots, actors);
+    }
+}
+
+/// Returns the total of `count` over `actors` and the type name of each actor repeated `count` times, as expected by
+/// [`SlotTrait::validate_access_pattern`].
+fn access<'b>(
+    actors: &'b [ActorEntry<'_>],
+    count: impl Fn(&ActorEntry<'_>) -> usize + Copy + 'b,
+) -> (usize, impl Iterator<Item = &'static str> + 'b) {
+    (
+        actors.iter().map(count).sum(),
+        actors
+            .iter()
+            .flat_map(move |actor| core::iter::repeat_n(actor.name, count(actor))),
+    )
+}
+
+/// State shared between the [`ActorWaker`]s of a runtime.
+struct WakeShared {
+    /// The outer [`Waker`] the runtime future is currently associated with.
+    waker: AtomicWaker,
+
+    /// Whether each actor was woken since it was last polled.
+    woken: Vec<AtomicBool>,
+}
+
+/// A [`Waker`] marking a single actor as woken and waking the outer task.
+struct ActorWaker {
+    index: usize,
+    shared: Arc<WakeShared>,
+}
+
+impl Wake for ActorWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.shared.woken[self.index].store(true, Ordering::Relaxed);
+        self.shared.waker.wake();
+    }
+}
@@ -47,4 +47,8 @@ pub trait Datastore {
     fn slot<S>(self: Pin<&Self>, requestor: &'static str) -> Pin<&S>
     where
         S: SlotTrait;
+
+    /// Calls `f` with every slot in the datastore.
+    #[cfg(feature = "introspection")]
+    fn for_each_slot(self: Pin<&Self>, f: &mut dyn FnMut(crate::introspection::SlotInfo<'_>));
 }
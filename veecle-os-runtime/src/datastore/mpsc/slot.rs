@@ -112,6 +112,24 @@ where
         core::any::type_name::<T>()
     }
 
+    #[cfg(feature = "introspection")]
+    fn inspect(&self, f: &mut dyn FnMut(&dyn core::fmt::Debug)) {
+        struct Items<'a, T>(&'a [RefCell<Option<T>>]);
+
+        impl<T> core::fmt::Debug for Items<'_, T>
+        where
+            T: core::fmt::Debug,
+        {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_list()
+                    .entries(self.0.iter().map(|item| item.borrow()))
+                    .finish()
+            }
+        }
+
+        f(&Items(&self.items));
+    }
+
     fn validate_access_pattern(
         (writers, writers_list): (usize, impl Iterator<Item = &'static str>),
         (exclusive_readers, exclusive_readers_list): (usize, impl Iterator<Item = &'static str>),
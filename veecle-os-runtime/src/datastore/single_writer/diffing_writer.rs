@@ -0,0 +1,198 @@
+//! Writer recording value changes as telemetry events.
+
+use super::slot::Slot;
+use super::writer::Writer;
+use crate::Sealed;
+use crate::cons::{Cons, Nil};
+use crate::datastore::Datastore;
+use crate::datastore::{DefinesSlot, Storable, StoreRequest};
+use core::pin::Pin;
+
+/// A [`Writer`] that records every change of the value as a telemetry event.
+///
+/// Each [`DiffingWriter::write`] and [`DiffingWriter::clear`] that changes the value adds a `value changed` event to
+/// the current span with the `type_name` of the [`Storable`] and the `old` and `new` values formatted with
+/// [`Debug`](core::fmt::Debug) (`None` if there was no value).
+/// Writing the value that is already stored, or clearing an empty slot, notifies readers but records nothing.
+/// This gives a record of how the state evolves in the trace without instrumenting the writer manually.
+///
+/// Unlike other telemetry integrations this isn't behind a feature flag: without the `veecle-telemetry` `enable`
+/// feature or a global collector the old value isn't compared either, which leaves no overhead on top of a [`Writer`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use veecle_os_runtime::{Never, Storable};
+/// use veecle_os_runtime::single_writer::DiffingWriter;
+///
+/// #[derive(Debug, PartialEq, Storable)]
+/// pub struct Gear(u8);
+///
+/// #[veecle_os_runtime::actor]
+/// async fn gearbox(mut gear: DiffingWriter<'_, Gear>) -> Never {
+///     loop {
+///         // Records e.g. `old = "Some(Gear(1))"`, `new = "Some(Gear(2))"`.
+///         gear.write(Gear(2)).await;
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct DiffingWriter<'a, T>
+where
+    T: Storable + 'static,
+{
+    writer: Writer<'a, T>,
+}
+
+impl<'a, T> DiffingWriter<'a, T>
+where
+    T: Storable + 'static,
+    T::DataType: PartialEq,
+{
+    /// Writes a new value, records the change if it differs from the old value and notifies readers.
+    ///
+    /// See [`Writer::write`].
+    pub async fn write(&mut self, item: T::DataType) {
+        self.writer
+            .modify(|mut slot| {
+                let old = slot.replace(item);
+                record_change::<T>(&old, &slot);
+            })
+            .await;
+    }
+
+    /// Clears the current value, records the change if there was a value and notifies readers.
+    ///
+    /// See [`Writer::clear`].
+    pub async fn clear(&mut self) {
+        self.writer
+            .modify(|mut slot| {
+                let old = slot.take();
+                record_change::<T>(&old, &slot);
+            })
+            .await;
+    }
+
+    /// Waits for the writer to be ready to perform a write operation.
+    ///
+    /// See [`Writer::ready`].
+    pub async fn ready(&mut self) {
+        self.writer.ready().await;
+    }
+
+    /// Returns the underlying [`Writer`], which no longer records changes.
+    pub fn into_inner(self) -> Writer<'a, T> {
+        self.writer
+    }
+}
+
+/// Adds an event recording the change of a `T` value from `old` to `new` to the current span, if they differ.
+///
+/// The values are only compared if telemetry is exported.
+fn record_change<T>(old: &Option<T::DataType>, new: &Option<T::DataType>)
+where
+    T: Storable,
+    T::DataType: PartialEq,
+{
+    if !veecle_telemetry::enabled() || old == new {
+        return;
+    }
+
+    veecle_telemetry::event!(
+        "value changed",
+        type_name = core::any::type_name::<T>(),
+        old = format_args!("{old:?}"),
+        new = format_args!("{new:?}")
+    );
+}
+
+impl<'a, T> From<Writer<'a, T>> for DiffingWriter<'a, T>
+where
+    T: Storable + 'static,
+{
+    fn from(writer: Writer<'a, T>) -> Self {
+        Self { writer }
+    }
+}
+
+impl<'a, T> DefinesSlot for DiffingWriter<'a, T>
+where
+    T: Storable,
+{
+    type Slot = Cons<Slot<T>, Nil>;
+}
+
+impl<T> Sealed for DiffingWriter<'_, T> where T: Storable + 'static {}
+
+impl<'a, T> StoreRequest<'a> for DiffingWriter<'a, T>
+where
+    T: Storable + 'static,
+{
+    async fn request(datastore: Pin<&'a impl Datastore>, requestor: &'static str) -> Self {
+        Writer::request(datastore, requestor).await.into()
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use crate::datastore::Storable;
+    use crate::datastore::single_writer::{DiffingWriter, Reader, Slot, Writer};
+    use crate::datastore::sync::generational;
+    use core::pin::pin;
+
+    #[test]
+    fn writes_and_clears() {
+        use futures::FutureExt;
+
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct Data(u8);
+        impl Storable for Data {
+            type DataType = Self;
+        }
+
+        let source = pin!(generational::Source::new());
+        let slot = pin!(Slot::<Data>::new());
+        let mut writer = DiffingWriter::from(Writer::new(source.as_ref().waiter(), slot.as_ref()));
+        let mut reader = Reader::from_slot(slot.as_ref());
+
+        source.as_ref().increment_generation();
+        writer.write(Data(1)).now_or_never().unwrap();
+        assert_eq!(reader.read_cloned(), Some(Data(1)));
+
+        // Like a `Writer`, the next write waits for the readers.
+        assert!(writer.ready().now_or_never().is_none());
+
+        source.as_ref().increment_generation();
+        writer.clear().now_or_never().unwrap();
+        assert_eq!(reader.read_cloned(), None);
+    }
+
+    #[test]
+    fn skips_comparison_without_telemetry() {
+        use futures::FutureExt;
+
+        /// Panics when compared, no global collector is set in unit tests so it must never be.
+        #[derive(Debug, Clone)]
+        pub struct Data;
+        impl PartialEq for Data {
+            fn eq(&self, _: &Self) -> bool {
+                panic!("compared without telemetry");
+            }
+        }
+        impl Storable for Data {
+            type DataType = Self;
+        }
+
+        let source = pin!(generational::Source::new());
+        let slot = pin!(Slot::<Data>::new());
+        let mut writer = DiffingWriter::from(Writer::new(source.as_ref().waiter(), slot.as_ref()));
+        let _reader = Reader::from_slot(slot.as_ref());
+
+        assert!(!veecle_telemetry::enabled());
+        for _ in 0..2 {
+            source.as_ref().increment_generation();
+            writer.write(Data).now_or_never().unwrap();
+        }
+    }
+}
@@ -16,8 +16,8 @@ use crate::datastore::{CombinableReader, Datastore, DefinesSlot, Storable, Store
 /// The generic type `T` from the reader specifies the type of the value that is being read.
 ///
 /// The reader allows reading the current value.
-/// If no value for type `T` has been written yet, [`ExclusiveReader::read`] and
-/// [`ExclusiveReader::take`] will return `None`.
+/// If no value for type `T` has been written yet, or the [`Writer`][super::Writer] cleared it,
+/// [`ExclusiveReader::read`] and [`ExclusiveReader::take`] will return `None`.
 ///
 /// # Usage
 ///
@@ -98,11 +98,12 @@ where
     /// Reads the next unseen value of a type.
     ///
     /// Waits until an unseen value is available, then reads it.
+    /// Values cleared with [`Writer::clear`][super::Writer::clear] are skipped.
     /// Marks the current value as seen.
     /// This method takes a closure to ensure the reference is not held across await points.
     #[veecle_telemetry::instrument]
     pub async fn read_updated<U>(&mut self, f: impl FnOnce(&T::DataType) -> U) -> U {
-        self.wait_for_update().await;
+        self.waiter.wait_for_value().await;
         self.waiter.update_generation();
         self.waiter.read(|value| {
             let value = value.as_ref().unwrap();
@@ -127,6 +128,7 @@ where
     /// Reads and clones the next unseen value.
     ///
     /// Waits until an unseen value is available, then reads it.
+    /// Values cleared with [`Writer::clear`][super::Writer::clear] are skipped.
     /// Marks the current value as seen.
     /// This is a wrapper around [`Self::read_updated`] that additionally clones the value.
     /// You can use it instead of `reader.read_updated(|c| c.clone())`.
@@ -176,12 +178,13 @@ where
     /// Takes the next unseen value of the type, leaving behind `None`.
     ///
     /// Waits until an unseen value is available, then takes it.
+    /// Values cleared with [`Writer::clear`][super::Writer::clear] are skipped.
     /// Marks the current value as seen.
     pub async fn take_updated(&mut self) -> T::DataType {
         let span = veecle_telemetry::span!("take");
         let _guard = span.enter();
 
-        self.wait_for_update().await;
+        self.waiter.wait_for_value().await;
         self.waiter.update_generation();
 
         let value = self.waiter.take(span.context()).unwrap();
@@ -190,6 +193,43 @@ where
 
         value
     }
+
+    /// Transforms the current value of the type in place by moving it through `f`.
+    ///
+    /// The value is moved into `f` and the returned value is stored in its place, without requiring `T::DataType` to
+    /// be [`Clone`].
+    /// If no value is available, `f` is not called.
+    /// Marks the current value as seen.
+    ///
+    /// Replacing is not a write: it neither wakes this reader nor affects when the [`Writer`][super::Writer] may write
+    /// again.
+    /// The next write from the [`Writer`][super::Writer] overwrites the replaced value as usual.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use veecle_os_runtime::{Storable, single_writer::ExclusiveReader};
+    /// #
+    /// # #[derive(Debug, Storable)]
+    /// # pub struct Batch(Vec<u8>);
+    /// #
+    /// #[veecle_os_runtime::actor]
+    /// async fn batch_reader(mut reader: ExclusiveReader<'_, Batch>) -> veecle_os_runtime::Never {
+    ///     loop {
+    ///         reader.wait_for_update().await.replace(|Batch(mut bytes)| {
+    ///             bytes.retain(|byte| *byte != 0);
+    ///             Batch(bytes)
+    ///         });
+    ///     }
+    /// }
+    /// ```
+    pub fn replace(&mut self, f: impl FnOnce(T::DataType) -> T::DataType) {
+        let span = veecle_telemetry::span!("replace");
+        let _guard = span.enter();
+
+        self.waiter.update_generation();
+        self.waiter.replace(f, span.context());
+    }
 }
 
 impl<'a, T> ExclusiveReader<'a, T>
@@ -299,6 +339,35 @@ mod tests {
         assert_eq!(reader.take(), None);
     }
 
+    #[test]
+    fn replace() {
+        #[derive(Eq, PartialEq, Debug, Storable)]
+        #[storable(crate = crate)]
+        struct Queue(std::vec::Vec<u8>);
+
+        let source = pin!(generational::Source::new());
+        let slot = pin!(Slot::<Queue>::new());
+
+        let mut reader = ExclusiveReader::from_slot(slot.as_ref());
+        let mut writer = Writer::new(source.as_ref().waiter(), slot.as_ref());
+
+        reader.replace(|_| unreachable!("no value has been written"));
+        assert_eq!(reader.take(), None);
+
+        source.as_ref().increment_generation();
+        writer
+            .write(Queue(std::vec![1, 2, 3]))
+            .now_or_never()
+            .unwrap();
+
+        reader.replace(|Queue(mut items)| {
+            items.remove(0);
+            Queue(items)
+        });
+        assert!(!reader.is_updated());
+        assert_eq!(reader.take(), Some(Queue(std::vec![2, 3])));
+    }
+
     #[test]
     fn read_updated() {
         #[derive(Eq, PartialEq, Debug, Clone, Storable)]
@@ -338,6 +407,13 @@ mod tests {
 
         assert_eq!(reader.take_updated().now_or_never(), Some(Sensor(1)));
         assert_eq!(reader.take_updated().now_or_never(), None);
+
+        source.as_ref().increment_generation();
+        writer.clear().now_or_never().unwrap();
+
+        assert!(reader.is_updated());
+        assert_eq!(reader.take_updated().now_or_never(), None);
+        assert!(!reader.is_updated());
     }
 
     #[test]
@@ -3,12 +3,14 @@
 //! This module provides a slot implementation where exactly one writer
 //! can write to a slot, and multiple readers can read from it.
 
+mod diffing_writer;
 mod exclusive_reader;
 mod reader;
 mod slot;
 mod waiter;
 mod writer;
 
+pub use self::diffing_writer::DiffingWriter;
 pub use self::exclusive_reader::ExclusiveReader;
 pub use self::reader::Reader;
 pub(crate) use self::slot::Slot;
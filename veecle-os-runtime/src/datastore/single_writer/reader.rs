@@ -18,7 +18,8 @@ use crate::datastore::{CombinableReader, Datastore, DefinesSlot, Storable, Store
 /// The generic type `T` from the reader specifies the type of the value that is being read.
 ///
 /// The reader allows reading the current value.
-/// If no value for type `T` has been written to yet, [`Reader::read`] will return `None`.
+/// If no value for type `T` has been written to yet, or the [`Writer`][super::Writer] cleared it,
+/// [`Reader::read`] will return `None`.
 ///
 /// # Usage
 ///
@@ -50,6 +51,23 @@ use crate::datastore::{CombinableReader, Datastore, DefinesSlot, Storable, Store
 /// [`Reader::is_updated`] returns `true` if the current value is unseen.
 /// [`Reader::wait_for_update`] waits until an unseen value is available.
 ///
+/// # Cleared values
+///
+/// A [`Writer`][super::Writer] can retract its value with [`Writer::clear`][super::Writer::clear], e.g. because an
+/// input became stale.
+/// Clearing counts as a write: [`Reader::wait_for_update`] resolves and [`Reader::read`] then returns `None`,
+/// distinguishing a retracted value from any value of `T`.
+/// [`Reader::read_updated`] skips cleared values and waits for the next written value instead.
+///
+/// # Generations
+///
+/// Every write to the slot increments its generation, see [`Reader::generation`].
+/// An actor that reads on its own schedule can remember the generation it last acted on and later check with
+/// [`Reader::changed_since`] whether the value was written in the meantime, and by comparing generations how many
+/// writes it missed.
+/// Unlike the seen state, generations are shared by all readers and are not affected by reading, so checking them
+/// neither marks the value as seen nor lets the [`Writer`][super::Writer] continue.
+///
 /// # Example
 ///
 /// ```rust
@@ -103,11 +121,12 @@ where
     /// Reads the next unseen value of a type.
     ///
     /// Waits until an unseen value is available, then reads it.
+    /// Values cleared with [`Writer::clear`][super::Writer::clear] are skipped.
     /// Marks the current value as seen.
     /// This method takes a closure to ensure the reference is not held across await points.
     #[veecle_telemetry::instrument]
     pub async fn read_updated<U>(&mut self, f: impl FnOnce(&T::DataType) -> U) -> U {
-        self.wait_for_update().await;
+        self.waiter.wait_for_value().await;
         self.waiter.update_generation();
         self.waiter.read(|value| {
             let value = value.as_ref().unwrap();
@@ -132,6 +151,7 @@ where
     /// Reads and clones the next unseen value.
     ///
     /// Waits until an unseen value is available, then reads it.
+    /// Values cleared with [`Writer::clear`][super::Writer::clear] are skipped.
     /// Marks the current value as seen.
     /// This is a wrapper around [`Self::read_updated`] that additionally clones the value.
     /// You can use it instead of `reader.read_updated(|c| c.clone())`.
@@ -150,6 +170,44 @@ where
         self.waiter.is_updated()
     }
 
+    /// Returns the generation of the slot, the number of writes (including clears) to it so far.
+    ///
+    /// The generation is the same for all readers of the slot and only ever increases.
+    /// Calling this does not mark the current value as seen.
+    pub fn generation(&self) -> usize {
+        self.waiter.generation()
+    }
+
+    /// Returns `true` if the slot was written since it had the given `generation`.
+    ///
+    /// Unlike [`is_updated`][Self::is_updated], this is independent of which values this reader has seen and does not
+    /// mark the current value as seen, so the [`Writer`][super::Writer] still waits for this reader before writing
+    /// again.
+    ///
+    /// ```rust
+    /// # use veecle_os_runtime::{Never, Storable, single_writer::Reader};
+    /// #
+    /// # #[derive(Debug, Default, Storable)]
+    /// # pub struct Foo;
+    /// #
+    /// #[veecle_os_runtime::actor]
+    /// async fn foo_reader(mut reader: Reader<'_, Foo>) -> Never {
+    ///     let mut last = reader.generation();
+    ///     loop {
+    ///         // ... do other work, e.g. wait for a timer ...
+    ///         if reader.changed_since(last) {
+    ///             last = reader.generation();
+    ///             reader.read(|value: Option<&Foo>| {
+    ///                 // do something with the value.
+    ///             });
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn changed_since(&self, generation: usize) -> bool {
+        self.generation() != generation
+    }
+
     /// Waits for any write to occur.
     ///
     /// This future resolving does not imply that `previous_value != new_value`, just that a
@@ -292,6 +350,37 @@ mod tests {
         assert!(!reader.is_updated());
     }
 
+    #[test]
+    fn changed_since() {
+        #[derive(Eq, PartialEq, Debug, Clone, Storable)]
+        #[storable(crate = crate)]
+        struct Sensor(u8);
+
+        let source = pin!(generational::Source::new());
+        let slot = pin!(Slot::<Sensor>::new());
+
+        let mut reader = Reader::from_slot(slot.as_ref());
+        let mut writer = Writer::new(source.as_ref().waiter(), slot.as_ref());
+
+        let generation = reader.generation();
+        assert!(!reader.changed_since(generation));
+
+        source.as_ref().increment_generation();
+        writer.write(Sensor(1)).now_or_never().unwrap();
+
+        assert!(reader.changed_since(generation));
+        assert_eq!(reader.generation(), generation + 1);
+
+        // Checking the generation does not mark the value as seen, reading does not reset the generation.
+        assert!(reader.is_updated());
+        reader.read(|x| assert_eq!(x, Some(&Sensor(1))));
+        assert!(reader.changed_since(generation));
+
+        source.as_ref().increment_generation();
+        writer.clear().now_or_never().unwrap();
+        assert_eq!(reader.generation(), generation + 2);
+    }
+
     #[test]
     fn wait_for_update() {
         #[derive(Eq, PartialEq, Debug, Clone, Storable)]
@@ -313,4 +402,38 @@ mod tests {
         reader.read(|x| assert_eq!(x, Some(&Sensor(1))));
         assert!(reader.wait_for_update().now_or_never().is_none());
     }
+
+    #[test]
+    fn clear() {
+        #[derive(Eq, PartialEq, Debug, Clone, Storable)]
+        #[storable(crate = crate)]
+        struct Sensor(u8);
+
+        let source = pin!(generational::Source::new());
+        let slot = pin!(Slot::<Sensor>::new());
+
+        let mut reader = Reader::from_slot(slot.as_ref());
+        let mut writer = Writer::new(source.as_ref().waiter(), slot.as_ref());
+
+        source.as_ref().increment_generation();
+        writer.write(Sensor(1)).now_or_never().unwrap();
+        assert_eq!(reader.read_cloned(), Some(Sensor(1)));
+
+        source.as_ref().increment_generation();
+        writer.clear().now_or_never().unwrap();
+
+        assert!(reader.is_updated());
+        assert_eq!(reader.read_cloned(), None);
+        assert!(!reader.is_updated());
+
+        // `read_updated` skips the cleared value and waits for the next written one.
+        source.as_ref().increment_generation();
+        writer.clear().now_or_never().unwrap();
+        assert_eq!(reader.read_updated_cloned().now_or_never(), None);
+        assert!(!reader.is_updated());
+
+        source.as_ref().increment_generation();
+        writer.write(Sensor(2)).now_or_never().unwrap();
+        assert_eq!(reader.read_updated_cloned().now_or_never(), Some(Sensor(2)));
+    }
 }
@@ -25,6 +25,11 @@ where
 
     writer_context: Cell<Option<SpanContext>>,
 
+    /// Number of live readers of this slot.
+    reader_count: Cell<usize>,
+    /// Number of readers that have not yet seen the latest write.
+    pending_readers: Cell<usize>,
+
     item: RefCell<Option<T::DataType>>,
 }
 
@@ -38,6 +43,8 @@ where
             source: generational::Source::new(),
             writer_taken: Cell::new(false),
             writer_context: Cell::new(None),
+            reader_count: Cell::new(0),
+            pending_readers: Cell::new(0),
         }
     }
 
@@ -53,6 +60,25 @@ where
         self.borrow_mut(span_context).take()
     }
 
+    /// Replaces the current value of the slot with the result of `f`, leaving `None` untouched.
+    ///
+    /// Stores the provided `span_context` to connect this write to the next read operation.
+    #[veecle_telemetry::instrument]
+    pub(crate) fn replace(
+        &self,
+        f: impl FnOnce(T::DataType) -> T::DataType,
+        span_context: Option<SpanContext>,
+    ) {
+        if let Some(writer_context) = self.writer_context.get() {
+            veecle_telemetry::CurrentSpan::add_link(writer_context);
+        }
+
+        let mut item = self.borrow_mut(span_context);
+        if let Some(value) = item.take() {
+            *item = Some(f(value));
+        }
+    }
+
     /// Returns the type name of the value stored in this slot.
     pub(crate) fn inner_type_name(&self) -> &'static str {
         core::any::type_name::<T>()
@@ -60,6 +86,7 @@ where
 
     /// Returns a new waiter for this slot.
     pub(crate) fn waiter(self: Pin<&Self>) -> Waiter<'_, T> {
+        self.reader_count.set(self.reader_count.get() + 1);
         Waiter::new(self, self.project_ref().source.waiter())
     }
 
@@ -71,6 +98,11 @@ where
         );
     }
 
+    /// Returns `true` if the slot currently holds a value.
+    pub(crate) fn has_value(&self) -> bool {
+        self.item.borrow().is_some()
+    }
+
     pub(crate) fn borrow(&self) -> Ref<'_, Option<T::DataType>> {
         if let Some(writer_context) = self.writer_context.get() {
             veecle_telemetry::CurrentSpan::add_link(writer_context);
@@ -108,7 +140,29 @@ where
         f(&mut *self.borrow_mut(span_context))
     }
 
+    /// Records that a reader of this slot was dropped.
+    pub(super) fn remove_reader(&self) {
+        self.reader_count.set(self.reader_count.get() - 1);
+    }
+
+    /// Records that a reader has seen the latest write.
+    pub(super) fn mark_seen(&self) {
+        self.pending_readers
+            .set(self.pending_readers.get().saturating_sub(1));
+    }
+
+    /// Returns the number of readers that have not yet seen the latest write.
+    pub(crate) fn pending_reader_count(&self) -> usize {
+        self.pending_readers.get()
+    }
+
+    /// Returns the number of writes to this slot so far.
+    pub(crate) fn generation(&self) -> usize {
+        self.source.generation()
+    }
+
     pub(crate) fn increment_generation(self: Pin<&Self>) {
+        self.pending_readers.set(self.reader_count.get());
         self.project_ref().source.increment_generation();
     }
 }
@@ -129,6 +183,11 @@ where
         core::any::type_name::<T>()
     }
 
+    #[cfg(feature = "introspection")]
+    fn inspect(&self, f: &mut dyn FnMut(&dyn core::fmt::Debug)) {
+        f(&*self.item.borrow());
+    }
+
     fn validate_access_pattern(
         (writers, writers_list): (usize, impl Iterator<Item = &'static str>),
         (exclusive_readers, exclusive_readers_list): (usize, impl Iterator<Item = &'static str>),
@@ -177,6 +236,8 @@ where
         debug.field("source", &self.source);
         debug.field("writer_taken", &self.writer_taken);
         debug.field("writer_context", &self.writer_context.get());
+        debug.field("reader_count", &self.reader_count);
+        debug.field("pending_readers", &self.pending_readers);
         debug.field("item", &"<opaque>");
 
         debug.finish()
@@ -40,9 +40,16 @@ where
 
     /// Updates the last seen generation of this waiter so that we will wait for a newer value.
     pub(crate) fn update_generation(&mut self) {
+        if self.waiter.is_updated() {
+            self.slot.mark_seen();
+        }
         self.waiter.update_generation();
     }
 
+    pub(crate) fn generation(&self) -> usize {
+        self.slot.generation()
+    }
+
     pub(crate) fn borrow(&self) -> core::cell::Ref<'_, Option<T::DataType>> {
         self.slot.borrow()
     }
@@ -67,6 +74,19 @@ where
         }
     }
 
+    /// Waits for an unseen write that left a value in the slot.
+    ///
+    /// Unseen writes that cleared the slot are marked as seen and skipped.
+    pub(crate) async fn wait_for_value(&mut self) {
+        loop {
+            self.wait().await;
+            if self.slot.has_value() {
+                return;
+            }
+            self.update_generation();
+        }
+    }
+
     /// Takes the current value of the slot, leaving behind `None`.
     ///
     /// Stores the provided `span_context` to connect this write to the next read operation.
@@ -76,4 +96,28 @@ where
     ) -> Option<T::DataType> {
         self.slot.take(span_context)
     }
+
+    /// Replaces the current value of the slot with the result of `f`, leaving `None` untouched.
+    ///
+    /// Stores the provided `span_context` to connect this write to the next read operation.
+    pub(crate) fn replace(
+        &mut self,
+        f: impl FnOnce(T::DataType) -> T::DataType,
+        span_context: Option<veecle_telemetry::SpanContext>,
+    ) {
+        self.slot.replace(f, span_context)
+    }
+}
+
+impl<T> Drop for Waiter<'_, T>
+where
+    T: Storable + 'static,
+{
+    fn drop(&mut self) {
+        // A dropped reader is no longer waited for, neither for the latest write nor for the following ones.
+        if self.waiter.is_updated() {
+            self.slot.mark_seen();
+        }
+        self.slot.remove_reader();
+    }
 }
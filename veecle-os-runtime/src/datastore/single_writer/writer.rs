@@ -118,6 +118,31 @@ where
         .await;
     }
 
+    /// Clears the current value and notifies readers.
+    ///
+    /// Readers observe the clear as an update and read `None` until the next write, which distinguishes a retracted
+    /// value from a default one.
+    /// [`Reader::read_updated`][super::Reader::read_updated] and similar methods skip cleared values.
+    /// Like [`Writer::write`], this yields to any readers needing to read the last value.
+    #[veecle_telemetry::instrument]
+    pub async fn clear(&mut self) {
+        self.modify(|mut slot| {
+            *slot = None;
+        })
+        .await;
+    }
+
+    /// Returns the number of readers that have not read the slot since the latest write or clear.
+    ///
+    /// Every write resets the count to the number of readers, each reader is subtracted once it reads, takes or
+    /// replaces the value (or skips a cleared value) for the first time after that write.
+    /// Readers that missed several writes are counted once, readers that already read the latest value are not counted
+    /// again when they read it repeatedly.
+    /// This is intended for diagnostics, e.g. reporting readers that fall behind via telemetry.
+    pub fn pending_reader_count(&self) -> usize {
+        self.slot.pending_reader_count()
+    }
+
     /// Waits for the writer to be ready to perform a write operation.
     ///
     /// After awaiting this method, the next call to [`Writer::write()`]
@@ -232,6 +257,131 @@ mod tests {
         assert!(writer.write(Data {}).now_or_never().is_none());
     }
 
+    #[test]
+    fn pending_reader_count() {
+        use futures::FutureExt;
+
+        use crate::datastore::single_writer::Reader;
+
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct Data;
+        impl Storable for Data {
+            type DataType = Self;
+        }
+
+        let source = pin!(generational::Source::new());
+        let slot = pin!(Slot::<Data>::new());
+        let mut writer = Writer::new(source.as_ref().waiter(), slot.as_ref());
+        let mut reader_a = Reader::from_slot(slot.as_ref());
+        let mut reader_b = Reader::from_slot(slot.as_ref());
+
+        assert_eq!(writer.pending_reader_count(), 0);
+
+        source.as_ref().increment_generation();
+        writer.write(Data).now_or_never().unwrap();
+        assert_eq!(writer.pending_reader_count(), 2);
+
+        reader_a.read(|_| ());
+        assert_eq!(writer.pending_reader_count(), 1);
+
+        // Reading the same value again does not count twice.
+        reader_a.read(|_| ());
+        assert_eq!(writer.pending_reader_count(), 1);
+
+        assert_eq!(reader_b.read_cloned(), Some(Data));
+        assert_eq!(writer.pending_reader_count(), 0);
+    }
+
+    #[test]
+    fn pending_reader_count_mixed_readers() {
+        use futures::FutureExt;
+
+        use crate::datastore::single_writer::{ExclusiveReader, Reader};
+
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct Data(usize);
+        impl Storable for Data {
+            type DataType = Self;
+        }
+
+        let source = pin!(generational::Source::new());
+        let slot = pin!(Slot::<Data>::new());
+        let mut writer = Writer::new(source.as_ref().waiter(), slot.as_ref());
+        let mut reader_a = Reader::from_slot(slot.as_ref());
+        let mut reader_b = Reader::from_slot(slot.as_ref());
+        let mut reader_c = ExclusiveReader::from_slot(slot.as_ref());
+
+        source.as_ref().increment_generation();
+        writer.write(Data(1)).now_or_never().unwrap();
+        assert_eq!(writer.pending_reader_count(), 3);
+
+        // `reader_a` reads, `reader_b` does not, `reader_c` consumes the value.
+        assert_eq!(reader_a.read_cloned(), Some(Data(1)));
+        assert_eq!(reader_c.take(), Some(Data(1)));
+        assert_eq!(writer.pending_reader_count(), 1);
+
+        // Readers that already read the value are not counted again, even though it was taken in the meantime.
+        assert_eq!(reader_a.read_cloned(), None);
+        assert_eq!(reader_c.take(), None);
+        assert_eq!(writer.pending_reader_count(), 1);
+
+        source.as_ref().increment_generation();
+        writer.write(Data(2)).now_or_never().unwrap();
+        assert_eq!(writer.pending_reader_count(), 3);
+
+        // `reader_b` missed the first write, it is still counted only once.
+        assert_eq!(reader_b.read_cloned(), Some(Data(2)));
+        assert_eq!(writer.pending_reader_count(), 2);
+
+        assert_eq!(reader_a.read_cloned(), Some(Data(2)));
+        assert_eq!(reader_c.take(), Some(Data(2)));
+        assert_eq!(writer.pending_reader_count(), 0);
+    }
+
+    #[test]
+    fn pending_reader_count_dropped_readers() {
+        use futures::FutureExt;
+
+        use crate::datastore::single_writer::{ExclusiveReader, Reader};
+
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct Data(usize);
+        impl Storable for Data {
+            type DataType = Self;
+        }
+
+        let source = pin!(generational::Source::new());
+        let slot = pin!(Slot::<Data>::new());
+        let mut writer = Writer::new(source.as_ref().waiter(), slot.as_ref());
+        let mut reader_a = Reader::from_slot(slot.as_ref());
+        let reader_b = Reader::from_slot(slot.as_ref());
+        let reader_c = ExclusiveReader::from_slot(slot.as_ref());
+
+        // Readers dropped before a write are not waited for.
+        drop(reader_c);
+        source.as_ref().increment_generation();
+        writer.write(Data(1)).now_or_never().unwrap();
+        assert_eq!(writer.pending_reader_count(), 2);
+
+        // A reader dropped before reading the latest write is no longer pending.
+        drop(reader_b);
+        assert_eq!(writer.pending_reader_count(), 1);
+
+        // A reader dropped after reading doesn't affect the count for the current write, only for the next ones.
+        let mut reader_d = Reader::from_slot(slot.as_ref());
+        assert_eq!(reader_a.read_cloned(), Some(Data(1)));
+        assert_eq!(writer.pending_reader_count(), 0);
+        drop(reader_a);
+        assert_eq!(writer.pending_reader_count(), 0);
+
+        source.as_ref().increment_generation();
+        writer.write(Data(2)).now_or_never().unwrap();
+        assert_eq!(writer.pending_reader_count(), 1);
+
+        assert_eq!(reader_d.read_cloned(), Some(Data(2)));
+        assert_eq!(writer.pending_reader_count(), 0);
+    }
+
     #[test]
     fn modify_only_blocks_next_write_when_returning_true() {
         use futures::FutureExt;
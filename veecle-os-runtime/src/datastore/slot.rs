@@ -14,6 +14,10 @@ pub(crate) trait SlotTrait: Sized + 'static + core::any::Any {
     /// Returns the type name of the data type stored in this slot.
     fn data_type_name() -> &'static str;
 
+    /// Calls `f` with the current value of this slot formatted for introspection.
+    #[cfg(feature = "introspection")]
+    fn inspect(&self, f: &mut dyn FnMut(&dyn core::fmt::Debug));
+
     /// Validates that this slot type meets its requirements given the access patterns.
     ///
     /// The defining reader/writer amount cannot be zero because a slot is only created for types
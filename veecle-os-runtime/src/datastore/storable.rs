@@ -40,7 +40,59 @@ use core::fmt::Debug;
 ///     type DataType = Self;
 /// }
 /// ```
+///
+/// # Provided implementations
+///
+/// Primitive integers, floats, `bool`, `char` and arrays of [`Debug`] types implement [`Storable`] with
+/// `DataType = Self`, so trivial values can be stored without a newtype.
+///
+/// Slots are identified by the [`Storable`] type, so all actors using e.g. `u32` share the same slot.
+/// As every slot allows only a single writer, two actors writing a `u32` are rejected when the runtime is created.
+/// Use a newtype (or an identifier type as shown above) to keep semantically distinct values apart.
+///
+/// ```
+/// use veecle_os_runtime::Never;
+/// use veecle_os_runtime::single_writer::Writer;
+///
+/// #[veecle_os_runtime::actor]
+/// async fn counter(mut writer: Writer<'_, u32>) -> Never {
+///     let mut count = 0;
+///     loop {
+///         writer.write(count).await;
+///         count += 1;
+///     }
+/// }
+/// ```
 pub trait Storable {
     /// The data type being read/written from/to a slot.
     type DataType: Debug;
+
+    /// The version of the encoded schema of [`Self::DataType`].
+    ///
+    /// Increment this on incompatible changes to the encoded data type, so peers exchanging encoded values (e.g. via
+    /// `veecle-ipc`) can detect values encoded by an older or newer version.
+    /// Values without a version are treated as version `0`.
+    const SCHEMA_VERSION: u32 = 0;
+}
+
+/// Implements [`Storable`] with `DataType = Self` for the given types.
+macro_rules! impl_storable_for_self {
+    ($($type:ty),* $(,)?) => {
+        $(
+            impl Storable for $type {
+                type DataType = Self;
+            }
+        )*
+    };
+}
+
+impl_storable_for_self!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool, char,
+);
+
+impl<T, const N: usize> Storable for [T; N]
+where
+    T: Debug,
+{
+    type DataType = Self;
 }
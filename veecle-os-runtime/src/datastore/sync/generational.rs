@@ -27,6 +27,11 @@ impl Source {
         Self::default()
     }
 
+    /// Returns the current generation, starting at `0` and incremented by every [`Source::increment_generation`].
+    pub(crate) fn generation(&self) -> usize {
+        self.generation.get()
+    }
+
     /// Returns a new waiter for this source.
     ///
     /// # Panics
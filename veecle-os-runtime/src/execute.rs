@@ -10,9 +10,10 @@ use crate::Never;
 use crate::actor::Actor;
 use crate::cons::{Cons, Nil, TupleConsToCons};
 use crate::datastore::mpsc;
-use crate::datastore::single_writer::{ExclusiveReader, Reader, Writer};
+use crate::datastore::single_writer::{DiffingWriter, ExclusiveReader, Reader, Writer};
 use crate::datastore::sync::generational;
 use crate::datastore::{Datastore, SlotTrait, Storable, StoreRequest};
+use crate::panic::{IsolatedActor, PanicPolicy};
 use core::any::TypeId;
 use core::pin::Pin;
 
@@ -23,6 +24,10 @@ trait SlotAccess {
     fn try_slot<S>(self: Pin<&Self>) -> Option<Pin<&S>>
     where
         S: SlotTrait;
+
+    /// Calls `f` with every slot in this list.
+    #[cfg(feature = "introspection")]
+    fn for_each_slot(self: Pin<&Self>, f: &mut dyn FnMut(crate::introspection::SlotInfo<'_>));
 }
 
 impl SlotAccess for Nil {
@@ -32,6 +37,9 @@ impl SlotAccess for Nil {
     {
         None
     }
+
+    #[cfg(feature = "introspection")]
+    fn for_each_slot(self: Pin<&Self>, _f: &mut dyn FnMut(crate::introspection::SlotInfo<'_>)) {}
 }
 
 impl<T> SlotAccess for T
@@ -55,6 +63,16 @@ where
             None
         }
     }
+
+    #[cfg(feature = "introspection")]
+    fn for_each_slot(self: Pin<&Self>, f: &mut dyn FnMut(crate::introspection::SlotInfo<'_>)) {
+        self.inspect(&mut |value| {
+            f(crate::introspection::SlotInfo::new(
+                T::data_type_name(),
+                value,
+            ));
+        });
+    }
 }
 
 impl<U, R> SlotAccess for Cons<U, R>
@@ -70,10 +88,17 @@ where
 
         this.0.try_slot::<S>().or_else(|| this.1.try_slot::<S>())
     }
+
+    #[cfg(feature = "introspection")]
+    fn for_each_slot(self: Pin<&Self>, f: &mut dyn FnMut(crate::introspection::SlotInfo<'_>)) {
+        let this = self.project_ref();
+        this.0.for_each_slot(f);
+        this.1.for_each_slot(f);
+    }
 }
 
 /// Internal helper to construct runtime slot instances from a type-level cons list of slots.
-trait IntoSlotConsList {
+pub(crate) trait IntoSlotConsList {
     /// The same cons-list type, used to construct slot instances.
     type Slots: SlotAccess;
 
@@ -182,6 +207,11 @@ where
             )
         })
     }
+
+    #[cfg(feature = "introspection")]
+    fn for_each_slot(self: Pin<&Self>, f: &mut dyn FnMut(crate::introspection::SlotInfo<'_>)) {
+        self.project_ref().1.for_each_slot(f);
+    }
 }
 
 /// Given a cons-list of slot types, returns a complete [`Datastore`] that contains those slots.
@@ -219,6 +249,15 @@ where
     }
 }
 
+impl<T> AccessKind for DiffingWriter<'_, T>
+where
+    T: Storable + 'static,
+{
+    fn writer(type_id: TypeId) -> bool {
+        type_id == TypeId::of::<T>()
+    }
+}
+
 impl<T> AccessKind for Reader<'_, T>
 where
     T: Storable + 'static,
@@ -448,16 +487,19 @@ where
 }
 
 /// Internal helper to get a full future that initializes and executes an [`Actor`] given a [`Datastore`]
+///
+/// Panics of the actor are reported and handled according to `panic_policy`.
 pub async fn execute_actor<'a, A>(
     store: Pin<&'a impl Datastore>,
     init_context: A::InitContext,
+    panic_policy: PanicPolicy,
 ) -> Never
 where
     A: Actor<'a>,
 {
     let requestor = core::any::type_name::<A>();
     veecle_telemetry::future::FutureExt::with_span(
-        async move {
+        IsolatedActor::new(requestor, panic_policy, async move {
             match A::new(
                 A::StoreRequest::request(store, requestor).await,
                 init_context,
@@ -467,7 +509,7 @@ where
             {
                 Err(error) => panic!("{error}"),
             }
-        },
+        }),
         veecle_telemetry::span!("actor", actor = core::any::type_name::<A>()),
     )
     .await
@@ -475,6 +517,40 @@ where
 
 /// Execute a given set of actors without heap allocation.
 ///
+/// An optional `panic_policy: PanicPolicy::...` argument after the actors selects what happens when an actor panics,
+/// see [`PanicPolicy`](crate::PanicPolicy).
+///
+/// # Executor capacity
+///
+/// The actors are run by an executor with a fixed capacity, which defaults to the number of actors.
+/// An optional `capacity: N` argument after the other arguments sets it explicitly, e.g. to keep the size of the
+/// executor the same across targets with different sets of actors.
+/// Each unit of capacity takes four words whether an actor uses it or not, two in a `static` and two in the runtime
+/// future, so it should be no larger than needed on tightly constrained targets.
+/// Passing more actors than the capacity fails to compile:
+///
+/// ```rust,compile_fail
+/// use veecle_os_runtime::Never;
+///
+/// #[veecle_os_runtime::actor]
+/// async fn idle_actor() -> Never {
+///     core::future::pending().await
+/// }
+///
+/// #[veecle_os_runtime::actor]
+/// async fn other_idle_actor() -> Never {
+///     core::future::pending().await
+/// }
+///
+/// let runtime = veecle_os_runtime::execute! {
+///     actors: [IdleActor, OtherIdleActor],
+///     capacity: 1,
+/// };
+/// # let _ = futures::executor::block_on(runtime);
+/// ```
+///
+/// # Examples
+///
 /// ```rust
 /// use core::fmt::Debug;
 ///
@@ -529,9 +605,86 @@ macro_rules! execute {
     (
         actors: [
             $($actor_type:ty $(: $init_context:expr )? ),* $(,)?
-        ] $(,)?
+        ]
+        $(, panic_policy: $panic_policy:expr)?
+        $(, capacity: $capacity:expr)? $(,)?
+    ) => {
+        $crate::__execute! {
+            executor => executor.run().await,
+            actors: [$($actor_type $(: $init_context)?,)*],
+            panic_policy: [$($panic_policy)?],
+            capacity: [$($capacity)?],
+        }
+    };
+}
+
+/// Execute a given set of actors without heap allocation until a shutdown is requested.
+///
+/// Takes the same input as [`execute!`] and returns a tuple of a [`ShutdownHandle`](crate::ShutdownHandle) and the
+/// runtime future.
+/// Calling [`ShutdownHandle::shutdown`](crate::ShutdownHandle::shutdown) makes the runtime stop polling the actors and
+/// the future resolve, dropping all actor futures.
+///
+/// The handle can be used from any thread or task, e.g. one handling termination signals, or from an actor of the
+/// runtime itself.
+/// Creating the runtime future again from the same macro invocation clears any previous shutdown request.
+///
+/// ```rust
+/// use veecle_os_runtime::Never;
+///
+/// #[veecle_os_runtime::actor]
+/// async fn idle_actor() -> Never {
+///     core::future::pending().await
+/// }
+///
+/// let (handle, runtime) = veecle_os_runtime::execute_with_shutdown! {
+///     actors: [IdleActor],
+/// };
+///
+/// std::thread::spawn(move || handle.shutdown());
+///
+/// futures::executor::block_on(runtime);
+/// assert!(handle.is_shutdown_requested());
+/// ```
+#[macro_export]
+macro_rules! execute_with_shutdown {
+    (
+        actors: [
+            $($actor_type:ty $(: $init_context:expr )? ),* $(,)?
+        ]
+        $(, panic_policy: $panic_policy:expr)?
+        $(, capacity: $capacity:expr)? $(,)?
+    ) => {{
+        static SHUTDOWN: $crate::__exports::ShutdownSignal = $crate::__exports::ShutdownSignal::new();
+
+        (
+            SHUTDOWN.handle(),
+            $crate::__execute! {
+                executor => executor.run_until_shutdown(&SHUTDOWN).await,
+                actors: [$($actor_type $(: $init_context)?,)*],
+                panic_policy: [$($panic_policy)?],
+                capacity: [$($capacity)?],
+            },
+        )
+    }};
+}
+
+/// Internal helper for [`execute!`] and [`execute_with_shutdown!`], returns a future that builds the store and the
+/// executor for the given actors and then evaluates `$run` with the executor bound to `$executor`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __execute {
+    (
+        $executor:ident => $run:expr,
+        actors: [
+            $($actor_type:ty $(: $init_context:expr )? ,)*
+        ],
+        panic_policy: [$($panic_policy:expr)?],
+        capacity: [$($capacity:expr)?],
     ) => {{
         async {
+            let panic_policy: $crate::PanicPolicy = $crate::__panic_policy!($($panic_policy)?);
+
             let (store, init_contexts) = {
                 let (store, init_contexts) = $crate::__exports::make_store_and_validate::<
                     $crate::__make_cons!(@type $($actor_type,)*),
@@ -546,29 +699,57 @@ macro_rules! execute {
             let store = store.as_ref();
 
             // To count how many actors there are, we create an array of `()` with the appropriate length.
-            const LEN: usize = [$($crate::discard_to_unit!($actor_type),)*].len();
+            const ACTORS: usize = [$($crate::discard_to_unit!($actor_type),)*].len();
+            const LEN: usize = $crate::__capacity!(ACTORS $(, $capacity)?);
+            // Checked here too as the check in `Executor::new` is only evaluated during code generation.
+            const _: () = assert!(ACTORS <= LEN, "the executor capacity is smaller than the number of actors");
 
-            let futures: [core::pin::Pin<&mut dyn core::future::Future<Output = $crate::Never>>; LEN] =
+            let futures: [core::pin::Pin<&mut dyn core::future::Future<Output = $crate::Never>>; ACTORS] =
                 $crate::make_futures! {
                     init_contexts: init_contexts,
                     store: store,
+                    panic_policy: panic_policy,
                     actors: [$($actor_type,)*],
                 };
 
             static SHARED: $crate::__exports::ExecutorShared<LEN>
                 = $crate::__exports::ExecutorShared::new(&SHARED);
 
-            let executor = $crate::__exports::Executor::new(
+            let $executor = $crate::__exports::Executor::new(
                 &SHARED,
                 $crate::__exports::Datastore::source(store),
                 futures,
             );
 
-            executor.run().await
+            $run
         }
     }};
 }
 
+/// Internal helper to default the optional `panic_policy` argument of [`execute!`].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __panic_policy {
+    () => {
+        <$crate::PanicPolicy as core::default::Default>::default()
+    };
+    ($panic_policy:expr) => {
+        $panic_policy
+    };
+}
+
+/// Internal helper to default the optional `capacity` argument of [`execute!`] to the number of actors.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __capacity {
+    ($actors:expr) => {
+        $actors
+    };
+    ($actors:expr, $capacity:expr) => {
+        $capacity
+    };
+}
+
 /// Internal helper to construct an array of pinned futures for given actors + init-contexts + store.
 ///
 /// Returns essentially `[Pin<&mut dyn Future<Output = Never>; actors.len()]`, but likely needs annotation at the
@@ -580,6 +761,7 @@ macro_rules! make_futures {
         // A cons-list of init-contexts for the passed actors.
         init_contexts: $init_contexts:expr,
         store: $store:expr,
+        panic_policy: $panic_policy:expr,
         actors: [
             $($types:ty,)*
         ],
@@ -587,6 +769,7 @@ macro_rules! make_futures {
         $crate::make_futures! {
             init_contexts: $init_contexts,
             store: $store,
+            panic_policy: $panic_policy,
             done: [],
             todo: [$($types,)*],
             futures: [],
@@ -597,6 +780,7 @@ macro_rules! make_futures {
     (
         init_contexts: $init_contexts:expr,
         store: $store:expr,
+        panic_policy: $panic_policy:expr,
         done: [$($done:ty,)*],
         todo: [],
         futures: [
@@ -612,6 +796,7 @@ macro_rules! make_futures {
     (
         init_contexts: $init_contexts:expr,
         store: $store:expr,
+        panic_policy: $panic_policy:expr,
         done: [$($done:ty,)*],
         todo: [$current:ty, $($todo:ty,)*],
         futures: [
@@ -621,6 +806,7 @@ macro_rules! make_futures {
         $crate::make_futures! {
             init_contexts: $init_contexts,
             store: $store,
+            panic_policy: $panic_policy,
             done: [$($done,)* $current,],
             todo: [$($todo,)*],
             futures: [
@@ -632,6 +818,7 @@ macro_rules! make_futures {
                             from: $init_contexts,
                             depth: [$($done)*],
                         },
+                        $panic_policy,
                     )
                 ),
             ],
@@ -14,6 +14,7 @@ use typenum::operator_aliases::{Quot, Sum};
 use typenum::{Const, ToUInt, U};
 
 use crate::datastore::sync::generational;
+use crate::shutdown::ShutdownSignal;
 
 type UsizeBits = U<{ usize::BITS as usize }>;
 type UsizeBitsMinusOne = typenum::operator_aliases::Sub1<UsizeBits>;
@@ -271,6 +272,15 @@ where
 /// esoteric platforms like `wasm32-web` which cannot have a thread-park based executor—while still giving the required
 /// guarantees about when and how the sub-futures are polled.
 ///
+/// # Capacity
+///
+/// `LEN` is the number of futures the executor can run, it may be larger than the number of futures passed to
+/// [`Executor::new()`].
+/// Each unit of capacity takes four words whether it is used or not: a waker in the [`ExecutorShared`] `static` (two
+/// words, plus one bit of the wake flags) and a future reference in the `Executor` itself (two words, part of the
+/// runtime future).
+/// See [`execute!`](crate::execute) for how to set it.
+///
 /// # Polling strategy
 ///
 /// The executor polls all woken futures in a fixed order.
@@ -300,7 +310,8 @@ where
     /// A generational source provided by the datastore.
     source: Pin<&'a generational::Source>,
     shared: &'static ExecutorShared<LEN>,
-    futures: [Pin<&'a mut (dyn Future<Output = Never> + 'a)>; LEN],
+    /// The futures to run, `None` for unused capacity.
+    futures: [Option<Pin<&'a mut (dyn Future<Output = Never> + 'a)>>; LEN],
 }
 
 impl<const LEN: usize> core::fmt::Debug for Executor<'_, LEN>
@@ -322,15 +333,28 @@ where
     Const<LEN>: Internal,
 {
     /// Creates a new [`Executor`] from the provided futures.
-    pub fn new(
+    ///
+    /// Passing more futures than the executor's capacity `LEN` fails to compile.
+    /// As `FUTURES` and `LEN` are generic this is only checked once `new` is instantiated with concrete values during
+    /// code generation, so e.g. `cargo check` does not report it.
+    /// [`execute!`](crate::execute) additionally checks its arguments where it is invoked.
+    pub fn new<const FUTURES: usize>(
         shared: &'static ExecutorShared<LEN>,
         source: Pin<&'a generational::Source>,
-        futures: [Pin<&'a mut (dyn Future<Output = Never> + 'a)>; LEN],
+        futures: [Pin<&'a mut (dyn Future<Output = Never> + 'a)>; FUTURES],
     ) -> Self {
+        const {
+            assert!(
+                FUTURES <= LEN,
+                "the executor capacity is smaller than the number of actors"
+            )
+        };
+
+        let mut futures = futures.into_iter();
         Self {
             source,
             shared,
-            futures,
+            futures: core::array::from_fn(|_| futures.next()),
         }
     }
 
@@ -339,7 +363,9 @@ where
         let mut polled = false;
 
         for index in self.shared.shared.reset_all() {
-            let future = &mut self.futures[index];
+            let Some(future) = &mut self.futures[index] else {
+                continue;
+            };
             let waker = self.shared.bit_wakers[index].as_waker();
             let mut context = Context::from_waker(&waker);
             match future.as_mut().poll(&mut context) {
@@ -376,6 +402,36 @@ where
             .await;
         }
     }
+
+    /// Runs all futures until a shutdown is requested through `shutdown`.
+    ///
+    /// The futures are dropped once this future resolves.
+    pub async fn run_until_shutdown(mut self, shutdown: &'static ShutdownSignal) {
+        loop {
+            let shutdown_requested = core::future::poll_fn(|ctx| {
+                self.shared.shared.waker.register(ctx.waker());
+                Poll::Ready(shutdown.poll_requested(ctx))
+            })
+            .await;
+
+            if shutdown_requested {
+                return;
+            }
+
+            self.run_once();
+
+            let mut yielded = false;
+            core::future::poll_fn(|_| {
+                if yielded {
+                    Poll::Ready(())
+                } else {
+                    yielded = true;
+                    Poll::Pending
+                }
+            })
+            .await;
+        }
+    }
 }
 
 #[cfg(test)]
@@ -507,4 +563,26 @@ mod tests {
 
         assert!(rx.recv_timeout(std::time::Duration::from_secs(1)).is_ok());
     }
+
+    #[test]
+    fn unused_capacity() {
+        static SHARED: ExecutorShared<3> = ExecutorShared::new(&SHARED);
+
+        let source = pin!(generational::Source::new());
+        let polls = core::cell::Cell::new(0);
+        let futures = [pin!(async {
+            core::future::poll_fn(|_| {
+                polls.set(polls.get() + 1);
+                Poll::<()>::Pending
+            })
+            .await;
+            unreachable!()
+        }) as _];
+        let mut executor = Executor::new(&SHARED, source.as_ref(), futures);
+
+        // Only the provided future is polled, the unused capacity is skipped.
+        assert!(executor.run_once());
+        assert!(!executor.run_once());
+        assert_eq!(polls.get(), 1);
+    }
 }
@@ -0,0 +1,136 @@
+//! Inspecting the contents of a store at runtime, e.g. for a debug console.
+//!
+//! This module is only available with the `introspection` feature.
+//! Inspection walks every slot of the store and formats values on demand, it is meant for occasional diagnostics and
+//! should not be called from the hot path of an actor.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use veecle_os_runtime::single_writer::{Reader, Writer};
+//! use veecle_os_runtime::{Never, Runtime, RuntimeStore, Storable, introspection};
+//!
+//! #[derive(Debug, Clone, PartialEq, Eq, Default, Storable)]
+//! pub struct Speed(u32);
+//!
+//! #[veecle_os_runtime::actor]
+//! async fn sensor(mut speed: Writer<'_, Speed>) -> Never {
+//!     loop {
+//!         speed.write(Speed(42)).await;
+//!     }
+//! }
+//!
+//! #[veecle_os_runtime::actor]
+//! async fn display(mut speed: Reader<'_, Speed>) -> Never {
+//!     loop {
+//!         speed.read_updated(|speed| println!("{speed:?}")).await;
+//!     }
+//! }
+//!
+//! let store = core::pin::pin!(RuntimeStore::new());
+//! let _runtime = Runtime::builder(store.as_ref())
+//!     .actor::<Sensor>(())
+//!     .actor::<Display>(())
+//!     .build();
+//!
+//! introspection::for_each_slot(store.as_ref(), |slot| {
+//!     println!("{slot}");
+//! });
+//!
+//! let snapshot = introspection::snapshot(store.as_ref());
+//! assert_eq!(snapshot[0].type_name, core::any::type_name::<Speed>());
+//! assert_eq!(snapshot[0].value, "None");
+//! ```
+
+use core::fmt::Debug;
+use core::pin::Pin;
+
+use crate::datastore::Datastore;
+
+/// A view of a single slot of a store and its current value.
+///
+/// Formatting with [`Display`](core::fmt::Display) writes `type_name = value`.
+#[derive(Clone, Copy)]
+pub struct SlotInfo<'a> {
+    type_name: &'static str,
+    value: &'a dyn Debug,
+}
+
+impl<'a> SlotInfo<'a> {
+    pub(crate) fn new(type_name: &'static str, value: &'a dyn Debug) -> Self {
+        Self { type_name, value }
+    }
+
+    /// Returns the type name of the [`Storable`](crate::Storable) stored in the slot.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns the current value of the slot.
+    ///
+    /// For [`single_writer`](crate::single_writer) slots this is the `Option` of the value, for [`mpsc`](crate::mpsc)
+    /// slots a list with the pending `Option` of every writer.
+    pub fn value(&self) -> &'a dyn Debug {
+        self.value
+    }
+}
+
+impl Debug for SlotInfo<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SlotInfo")
+            .field("type_name", &self.type_name)
+            .field("value", self.value)
+            .finish()
+    }
+}
+
+impl core::fmt::Display for SlotInfo<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} = {:?}", self.type_name, self.value)
+    }
+}
+
+/// Calls `f` with every slot of `store` in turn.
+///
+/// `store` is the [`RuntimeStore`](crate::RuntimeStore) of a runtime assembled with
+/// [`Runtime::builder`](crate::Runtime::builder).
+/// Slots are visited in an unspecified but stable order.
+///
+/// # Panics
+///
+/// If `f` is called from within [`Writer::modify`](crate::single_writer::Writer::modify) and visits the slot being
+/// modified.
+pub fn for_each_slot<S>(store: Pin<&S>, mut f: impl FnMut(SlotInfo<'_>))
+where
+    S: Datastore,
+{
+    store.for_each_slot(&mut f);
+}
+
+/// The type name and formatted value of a slot, see [`snapshot`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlotSnapshot {
+    /// The type name of the [`Storable`](crate::Storable) stored in the slot.
+    pub type_name: &'static str,
+    /// The current value of the slot formatted with [`Debug`], see [`SlotInfo::value`].
+    pub value: std::string::String,
+}
+
+/// Returns the type names and current values of all slots of `store`.
+///
+/// See [`for_each_slot`] for details.
+#[cfg(feature = "std")]
+pub fn snapshot<S>(store: Pin<&S>) -> std::vec::Vec<SlotSnapshot>
+where
+    S: Datastore,
+{
+    let mut snapshot = std::vec::Vec::new();
+    for_each_slot(store, |slot| {
+        snapshot.push(SlotSnapshot {
+            type_name: slot.type_name(),
+            value: std::format!("{:?}", slot.value()),
+        });
+    });
+    snapshot
+}
@@ -99,22 +99,35 @@
 #![cfg_attr(coverage_nightly, feature(coverage_attribute))]
 #![no_std]
 
-#[cfg(test)]
+#[cfg(any(test, feature = "std"))]
 extern crate std;
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 pub(crate) mod actor;
+#[cfg(feature = "alloc")]
+mod builder;
 mod cons;
 pub(crate) mod datastore;
 mod execute;
 
 mod executor;
+mod panic;
+mod shutdown;
 
+#[cfg(feature = "introspection")]
+pub mod introspection;
 pub mod memory_pool;
 
 pub use self::actor::{Actor, StoreRequest, actor};
+#[cfg(feature = "alloc")]
+pub use self::builder::{Runtime, RuntimeBuilder, RuntimeStore};
 pub use self::datastore::mpsc;
 pub use self::datastore::single_writer;
 pub use self::datastore::{CombinableReader, CombineReaders, Modify, Storable};
+pub use self::panic::PanicPolicy;
+pub use self::shutdown::ShutdownHandle;
 
 /// Internal exports for proc-macro and `macro_rules!` purposes.
 #[doc(hidden)]
@@ -125,6 +138,7 @@ pub mod __exports {
     pub use crate::datastore::DefinesSlot;
     pub use crate::execute::{execute_actor, make_store_and_validate};
     pub use crate::executor::{Executor, ExecutorShared};
+    pub use crate::shutdown::ShutdownSignal;
 }
 
 /// A type that can never be constructed.
@@ -133,6 +147,18 @@ pub mod __exports {
 /// never returns successfully, only by error. This is semantically clearer than
 /// using `Infallible` which suggests "cannot fail."
 ///
+/// `Never` and [`Infallible`](core::convert::Infallible) convert into each other via [`From`], e.g. to use
+/// `Result<T, Infallible>` with code expecting `Result<T, Never>`:
+///
+/// ```rust
+/// use core::convert::Infallible;
+/// use veecle_os_runtime::Never;
+///
+/// let result: Result<u8, Infallible> = Ok(1);
+/// let result: Result<u8, Never> = result.map_err(Never::from);
+/// # assert_eq!(result, Ok(1));
+/// ```
+///
 // TODO(https://github.com/rust-lang/rust/issues/35121)
 /// This type will be replaced with the never type [`!`] once it is stabilized.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -146,5 +172,17 @@ impl core::fmt::Display for Never {
 
 impl core::error::Error for Never {}
 
+impl From<core::convert::Infallible> for Never {
+    fn from(value: core::convert::Infallible) -> Self {
+        match value {}
+    }
+}
+
+impl From<Never> for core::convert::Infallible {
+    fn from(value: Never) -> Self {
+        match value {}
+    }
+}
+
 /// Marker trait to seal internal traits.
 trait Sealed {}
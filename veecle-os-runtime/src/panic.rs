@@ -0,0 +1,127 @@
+//! Reporting and isolation of panicking actors.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use pin_project::pin_project;
+
+use crate::Never;
+
+/// What the runtime does when an actor panics.
+///
+/// Set with the `panic_policy` argument of [`execute!`](crate::execute!) or with
+/// [`RuntimeBuilder::panic_policy`](crate::RuntimeBuilder::panic_policy).
+///
+/// Independent of the policy, a panicking actor is reported with its name through `veecle-telemetry` before the
+/// policy is applied.
+/// Without the `std` feature, panics cannot be caught, so the actor is only reported while the panic unwinds (if the
+/// target unwinds at all) and every policy stops the runtime.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PanicPolicy {
+    /// Stops the runtime by continuing the panic.
+    ///
+    /// With the `std` feature, the panic message is prefixed with the name of the panicking actor, e.g.
+    /// ``actor `my_crate::MyActor` panicked: index out of bounds``.
+    #[default]
+    Abort,
+    /// Continues running all other actors, the panicking actor is dropped and not polled anymore.
+    ///
+    /// The last values written by the panicking actor stay in the store.
+    ///
+    /// Requires the `std` feature to catch the panic, without it this behaves like [`PanicPolicy::Abort`].
+    Continue,
+}
+
+/// Wraps the future of an actor, reporting the actor and applying the [`PanicPolicy`] if polling it panics.
+#[pin_project]
+pub(crate) struct IsolatedActor<F> {
+    actor: &'static str,
+    policy: PanicPolicy,
+    /// `None` after the actor panicked with [`PanicPolicy::Continue`].
+    #[pin]
+    future: Option<F>,
+}
+
+impl<F> IsolatedActor<F> {
+    pub(crate) fn new(actor: &'static str, policy: PanicPolicy, future: F) -> Self {
+        Self {
+            actor,
+            policy,
+            future: Some(future),
+        }
+    }
+}
+
+impl<F> Future for IsolatedActor<F>
+where
+    F: Future<Output = Never>,
+{
+    type Output = Never;
+
+    #[cfg(not(feature = "std"))]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Never> {
+        let this = self.project();
+        let Some(future) = this.future.as_pin_mut() else {
+            return Poll::Pending;
+        };
+
+        let guard = UnwindGuard { actor: this.actor };
+        let poll = future.poll(cx);
+        core::mem::forget(guard);
+        poll
+    }
+
+    #[cfg(feature = "std")]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Never> {
+        let mut this = self.project();
+        let Some(future) = this.future.as_mut().as_pin_mut() else {
+            return Poll::Pending;
+        };
+
+        let payload =
+            match std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| future.poll(cx))) {
+                Ok(poll) => return poll,
+                Err(payload) => payload,
+            };
+
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| {
+                payload
+                    .downcast_ref::<std::string::String>()
+                    .map(|message| message.as_str())
+            })
+            .unwrap_or("<non-string panic payload>");
+        report(this.actor, message);
+
+        match this.policy {
+            PanicPolicy::Abort => {
+                let message = std::format!("actor `{}` panicked: {message}", this.actor);
+                std::panic::resume_unwind(std::boxed::Box::new(message))
+            }
+            PanicPolicy::Continue => {
+                this.future.set(None);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Reports the actor if it is dropped, which only happens while unwinding out of a poll.
+#[cfg(not(feature = "std"))]
+struct UnwindGuard<'a> {
+    actor: &'a str,
+}
+
+#[cfg(not(feature = "std"))]
+impl Drop for UnwindGuard<'_> {
+    fn drop(&mut self) {
+        report(self.actor, "<panic message unavailable without `std`>");
+    }
+}
+
+fn report(actor: &str, message: &str) {
+    veecle_telemetry::error!("actor panicked", actor = actor, message = message);
+}
@@ -0,0 +1,69 @@
+//! External shutdown of a runtime started with [`execute_with_shutdown!`](crate::execute_with_shutdown!).
+
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::task::Context;
+
+use futures::task::AtomicWaker;
+
+/// Shared state between a [`ShutdownHandle`] and the runtime it stops.
+///
+/// This has to be stored in a `static`, [`execute_with_shutdown!`](crate::execute_with_shutdown!) creates one per
+/// invocation.
+#[derive(Debug)]
+pub struct ShutdownSignal {
+    requested: AtomicBool,
+    waker: AtomicWaker,
+}
+
+impl ShutdownSignal {
+    /// Creates a new signal without a shutdown requested.
+    pub const fn new() -> Self {
+        Self {
+            requested: AtomicBool::new(false),
+            waker: AtomicWaker::new(),
+        }
+    }
+
+    /// Clears any previous shutdown request and returns a handle to request a new one.
+    pub fn handle(&'static self) -> ShutdownHandle {
+        self.requested.store(false, Ordering::Release);
+        ShutdownHandle { signal: self }
+    }
+
+    /// Registers the waker of `context` to be woken on a shutdown request and returns whether one was requested.
+    pub(crate) fn poll_requested(&self, context: &mut Context<'_>) -> bool {
+        self.waker.register(context.waker());
+        self.requested.load(Ordering::Acquire)
+    }
+}
+
+impl Default for ShutdownSignal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Requests the shutdown of a runtime started with [`execute_with_shutdown!`](crate::execute_with_shutdown!).
+///
+/// The handle can be copied and sent to other threads or tasks, e.g. one waiting for a termination signal.
+#[derive(Debug, Clone, Copy)]
+pub struct ShutdownHandle {
+    signal: &'static ShutdownSignal,
+}
+
+impl ShutdownHandle {
+    /// Requests the runtime to shut down.
+    ///
+    /// The runtime finishes polling the actors that are currently being polled, then stops polling them and its future
+    /// resolves, dropping all actor futures.
+    /// Requesting a shutdown multiple times has no additional effect.
+    pub fn shutdown(&self) {
+        self.signal.requested.store(true, Ordering::Release);
+        self.signal.waker.wake();
+    }
+
+    /// Returns `true` if a shutdown has been requested.
+    pub fn is_shutdown_requested(&self) -> bool {
+        self.signal.requested.load(Ordering::Acquire)
+    }
+}
@@ -0,0 +1,57 @@
+#![expect(missing_docs)]
+
+// Installs the global telemetry collector, so this lives in its own test binary.
+
+use veecle_os_runtime::Storable;
+use veecle_os_runtime::single_writer::DiffingWriter;
+use veecle_osal_std::thread::Thread;
+use veecle_osal_std::time::Time;
+use veecle_telemetry::collector::TestExporter;
+use veecle_telemetry::protocol::owned::Value;
+
+#[derive(Debug, PartialEq, Storable)]
+pub struct Gear(u8);
+
+#[test]
+fn records_only_changes() {
+    let exporter = TestExporter::set_global::<Time, Thread>();
+
+    veecle_os_test::block_on_future(veecle_os_test::execute! {
+        actors: [],
+
+        validation: async |mut gear: DiffingWriter<'_, Gear>| {
+            gear.write(Gear(1)).await;
+            gear.write(Gear(1)).await;
+            gear.write(Gear(2)).await;
+            gear.clear().await;
+            gear.clear().await;
+        }
+    });
+
+    let changes: Vec<_> = exporter
+        .events_with_attr("type_name", core::any::type_name::<Gear>())
+        .into_iter()
+        .map(|event| {
+            assert_eq!(event.name, "value changed");
+            let attribute = |key: &str| {
+                event
+                    .attributes
+                    .iter()
+                    .find(|attribute| attribute.key == key)
+                    .map(|attribute| attribute.value.clone())
+                    .unwrap_or_else(|| panic!("missing `{key}` attribute"))
+            };
+            (attribute("old"), attribute("new"))
+        })
+        .collect();
+
+    assert_eq!(
+        changes,
+        [
+            ("None", "Some(Gear(1))"),
+            ("Some(Gear(1))", "Some(Gear(2))"),
+            ("Some(Gear(2))", "None"),
+        ]
+        .map(|(old, new)| (Value::from(old), Value::from(new)))
+    );
+}
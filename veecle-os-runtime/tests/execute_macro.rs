@@ -307,3 +307,14 @@ fn make_executor_smoke_test15() {
         ],
     });
 }
+
+#[test]
+#[should_panic(expected = "done")]
+fn make_executor_with_capacity() {
+    futures::executor::block_on(veecle_os_runtime::execute! {
+        actors: [
+            SensorReaderWriter, SensorReader,
+        ],
+        capacity: 5,
+    });
+}
@@ -0,0 +1,100 @@
+#![expect(missing_docs)]
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use veecle_os_runtime::single_writer::{Reader, Writer};
+use veecle_os_runtime::{Never, ShutdownHandle};
+
+#[derive(Eq, PartialEq, Debug, Clone, veecle_os_runtime::Storable)]
+pub struct Count(usize);
+
+/// Sets the flag when dropped, to observe that the actor future was dropped.
+struct DropFlag<'a>(&'a AtomicBool);
+
+impl Drop for DropFlag<'_> {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+#[veecle_os_runtime::actor]
+async fn counter_actor(
+    mut writer: Writer<'_, Count>,
+    #[init_context] dropped: &'static AtomicBool,
+) -> Never {
+    let _flag = DropFlag(dropped);
+    let mut count = 0;
+    loop {
+        writer.write(Count(count)).await;
+        count += 1;
+    }
+}
+
+#[veecle_os_runtime::actor]
+async fn stopper_actor(
+    mut reader: Reader<'_, Count>,
+    #[init_context] context: (&'static AtomicUsize, ShutdownHandle),
+) -> Never {
+    let (seen, handle) = context;
+    loop {
+        let Count(count) = reader.read_updated_cloned().await;
+        seen.store(count, Ordering::Relaxed);
+        if count == 10 {
+            handle.shutdown();
+        }
+    }
+}
+
+#[test]
+fn shutdown_from_actor() {
+    static DROPPED: AtomicBool = AtomicBool::new(false);
+    static SEEN: AtomicUsize = AtomicUsize::new(0);
+
+    let handle_slot = std::sync::OnceLock::<ShutdownHandle>::new();
+    let (handle, runtime) = veecle_os_runtime::execute_with_shutdown! {
+        actors: [
+            CounterActor: &DROPPED,
+            StopperActor: (&SEEN, *handle_slot.get().unwrap()),
+        ],
+    };
+    handle_slot.set(handle).unwrap();
+    assert!(!handle.is_shutdown_requested());
+
+    futures::executor::block_on(runtime);
+
+    assert!(handle.is_shutdown_requested());
+    assert!(DROPPED.load(Ordering::Relaxed));
+    assert_eq!(SEEN.load(Ordering::Relaxed), 10);
+}
+
+#[veecle_os_runtime::actor]
+async fn idle_actor() -> Never {
+    core::future::pending().await
+}
+
+#[test]
+fn shutdown_from_thread() {
+    let (handle, runtime) = veecle_os_runtime::execute_with_shutdown! {
+        actors: [IdleActor],
+    };
+
+    let thread = std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        handle.shutdown();
+    });
+
+    futures::executor::block_on(runtime);
+    thread.join().unwrap();
+
+    assert!(handle.is_shutdown_requested());
+}
+
+#[test]
+fn shutdown_before_start() {
+    let (handle, runtime) = veecle_os_runtime::execute_with_shutdown! {
+        actors: [IdleActor],
+    };
+    handle.shutdown();
+
+    futures::executor::block_on(runtime);
+}
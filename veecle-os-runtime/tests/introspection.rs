@@ -0,0 +1,95 @@
+#![expect(missing_docs)]
+
+use core::pin::pin;
+use core::task::{Context, Poll};
+
+use futures::task::noop_waker_ref;
+use veecle_os_runtime::introspection::{self, SlotSnapshot};
+use veecle_os_runtime::{Never, Runtime, RuntimeStore, Storable, mpsc, single_writer};
+
+#[derive(Eq, PartialEq, Debug, Clone, Storable)]
+pub struct Counter(u32);
+
+#[derive(Eq, PartialEq, Debug, Clone, Storable)]
+pub struct Event(u8);
+
+#[derive(Eq, PartialEq, Debug, Clone, Storable)]
+pub struct Unwritten;
+
+#[veecle_os_runtime::actor]
+async fn counter_writer(mut writer: single_writer::Writer<'_, Counter>) -> Never {
+    writer.write(Counter(5)).await;
+    core::future::pending().await
+}
+
+#[veecle_os_runtime::actor]
+async fn event_writer(mut writer: mpsc::Writer<'_, Event, 2>) -> Never {
+    writer.write(Event(7)).await;
+    core::future::pending().await
+}
+
+#[veecle_os_runtime::actor]
+async fn unwritten_writer(_writer: single_writer::Writer<'_, Unwritten>) -> Never {
+    core::future::pending().await
+}
+
+#[veecle_os_runtime::actor]
+async fn idle_reader(
+    _counter: single_writer::Reader<'_, Counter>,
+    _event: mpsc::Reader<'_, Event, 2>,
+    _unwritten: single_writer::Reader<'_, Unwritten>,
+) -> Never {
+    core::future::pending().await
+}
+
+#[test]
+fn snapshot_lists_all_slots() {
+    let store = pin!(RuntimeStore::new());
+    let mut run = pin!(
+        Runtime::builder(store.as_ref())
+            .actor::<CounterWriter>(())
+            .actor::<EventWriter>(())
+            .actor::<UnwrittenWriter>(())
+            .actor::<IdleReader>(())
+            .build()
+    );
+
+    let mut snapshot = introspection::snapshot(store.as_ref());
+    snapshot.sort_by_key(|slot| slot.type_name);
+    assert_eq!(
+        snapshot.iter().map(|slot| &*slot.value).collect::<Vec<_>>(),
+        ["None", "[None, None]", "None"]
+    );
+
+    let mut context = Context::from_waker(noop_waker_ref());
+    for _ in 0..3 {
+        assert!(matches!(run.as_mut().poll(&mut context), Poll::Pending));
+    }
+
+    let mut snapshot = introspection::snapshot(store.as_ref());
+    snapshot.sort_by_key(|slot| slot.type_name);
+    assert_eq!(
+        snapshot,
+        [
+            SlotSnapshot {
+                type_name: core::any::type_name::<Counter>(),
+                value: "Some(Counter(5))".to_owned(),
+            },
+            SlotSnapshot {
+                type_name: core::any::type_name::<Event>(),
+                value: "[Some(Event(7)), None]".to_owned(),
+            },
+            SlotSnapshot {
+                type_name: core::any::type_name::<Unwritten>(),
+                value: "None".to_owned(),
+            },
+        ]
+    );
+
+    let mut names = Vec::new();
+    introspection::for_each_slot(store.as_ref(), |slot| names.push(slot.to_string()));
+    assert!(names.contains(&format!(
+        "{} = Some(Counter(5))",
+        core::any::type_name::<Counter>()
+    )));
+}
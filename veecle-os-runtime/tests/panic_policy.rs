@@ -0,0 +1,83 @@
+#![expect(missing_docs)]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use veecle_os_runtime::single_writer::{Reader, Writer};
+use veecle_os_runtime::{Never, PanicPolicy, Runtime, RuntimeStore};
+
+#[derive(Eq, PartialEq, Debug, Clone, veecle_os_runtime::Storable)]
+pub struct Count(usize);
+
+#[veecle_os_runtime::actor]
+async fn panicking_actor() -> Never {
+    panic!("broken sensor")
+}
+
+#[veecle_os_runtime::actor]
+async fn counter_actor(mut writer: Writer<'_, Count>) -> Never {
+    let mut count = 0;
+    loop {
+        writer.write(Count(count)).await;
+        count += 1;
+    }
+}
+
+#[veecle_os_runtime::actor]
+async fn counter_reader(
+    mut reader: Reader<'_, Count>,
+    #[init_context] seen: &'static AtomicUsize,
+) -> Never {
+    loop {
+        let Count(count) = reader.read_updated_cloned().await;
+        seen.store(count, Ordering::Relaxed);
+    }
+}
+
+/// Runs `runtime` until `seen` reaches 10.
+fn run_until_seen(runtime: impl Future<Output = Never>, seen: &AtomicUsize) {
+    let reached = core::future::poll_fn(|cx| {
+        if seen.load(Ordering::Relaxed) >= 10 {
+            core::task::Poll::Ready(())
+        } else {
+            cx.waker().wake_by_ref();
+            core::task::Poll::Pending
+        }
+    });
+    futures::executor::block_on(futures::future::select(
+        core::pin::pin!(runtime),
+        core::pin::pin!(reached),
+    ));
+}
+
+#[test]
+#[should_panic(expected = "actor `panic_policy::PanickingActor<'_>` panicked: broken sensor")]
+fn abort_reports_actor() {
+    futures::executor::block_on(veecle_os_runtime::execute! {
+        actors: [PanickingActor],
+    });
+}
+
+#[test]
+fn continue_runs_other_actors() {
+    static SEEN: AtomicUsize = AtomicUsize::new(0);
+
+    let runtime = veecle_os_runtime::execute! {
+        actors: [PanickingActor, CounterActor, CounterReader: &SEEN],
+        panic_policy: PanicPolicy::Continue,
+    };
+    run_until_seen(runtime, &SEEN);
+}
+
+#[test]
+fn builder_continue_runs_other_actors() {
+    static SEEN: AtomicUsize = AtomicUsize::new(0);
+
+    let store = core::pin::pin!(RuntimeStore::new());
+    let runtime = Runtime::builder(store.as_ref())
+        .actor::<PanickingActor>(())
+        .actor::<CounterActor>(())
+        .actor::<CounterReader>(&SEEN)
+        .panic_policy(PanicPolicy::Continue)
+        .build();
+    run_until_seen(runtime, &SEEN);
+}
@@ -0,0 +1,148 @@
+#![expect(missing_docs)]
+
+use core::pin::pin;
+use core::task::{Context, Poll, Waker};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use veecle_os_runtime::single_writer::{Reader, Writer};
+use veecle_os_runtime::{Never, Runtime, RuntimeStore, Storable};
+
+#[derive(Eq, PartialEq, Debug, Clone, Storable)]
+pub struct Counter(u32);
+
+#[derive(Eq, PartialEq, Debug, Clone, Storable)]
+pub struct Unread(u32);
+
+#[veecle_os_runtime::actor]
+async fn counter_writer(mut writer: Writer<'_, Counter>) -> Never {
+    let mut value = 0;
+    loop {
+        writer.write(Counter(value)).await;
+        value += 1;
+    }
+}
+
+#[veecle_os_runtime::actor]
+async fn counter_reader(mut reader: Reader<'_, Counter>, #[init_context] limit: u32) -> Never {
+    let mut expected = 0;
+    loop {
+        let Counter(value) = reader.read_updated_cloned().await;
+        assert_eq!(value, expected, "every write should be observed");
+        expected += 1;
+
+        if value == limit {
+            panic!("done {value}");
+        }
+    }
+}
+
+#[veecle_os_runtime::actor]
+async fn unread_writer(mut writer: Writer<'_, Unread>) -> Never {
+    loop {
+        writer.write(Unread(0)).await;
+    }
+}
+
+#[veecle_os_runtime::actor]
+async fn idle_actor(#[init_context] polls: &'static AtomicUsize) -> Never {
+    core::future::poll_fn(|_| {
+        polls.fetch_add(1, Ordering::Relaxed);
+        Poll::Pending
+    })
+    .await
+}
+
+#[test]
+#[should_panic(expected = "done 10")]
+#[allow(
+    unreachable_code,
+    reason = "the runtime never returns, the test ends with the reader panicking"
+)]
+fn builder_runs_actors() {
+    let store = pin!(RuntimeStore::new());
+    let runtime = Runtime::builder(store.as_ref())
+        .actor::<CounterWriter>(())
+        .actor::<CounterReader>(10)
+        .build();
+
+    futures::executor::block_on(runtime);
+}
+
+#[test]
+#[should_panic(expected = "done 10")]
+#[allow(
+    unreachable_code,
+    reason = "the runtime never returns, the test ends with the reader panicking"
+)]
+fn builder_runs_actors_in_any_order() {
+    let store = pin!(RuntimeStore::new());
+    let runtime = Runtime::builder(store.as_ref())
+        .actor::<CounterReader>(10)
+        .actor::<CounterWriter>(())
+        .build();
+
+    futures::executor::block_on(runtime);
+}
+
+#[test]
+#[should_panic(expected = "done 5")]
+#[allow(
+    unreachable_code,
+    reason = "the runtime never returns, the test ends with the reader panicking"
+)]
+fn builder_adds_actors_in_a_loop() {
+    let store = pin!(RuntimeStore::new());
+    let mut builder = Runtime::builder(store.as_ref()).actor::<CounterWriter>(());
+    for limit in [15, 5, 10] {
+        builder = builder.actor::<CounterReader>(limit);
+    }
+
+    futures::executor::block_on(builder.build());
+}
+
+#[test]
+fn builder_polls_only_woken_actors() {
+    static IDLE_POLLS: AtomicUsize = AtomicUsize::new(0);
+
+    let store = pin!(RuntimeStore::new());
+    let mut runtime = pin!(
+        Runtime::builder(store.as_ref())
+            .actor::<CounterWriter>(())
+            .actor::<CounterReader>(u32::MAX)
+            .actor::<IdleActor>(&IDLE_POLLS)
+            .build()
+    );
+
+    let mut context = Context::from_waker(Waker::noop());
+    for _ in 0..10 {
+        let Poll::Pending = runtime.as_mut().poll(&mut context);
+    }
+
+    // The writer and reader keep waking each other, the idle actor is only polled in the first round.
+    assert_eq!(IDLE_POLLS.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+#[should_panic(expected = "missing reader for `runtime_builder::Unread`")]
+fn builder_validates_access_patterns() {
+    let store = pin!(RuntimeStore::new());
+    let _runtime = Runtime::builder(store.as_ref())
+        .actor::<CounterWriter>(())
+        .actor::<CounterReader>(10)
+        .actor::<UnreadWriter>(())
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "the store is already used by another runtime")]
+fn builder_rejects_reused_store() {
+    let store = pin!(RuntimeStore::new());
+    let _runtime = Runtime::builder(store.as_ref())
+        .actor::<CounterWriter>(())
+        .actor::<CounterReader>(10)
+        .build();
+    let _runtime = Runtime::builder(store.as_ref())
+        .actor::<CounterWriter>(())
+        .actor::<CounterReader>(10)
+        .build();
+}
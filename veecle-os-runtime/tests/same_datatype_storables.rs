@@ -45,3 +45,26 @@ fn test_two_storables_with_same_datatype() {
         }
     });
 }
+
+#[test]
+fn test_primitive_storables() {
+    veecle_os_test::block_on_future(veecle_os_test::execute! {
+        actors: [],
+        validation: async |
+            mut counter_reader: Reader<'_, u32>,
+            mut counter_writer: Writer<'_, u32>,
+            mut flag_reader: Reader<'_, bool>,
+            mut flag_writer: Writer<'_, bool>,
+            mut bytes_reader: Reader<'_, [u8; 4]>,
+            mut bytes_writer: Writer<'_, [u8; 4]>,
+        | {
+            counter_writer.write(1).await;
+            flag_writer.write(true).await;
+            bytes_writer.write([1, 2, 3, 4]).await;
+
+            assert_eq!(counter_reader.read_cloned(), Some(1));
+            assert_eq!(flag_reader.read_cloned(), Some(true));
+            assert_eq!(bytes_reader.read_cloned(), Some([1, 2, 3, 4]));
+        }
+    });
+}
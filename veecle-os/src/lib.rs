@@ -27,6 +27,8 @@ pub use veecle_telemetry as telemetry;
 #[doc(inline)]
 pub use veecle_telemetry::{debug, error, event, fatal, info, log, span, trace};
 
+pub mod watchdog;
+
 /// Support modules for working with various data formats.
 pub mod data_support {
     #[doc(inline)]
@@ -0,0 +1,104 @@
+//! Watchdog actor for detecting stale values.
+
+use futures::future::Either;
+use veecle_os_runtime::single_writer::Reader;
+use veecle_os_runtime::{Never, Storable};
+use veecle_osal_api::time::{Duration, Exceeded, TimeAbstraction};
+
+/// Configuration for the [`Watchdog`] actor.
+#[derive(Debug, Clone)]
+pub struct WatchdogConfig<F> {
+    timeout: Duration,
+    on_timeout: F,
+}
+
+impl<F> WatchdogConfig<F>
+where
+    F: FnMut(),
+{
+    /// Creates a configuration calling `on_timeout` once `timeout` passes without an update.
+    pub fn new(timeout: Duration, on_timeout: F) -> Self {
+        Self {
+            timeout,
+            on_timeout,
+        }
+    }
+}
+
+/// An actor that calls a callback if the value of type `T` is not written for longer than a timeout.
+///
+/// The deadline is reset on every write of `T`, including writes of an unchanged value.
+/// Once the deadline passes without a write, the callback is called a single time and the watchdog waits for the next
+/// write before re-arming.
+/// A write that is pending when the deadline passes resets the deadline instead of firing the callback.
+///
+/// The deadline is first armed when the actor starts.
+///
+/// # Examples
+///
+/// ```rust
+/// use veecle_os::osal::api::time::Duration;
+/// use veecle_os::runtime::Storable;
+/// use veecle_os::watchdog::{Watchdog, WatchdogConfig};
+/// use veecle_osal_std::time::Time;
+///
+/// #[derive(Debug, Storable)]
+/// pub struct Heartbeat;
+///
+/// fn heartbeat_missed() {
+///     println!("heartbeat missed");
+/// }
+///
+/// # let _ = async {
+/// veecle_os::runtime::execute! {
+///     actors: [
+///         Watchdog<Heartbeat, Time, fn()>: WatchdogConfig::new(Duration::from_millis(100), heartbeat_missed),
+///         // ... the actor writing `Heartbeat`.
+///     ],
+/// }
+/// .await;
+/// # };
+/// ```
+#[veecle_os_runtime::actor]
+pub async fn watchdog<T, Time, F>(
+    mut reader: Reader<'_, T>,
+    #[init_context] config: WatchdogConfig<F>,
+) -> Result<Never, veecle_osal_api::Error>
+where
+    T: Storable + 'static,
+    Time: TimeAbstraction,
+    F: FnMut(),
+{
+    let WatchdogConfig {
+        timeout,
+        mut on_timeout,
+    } = config;
+
+    loop {
+        let deadline = Time::now() + timeout;
+
+        let result = Time::timeout_at(deadline, reader.wait_for_update())
+            .await
+            .map(|_| ());
+
+        match result {
+            Ok(()) => {
+                reader.read(|_| {});
+            }
+            // The deadline and an update may be ready at the same time, prefer resetting over firing.
+            Err(Either::Left(Exceeded)) if reader.is_updated() => {
+                reader.read(|_| {});
+            }
+            Err(Either::Left(Exceeded)) => {
+                veecle_telemetry::warn!(
+                    "Watchdog timeout",
+                    type_name = core::any::type_name::<T>()
+                );
+                on_timeout();
+
+                reader.wait_for_update().await.read(|_| {});
+            }
+            Err(Either::Right(error)) => return Err(error),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+#![expect(missing_docs)]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use veecle_os::osal::api::time::{Duration, TimeAbstraction};
+use veecle_os::runtime::single_writer::Writer;
+use veecle_os::runtime::{Never, Storable};
+use veecle_os::watchdog::{Watchdog, WatchdogConfig};
+use veecle_osal_std::time::Time;
+
+#[derive(Debug, Clone, Storable)]
+pub struct Heartbeat;
+
+/// Writes a heartbeat every 10 milliseconds, stopping after `limit` heartbeats if set.
+#[veecle_os::runtime::actor]
+async fn heartbeat_writer(
+    mut writer: Writer<'_, Heartbeat>,
+    #[init_context] limit: Option<usize>,
+) -> Never {
+    let mut written = 0;
+    loop {
+        if limit.is_some_and(|limit| written >= limit) {
+            core::future::pending::<()>().await;
+        }
+
+        writer.write(Heartbeat).await;
+        written += 1;
+
+        Time::sleep(Duration::from_millis(10)).await.unwrap();
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn watchdog_fires_once_without_writes() {
+    static FIRED: AtomicUsize = AtomicUsize::new(0);
+
+    fn on_timeout() {
+        FIRED.fetch_add(1, Ordering::Relaxed);
+    }
+
+    let runtime = veecle_os::runtime::execute! {
+        actors: [
+            HeartbeatWriter: Some(5),
+            Watchdog<Heartbeat, Time, fn()>: WatchdogConfig::new(Duration::from_millis(50), on_timeout),
+        ],
+    };
+
+    let _ = tokio::time::timeout(std::time::Duration::from_millis(400), runtime).await;
+
+    assert_eq!(FIRED.load(Ordering::Relaxed), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn watchdog_does_not_fire_with_regular_writes() {
+    static FIRED: AtomicUsize = AtomicUsize::new(0);
+
+    fn on_timeout() {
+        FIRED.fetch_add(1, Ordering::Relaxed);
+    }
+
+    let runtime = veecle_os::runtime::execute! {
+        actors: [
+            HeartbeatWriter: None,
+            Watchdog<Heartbeat, Time, fn()>: WatchdogConfig::new(Duration::from_millis(200), on_timeout),
+        ],
+    };
+
+    let _ = tokio::time::timeout(std::time::Duration::from_millis(400), runtime).await;
+
+    assert_eq!(FIRED.load(Ordering::Relaxed), 0);
+}
@@ -9,6 +9,8 @@ extern crate alloc;
 extern crate std;
 
 pub mod log;
+#[cfg(feature = "telemetry")]
+pub mod telemetry;
 pub mod time;
 
 mod error;
@@ -0,0 +1,119 @@
+//! Telemetry related system utilities.
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use veecle_freertos_integration::{Duration, Queue};
+use veecle_telemetry::collector::Export;
+use veecle_telemetry::protocol::transient::InstanceMessage;
+
+/// A telemetry message serialized to JSON, as sent over the queue of a [`QueueExporter`].
+///
+/// FreeRTOS queues copy their items byte-wise, so the message is stored inline in a buffer of `N` bytes instead of in
+/// an owning heap allocation.
+#[derive(Clone, Copy)]
+pub struct QueueMessage<const N: usize> {
+    len: usize,
+    bytes: [u8; N],
+}
+
+impl<const N: usize> QueueMessage<N> {
+    /// Copies `line` into a new message, returns `None` if it is longer than `N` bytes.
+    fn new(line: &str) -> Option<Self> {
+        let mut bytes = [0; N];
+        bytes
+            .get_mut(..line.len())?
+            .copy_from_slice(line.as_bytes());
+        Some(Self {
+            len: line.len(),
+            bytes,
+        })
+    }
+
+    /// Returns the serialized message.
+    pub fn as_str(&self) -> &str {
+        // The bytes are only ever copied from a `str` in `QueueMessage::new`.
+        core::str::from_utf8(&self.bytes[..self.len]).expect("messages are valid UTF-8")
+    }
+}
+
+impl<const N: usize> core::fmt::Debug for QueueMessage<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("QueueMessage").field(&self.as_str()).finish()
+    }
+}
+
+/// An [`Export`] implementer that serializes telemetry messages to JSON and pushes them onto a FreeRTOS [`Queue`].
+///
+/// Exporting never blocks, so instrumented tasks don't wait on I/O. A dedicated drain task should receive from the
+/// queue and ship the messages off-device. If the queue is full or a serialized message is longer than `N` bytes, the
+/// message is dropped and counted in [`QueueExporter::dropped`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use core::num::NonZeroU64;
+/// # struct Thread;
+/// # impl veecle_osal_api::thread::ThreadAbstraction for Thread {
+/// #     fn current_thread_id() -> NonZeroU64 { NonZeroU64::MIN }
+/// # }
+/// use veecle_freertos_integration::{Duration, Queue};
+/// use veecle_osal_freertos::telemetry::{QueueExporter, QueueMessage};
+/// use veecle_telemetry::collector::ProcessId;
+///
+/// let queue = Queue::<QueueMessage<512>>::new(32).unwrap();
+/// let exporter: &'static QueueExporter<512> = Box::leak(Box::new(QueueExporter::new(queue.clone())));
+///
+/// veecle_telemetry::collector::build()
+///     .process_id(ProcessId::from_u128(0x1234))
+///     .exporter(exporter)
+///     .time::<veecle_osal_freertos::time::Time>()
+///     .thread::<Thread>()
+///     .set_global()
+///     .unwrap();
+///
+/// // In the drain task:
+/// loop {
+///     if let Ok(message) = queue.receive(Duration::infinite()) {
+///         // Send `message.as_str()` off-device, e.g. over a UART or network connection, along with
+///         // `exporter.dropped()`.
+///         # drop(message.as_str());
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct QueueExporter<const N: usize> {
+    queue: Queue<QueueMessage<N>>,
+    dropped: AtomicUsize,
+}
+
+impl<const N: usize> QueueExporter<N> {
+    /// Creates a new exporter pushing serialized messages onto `queue`.
+    pub fn new(queue: Queue<QueueMessage<N>>) -> Self {
+        Self {
+            queue,
+            dropped: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the number of messages dropped because the queue was full or the message failed to serialize or was
+    /// too long.
+    pub fn dropped(&self) -> usize {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl<const N: usize> Export for QueueExporter<N> {
+    fn export(&self, message: InstanceMessage<'_>) {
+        let Some(message) = serde_json::to_string(&message)
+            .ok()
+            .and_then(|line| QueueMessage::new(&line))
+        else {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            return;
+        };
+
+        if self.queue.send(message, Duration::zero()).is_err() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
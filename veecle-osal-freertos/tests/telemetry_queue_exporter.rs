@@ -0,0 +1,59 @@
+#![expect(missing_docs)]
+
+use core::num::NonZeroU64;
+
+use veecle_freertos_integration::{Duration, Queue};
+use veecle_osal_freertos::telemetry::{QueueExporter, QueueMessage};
+use veecle_telemetry::collector::Export;
+use veecle_telemetry::protocol::transient::{
+    InstanceMessage, LogMessage, ProcessId, Severity, TelemetryMessage, ThreadId,
+};
+
+pub mod common;
+
+fn log(body: &str) -> InstanceMessage<'_> {
+    InstanceMessage {
+        thread_id: ThreadId::from_raw(ProcessId::from_raw(1), NonZeroU64::MIN),
+        message: TelemetryMessage::Log(LogMessage {
+            time_unix_nano: 0,
+            severity: Severity::Info,
+            body,
+            attributes: &[],
+        }),
+    }
+}
+
+#[test]
+fn telemetry_queue_exporter() {
+    common::run_freertos_test(|| {
+        let queue = Queue::<QueueMessage<256>>::new(1).unwrap();
+        let exporter = QueueExporter::new(queue.clone());
+
+        exporter.export(log("first"));
+        assert_eq!(exporter.dropped(), 0);
+
+        // The queue is full.
+        exporter.export(log("second"));
+        assert_eq!(exporter.dropped(), 1);
+
+        let message = queue.receive(Duration::zero()).unwrap();
+        assert_eq!(
+            message.as_str(),
+            serde_json::to_string(&log("first")).unwrap()
+        );
+
+        // The serialized message doesn't fit into the queue item.
+        exporter.export(log(&"x".repeat(256)));
+        assert_eq!(exporter.dropped(), 2);
+        assert!(queue.receive(Duration::zero()).is_err());
+
+        // The received message stays valid after the exporter and the serialized source are gone.
+        exporter.export(log("third"));
+        drop(exporter);
+        let message = queue.receive(Duration::zero()).unwrap();
+        assert_eq!(
+            message.as_str(),
+            serde_json::to_string(&log("third")).unwrap()
+        );
+    });
+}
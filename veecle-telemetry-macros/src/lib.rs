@@ -20,6 +20,7 @@ use syn::*;
 struct Arguments {
     name: Option<LitStr>,
     short_name: bool,
+    level: Option<Ident>,
     properties: Vec<Property>,
     veecle_telemetry_crate: Option<syn::Path>,
     span: Span,
@@ -27,15 +28,62 @@ struct Arguments {
 
 struct Property {
     key: LitStr,
-    value: Lit,
+    value: PropertyValue,
     span: Span,
 }
 
+/// A property value, either a literal or a (possibly nested) array of property values.
+enum PropertyValue {
+    Lit(Lit),
+    Array(Vec<PropertyValue>, Span),
+}
+
+impl PropertyValue {
+    fn span(&self) -> Span {
+        match self {
+            PropertyValue::Lit(lit) => lit.span(),
+            PropertyValue::Array(_, span) => *span,
+        }
+    }
+
+    fn to_tokens(&self, veecle_telemetry_crate: &syn::Path) -> proc_macro2::TokenStream {
+        match self {
+            PropertyValue::Lit(lit) => quote!(#lit),
+            PropertyValue::Array(elements, span) => {
+                let elements = elements
+                    .iter()
+                    .map(|element| element.to_tokens(veecle_telemetry_crate));
+                quote_spanned!(*span=>
+                    #veecle_telemetry_crate::protocol::transient::Value::Array(&[
+                        #(#veecle_telemetry_crate::protocol::transient::Value::from(#elements)),*
+                    ])
+                )
+            }
+        }
+    }
+}
+
+impl Parse for PropertyValue {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(token::Bracket) {
+            let content;
+            let bracket_token = bracketed!(content in input);
+            let elements = content.parse_terminated(PropertyValue::parse, Token![,])?;
+            Ok(PropertyValue::Array(
+                elements.into_iter().collect(),
+                bracket_token.span.join(),
+            ))
+        } else {
+            Ok(PropertyValue::Lit(input.parse()?))
+        }
+    }
+}
+
 impl Parse for Property {
     fn parse(input: ParseStream) -> Result<Self> {
         let key: LitStr = input.parse()?;
         input.parse::<Token![:]>()?;
-        let value: Lit = input.parse()?;
+        let value: PropertyValue = input.parse()?;
 
         // For some reason, `join` fails in doc macros.
         let span = key.span().join(value.span()).unwrap_or_else(|| key.span());
@@ -47,6 +95,7 @@ impl Parse for Arguments {
     fn parse(input: ParseStream) -> Result<Self> {
         let mut name = None;
         let mut short_name = false;
+        let mut level = None;
         let mut properties = Vec::<Property>::new();
         let mut veecle_telemetry_crate = None;
         let mut seen = HashMap::new();
@@ -67,6 +116,24 @@ impl Parse for Arguments {
                     let parsed_short_name: LitBool = input.parse()?;
                     short_name = parsed_short_name.value;
                 }
+                "level" => {
+                    let parsed_level: LitStr = input.parse()?;
+                    let variant = match parsed_level.value().to_ascii_lowercase().as_str() {
+                        "trace" => "Trace",
+                        "debug" => "Debug",
+                        "info" => "Info",
+                        "warn" => "Warn",
+                        "error" => "Error",
+                        "fatal" => "Fatal",
+                        _ => {
+                            return Err(Error::new(
+                                parsed_level.span(),
+                                "expected one of `trace`, `debug`, `info`, `warn`, `error` or `fatal`",
+                            ));
+                        }
+                    };
+                    level = Some(Ident::new(variant, parsed_level.span()));
+                }
                 "properties" => {
                     let content;
                     let _brace_token = braced!(content in input);
@@ -95,6 +162,7 @@ impl Parse for Arguments {
         Ok(Arguments {
             name,
             short_name,
+            level,
             properties,
             veecle_telemetry_crate,
             span: input.span(),
@@ -116,8 +184,20 @@ impl Parse for Arguments {
 ///
 /// * `name` - The name of the span. Defaults to the full path of the function.
 /// * `short_name` - Whether to use the function name without path as the span name. Defaults to `false`.
+/// * `level` - The level of the span, one of `"trace"`, `"debug"`, `"info"`, `"warn"`, `"error"` or `"fatal"`.
+///   If the level is below `veecle_telemetry::MAX_LEVEL` the function body runs without creating a span.
+///   Defaults to always creating the span.
 /// * `properties` - A list of key-value pairs to be added as properties to the span. The value can be a format string,
-///   where the function arguments are accessible. Defaults to `{}`.
+///   where the function arguments are accessible, or an array literal of values (e.g. `["a", "b"]`). Defaults to `{}`.
+///
+/// `level` is independent of the other arguments: `name`, `short_name` and `properties` configure the span when it is
+/// created, and are not evaluated at all when the level is disabled.
+///
+/// `async fn`s, including `async fn`s in traits, are instrumented by wrapping their body future.
+/// Functions returning `impl Future` (e.g. a trait method desugared to `fn method(&self) -> impl Future<Output = T> +
+/// Send`) run their body within the span and instrument the returned future with the same span, so work done when
+/// polling the future is recorded as well.
+/// An early `return` in such a function bypasses the instrumentation and is rejected by the compiler.
 ///
 /// # Examples
 ///
@@ -138,6 +218,11 @@ impl Parse for Arguments {
 /// async fn properties(a: u64) {
 ///     // ...
 /// }
+///
+/// #[veecle_telemetry::instrument(level = "debug")]
+/// fn leveled() {
+///     // ...
+/// }
 /// ```
 ///
 /// The code snippets above will be expanded to:
@@ -174,6 +259,18 @@ impl Parse for Arguments {
 ///     )
 ///     .await
 /// }
+///
+/// fn leveled() {
+///     let __guard__ = if const {
+///         veecle_telemetry::level_enabled(veecle_telemetry::protocol::base::Severity::Debug)
+///     } {
+///         Span::new("example::leveled", &[])
+///     } else {
+///         Span::noop()
+///     }
+///     .entered();
+///     // ...
+/// }
 /// ```
 #[proc_macro_attribute]
 pub fn instrument(
@@ -201,10 +298,18 @@ pub fn instrument(
 
     let function_name = &input.sig.ident;
 
+    let kind = if input.sig.asyncness.is_some() {
+        FunctionKind::Async
+    } else if returns_impl_future(&input.sig.output) {
+        FunctionKind::ReturnsFuture
+    } else {
+        FunctionKind::Sync
+    };
+
     let block = match generate_block(
         function_name,
         &input.block,
-        input.sig.asyncness.is_some(),
+        kind,
         &arguments,
         &veecle_telemetry_crate,
     ) {
@@ -281,6 +386,7 @@ fn generate_properties(
         .properties
         .iter()
         .map(|Property { key, value, span }| {
+            let value = value.to_tokens(veecle_telemetry_crate);
             quote_spanned!(*span=>
                 #veecle_telemetry_crate::protocol::transient::KeyValue::new(#key, #value)
             )
@@ -291,9 +397,69 @@ fn generate_properties(
     )
 }
 
+/// Returns the span creation expression, gated on the configured level if there is one.
+///
+/// The level check is a `const` block so disabled spans are removed at compile time.
+fn generate_span(
+    name: proc_macro2::TokenStream,
+    properties: proc_macro2::TokenStream,
+    arguments: &Arguments,
+    veecle_telemetry_crate: &syn::Path,
+    span: Span,
+) -> proc_macro2::TokenStream {
+    let new_span = quote_spanned!(span=> #veecle_telemetry_crate::Span::new(#name, #properties));
+
+    match &arguments.level {
+        None => new_span,
+        Some(level) => quote_spanned!(span=>
+            if const {
+                #veecle_telemetry_crate::level_enabled(
+                    #veecle_telemetry_crate::protocol::base::Severity::#level
+                )
+            } {
+                #new_span
+            } else {
+                #veecle_telemetry_crate::Span::noop()
+            }
+        ),
+    }
+}
+
+/// How the body of an instrumented function is run.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FunctionKind {
+    /// A plain function, the body runs within the span.
+    Sync,
+    /// An `async fn`, including `async fn` in traits.
+    Async,
+    /// A function returning `impl Future`, e.g. a desugared `async fn` in a trait.
+    ///
+    /// The body runs within the span and the returned future is instrumented with the same span.
+    ReturnsFuture,
+}
+
+/// Returns whether `output` is `-> impl Future<...>` (with any path to the `Future` trait).
+fn returns_impl_future(output: &ReturnType) -> bool {
+    let ReturnType::Type(_, ty) = output else {
+        return false;
+    };
+    let Type::ImplTrait(impl_trait) = &**ty else {
+        return false;
+    };
+    impl_trait.bounds.iter().any(|bound| {
+        matches!(
+            bound,
+            TypeParamBound::Trait(TraitBound { path, .. })
+                if path.segments.last().is_some_and(|segment| segment.ident == "Future")
+        )
+    })
+}
+
 /// Generates the instrumented function body as a [`Block`] reusing the original brace tokens.
 ///
 /// For async functions, wraps the body in `veecle_telemetry::future::FutureExt::with_span`.
+/// For functions returning `impl Future`, runs the body within the span and instruments the returned future with the
+/// same span, an early `return` in the body bypasses the instrumentation and fails to compile.
 /// For sync functions, enters a span guard before the body.
 ///
 /// The returned [`Block`] preserves the original brace token spans from the user's source.
@@ -304,41 +470,52 @@ fn generate_properties(
 fn generate_block(
     func_name: &Ident,
     block: &Block,
-    async_context: bool,
+    kind: FunctionKind,
     arguments: &Arguments,
     veecle_telemetry_crate: &syn::Path,
 ) -> syn::Result<Block> {
+    let async_context = kind == FunctionKind::Async;
     let name = generate_name(func_name, arguments, async_context, veecle_telemetry_crate)?;
     let properties = generate_properties(arguments, veecle_telemetry_crate);
     let stmts = &block.stmts;
     let span = func_name.span();
+    let new_span = generate_span(name, properties, arguments, veecle_telemetry_crate, span);
 
-    let wrapper: Block = if async_context {
-        // Build `async move { ... }` manually so the block's brace tokens carry the original
-        // source spans.  The `async move` block is a separate closure/generator from `rustc`'s
-        // perspective, so its body span is subject to the same `eq_ctxt` coverage filter as the
-        // outer function body.
-        let async_block = Expr::Async(ExprAsync {
-            attrs: Vec::new(),
-            async_token: token::Async { span },
-            capture: Some(token::Move { span }),
-            block: Block {
-                brace_token: block.brace_token,
-                stmts: block.stmts.clone(),
-            },
-        });
+    let wrapper: Block = match kind {
+        FunctionKind::Async => {
+            // Build `async move { ... }` manually so the block's brace tokens carry the original
+            // source spans.  The `async move` block is a separate closure/generator from `rustc`'s
+            // perspective, so its body span is subject to the same `eq_ctxt` coverage filter as the
+            // outer function body.
+            let async_block = Expr::Async(ExprAsync {
+                attrs: Vec::new(),
+                async_token: token::Async { span },
+                capture: Some(token::Move { span }),
+                block: Block {
+                    brace_token: block.brace_token,
+                    stmts: block.stmts.clone(),
+                },
+            });
 
-        syn::parse2(quote_spanned!(span=> {
-            #veecle_telemetry_crate::future::FutureExt::with_span(
-                #async_block,
-                #veecle_telemetry_crate::Span::new(#name, #properties),
-            ).await
-        }))?
-    } else {
-        syn::parse2(quote_spanned!(span=> {
-            let __guard__ = #veecle_telemetry_crate::Span::new(#name, #properties).entered();
+            syn::parse2(quote_spanned!(span=> {
+                #veecle_telemetry_crate::future::FutureExt::with_span(
+                    #async_block,
+                    #new_span,
+                ).await
+            }))?
+        }
+        FunctionKind::ReturnsFuture => syn::parse2(quote_spanned!(span=> {
+            let __span__ = #new_span;
+            let __future__ = {
+                let __guard__ = __span__.enter();
+                #(#stmts)*
+            };
+            #veecle_telemetry_crate::future::FutureExt::with_span(__future__, __span__)
+        }))?,
+        FunctionKind::Sync => syn::parse2(quote_spanned!(span=> {
+            let __guard__ = (#new_span).entered();
             #(#stmts)*
-        }))?
+        }))?,
     };
 
     Ok(Block {
@@ -51,3 +51,30 @@ pub async fn async_properties() {
 pub async fn async_short_name_and_properties() {
     unimplemented!("testing compilation")
 }
+
+// Async functions in traits:
+#[expect(async_fn_in_trait, reason = "only testing compilation")]
+pub trait AsyncTrait {
+    #[my_veecle_telemetry::instrument]
+    async fn provided_method(&self) {
+        unimplemented!("testing compilation")
+    }
+
+    async fn required_method(&self, value: u64) -> u64;
+
+    fn desugared_method(&self) -> impl core::future::Future<Output = ()> + Send;
+}
+
+pub struct AsyncTraitImpl;
+
+impl AsyncTrait for AsyncTraitImpl {
+    #[my_veecle_telemetry::instrument(short_name = true)]
+    async fn required_method(&self, value: u64) -> u64 {
+        unimplemented!("testing compilation {value}")
+    }
+
+    #[my_veecle_telemetry::instrument(properties = { "key": "value" })]
+    fn desugared_method(&self) -> impl core::future::Future<Output = ()> + Send {
+        async { unimplemented!("testing compilation") }
+    }
+}
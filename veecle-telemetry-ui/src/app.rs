@@ -7,6 +7,7 @@ use egui::{Color32, Shadow};
 use egui_notify::{Toast, ToastLevel, Toasts};
 use egui_remixicon::icons;
 use log::Level;
+use veecle_telemetry::protocol::owned::ProcessId;
 #[cfg(target_arch = "wasm32")]
 use web_time::{Duration, Instant};
 
@@ -14,7 +15,7 @@ use crate::command::{CommandReceiver, SystemCommand, UICommand, command_channel}
 #[cfg(not(target_arch = "wasm32"))]
 use crate::connection::file::FileConnection;
 use crate::connection::file_contents::{FileContents, FileContentsConnection};
-use crate::connection::{Connection, ConnectionMessage};
+use crate::connection::{Connection, ConnectionMessage, ConnectionStatus};
 use crate::state::AppState;
 use crate::store::Store;
 use crate::ui::filter_panel::filter_panel_ui;
@@ -34,6 +35,8 @@ pub struct StartupOptions {
     pub hide_menu: bool,
     /// Pass a connection to use right away.
     pub connection: Option<Box<dyn Connection>>,
+    /// Offsets in nanoseconds added to the timestamps of each process, see [`Store::set_clock_offset`].
+    pub clock_offsets: Vec<(ProcessId, i64)>,
 }
 
 /// The egui app for `veecle-telemetry-ui`.
@@ -86,10 +89,15 @@ impl VeecleTelemetryApp {
 
         let connection = startup_options.connection.take();
 
+        let mut store = Store::default();
+        for &(process_id, offset_nanos) in &startup_options.clock_offsets {
+            store.set_clock_offset(process_id, offset_nanos);
+        }
+
         let app = VeecleTelemetryApp {
             startup_options,
 
-            store: Default::default(),
+            store,
 
             state: AppState::new(command_sender),
 
@@ -209,7 +217,7 @@ impl VeecleTelemetryApp {
                 let egui_ctx = egui_ctx.clone();
 
                 let promise = poll_promise::Promise::spawn_local(async move {
-                    let file = open_file_dialog_web().await;
+                    let file = open_file_dialog_web(egui_ctx.clone()).await;
                     egui_ctx.request_repaint(); // Wake ui thread
                     file
                 });
@@ -221,6 +229,13 @@ impl VeecleTelemetryApp {
                 self.web_socket_modal.show();
             }
 
+            UICommand::Reconnect => {
+                if let Some(connection) = &mut self.connection {
+                    connection.reconnect();
+                    egui_ctx.request_repaint();
+                }
+            }
+
             #[cfg(not(target_arch = "wasm32"))]
             UICommand::Quit => {
                 egui_ctx.send_viewport_cmd(egui::ViewportCommand::Close);
@@ -318,6 +333,26 @@ impl VeecleTelemetryApp {
                         if let Some(connection) = &self.connection {
                             ui.horizontal(|ui| {
                                 ui.monospace(connection.to_string());
+
+                                if let Some(status) = connection.status() {
+                                    ui.label(status.to_string());
+
+                                    if status == ConnectionStatus::Disconnected
+                                        && ui.button("Reconnect").clicked()
+                                    {
+                                        self.state.send_ui(UICommand::Reconnect);
+                                    }
+                                }
+
+                                if !connection.is_done()
+                                    && let Some(progress) = connection.progress()
+                                {
+                                    ui.add(
+                                        egui::ProgressBar::new(progress)
+                                            .desired_width(100.0)
+                                            .show_percentage(),
+                                    );
+                                }
                             });
                         }
                     });
@@ -385,10 +420,7 @@ impl VeecleTelemetryApp {
 
         if let Some(bytes) = file.bytes {
             state.send_system(SystemCommand::Connect(FileContentsConnection::new_boxed(
-                FileContents {
-                    name: file.name,
-                    bytes,
-                },
+                FileContents::from_bytes(file.name, bytes),
             )));
         }
     }
@@ -488,7 +520,7 @@ fn open_file_dialog_native() -> Option<std::path::PathBuf> {
 }
 
 #[cfg(target_arch = "wasm32")]
-async fn open_file_dialog_web() -> Option<FileContents> {
+async fn open_file_dialog_web(egui_ctx: egui::Context) -> Option<FileContents> {
     let file = rfd::AsyncFileDialog::new()
         .add_filter("Supported files", &["jsonl"])
         .pick_file()
@@ -497,11 +529,9 @@ async fn open_file_dialog_web() -> Option<FileContents> {
     let file_name = file.file_name();
     log::debug!("Reading {file_name}…");
 
-    let bytes = file.read().await;
-    log::debug!("{file_name} was {} bytes", bytes.len());
-
-    Some(FileContents {
-        name: file_name,
-        bytes: bytes.into(),
-    })
+    Some(FileContents::read_web_file(
+        file_name,
+        file.inner().clone(),
+        egui_ctx,
+    ))
 }
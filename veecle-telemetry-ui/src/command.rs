@@ -42,6 +42,8 @@ pub enum UICommand {
     Open,
     /// Connect to WebSocket (open modal).
     Connect,
+    /// Re-establish the current connection.
+    Reconnect,
     #[cfg(not(target_arch = "wasm32"))]
     Quit,
 
@@ -1,33 +1,140 @@
 //! See [FileContentsConnection].
 
 use std::io::{BufRead, Cursor};
-use std::sync::Arc;
+use std::sync::{Arc, mpsc};
 
 use crate::connection::{Connection, ConnectionMessage};
 
-/// The contents of a file that was dropped onto the app.
-#[derive(Clone)]
+/// Size of the chunks a file picked in the browser is read in.
+#[cfg(target_arch = "wasm32")]
+const CHUNK_SIZE: f64 = 1024.0 * 1024.0;
+
+/// Number of chunks of a file picked in the browser that are read ahead of the connection parsing them.
+#[cfg(target_arch = "wasm32")]
+const CHUNKS_AHEAD: usize = 4;
+
+/// The contents of a file that was dropped onto the app or picked in the browser, received in chunks.
 pub struct FileContents {
     /// File name.
     pub name: String,
-    /// File content in bytes.
-    pub bytes: Arc<[u8]>,
+    /// Total size of the file in bytes.
+    pub size: u64,
+    /// Receives the file content in chunks, disconnected once the whole file has been sent.
+    pub chunks: mpsc::Receiver<anyhow::Result<Arc<[u8]>>>,
+}
+
+impl FileContents {
+    /// Creates the contents of a file that is already in memory, e.g. one that was dropped onto the app.
+    pub fn from_bytes(name: String, bytes: Arc<[u8]>) -> Self {
+        let (sender, chunks) = mpsc::channel();
+        let size = bytes.len() as u64;
+        sender
+            .send(Ok(bytes))
+            .expect("the receiver is alive until returned");
+
+        Self { name, size, chunks }
+    }
+
+    /// Creates the contents of a file picked in the browser, reading it in chunks in the background.
+    ///
+    /// `egui_ctx` is woken up whenever a chunk was read.
+    /// At most [`CHUNKS_AHEAD`] chunks are buffered, reading pauses until the connection has parsed them.
+    #[cfg(target_arch = "wasm32")]
+    pub fn read_web_file(name: String, file: web_sys::File, egui_ctx: egui::Context) -> Self {
+        let (sender, chunks) = mpsc::sync_channel(CHUNKS_AHEAD);
+        let size = file.size();
+
+        wasm_bindgen_futures::spawn_local(async move {
+            let mut start = 0.0;
+            while start < size {
+                let end = (start + CHUNK_SIZE).min(size);
+                let mut chunk = read_web_file_slice(&file, start, end).await;
+                let failed = chunk.is_err();
+
+                // Blocking on a full channel would block the browser's only thread, so wait for it to drain instead.
+                loop {
+                    match sender.try_send(chunk) {
+                        Ok(()) => break,
+                        Err(mpsc::TrySendError::Full(unsent)) => {
+                            chunk = unsent;
+                            egui_ctx.request_repaint();
+                            sleep_web(10).await;
+                        }
+                        // Stop reading once the connection is gone.
+                        Err(mpsc::TrySendError::Disconnected(_)) => return,
+                    }
+                }
+                if failed {
+                    return;
+                }
+                egui_ctx.request_repaint();
+
+                start = end;
+            }
+        });
+
+        Self {
+            name,
+            size: size as u64,
+            chunks,
+        }
+    }
+}
+
+/// Reads the bytes from `start` to `end` of `file`.
+#[cfg(target_arch = "wasm32")]
+async fn read_web_file_slice(
+    file: &web_sys::File,
+    start: f64,
+    end: f64,
+) -> anyhow::Result<Arc<[u8]>> {
+    let blob = file
+        .slice_with_f64_and_f64(start, end)
+        .map_err(|error| anyhow::anyhow!("slicing file: {error:?}"))?;
+    let buffer = wasm_bindgen_futures::JsFuture::from(blob.array_buffer())
+        .await
+        .map_err(|error| anyhow::anyhow!("reading file: {error:?}"))?;
+
+    Ok(js_sys::Uint8Array::new(&buffer).to_vec().into())
+}
+
+/// Waits for `millis` milliseconds without blocking the browser.
+#[cfg(target_arch = "wasm32")]
+async fn sleep_web(millis: i32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        web_sys::window()
+            .expect("running in a browser window")
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, millis)
+            .expect("setting a timeout");
+    });
+    // The promise is only ever resolved.
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
 }
 
 impl std::fmt::Debug for FileContents {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("FileContents")
             .field("name", &self.name)
-            .field("bytes", &format_args!("{} bytes", self.bytes.len()))
-            .finish()
+            .field("size", &format_args!("{} bytes", self.size))
+            .finish_non_exhaustive()
     }
 }
 
-/// A connection to an in memory file in the browser.
+/// A connection to a file in the browser, parsing lines as its chunks are received.
+///
+/// Only the current chunk and line are held in memory, so rendering starts before the whole file has been read.
 #[derive(Debug)]
 pub struct FileContentsConnection {
     file_name: String,
-    bytes: Cursor<Arc<[u8]>>,
+    size: u64,
+    chunks: mpsc::Receiver<anyhow::Result<Arc<[u8]>>>,
+
+    /// The chunk lines are currently read from.
+    chunk: Cursor<Arc<[u8]>>,
+    /// The line read so far, it may continue in the next chunk.
+    line: Vec<u8>,
+    /// Number of bytes read so far.
+    bytes_read: u64,
 
     done: bool,
 }
@@ -36,27 +143,63 @@ impl FileContentsConnection {
     /// Create a new connection to a file in memory.
     pub fn new_boxed(file_contents: FileContents) -> Box<dyn Connection> {
         Box::new(Self {
-            file_name: file_contents.name.clone(),
-            bytes: Cursor::new(file_contents.bytes),
+            file_name: file_contents.name,
+            size: file_contents.size,
+            chunks: file_contents.chunks,
+
+            chunk: Cursor::new(Arc::from([])),
+            line: Vec::new(),
+            bytes_read: 0,
 
             done: false,
         })
     }
+
+    /// Returns the line read so far and starts a new one.
+    fn take_line(&mut self) -> ConnectionMessage {
+        match String::from_utf8(std::mem::take(&mut self.line)) {
+            Ok(line) => ConnectionMessage::Line(line),
+            Err(error) => ConnectionMessage::Error(
+                anyhow::anyhow!(error).context("file contents connection error"),
+            ),
+        }
+    }
 }
 
 impl Connection for FileContentsConnection {
     fn try_recv(&mut self) -> Option<ConnectionMessage> {
-        let mut buffer = String::new();
+        loop {
+            let read = match self.chunk.read_until(b'\n', &mut self.line) {
+                Ok(read) => read,
+                Err(error) => {
+                    return Some(ConnectionMessage::Error(
+                        anyhow::anyhow!(error).context("file contents connection error"),
+                    ));
+                }
+            };
+            self.bytes_read += read as u64;
 
-        match self.bytes.read_line(&mut buffer) {
-            Ok(0) => {
-                self.done = true;
-                None
+            if self.line.ends_with(b"\n") {
+                return Some(self.take_line());
+            }
+
+            // The current chunk is exhausted, continue the line in the next one.
+            match self.chunks.try_recv() {
+                Ok(Ok(chunk)) => self.chunk = Cursor::new(chunk),
+                Ok(Err(error)) => {
+                    self.done = true;
+                    return Some(ConnectionMessage::Error(
+                        error.context("file contents connection error"),
+                    ));
+                }
+                Err(mpsc::TryRecvError::Empty) => return None,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.done = true;
+
+                    // The last line is not necessarily terminated.
+                    return (!self.line.is_empty()).then(|| self.take_line());
+                }
             }
-            Ok(_) => Some(ConnectionMessage::Line(buffer)),
-            Err(error) => Some(ConnectionMessage::Error(
-                anyhow::anyhow!(error).context("file contents connection error"),
-            )),
         }
     }
 
@@ -67,6 +210,13 @@ impl Connection for FileContentsConnection {
     fn is_done(&self) -> bool {
         self.done
     }
+
+    fn progress(&self) -> Option<f32> {
+        match self.size {
+            0 => Some(1.0),
+            size => Some((self.bytes_read as f64 / size as f64).min(1.0) as f32),
+        }
+    }
 }
 
 impl std::fmt::Display for FileContentsConnection {
@@ -74,3 +224,80 @@ impl std::fmt::Display for FileContentsConnection {
         write!(f, "file ({})", self.file_name)
     }
 }
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::sync::{Arc, mpsc};
+
+    use super::{FileContents, FileContentsConnection};
+    use crate::connection::ConnectionMessage;
+
+    /// Returns a connection receiving `chunks`, and all lines it parses until it is done.
+    fn lines(chunks: &[&str]) -> Vec<String> {
+        let (sender, receiver) = mpsc::channel();
+        for chunk in chunks {
+            sender.send(Ok(Arc::from(chunk.as_bytes()))).unwrap();
+        }
+        drop(sender);
+
+        let mut connection = FileContentsConnection::new_boxed(FileContents {
+            name: "test".to_owned(),
+            size: chunks.iter().map(|chunk| chunk.len() as u64).sum(),
+            chunks: receiver,
+        });
+
+        let mut lines = Vec::new();
+        while let Some(message) = connection.try_recv() {
+            match message {
+                ConnectionMessage::Line(line) => lines.push(line),
+                message => panic!("unexpected message {message:?}"),
+            }
+        }
+        assert!(connection.is_done());
+        assert_eq!(connection.progress(), Some(1.0));
+
+        lines
+    }
+
+    #[test]
+    fn lines_spanning_chunks() {
+        assert_eq!(
+            lines(&["fi", "rst\nsec", "", "ond\n", "\nthird\n"]),
+            ["first\n", "second\n", "\n", "third\n"]
+        );
+    }
+
+    #[test]
+    fn unterminated_last_line() {
+        assert_eq!(lines(&["first\nla", "st"]), ["first\n", "last"]);
+        assert_eq!(lines(&["first\n"]), ["first\n"]);
+        assert!(lines(&[]).is_empty());
+    }
+
+    #[test]
+    fn waits_for_chunks() {
+        let (sender, receiver) = mpsc::sync_channel(1);
+        let mut connection = FileContentsConnection::new_boxed(FileContents {
+            name: "test".to_owned(),
+            size: 10,
+            chunks: receiver,
+        });
+
+        sender.send(Ok(Arc::from(&b"first\nla"[..]))).unwrap();
+        assert!(
+            matches!(connection.try_recv(), Some(ConnectionMessage::Line(line)) if line == "first\n")
+        );
+
+        // The unterminated line is only complete once the reader is done.
+        assert!(connection.try_recv().is_none());
+        assert!(!connection.is_done());
+
+        sender.send(Ok(Arc::from(&b"st"[..]))).unwrap();
+        drop(sender);
+        assert!(
+            matches!(connection.try_recv(), Some(ConnectionMessage::Line(line)) if line == "last")
+        );
+        assert!(connection.is_done());
+    }
+}
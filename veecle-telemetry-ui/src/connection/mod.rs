@@ -17,6 +17,46 @@ pub trait Connection: std::fmt::Display + std::fmt::Debug {
 
     /// Returns `true` if all data has been received.
     fn is_done(&self) -> bool;
+
+    /// Returns the fraction of the data that has been received, between `0.0` and `1.0`.
+    ///
+    /// Returns `None` if the total amount of data is not known.
+    fn progress(&self) -> Option<f32> {
+        None
+    }
+
+    /// Returns the status of the connection to a remote.
+    ///
+    /// Returns `None` for connections that are not to a remote, e.g. files.
+    fn status(&self) -> Option<ConnectionStatus> {
+        None
+    }
+
+    /// Re-establishes the connection to the remote, dropping the current one if any.
+    ///
+    /// Does nothing for connections that are not to a remote.
+    fn reconnect(&mut self) {}
+}
+
+/// The status of a connection to a remote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionStatus {
+    /// Waiting for the connection to be established.
+    Connecting,
+    /// The connection is established.
+    Connected,
+    /// The connection was lost, a reconnection attempt may be scheduled.
+    Disconnected,
+}
+
+impl std::fmt::Display for ConnectionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ConnectionStatus::Connecting => "connecting",
+            ConnectionStatus::Connected => "connected",
+            ConnectionStatus::Disconnected => "disconnected",
+        })
+    }
 }
 
 /// Messages received from a connection.
@@ -1,20 +1,41 @@
 //! See [WebSocketConnection].
 
 use std::collections::VecDeque;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
 
 use ewebsock::{WsEvent, WsMessage};
 use veecle_telemetry_server_protocol::TracingMessage;
+#[cfg(target_arch = "wasm32")]
+use web_time::{Duration, Instant};
 
-use crate::connection::{Connection, ConnectionMessage};
+use crate::connection::{Connection, ConnectionMessage, ConnectionStatus};
 use anyhow::Context;
 
+/// Delay before the first reconnection attempt, doubled after every failed attempt.
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_millis(500);
+
+/// Upper bound for the delay between reconnection attempts.
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
 /// Represents a `WebSocket` connection.
+///
+/// Lost connections are re-established automatically with exponential backoff.
 pub struct WebSocketConnection {
     url: String,
-
-    #[expect(unused, reason = "when this is dropped, the connection gets closed")]
-    sender: ewebsock::WsSender,
-    receiver: ewebsock::WsReceiver,
+    egui_ctx: egui::Context,
+
+    /// When the sender is dropped, the connection gets closed.
+    socket: Option<(ewebsock::WsSender, ewebsock::WsReceiver)>,
+    status: ConnectionStatus,
+    /// Number of failed connection attempts since the connection was last established.
+    failed_attempts: u32,
+    /// When to attempt the next reconnection, if disconnected.
+    reconnect_at: Option<Instant>,
+    /// Whether the store needs to be cleared once the connection is established.
+    ///
+    /// The server sends all of its data on every new connection.
+    restart_pending: bool,
 
     buffer: VecDeque<String>,
     total: usize,
@@ -24,24 +45,70 @@ pub struct WebSocketConnection {
 impl WebSocketConnection {
     /// Create a new `WebSocket` connection.
     pub fn new_boxed(url: String, egui_ctx: egui::Context) -> anyhow::Result<Box<dyn Connection>> {
-        let wake_up = move || egui_ctx.request_repaint(); // wake up UI thread on new message
-
-        let (sender, receiver) =
-            ewebsock::connect_with_wakeup(url.clone(), Default::default(), wake_up)
-                .map_err(anyhow::Error::msg)
-                .with_context(|| format!("connecting to {url}"))?;
+        let socket = Self::connect(&url, egui_ctx.clone())?;
 
         Ok(Box::new(Self {
             url,
+            egui_ctx,
 
-            sender,
-            receiver,
+            socket: Some(socket),
+            status: ConnectionStatus::Connecting,
+            failed_attempts: 0,
+            reconnect_at: None,
+            restart_pending: false,
 
             buffer: Default::default(),
             total: Default::default(),
             done: Default::default(),
         }))
     }
+
+    fn connect(
+        url: &str,
+        egui_ctx: egui::Context,
+    ) -> anyhow::Result<(ewebsock::WsSender, ewebsock::WsReceiver)> {
+        let wake_up = move || egui_ctx.request_repaint(); // wake up UI thread on new message
+
+        ewebsock::connect_with_wakeup(url, Default::default(), wake_up)
+            .map_err(anyhow::Error::msg)
+            .with_context(|| format!("connecting to {url}"))
+    }
+
+    /// Opens a new connection, replacing the current one if any.
+    fn open(&mut self) {
+        self.socket = None;
+        self.reconnect_at = None;
+        self.restart_pending = true;
+
+        self.buffer.clear();
+        self.total = 0;
+        self.done = false;
+
+        match Self::connect(&self.url, self.egui_ctx.clone()) {
+            Ok(socket) => {
+                self.socket = Some(socket);
+                self.status = ConnectionStatus::Connecting;
+            }
+            Err(error) => {
+                log::warn!("WebSocket reconnection failed: {error:?}");
+                self.disconnect();
+            }
+        }
+    }
+
+    /// Drops the current connection and schedules the next reconnection attempt.
+    fn disconnect(&mut self) {
+        self.socket = None;
+        self.status = ConnectionStatus::Disconnected;
+
+        let delay = INITIAL_RECONNECT_DELAY
+            .saturating_mul(2u32.saturating_pow(self.failed_attempts))
+            .min(MAX_RECONNECT_DELAY);
+        self.failed_attempts = self.failed_attempts.saturating_add(1);
+
+        self.reconnect_at = Some(Instant::now() + delay);
+        self.egui_ctx.request_repaint_after(delay);
+    }
 }
 
 impl Connection for WebSocketConnection {
@@ -50,20 +117,39 @@ impl Connection for WebSocketConnection {
             return Some(ConnectionMessage::Line(line));
         }
 
-        let event = self.receiver.try_recv()?;
+        if self
+            .reconnect_at
+            .is_some_and(|reconnect_at| Instant::now() >= reconnect_at)
+        {
+            self.open();
+        }
+
+        let (_, receiver) = self.socket.as_ref()?;
+
+        let event = receiver.try_recv()?;
         let message = match event {
             WsEvent::Message(message) => message,
             WsEvent::Error(error) => {
+                self.disconnect();
                 return Some(ConnectionMessage::Error(
                     anyhow::anyhow!(error).context("WebSocket Error"),
                 ));
             }
             WsEvent::Closed => {
+                self.disconnect();
                 return Some(ConnectionMessage::Error(anyhow::anyhow!(
                     "WebSocket connection closed."
                 )));
             }
-            WsEvent::Opened => return None,
+            WsEvent::Opened => {
+                self.status = ConnectionStatus::Connected;
+                self.failed_attempts = 0;
+
+                if std::mem::take(&mut self.restart_pending) {
+                    return Some(ConnectionMessage::Restart);
+                }
+                return None;
+            }
         };
 
         let message = match message {
@@ -102,6 +188,15 @@ impl Connection for WebSocketConnection {
     fn is_done(&self) -> bool {
         self.done
     }
+
+    fn status(&self) -> Option<ConnectionStatus> {
+        Some(self.status)
+    }
+
+    fn reconnect(&mut self) {
+        self.failed_attempts = 0;
+        self.open();
+    }
 }
 
 impl std::fmt::Display for WebSocketConnection {
@@ -114,6 +209,9 @@ impl std::fmt::Debug for WebSocketConnection {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("WebSocketConnection")
             .field("receiver", &"WsReceiver { rx: ... }".to_string())
+            .field("status", &self.status)
+            .field("failed_attempts", &self.failed_attempts)
+            .field("reconnect_at", &self.reconnect_at)
             .field("buffer", &self.buffer)
             .field("total", &self.total)
             .field("done", &self.done)
@@ -17,6 +17,8 @@ use anyhow::Context;
 #[cfg(not(target_arch = "wasm32"))]
 use clap::Parser;
 
+#[cfg(not(target_arch = "wasm32"))]
+use veecle_telemetry::protocol::owned::ProcessId;
 use veecle_telemetry_ui::app::{StartupOptions, VeecleTelemetryApp};
 #[cfg(not(target_arch = "wasm32"))]
 use veecle_telemetry_ui::connection::Connection;
@@ -115,6 +117,26 @@ struct Cli {
         help = "Data source to load traces from. Supports file paths (./spans.jsonl) or WebSocket URLs (ws://localhost:9000). If data is piped via stdin, this argument is ignored."
     )]
     source: InitialConnection,
+
+    #[arg(
+        long = "clock-offset",
+        value_name = "PROCESS_ID=NANOSECONDS",
+        value_parser = parse_clock_offset,
+        help = "Offset added to the timestamps of a process to correct the skew between the clocks of different devices. Can be given multiple times."
+    )]
+    clock_offsets: Vec<(ProcessId, i64)>,
+}
+
+/// Parses a `<PROCESS_ID>=<NANOSECONDS>` clock offset argument.
+#[cfg(not(target_arch = "wasm32"))]
+fn parse_clock_offset(argument: &str) -> anyhow::Result<(ProcessId, i64)> {
+    let (process_id, offset) = argument
+        .split_once('=')
+        .context("expected `<PROCESS_ID>=<NANOSECONDS>`")?;
+    Ok((
+        process_id.parse().context("parsing process id")?,
+        offset.parse().context("parsing offset")?,
+    ))
 }
 
 /// Spawns a separate thread to handle stdin without blocking.
@@ -167,6 +189,7 @@ fn main() -> eframe::Result<()> {
                 cc,
                 StartupOptions {
                     connection,
+                    clock_offsets: args.clock_offsets,
                     ..Default::default()
                 },
             )))
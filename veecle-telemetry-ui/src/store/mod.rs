@@ -12,7 +12,7 @@ use anyhow::Context;
 use egui::Color32;
 use indexmap::{IndexMap, IndexSet};
 use serde::{Deserialize, Serialize};
-use veecle_telemetry::protocol::owned::{
+use veecle_telemetry::protocol::borrowed::{
     InstanceMessage, LogMessage, ProcessId, Severity, SpanContext, SpanId as TelemetrySpanId,
     TelemetryMessage, ThreadId, TracingMessage, Value as TelemetryValue,
 };
@@ -49,6 +49,10 @@ pub struct Store {
     /// newly created spans.
     execution_contexts: HashMap<ThreadId, Vec<SpanContext>>,
 
+    /// Offsets in nanoseconds added to the timestamps of each process, e.g. to correct the skew between the clocks of
+    /// different devices.
+    clock_offsets: HashMap<ProcessId, i64>,
+
     /// The earliest known timestamp.
     ///
     /// Gets initialized to [`Timestamp::MAX`] so the first real timestamp becomes the start.
@@ -77,6 +81,7 @@ impl Default for Store {
             actors: HashSet::default(),
             thread_ids: HashSet::default(),
             execution_contexts: HashMap::default(),
+            clock_offsets: HashMap::default(),
             start: Timestamp::MAX,
             end: Timestamp::MIN,
             last_update: Instant::now(),
@@ -124,13 +129,22 @@ impl Store {
         self.thread_ids.iter().copied()
     }
 
+    /// Sets the offset in nanoseconds added to the timestamps of all messages from `process_id`.
+    ///
+    /// The offset applies to messages processed afterwards and is kept when the store is cleared, set it before
+    /// loading data.
+    pub fn set_clock_offset(&mut self, process_id: ProcessId, offset_nanos: i64) {
+        self.clock_offsets.insert(process_id, offset_nanos);
+    }
+
     /// Process a single line from a trace file or piped input.
     pub fn process_line(&mut self, line: &str) -> anyhow::Result<()> {
         if line.is_empty() {
             return Ok(());
         }
 
-        let message: InstanceMessage =
+        // Strings are borrowed from `line` where possible and only copied once into the store.
+        let message: InstanceMessage<'_> =
             serde_json::from_str(line).context("parsing instance message")?;
         self.process_message(message);
 
@@ -190,7 +204,7 @@ impl Store {
     /// Processes a single message from a trace file.
     ///
     /// Can also be used to process streaming data live.
-    pub fn process_message(&mut self, instance_message: InstanceMessage) {
+    pub fn process_message(&mut self, instance_message: InstanceMessage<'_>) {
         // ensure the program span is inserted.
         self.ensure_program_span();
 
@@ -207,15 +221,16 @@ impl Store {
             TelemetryMessage::Log(log_msg) => {
                 self.process_log_message(thread, log_msg);
             }
-            TelemetryMessage::TimeSync(_) => {
-                // TODO(DEV-601): handle these messages.
-            }
+            // Collectors exporting time sync messages already timestamp their messages with the system time, so
+            // there is nothing to correct using them, skew between processes is corrected with the clock offsets.
+            TelemetryMessage::TimeSync(_) => {}
         }
     }
 
-    /// Helper function to update timestamp bounds and last update time.
-    fn update_timestamp(&mut self, time_unix_nano: u64) -> Timestamp {
-        let timestamp = Timestamp::from_ns(time_unix_nano as i64);
+    /// Helper function to apply the clock offset of `process_id` and update timestamp bounds and last update time.
+    fn update_timestamp(&mut self, process_id: ProcessId, time_unix_nano: u64) -> Timestamp {
+        let offset = self.clock_offsets.get(&process_id).copied().unwrap_or(0);
+        let timestamp = Timestamp::from_ns((time_unix_nano as i64).saturating_add(offset));
         self.start = self.start.min(timestamp);
         self.end = self.end.max(timestamp);
         self.last_update = Instant::now();
@@ -228,13 +243,19 @@ impl Store {
     }
 
     /// Processes a single tracing message.
-    fn process_tracing_message(&mut self, thread_id: ThreadId, tracing_msg: TracingMessage) {
+    fn process_tracing_message(&mut self, thread_id: ThreadId, tracing_msg: TracingMessage<'_>) {
         match tracing_msg {
             TracingMessage::CreateSpan(span_msg) => {
-                let timestamp = self.update_timestamp(span_msg.start_time_unix_nano);
+                let timestamp =
+                    self.update_timestamp(thread_id.process, span_msg.start_time_unix_nano);
 
                 let context = SpanContext::new(thread_id.process, span_msg.span_id);
-                let parent_context = self.current_span_for(thread_id);
+                // An explicit parent may come from another process whose telemetry isn't part of this store, in
+                // which case the span is shown as a root span.
+                let parent_context = match span_msg.parent {
+                    Some(parent) => self.spans.contains_key(&parent).then_some(parent),
+                    None => self.current_span_for(thread_id),
+                };
 
                 let mut parent_span = parent_context.map(|parent| {
                     self.spans
@@ -250,8 +271,8 @@ impl Store {
 
                 let fields: IndexMap<String, Value> = span_msg
                     .attributes
-                    .iter()
-                    .map(|kv| (kv.key.as_str().to_string(), Value::from(kv.value.clone())))
+                    .into_iter()
+                    .map(|kv| (kv.key.0.into_owned(), Value::from(kv.value)))
                     .collect();
 
                 let actor = fields
@@ -265,7 +286,7 @@ impl Store {
                 self.thread_ids.insert(thread_id);
 
                 let metadata = Metadata {
-                    name: span_msg.name.as_str().to_string(),
+                    name: span_msg.name.0.into_owned(),
                     target: "unknown".to_string(),
                     // Default level
                     level: Level::Info,
@@ -279,7 +300,7 @@ impl Store {
                 self.spans.insert(context, span);
             }
             TracingMessage::EnterSpan(enter_msg) => {
-                let timestamp = self.update_timestamp(enter_msg.time_unix_nano);
+                let timestamp = self.update_timestamp(thread_id.process, enter_msg.time_unix_nano);
 
                 let span_context = SpanContext::new(thread_id.process, enter_msg.span_id);
                 self.execution_contexts
@@ -299,7 +320,7 @@ impl Store {
                 });
             }
             TracingMessage::ExitSpan(exit_msg) => {
-                let timestamp = self.update_timestamp(exit_msg.time_unix_nano);
+                let timestamp = self.update_timestamp(thread_id.process, exit_msg.time_unix_nano);
 
                 let span_context = SpanContext::new(thread_id.process, exit_msg.span_id);
                 let expected = self.execution_contexts.entry(thread_id).or_default().pop();
@@ -327,7 +348,8 @@ impl Store {
                 program_span.end = program_span.end.max(timestamp);
             }
             TracingMessage::CloseSpan(close_msg) => {
-                let timestamp = self.update_timestamp(close_msg.end_time_unix_nano);
+                let timestamp =
+                    self.update_timestamp(thread_id.process, close_msg.end_time_unix_nano);
 
                 let span_context = SpanContext::new(thread_id.process, close_msg.span_id);
                 let span = self
@@ -345,7 +367,7 @@ impl Store {
                     .map_or(timestamp, |activity| activity.end);
             }
             TracingMessage::AddEvent(event_msg) => {
-                let timestamp = self.update_timestamp(event_msg.time_unix_nano);
+                let timestamp = self.update_timestamp(thread_id.process, event_msg.time_unix_nano);
 
                 let Some(span_context) = event_msg
                     .span_id
@@ -374,17 +396,17 @@ impl Store {
 
                 let fields: IndexMap<String, Value> = event_msg
                     .attributes
-                    .iter()
-                    .map(|kv| (kv.key.as_str().to_string(), Value::from(kv.value.clone())))
+                    .into_iter()
+                    .map(|kv| (kv.key.0.into_owned(), Value::from(kv.value)))
                     .collect();
 
-                let message = event_msg.name.as_str().to_string();
+                let message = event_msg.name.to_string();
 
                 // TODO(DEV-584): add file or module path.
                 let metadata = Metadata {
-                    name: event_msg.name.as_str().to_string(),
+                    name: event_msg.name.0.into_owned(),
                     target: "unknown".to_string(),
-                    level: Level::Info,
+                    level: event_msg.severity.into(),
                     file: None,
                 };
 
@@ -439,7 +461,7 @@ impl Store {
                     .get_mut(&span_context)
                     .expect("span should exist");
 
-                let key = attr_msg.attribute.key.as_str().to_string();
+                let key = attr_msg.attribute.key.0.into_owned();
                 let value = Value::from(attr_msg.attribute.value);
                 span.fields.insert(key, value);
             }
@@ -447,8 +469,8 @@ impl Store {
     }
 
     /// Processes a single log message.
-    fn process_log_message(&mut self, thread_id: ThreadId, log_msg: LogMessage) {
-        let timestamp = self.update_timestamp(log_msg.time_unix_nano);
+    fn process_log_message(&mut self, thread_id: ThreadId, log_msg: LogMessage<'_>) {
+        let timestamp = self.update_timestamp(thread_id.process, log_msg.time_unix_nano);
 
         // Find the span this log belongs to, or use the program span.
         let span_context = self
@@ -468,11 +490,11 @@ impl Store {
 
         let fields: IndexMap<String, Value> = log_msg
             .attributes
-            .iter()
-            .map(|kv| (kv.key.as_str().to_string(), Value::from(kv.value.clone())))
+            .into_iter()
+            .map(|kv| (kv.key.0.into_owned(), Value::from(kv.value)))
             .collect();
 
-        let message = log_msg.body.as_str().to_string();
+        let message = log_msg.body.0.into_owned();
 
         // TODO(DEV-584): add file or module path.
         let metadata = Metadata {
@@ -665,6 +687,12 @@ pub enum Value {
     U128(u128),
     /// A [`bool`].
     Bool(bool),
+    /// Raw bytes.
+    Bytes(Vec<u8>),
+    /// An array of values.
+    Array(Vec<Value>),
+    /// A map of key-value pairs, in insertion order.
+    Map(Vec<(String, Value)>),
 }
 
 impl Value {
@@ -687,17 +715,54 @@ impl std::fmt::Display for Value {
             Value::I128(value) => std::fmt::Display::fmt(value, f),
             Value::U128(value) => std::fmt::Display::fmt(value, f),
             Value::Bool(value) => std::fmt::Display::fmt(value, f),
+            Value::Bytes(bytes) => {
+                f.write_str("0x")?;
+                for byte in bytes {
+                    write!(f, "{byte:02x}")?;
+                }
+                Ok(())
+            }
+            Value::Array(values) => {
+                f.write_str("[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    std::fmt::Display::fmt(value, f)?;
+                }
+                f.write_str("]")
+            }
+            Value::Map(entries) => {
+                f.write_str("{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{key}: {value}")?;
+                }
+                f.write_str("}")
+            }
         }
     }
 }
 
-impl From<TelemetryValue> for Value {
-    fn from(value: TelemetryValue) -> Self {
+impl From<TelemetryValue<'_>> for Value {
+    fn from(value: TelemetryValue<'_>) -> Self {
         match value {
-            TelemetryValue::String(s) => Value::Str(s),
+            TelemetryValue::String(s) => Value::Str(s.into_owned()),
             TelemetryValue::Bool(b) => Value::Bool(b),
             TelemetryValue::I64(i) => Value::I64(i),
             TelemetryValue::F64(f) => Value::F64(f),
+            TelemetryValue::Bytes(bytes) => Value::Bytes(bytes),
+            TelemetryValue::Array(values) => {
+                Value::Array(values.into_iter().map(Value::from).collect())
+            }
+            TelemetryValue::Map(entries) => Value::Map(
+                entries
+                    .into_iter()
+                    .map(|entry| (entry.key.0.into_owned(), Value::from(entry.value)))
+                    .collect(),
+            ),
         }
     }
 }
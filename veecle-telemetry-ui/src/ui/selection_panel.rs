@@ -2,7 +2,7 @@ use egui::RichText;
 
 use crate::selection::{Item, SelectionState};
 use crate::state::{AppState, PanelState};
-use crate::store::{LogRef, Metadata, SpanRef, Store};
+use crate::store::{LogRef, Metadata, SpanRef, Store, Value};
 use crate::ui::panel::{collapsing_grid_ui, panel_content_ui};
 
 pub fn selection_panel_ui(ui: &mut egui::Ui, app_state: &AppState, store: &Store) {
@@ -105,7 +105,7 @@ fn span_details_ui(ui: &mut egui::Ui, selection_state: &SelectionState, span: Sp
     collapsing_grid_ui(ui, "Fields", |ui| {
         for (key, value) in span.fields.iter() {
             ui.monospace(key);
-            ui.monospace(format!("{value}"));
+            field_value_ui(ui, value);
             ui.end_row();
         }
     });
@@ -192,7 +192,7 @@ fn log_details_ui(ui: &mut egui::Ui, selection_state: &SelectionState, log: LogR
     collapsing_grid_ui(ui, "Fields", |ui| {
         for (key, value) in log.fields.iter() {
             ui.monospace(key);
-            ui.monospace(format!("{value}"));
+            field_value_ui(ui, value);
             ui.end_row();
         }
     });
@@ -210,6 +210,61 @@ fn log_details_ui(ui: &mut egui::Ui, selection_state: &SelectionState, log: LogR
     });
 }
 
+/// Renders a field value, arrays and maps are shown as expandable trees.
+fn field_value_ui(ui: &mut egui::Ui, value: &Value) {
+    match value {
+        Value::Array(values) => {
+            egui::CollapsingHeader::new(format!("[{} items]", values.len()))
+                .id_salt(ui.next_auto_id())
+                .show(ui, |ui| {
+                    for (index, value) in values.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.monospace(format!("{index}:"));
+                            field_value_ui(ui, value);
+                        });
+                    }
+                });
+        }
+        Value::Map(entries) => {
+            egui::CollapsingHeader::new(format!("{{{} entries}}", entries.len()))
+                .id_salt(ui.next_auto_id())
+                .show(ui, |ui| {
+                    for (key, value) in entries {
+                        ui.horizontal(|ui| {
+                            ui.monospace(format!("{key}:"));
+                            field_value_ui(ui, value);
+                        });
+                    }
+                });
+        }
+        Value::Bytes(bytes) => {
+            egui::CollapsingHeader::new(format!("<{} bytes>", bytes.len()))
+                .id_salt(ui.next_auto_id())
+                .show(ui, |ui| {
+                    ui.monospace(hex_dump(bytes));
+                });
+        }
+        value => {
+            ui.monospace(format!("{value}"));
+        }
+    }
+}
+
+/// Formats bytes as a hex dump with 16 bytes per line, prefixed by the offset of the line.
+fn hex_dump(bytes: &[u8]) -> String {
+    let mut dump = String::new();
+    for (line, chunk) in bytes.chunks(16).enumerate() {
+        if line > 0 {
+            dump.push('\n');
+        }
+        dump.push_str(&format!("{:08x}:", line * 16));
+        for byte in chunk {
+            dump.push_str(&format!(" {byte:02x}"));
+        }
+    }
+    dump
+}
+
 fn metadata_details_ui(ui: &mut egui::Ui, metadata: &Metadata) {
     collapsing_grid_ui(ui, "Metadata", |ui| {
         ui.label("Name");
@@ -37,7 +37,7 @@ use veecle_telemetry::SpanContext;
 
 use crate::filter::Filters;
 use crate::selection::SelectionState;
-use crate::store::{LogRef, SpanRef, Store, Timestamp};
+use crate::store::{Level, LogRef, SpanRef, Store, Timestamp};
 use crate::ui::timeline::TimeRangeUi;
 
 pub const RECT_HEIGHT: f32 = 20.0;
@@ -74,8 +74,8 @@ pub fn links_ui(
         .collect::<Vec<_>>();
 
     for (source_pos, target_pos) in pairs {
+        // The linked span may still be running when the linking span starts, in which case the arrow doubles back.
         let span = target_pos.x - source_pos.x;
-        assert!(span >= 0.0, "target should not be before the source");
 
         let mid_offset = ARROW_LENGTH.min((span - ARROW_LENGTH).max(0.0));
         let mid_x = source_pos.x + mid_offset;
@@ -98,6 +98,12 @@ pub fn links_ui(
 
 const ARROW_LENGTH: f32 = 15.0;
 
+const ARROW_DASH_LENGTH: f32 = 4.0;
+const ARROW_GAP_LENGTH: f32 = 3.0;
+
+/// Paints a dashed arrow along `positions`.
+///
+/// Dashes distinguish links from the parent-child nesting of spans.
 fn paint_arrow(painter: &Painter, positions: &[Pos2]) {
     assert!(
         positions.len() >= 2,
@@ -121,7 +127,12 @@ fn paint_arrow(painter: &Painter, positions: &[Pos2]) {
         }
 
         if i < length - 1 {
-            painter.line_segment([from, to], stroke);
+            painter.add(Shape::dashed_line(
+                &[from, to],
+                stroke,
+                ARROW_DASH_LENGTH,
+                ARROW_GAP_LENGTH,
+            ));
             continue;
         }
 
@@ -135,7 +146,12 @@ fn paint_arrow(painter: &Painter, positions: &[Pos2]) {
             let arrow_length_fraction = arrow_length / distance;
             let line_length_fraction = 1.0 - arrow_length_fraction;
 
-            painter.line_segment([from, from.lerp(to, line_length_fraction)], stroke);
+            painter.add(Shape::dashed_line(
+                &[from, from.lerp(to, line_length_fraction)],
+                stroke,
+                ARROW_DASH_LENGTH,
+                ARROW_GAP_LENGTH,
+            ));
             painter.arrow(
                 from.lerp(to, line_length_fraction),
                 vec * arrow_length_fraction,
@@ -155,9 +171,8 @@ fn find_source_position(
         return Some(*pos);
     }
 
-    let span = store
-        .get_span(span_context)
-        .expect("span has to exist in the store");
+    // Links can point to spans of other processes which are not part of the loaded data.
+    let span = store.get_span(span_context)?;
 
     let x = time_range_ui.x_from_time_f32(span.end);
 
@@ -457,7 +472,12 @@ fn paint_record(
     }
 
     for_each_log(span, show_child_logs, &mut |log| {
-        paint_log_triangle(info, time_range_ui, log, rect_color, bottom_y_margin);
+        // Warnings and errors stand out from the span, other events blend in with it.
+        let color = match log.metadata.level {
+            Level::Error | Level::Warn => log.metadata.level.color(),
+            Level::Info | Level::Debug | Level::Trace => rect_color.into(),
+        };
+        paint_log_triangle(info, time_range_ui, log, color, bottom_y_margin);
     });
 
     let wide_enough_for_text = stop_x - start_x > 32.0;
@@ -1,5 +1,8 @@
+#[cfg(feature = "alloc")]
+use super::Redaction;
 use super::global::SetGlobalError;
-use super::{Collector, Export, ProcessId};
+use super::{Collector, Export, ProcessId, Sampler};
+use crate::protocol::base::TimeSyncMessage;
 
 use veecle_osal_api::thread::ThreadAbstraction;
 use veecle_osal_api::time::{Instant, SystemTime, SystemTimeError, TimeAbstraction};
@@ -40,6 +43,20 @@ where
     }
 }
 
+/// Returns a [`TimeSyncMessage`] pairing [`timestamp_fn_monotonic`] with
+/// [`<T as SystemTime>::duration_since_epoch`][SystemTime::duration_since_epoch] in nanoseconds, `None` if the system
+/// time is not synchronized.
+fn time_sync_fn<T>() -> Option<TimeSyncMessage>
+where
+    T: TimeAbstraction + SystemTime,
+{
+    let since_epoch = T::duration_since_epoch().ok()?;
+    Some(TimeSyncMessage {
+        local_timestamp: timestamp_fn_monotonic::<T>(),
+        since_epoch: since_epoch.as_micros() * 1000,
+    })
+}
+
 /// Type-state markers for builder
 mod state {
     #[derive(Debug)]
@@ -70,7 +87,11 @@ pub struct Builder<PID, EXP, TIME, THREAD> {
     process_id: Option<ProcessId>,
     exporter: Option<&'static (dyn Export + Sync)>,
     timestamp_fn: Option<fn() -> u64>,
+    time_sync_fn: Option<fn() -> Option<TimeSyncMessage>>,
     thread_id_fn: Option<fn() -> core::num::NonZeroU64>,
+    #[cfg(feature = "alloc")]
+    redaction: Option<&'static Redaction>,
+    sampler: Option<&'static (dyn Sampler + Sync)>,
     _pid: core::marker::PhantomData<PID>,
     _exp: core::marker::PhantomData<EXP>,
     _time: core::marker::PhantomData<TIME>,
@@ -97,7 +118,11 @@ pub fn build() -> Builder<state::NoProcessId, state::NoExporter, state::NoTime,
         process_id: None,
         exporter: None,
         timestamp_fn: None,
+        time_sync_fn: None,
         thread_id_fn: None,
+        #[cfg(feature = "alloc")]
+        redaction: None,
+        sampler: None,
         _pid: core::marker::PhantomData,
         _exp: core::marker::PhantomData,
         _time: core::marker::PhantomData,
@@ -115,7 +140,11 @@ impl<PID, EXP, TIME, THREAD> Builder<PID, EXP, TIME, THREAD> {
             process_id: Some(process_id),
             exporter: self.exporter,
             timestamp_fn: self.timestamp_fn,
+            time_sync_fn: self.time_sync_fn,
             thread_id_fn: self.thread_id_fn,
+            #[cfg(feature = "alloc")]
+            redaction: self.redaction,
+            sampler: self.sampler,
             _pid: core::marker::PhantomData,
             _exp: core::marker::PhantomData,
             _time: core::marker::PhantomData,
@@ -132,7 +161,11 @@ impl<PID, EXP, TIME, THREAD> Builder<PID, EXP, TIME, THREAD> {
             process_id: self.process_id,
             exporter: Some(exporter),
             timestamp_fn: self.timestamp_fn,
+            time_sync_fn: self.time_sync_fn,
             thread_id_fn: self.thread_id_fn,
+            #[cfg(feature = "alloc")]
+            redaction: self.redaction,
+            sampler: self.sampler,
             _pid: core::marker::PhantomData,
             _exp: core::marker::PhantomData,
             _time: core::marker::PhantomData,
@@ -140,6 +173,20 @@ impl<PID, EXP, TIME, THREAD> Builder<PID, EXP, TIME, THREAD> {
         }
     }
 
+    /// Sets the exporter for telemetry data, redacting the attributes matching `redaction` before they are exported.
+    ///
+    /// See [`Redaction`] for which attributes are redacted.
+    #[cfg(feature = "alloc")]
+    pub fn exporter_with_redaction(
+        self,
+        exporter: &'static (dyn Export + Sync),
+        redaction: &'static Redaction,
+    ) -> Builder<PID, state::WithExporter, TIME, THREAD> {
+        let mut builder = self.exporter(exporter);
+        builder.redaction = Some(redaction);
+        builder
+    }
+
     /// Configures the time abstraction to use (monotonic time only).
     pub fn time<T>(self) -> Builder<PID, EXP, state::WithTime, THREAD>
     where
@@ -149,7 +196,11 @@ impl<PID, EXP, TIME, THREAD> Builder<PID, EXP, TIME, THREAD> {
             process_id: self.process_id,
             exporter: self.exporter,
             timestamp_fn: Some(timestamp_fn_monotonic::<T>),
+            time_sync_fn: None,
             thread_id_fn: self.thread_id_fn,
+            #[cfg(feature = "alloc")]
+            redaction: self.redaction,
+            sampler: self.sampler,
             _pid: core::marker::PhantomData,
             _exp: core::marker::PhantomData,
             _time: core::marker::PhantomData,
@@ -158,6 +209,9 @@ impl<PID, EXP, TIME, THREAD> Builder<PID, EXP, TIME, THREAD> {
     }
 
     /// Configures the time abstraction with system time to use (Unix epoch synchronization).
+    ///
+    /// The collector additionally exports [`TimeSyncMessage`]s pairing the monotonic and the system time of this
+    /// process, see [`Collector::time_sync`].
     pub fn system_time<T>(self) -> Builder<PID, EXP, state::WithTime, THREAD>
     where
         T: TimeAbstraction + SystemTime,
@@ -166,7 +220,11 @@ impl<PID, EXP, TIME, THREAD> Builder<PID, EXP, TIME, THREAD> {
             process_id: self.process_id,
             exporter: self.exporter,
             timestamp_fn: Some(timestamp_fn_system_time::<T>),
+            time_sync_fn: Some(time_sync_fn::<T>),
             thread_id_fn: self.thread_id_fn,
+            #[cfg(feature = "alloc")]
+            redaction: self.redaction,
+            sampler: self.sampler,
             _pid: core::marker::PhantomData,
             _exp: core::marker::PhantomData,
             _time: core::marker::PhantomData,
@@ -183,13 +241,42 @@ impl<PID, EXP, TIME, THREAD> Builder<PID, EXP, TIME, THREAD> {
             process_id: self.process_id,
             exporter: self.exporter,
             timestamp_fn: self.timestamp_fn,
+            time_sync_fn: self.time_sync_fn,
             thread_id_fn: Some(Th::current_thread_id),
+            #[cfg(feature = "alloc")]
+            redaction: self.redaction,
+            sampler: self.sampler,
             _pid: core::marker::PhantomData,
             _exp: core::marker::PhantomData,
             _time: core::marker::PhantomData,
             _thread: core::marker::PhantomData,
         }
     }
+
+    /// Sets the sampler deciding which spans are exported, by default all spans are.
+    ///
+    /// See [`Sampler`] for what is exported of the spans that are not sampled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use veecle_osal_std::{time::Time, thread::Thread};
+    /// use veecle_telemetry::collector::{self, RatioSampler};
+    ///
+    /// static SAMPLER: RatioSampler = RatioSampler::new(0.1);
+    ///
+    /// collector::build()
+    ///     .random_process_id()
+    ///     .console_json_exporter()
+    ///     .sampler(&SAMPLER)
+    ///     .time::<Time>()
+    ///     .thread::<Thread>()
+    ///     .set_global().unwrap();
+    /// ```
+    pub fn sampler(mut self, sampler: &'static (dyn Sampler + Sync)) -> Self {
+        self.sampler = Some(sampler);
+        self
+    }
 }
 
 impl<EXP, TIME, THREAD> Builder<state::NoProcessId, EXP, TIME, THREAD> {
@@ -271,18 +358,34 @@ impl<PID, TIME, THREAD> Builder<PID, state::NoExporter, TIME, THREAD> {
 impl Builder<state::WithProcessId, state::WithExporter, state::WithTime, state::WithThread> {
     /// Builds this configuration into a [`Collector`] instance.
     pub fn build(self) -> Collector {
-        Collector::new(
+        let mut collector = Collector::new(
             self.process_id.unwrap(),
             self.exporter.unwrap(),
             self.timestamp_fn.unwrap(),
+            self.time_sync_fn,
             self.thread_id_fn.unwrap(),
-        )
+        );
+        #[cfg(feature = "alloc")]
+        if let Some(redaction) = self.redaction {
+            collector.set_redaction(redaction);
+        }
+        if let Some(sampler) = self.sampler {
+            collector.set_sampler(sampler);
+        }
+        collector
     }
 
     /// Sets this collector as the global collector instance.
     ///
     /// This can only be called once per process.
+    /// If the collector is configured with [`system_time`](Builder::system_time), an initial
+    /// [`TimeSyncMessage`] is exported right away.
     pub fn set_global(self) -> Result<(), SetGlobalError> {
-        super::global::set_collector(self.build())
+        super::global::set_collector(self.build())?;
+
+        #[cfg(feature = "enable")]
+        super::get_collector().time_sync();
+
+        Ok(())
     }
 }
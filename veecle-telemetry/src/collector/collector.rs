@@ -1,6 +1,9 @@
 use core::fmt::Debug;
 
-use super::{Export, ProcessId};
+#[cfg(feature = "alloc")]
+use super::Redaction;
+use super::{Export, ProcessId, Sampler};
+use crate::protocol::base::TimeSyncMessage;
 
 #[cfg(feature = "enable")]
 use crate::protocol::transient::{
@@ -29,7 +32,11 @@ struct CollectorInner {
     process_id: ProcessId,
     exporter: &'static (dyn Export + Sync),
     now_fn: fn() -> u64,
+    time_sync_fn: Option<fn() -> Option<TimeSyncMessage>>,
     thread_id_fn: fn() -> core::num::NonZeroU64,
+    #[cfg(feature = "alloc")]
+    redaction: Option<&'static Redaction>,
+    sampler: Option<&'static (dyn Sampler + Sync)>,
 }
 
 impl Collector {
@@ -37,10 +44,11 @@ impl Collector {
         process_id: ProcessId,
         exporter: &'static (dyn Export + Sync),
         now_fn: fn() -> u64,
+        time_sync_fn: Option<fn() -> Option<TimeSyncMessage>>,
         thread_id_fn: fn() -> core::num::NonZeroU64,
     ) -> Self {
         #[cfg(not(feature = "enable"))]
-        let _ = (process_id, exporter, now_fn, thread_id_fn);
+        let _ = (process_id, exporter, now_fn, time_sync_fn, thread_id_fn);
 
         Self {
             #[cfg(feature = "enable")]
@@ -48,11 +56,38 @@ impl Collector {
                 process_id,
                 exporter,
                 now_fn,
+                time_sync_fn,
                 thread_id_fn,
+                #[cfg(feature = "alloc")]
+                redaction: None,
+                sampler: None,
             },
         }
     }
 
+    /// Redacts the attributes matching `redaction` from all exported messages.
+    #[cfg(feature = "alloc")]
+    pub(super) fn set_redaction(&mut self, redaction: &'static Redaction) {
+        #[cfg(not(feature = "enable"))]
+        let _ = redaction;
+
+        #[cfg(feature = "enable")]
+        {
+            self.inner.redaction = Some(redaction);
+        }
+    }
+
+    /// Only exports the spans sampled by `sampler`.
+    pub(super) fn set_sampler(&mut self, sampler: &'static (dyn Sampler + Sync)) {
+        #[cfg(not(feature = "enable"))]
+        let _ = sampler;
+
+        #[cfg(feature = "enable")]
+        {
+            self.inner.sampler = Some(sampler);
+        }
+    }
+
     #[inline]
     #[cfg(feature = "enable")]
     pub(crate) fn process_id(&self) -> ProcessId {
@@ -75,7 +110,7 @@ impl Collector {
     ///
     /// This method allows external systems to inject telemetry messages into the
     /// collector pipeline.
-    /// The message will be exported using the configured exporter.
+    /// The message will be exported using the configured exporter, after applying the configured redaction.
     ///
     /// # Examples
     ///
@@ -103,9 +138,42 @@ impl Collector {
     #[inline]
     #[cfg(feature = "enable")]
     pub fn collect_external(&self, message: InstanceMessage<'_>) {
-        self.inner.exporter.export(message);
+        self.export(message);
     }
 
+    /// Exports a [`TimeSyncMessage`] pairing the current monotonic and system time of this process.
+    ///
+    /// Consumers can use these pairs to relate the timestamps of different processes, e.g. to correct the skew between
+    /// the clocks of multiple devices.
+    /// Only collectors configured with [`system_time`](super::Builder::system_time) export time sync messages, this
+    /// does nothing for other collectors or while the system time is not synchronized.
+    ///
+    /// An initial message is exported by [`set_global`](super::Builder::set_global), call this again after
+    /// (re-)synchronizing the system time and periodically to track the drift between the clocks.
+    #[inline]
+    #[cfg(feature = "enable")]
+    pub fn time_sync(&self) {
+        let Some(time_sync_fn) = self.inner.time_sync_fn else {
+            return;
+        };
+        if let Some(time_sync) = time_sync_fn() {
+            self.export(InstanceMessage {
+                thread_id: self.thread_id(),
+                message: TelemetryMessage::TimeSync(time_sync),
+            });
+        }
+    }
+
+    /// Returns whether a new span with `span_id` and `name` is exported, see [`Sampler`].
+    #[inline]
+    #[cfg(feature = "enable")]
+    pub(crate) fn should_sample(&self, span_id: SpanId, name: &str) -> bool {
+        self.inner
+            .sampler
+            .is_none_or(|sampler| sampler.should_sample(span_id, name))
+    }
+
+    /// Returns the start time of the new span.
     #[inline]
     #[cfg(feature = "enable")]
     pub(crate) fn new_span<'a>(
@@ -113,13 +181,17 @@ impl Collector {
         span_id: SpanId,
         name: &'a str,
         attributes: &'a [KeyValue<'a>],
-    ) {
+        parent: Option<SpanContext>,
+    ) -> u64 {
+        let start_time_unix_nano = self.now();
         self.tracing_message(TracingMessage::CreateSpan(SpanCreateMessage {
             span_id,
             name,
-            start_time_unix_nano: self.now(),
+            start_time_unix_nano,
             attributes,
+            parent,
         }));
+        start_time_unix_nano
     }
 
     #[inline]
@@ -142,10 +214,12 @@ impl Collector {
 
     #[inline]
     #[cfg(feature = "enable")]
-    pub(crate) fn close_span(&self, span_id: SpanId) {
+    pub(crate) fn close_span(&self, span_id: SpanId, start_time_unix_nano: u64) {
+        let end_time_unix_nano = self.now();
         self.tracing_message(TracingMessage::CloseSpan(SpanCloseMessage {
             span_id,
-            end_time_unix_nano: self.now(),
+            end_time_unix_nano,
+            duration_nano: end_time_unix_nano.saturating_sub(start_time_unix_nano),
         }));
     }
 
@@ -154,12 +228,14 @@ impl Collector {
     pub(crate) fn span_event<'a>(
         &self,
         span_id: Option<SpanId>,
+        severity: Severity,
         name: &'a str,
         attributes: &'a [KeyValue<'a>],
     ) {
         self.tracing_message(TracingMessage::AddEvent(SpanAddEventMessage {
             span_id,
             name,
+            severity,
             time_unix_nano: self.now(),
             attributes,
         }));
@@ -191,7 +267,7 @@ impl Collector {
         body: &'a str,
         attributes: &'a [KeyValue<'a>],
     ) {
-        self.inner.exporter.export(InstanceMessage {
+        self.export(InstanceMessage {
             thread_id: self.thread_id(),
             message: TelemetryMessage::Log(LogMessage {
                 time_unix_nano: self.now(),
@@ -205,9 +281,21 @@ impl Collector {
     #[inline]
     #[cfg(feature = "enable")]
     fn tracing_message(&self, message: TracingMessage<'_>) {
-        self.inner.exporter.export(InstanceMessage {
+        self.export(InstanceMessage {
             thread_id: self.thread_id(),
             message: TelemetryMessage::Tracing(message),
         });
     }
+
+    #[inline]
+    #[cfg(feature = "enable")]
+    fn export(&self, message: InstanceMessage<'_>) {
+        #[cfg(feature = "alloc")]
+        if let Some(redaction) = self.inner.redaction {
+            redaction.export(self.inner.exporter, message);
+            return;
+        }
+
+        self.inner.exporter.export(message);
+    }
 }
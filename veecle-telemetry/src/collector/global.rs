@@ -20,6 +20,7 @@ static NO_COLLECTOR: Collector = Collector::new(
     ProcessId::from_raw(0),
     &NO_EXPORTER,
     nop_timestamp,
+    None,
     nop_thread_id,
 );
 
@@ -29,6 +30,7 @@ static mut GLOBAL_COLLECTOR: Collector = Collector::new(
     ProcessId::from_raw(0),
     &NO_EXPORTER,
     nop_timestamp,
+    None,
     nop_thread_id,
 );
 
@@ -99,6 +101,12 @@ pub fn get_collector() -> &'static Collector {
     }
 }
 
+/// Returns whether a global collector has been set, without one all telemetry is discarded.
+#[cfg(feature = "enable")]
+pub(crate) fn is_collector_set() -> bool {
+    GLOBAL_INIT.load(Ordering::Acquire) == INITIALIZED
+}
+
 /// The type returned by [`set_global`][super::Builder::set_global] if the collector has already been initialized.
 #[derive(Debug)]
 pub struct SetGlobalError(());
@@ -20,7 +20,14 @@
 //! # Built-in Exporters
 //!
 //! - [`ConsoleJsonExporter`] - Exports telemetry data as JSON to stdout
+//! - [`MultiExporter`] - Forwards telemetry data to several exporters
 //! - [`TestExporter`] - Collects telemetry data in memory for testing purposes
+//! - [`StaticSink`] - Keeps recent serialized telemetry data in a fixed-capacity ring buffer, without `alloc`
+//!
+//! # Sampling
+//!
+//! A [`Sampler`] set with [`Builder::sampler`] decides which spans are exported, e.g. a [`RatioSampler`] exporting a
+//! fixed ratio of them, or a [`SpanNameAllowlist`] that always exports some spans by name.
 
 mod collector;
 mod global;
@@ -28,8 +35,14 @@ mod global;
 mod builder;
 #[cfg(feature = "std")]
 mod json_exporter;
+mod multi_exporter;
 #[cfg(feature = "std")]
 mod pretty_exporter;
+#[cfg(feature = "alloc")]
+mod redaction;
+mod sampler;
+#[cfg(feature = "static-sink")]
+mod static_sink;
 #[cfg(feature = "std")]
 mod test_exporter;
 
@@ -38,14 +51,21 @@ use core::fmt::Debug;
 pub use builder::{Builder, build};
 #[cfg(feature = "std")]
 pub use json_exporter::ConsoleJsonExporter;
+pub use multi_exporter::MultiExporter;
 #[cfg(feature = "std")]
 pub use pretty_exporter::ConsolePrettyExporter;
+#[cfg(feature = "alloc")]
+pub use redaction::Redaction;
+pub use sampler::{RatioSampler, Sampler, SpanNameAllowlist};
+#[cfg(feature = "static-sink")]
+pub use static_sink::{Encode, StaticSink};
 #[cfg(feature = "std")]
-#[doc(hidden)]
 pub use test_exporter::TestExporter;
 
 pub use self::collector::Collector;
 pub use self::global::get_collector;
+#[cfg(feature = "enable")]
+pub(crate) use self::global::is_collector_set;
 
 pub use crate::protocol::base::ProcessId;
 use crate::protocol::transient::InstanceMessage;
@@ -78,3 +98,30 @@ pub trait Export: Debug {
     /// The implementation should handle the message appropriately based on its type.
     fn export(&self, message: InstanceMessage<'_>);
 }
+
+/// Returns whether `key` matches `pattern`, where `*` matches any sequence of characters.
+///
+/// Used for the patterns of `Redaction` and [`SpanNameAllowlist`].
+fn pattern_matches(pattern: &str, key: &str) -> bool {
+    let Some((prefix, rest)) = pattern.split_once('*') else {
+        return pattern == key;
+    };
+    let (middle, suffix) = rest.rsplit_once('*').unwrap_or(("", rest));
+
+    let Some(key) = key.strip_prefix(prefix) else {
+        return false;
+    };
+    let Some(mut key) = key.strip_suffix(suffix) else {
+        return false;
+    };
+
+    // Match the parts between the first and last `*` as early as possible to leave the most room for the next ones.
+    for part in middle.split('*') {
+        let Some(index) = key.find(part) else {
+            return false;
+        };
+        key = &key[index + part.len()..];
+    }
+
+    true
+}
@@ -0,0 +1,164 @@
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use super::Export;
+use crate::protocol::transient::InstanceMessage;
+
+/// An exporter forwarding every telemetry message to several exporters.
+///
+/// Each message is passed to the exporters one after another, in the order they were passed to [`MultiExporter::new`].
+/// Exporters are expected to handle their own errors (e.g. by dropping the message) rather than panic.
+/// With the `std` feature, an exporter that panics anyway is isolated: the panic is caught, the exporter is disabled
+/// for all following messages and the remaining exporters still receive the message.
+/// Without the `std` feature panics cannot be caught and propagate to the caller as with a single exporter.
+///
+/// # Examples
+///
+/// ```rust
+/// use veecle_osal_std::{time::Time, thread::Thread};
+/// use veecle_telemetry::collector::{ConsoleJsonExporter, ConsolePrettyExporter, MultiExporter};
+///
+/// static EXPORTER: MultiExporter<2> =
+///     MultiExporter::new([&ConsoleJsonExporter::DEFAULT, &ConsolePrettyExporter::DEFAULT]);
+///
+/// veecle_telemetry::collector::build()
+///     .random_process_id()
+///     .exporter(&EXPORTER)
+///     .time::<Time>()
+///     .thread::<Thread>()
+///     .set_global()
+///     .unwrap();
+/// ```
+#[derive(Debug)]
+pub struct MultiExporter<const N: usize> {
+    exporters: [&'static (dyn Export + Sync); N],
+    failed: [AtomicBool; N],
+}
+
+impl<const N: usize> MultiExporter<N> {
+    /// Creates an exporter forwarding to `exporters` in the given order.
+    pub const fn new(exporters: [&'static (dyn Export + Sync); N]) -> Self {
+        Self {
+            exporters,
+            failed: [const { AtomicBool::new(false) }; N],
+        }
+    }
+
+    /// Returns whether the exporter at `index` panicked and no longer receives messages.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn is_failed(&self, index: usize) -> bool {
+        self.failed[index].load(Ordering::Relaxed)
+    }
+}
+
+impl<const N: usize> Export for MultiExporter<N> {
+    fn export(&self, message: InstanceMessage<'_>) {
+        for (exporter, failed) in self.exporters.iter().zip(&self.failed) {
+            if failed.load(Ordering::Relaxed) {
+                continue;
+            }
+
+            #[cfg(feature = "std")]
+            {
+                let message = message.clone();
+                let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+                    exporter.export(message)
+                }));
+                if result.is_err() {
+                    failed.store(true, Ordering::Relaxed);
+                }
+            }
+
+            #[cfg(not(feature = "std"))]
+            exporter.export(message.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::borrow::ToOwned;
+    use std::string::String;
+    use std::sync::Mutex;
+    use std::vec::Vec;
+
+    use super::MultiExporter;
+    use crate::collector::Export;
+    use crate::protocol::base::{ProcessId, ThreadId};
+    use crate::protocol::transient::{InstanceMessage, LogMessage, Severity, TelemetryMessage};
+
+    /// Records the bodies of exported log messages.
+    #[derive(Debug)]
+    struct Recorder(Mutex<Vec<String>>);
+
+    impl Recorder {
+        const fn new() -> Self {
+            Self(Mutex::new(Vec::new()))
+        }
+
+        fn bodies(&self) -> Vec<String> {
+            self.0.lock().unwrap().clone()
+        }
+    }
+
+    impl Export for Recorder {
+        fn export(&self, message: InstanceMessage<'_>) {
+            let TelemetryMessage::Log(log) = message.message else {
+                return;
+            };
+            self.0.lock().unwrap().push(log.body.to_owned());
+        }
+    }
+
+    /// Panics on every exported message.
+    #[derive(Debug)]
+    struct Panicking;
+
+    impl Export for Panicking {
+        fn export(&self, _message: InstanceMessage<'_>) {
+            panic!("exporter failure");
+        }
+    }
+
+    fn log(body: &str) -> InstanceMessage<'_> {
+        InstanceMessage {
+            thread_id: ThreadId::from_raw(ProcessId::from_raw(1), core::num::NonZeroU64::MIN),
+            message: TelemetryMessage::Log(LogMessage {
+                time_unix_nano: 0,
+                severity: Severity::Info,
+                body,
+                attributes: &[],
+            }),
+        }
+    }
+
+    #[test]
+    fn forwards_to_all() {
+        static FIRST: Recorder = Recorder::new();
+        static SECOND: Recorder = Recorder::new();
+        let exporter = MultiExporter::new([&FIRST, &SECOND]);
+
+        exporter.export(log("first"));
+        exporter.export(log("second"));
+
+        assert_eq!(FIRST.bodies(), ["first", "second"]);
+        assert_eq!(SECOND.bodies(), ["first", "second"]);
+    }
+
+    #[test]
+    fn isolates_panicking_exporter() {
+        static PANICKING: Panicking = Panicking;
+        static RECORDER: Recorder = Recorder::new();
+        let exporter = MultiExporter::new([&PANICKING, &RECORDER]);
+
+        exporter.export(log("first"));
+        assert!(exporter.is_failed(0));
+        assert!(!exporter.is_failed(1));
+
+        exporter.export(log("second"));
+        assert_eq!(RECORDER.bodies(), ["first", "second"]);
+    }
+}
@@ -0,0 +1,342 @@
+use alloc::vec::Vec;
+
+use super::{Export, pattern_matches};
+use crate::protocol::transient::{
+    InstanceMessage, KeyValue, LogMessage, SpanAddEventMessage, SpanCreateMessage,
+    SpanSetAttributeMessage, TelemetryMessage, TracingMessage, Value,
+};
+
+/// A set of attribute key patterns whose values are replaced with [`Redaction::PLACEHOLDER`] before export.
+///
+/// Redaction is applied by the collector to the attributes of spans, span events, span attribute updates and log
+/// messages, including messages passed to [`Collector::collect_external`][super::Collector::collect_external], so
+/// sensitive values are removed regardless of where they were recorded.
+/// Only top-level attributes are matched, keys nested in [`Value::Map`]s are not.
+///
+/// Patterns match keys exactly and case-sensitively, except for `*` which matches any sequence of characters, e.g.
+/// `"vin"` only matches `vin` while `"user_*"` matches `user_id` and `user_name`.
+///
+/// # Examples
+///
+/// ```rust
+/// use veecle_osal_std::{time::Time, thread::Thread};
+/// use veecle_telemetry::collector::{ConsoleJsonExporter, Redaction};
+///
+/// static REDACTION: Redaction = Redaction::new(&["vin", "user_*"]);
+///
+/// veecle_telemetry::collector::build()
+///     .random_process_id()
+///     .exporter_with_redaction(&ConsoleJsonExporter::DEFAULT, &REDACTION)
+///     .time::<Time>()
+///     .thread::<Thread>()
+///     .set_global()
+///     .unwrap();
+/// ```
+#[derive(Debug)]
+pub struct Redaction {
+    patterns: &'static [&'static str],
+}
+
+impl Redaction {
+    /// The value redacted attributes are replaced with.
+    pub const PLACEHOLDER: &'static str = "[redacted]";
+
+    /// Creates a redaction of the attributes with keys matching any of `patterns`.
+    pub const fn new(patterns: &'static [&'static str]) -> Self {
+        Self { patterns }
+    }
+
+    /// Returns whether the value of an attribute with the given key is redacted.
+    pub fn matches(&self, key: &str) -> bool {
+        self.patterns
+            .iter()
+            .any(|pattern| pattern_matches(pattern, key))
+    }
+
+    /// Exports `message` to `exporter` with all matching attributes redacted.
+    pub(super) fn export(&self, exporter: &dyn Export, message: InstanceMessage<'_>) {
+        let thread_id = message.thread_id;
+        match &message.message {
+            TelemetryMessage::Log(log) => {
+                if let Some(attributes) = self.redact_all(log.attributes) {
+                    return exporter.export(InstanceMessage {
+                        thread_id,
+                        message: TelemetryMessage::Log(LogMessage {
+                            attributes: &attributes,
+                            ..log.clone()
+                        }),
+                    });
+                }
+            }
+            TelemetryMessage::Tracing(TracingMessage::CreateSpan(span)) => {
+                if let Some(attributes) = self.redact_all(span.attributes) {
+                    return exporter.export(InstanceMessage {
+                        thread_id,
+                        message: TelemetryMessage::Tracing(TracingMessage::CreateSpan(
+                            SpanCreateMessage {
+                                attributes: &attributes,
+                                ..span.clone()
+                            },
+                        )),
+                    });
+                }
+            }
+            TelemetryMessage::Tracing(TracingMessage::AddEvent(event)) => {
+                if let Some(attributes) = self.redact_all(event.attributes) {
+                    return exporter.export(InstanceMessage {
+                        thread_id,
+                        message: TelemetryMessage::Tracing(TracingMessage::AddEvent(
+                            SpanAddEventMessage {
+                                attributes: &attributes,
+                                ..event.clone()
+                            },
+                        )),
+                    });
+                }
+            }
+            TelemetryMessage::Tracing(TracingMessage::SetAttribute(set))
+                if self.matches(set.attribute.key) =>
+            {
+                return exporter.export(InstanceMessage {
+                    thread_id,
+                    message: TelemetryMessage::Tracing(TracingMessage::SetAttribute(
+                        SpanSetAttributeMessage {
+                            span_id: set.span_id,
+                            attribute: redacted(set.attribute.key),
+                        },
+                    )),
+                });
+            }
+            _ => {}
+        }
+
+        exporter.export(message);
+    }
+
+    /// Returns a copy of `attributes` with the matching values redacted, `None` if no attribute matches.
+    fn redact_all<'a>(&self, attributes: &[KeyValue<'a>]) -> Option<Vec<KeyValue<'a>>> {
+        if !attributes
+            .iter()
+            .any(|attribute| self.matches(attribute.key))
+        {
+            return None;
+        }
+
+        Some(
+            attributes
+                .iter()
+                .map(|attribute| {
+                    if self.matches(attribute.key) {
+                        redacted(attribute.key)
+                    } else {
+                        attribute.clone()
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Returns an attribute with the given key and the [`Redaction::PLACEHOLDER`] value.
+fn redacted(key: &str) -> KeyValue<'_> {
+    KeyValue {
+        key,
+        value: Value::String(Redaction::PLACEHOLDER),
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::Redaction;
+    use crate::collector::TestExporter;
+    use crate::collector::pattern_matches;
+    use crate::protocol::owned;
+    use crate::protocol::transient::{
+        InstanceMessage, KeyValue, LogMessage, ProcessId, Severity, SpanAddEventMessage, SpanId,
+        SpanSetAttributeMessage, TelemetryMessage, ThreadId, TracingMessage, Value,
+    };
+
+    const THREAD_ID: ThreadId =
+        ThreadId::from_raw(ProcessId::from_raw(1), core::num::NonZeroU64::MIN);
+
+    #[test]
+    fn patterns() {
+        assert!(pattern_matches("vin", "vin"));
+        assert!(!pattern_matches("vin", "vin_hash"));
+        assert!(!pattern_matches("vin", "VIN"));
+        assert!(pattern_matches("user_*", "user_id"));
+        assert!(pattern_matches("user_*", "user_"));
+        assert!(!pattern_matches("user_*", "username"));
+        assert!(pattern_matches("*_token", "auth_token"));
+        assert!(pattern_matches("*", ""));
+        assert!(pattern_matches("a*b*c", "abc"));
+        assert!(pattern_matches("a*b*c", "axxbxxbxc"));
+        assert!(!pattern_matches("a*b*c", "acb"));
+        assert!(!pattern_matches("ab*ba", "aba"));
+    }
+
+    #[test]
+    fn redacts_matching_attributes() {
+        let redaction = Redaction::new(&["vin", "user_*"]);
+        let (exporter, _) = TestExporter::new();
+
+        let attributes = [
+            KeyValue::new("vin", "WVWZZZ1JZXW000001"),
+            KeyValue::new("speed", 42),
+            KeyValue::new("user_id", 7),
+        ];
+        redaction.export(
+            &exporter,
+            InstanceMessage {
+                thread_id: THREAD_ID,
+                message: TelemetryMessage::Tracing(TracingMessage::AddEvent(SpanAddEventMessage {
+                    span_id: None,
+                    name: "trip",
+                    severity: Severity::Info,
+                    time_unix_nano: 0,
+                    attributes: &attributes,
+                })),
+            },
+        );
+        redaction.export(
+            &exporter,
+            InstanceMessage {
+                thread_id: THREAD_ID,
+                message: TelemetryMessage::Log(LogMessage {
+                    time_unix_nano: 0,
+                    severity: Severity::Info,
+                    body: "login",
+                    attributes: &attributes,
+                }),
+            },
+        );
+        redaction.export(
+            &exporter,
+            InstanceMessage {
+                thread_id: THREAD_ID,
+                message: TelemetryMessage::Tracing(TracingMessage::SetAttribute(
+                    SpanSetAttributeMessage {
+                        span_id: Some(SpanId(1)),
+                        attribute: KeyValue::new("user_name", "alice"),
+                    },
+                )),
+            },
+        );
+
+        let redacted = owned::Value::String(Redaction::PLACEHOLDER.into());
+        let expected = [
+            owned::KeyValue::new("vin", redacted.clone()),
+            owned::KeyValue::new("speed", 42),
+            owned::KeyValue::new("user_id", redacted.clone()),
+        ];
+        let messages = exporter.messages();
+        let [event, log, set] = messages.as_slice() else {
+            panic!("expected three messages, got {messages:?}");
+        };
+
+        let owned::TelemetryMessage::Tracing(owned::TracingMessage::AddEvent(event)) =
+            &event.message
+        else {
+            panic!("expected an event, got {event:?}");
+        };
+        assert_eq!(event.attributes, expected);
+
+        let owned::TelemetryMessage::Log(log) = &log.message else {
+            panic!("expected a log message, got {log:?}");
+        };
+        assert_eq!(log.body, "login");
+        assert_eq!(log.attributes, expected);
+
+        let owned::TelemetryMessage::Tracing(owned::TracingMessage::SetAttribute(set)) =
+            &set.message
+        else {
+            panic!("expected an attribute update, got {set:?}");
+        };
+        assert_eq!(set.attribute, owned::KeyValue::new("user_name", redacted));
+    }
+
+    #[test]
+    fn passes_through_without_matches() {
+        let redaction = Redaction::new(&["vin"]);
+        let (exporter, _) = TestExporter::new();
+
+        let attributes = [KeyValue::new("speed", Value::I64(42))];
+        redaction.export(
+            &exporter,
+            InstanceMessage {
+                thread_id: THREAD_ID,
+                message: TelemetryMessage::Log(LogMessage {
+                    time_unix_nano: 0,
+                    severity: Severity::Info,
+                    body: "status",
+                    attributes: &attributes,
+                }),
+            },
+        );
+
+        let messages = exporter.messages();
+        let [message] = messages.as_slice() else {
+            panic!("expected one message, got {messages:?}");
+        };
+        let owned::TelemetryMessage::Log(log) = &message.message else {
+            panic!("expected a log message, got {message:?}");
+        };
+        assert_eq!(log.attributes, [owned::KeyValue::new("speed", 42)]);
+    }
+
+    #[test]
+    fn collector_redacts_exported_messages() {
+        use veecle_osal_std::{thread::Thread, time::Time};
+
+        static REDACTION: Redaction = Redaction::new(&["vin"]);
+
+        let (exporter, _) = TestExporter::new();
+        let collector = crate::collector::build()
+            .process_id(ProcessId::from_raw(1))
+            .exporter_with_redaction(
+                alloc::boxed::Box::leak(alloc::boxed::Box::new(exporter.clone())),
+                &REDACTION,
+            )
+            .time::<Time>()
+            .thread::<Thread>()
+            .build();
+
+        let attributes = [
+            KeyValue::new("vin", "WVWZZZ1JZXW000001"),
+            KeyValue::new("speed", 42),
+        ];
+        collector.log_message(Severity::Info, "trip", &attributes);
+        collector.new_span(SpanId(1), "drive", &attributes, None);
+        collector.collect_external(InstanceMessage {
+            thread_id: THREAD_ID,
+            message: TelemetryMessage::Log(LogMessage {
+                time_unix_nano: 0,
+                severity: Severity::Info,
+                body: "external",
+                attributes: &attributes,
+            }),
+        });
+
+        let expected = [
+            owned::KeyValue::new("vin", owned::Value::String(Redaction::PLACEHOLDER.into())),
+            owned::KeyValue::new("speed", 42),
+        ];
+        let messages = exporter.messages();
+        let [log, span, external] = messages.as_slice() else {
+            panic!("expected three messages, got {messages:?}");
+        };
+        for log in [log, external] {
+            let owned::TelemetryMessage::Log(log) = &log.message else {
+                panic!("expected a log message, got {log:?}");
+            };
+            assert_eq!(log.attributes, expected);
+        }
+        let owned::TelemetryMessage::Tracing(owned::TracingMessage::CreateSpan(span)) =
+            &span.message
+        else {
+            panic!("expected a span, got {span:?}");
+        };
+        assert_eq!(span.attributes, expected);
+    }
+}
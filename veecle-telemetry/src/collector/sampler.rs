@@ -0,0 +1,173 @@
+use core::fmt::Debug;
+
+use super::pattern_matches;
+use crate::SpanId;
+
+/// Decides which spans are exported, set with [`Builder::sampler`][super::Builder::sampler].
+///
+/// The decision is made once when a span is created. A span that is not sampled is created as [`Span::noop`], so
+/// neither the span nor the events, links and attribute updates added to it are exported. Spans created while it would
+/// have been the current span are sampled on their own and, if sampled, belong to the closest sampled ancestor
+/// instead, as do events, links and attribute updates added through [`CurrentSpan`].
+/// Log messages are not affected by sampling.
+///
+/// [`Span::noop`]: crate::Span::noop
+/// [`CurrentSpan`]: crate::CurrentSpan
+///
+/// # Examples
+///
+/// ```rust
+/// use veecle_telemetry::SpanId;
+/// use veecle_telemetry::collector::Sampler;
+///
+/// /// Only samples spans created by the `control` module.
+/// #[derive(Debug)]
+/// struct ControlSampler;
+///
+/// impl Sampler for ControlSampler {
+///     fn should_sample(&self, _span_id: SpanId, name: &str) -> bool {
+///         name.starts_with("control::")
+///     }
+/// }
+/// ```
+pub trait Sampler: Debug {
+    /// Returns whether the new span with `span_id` and `name` is exported.
+    fn should_sample(&self, span_id: SpanId, name: &str) -> bool;
+}
+
+/// Samples a fixed ratio of the spans, independent of their names.
+///
+/// The decision is derived from the [`SpanId`], so it is deterministic for a given id and does not require a random
+/// number generator.
+///
+/// # Examples
+///
+/// ```rust
+/// use veecle_telemetry::collector::RatioSampler;
+///
+/// // Export about one in ten spans.
+/// static SAMPLER: RatioSampler = RatioSampler::new(0.1);
+/// ```
+#[derive(Debug)]
+pub struct RatioSampler {
+    ratio: f64,
+}
+
+impl RatioSampler {
+    /// Creates a sampler exporting `ratio` of the spans, `0.0` exports none and `1.0` (or more) all of them.
+    pub const fn new(ratio: f64) -> Self {
+        Self { ratio }
+    }
+}
+
+impl Sampler for RatioSampler {
+    fn should_sample(&self, span_id: SpanId, _name: &str) -> bool {
+        if self.ratio >= 1.0 {
+            return true;
+        }
+
+        // Span ids are usually sequential, mix the bits so every ratio samples evenly spread spans.
+        let mut hash = span_id.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        hash = (hash ^ (hash >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        hash = (hash ^ (hash >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        hash ^= hash >> 31;
+
+        (hash as f64) < self.ratio * (u64::MAX as f64)
+    }
+}
+
+/// Always samples spans whose names match an allowlist and leaves the decision for all other spans to a fallback
+/// sampler.
+///
+/// This keeps important spans, e.g. of error handling paths, while high-volume routine spans are sampled.
+/// Patterns match names exactly and case-sensitively, except for `*` which matches any sequence of characters, e.g.
+/// `"recover"` only matches `recover` while `"error::*"` matches `error::retry` and `error::report`.
+///
+/// # Examples
+///
+/// ```rust
+/// use veecle_osal_std::{time::Time, thread::Thread};
+/// use veecle_telemetry::collector::{RatioSampler, SpanNameAllowlist};
+///
+/// static FALLBACK: RatioSampler = RatioSampler::new(0.01);
+/// static SAMPLER: SpanNameAllowlist = SpanNameAllowlist::new(&["error::*", "recover"], &FALLBACK);
+///
+/// veecle_telemetry::collector::build()
+///     .random_process_id()
+///     .console_json_exporter()
+///     .sampler(&SAMPLER)
+///     .time::<Time>()
+///     .thread::<Thread>()
+///     .set_global()
+///     .unwrap();
+/// ```
+#[derive(Debug)]
+pub struct SpanNameAllowlist {
+    patterns: &'static [&'static str],
+    fallback: &'static (dyn Sampler + Sync),
+}
+
+impl SpanNameAllowlist {
+    /// Creates a sampler that always samples spans with names matching any of `patterns` and asks `fallback` about
+    /// the others.
+    pub const fn new(
+        patterns: &'static [&'static str],
+        fallback: &'static (dyn Sampler + Sync),
+    ) -> Self {
+        Self { patterns, fallback }
+    }
+
+    /// Returns whether `name` matches any of the patterns.
+    pub fn matches(&self, name: &str) -> bool {
+        self.patterns
+            .iter()
+            .any(|pattern| pattern_matches(pattern, name))
+    }
+}
+
+impl Sampler for SpanNameAllowlist {
+    fn should_sample(&self, span_id: SpanId, name: &str) -> bool {
+        self.matches(name) || self.fallback.should_sample(span_id, name)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::{RatioSampler, Sampler, SpanNameAllowlist};
+    use crate::SpanId;
+
+    #[test]
+    fn ratio() {
+        let sampled = |sampler: &RatioSampler| {
+            (1..=10_000)
+                .filter(|&id| sampler.should_sample(SpanId(id), "span"))
+                .count()
+        };
+
+        assert_eq!(sampled(&RatioSampler::new(0.0)), 0);
+        assert_eq!(sampled(&RatioSampler::new(1.0)), 10_000);
+        assert!((900..1100).contains(&sampled(&RatioSampler::new(0.1))));
+
+        let sampler = RatioSampler::new(0.5);
+        assert_eq!(
+            sampler.should_sample(SpanId(42), "a"),
+            sampler.should_sample(SpanId(42), "b")
+        );
+    }
+
+    #[test]
+    fn allowlist() {
+        static NONE: RatioSampler = RatioSampler::new(0.0);
+        static ALL: RatioSampler = RatioSampler::new(1.0);
+
+        let sampler = SpanNameAllowlist::new(&["error::*", "recover"], &NONE);
+        assert!(sampler.should_sample(SpanId(1), "error::retry"));
+        assert!(sampler.should_sample(SpanId(2), "recover"));
+        assert!(!sampler.should_sample(SpanId(3), "recover_later"));
+        assert!(!sampler.should_sample(SpanId(4), "poll"));
+
+        let sampler = SpanNameAllowlist::new(&[], &ALL);
+        assert!(sampler.should_sample(SpanId(5), "poll"));
+    }
+}
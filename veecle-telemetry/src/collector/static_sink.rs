@@ -0,0 +1,323 @@
+use core::cell::RefCell;
+use core::fmt;
+
+use critical_section::Mutex;
+
+use super::Export;
+use crate::protocol::transient::InstanceMessage;
+
+/// Size of the length prefix stored before each record.
+const PREFIX_LEN: usize = size_of::<u16>();
+
+/// Serializes a telemetry message into `buffer`, returning the number of bytes written.
+///
+/// Returns `None` if the message could not be serialized, e.g. because it doesn't fit into `buffer`.
+pub type Encode = fn(message: &InstanceMessage<'_>, buffer: &mut [u8]) -> Option<usize>;
+
+/// An [`Export`] implementer that keeps the most recent serialized telemetry messages in a fixed-capacity ring buffer.
+///
+/// This doesn't require an allocator, so it can be used to collect a recent window of telemetry on the smallest
+/// targets, e.g. for post-mortem analysis. Messages are serialized with a user provided [`Encode`] function into a
+/// stack buffer of `M` bytes and then stored in a ring buffer of `N` bytes, which can be placed in a `static`.
+/// A transport task retrieves the stored messages with [`StaticSink::pop`] or [`StaticSink::drain`].
+///
+/// When the ring buffer is full, the oldest messages are dropped to make room for new ones.
+/// Messages that fail to serialize are dropped as well.
+/// Both are counted in [`StaticSink::dropped`].
+///
+/// Each stored message uses two bytes in addition to its serialized length.
+///
+/// # Examples
+///
+/// ```rust
+/// use veecle_osal_std::{thread::Thread, time::Time};
+/// use veecle_telemetry::collector::StaticSink;
+/// use veecle_telemetry::protocol::transient::InstanceMessage;
+///
+/// // On targets without an allocator, use a serialization format that can write to a slice, e.g. `postcard`.
+/// fn encode(message: &InstanceMessage<'_>, buffer: &mut [u8]) -> Option<usize> {
+///     let encoded = serde_json::to_vec(message).ok()?;
+///     buffer.get_mut(..encoded.len())?.copy_from_slice(&encoded);
+///     Some(encoded.len())
+/// }
+///
+/// static SINK: StaticSink<4096> = StaticSink::new(encode);
+///
+/// veecle_telemetry::collector::build()
+///     .random_process_id()
+///     .exporter(&SINK)
+///     .time::<Time>()
+///     .thread::<Thread>()
+///     .set_global()
+///     .unwrap();
+///
+/// veecle_telemetry::info!("Server started");
+///
+/// // In the transport task:
+/// SINK.drain(|message| {
+///     // Send `message` off-device, e.g. over a UART.
+///     # let _ = message;
+/// });
+/// ```
+pub struct StaticSink<const N: usize, const M: usize = 256> {
+    encode: Encode,
+    ring: Mutex<RefCell<Ring<N>>>,
+}
+
+impl<const N: usize, const M: usize> StaticSink<N, M> {
+    /// Creates a new, empty sink serializing messages with `encode`.
+    pub const fn new(encode: Encode) -> Self {
+        const {
+            assert!(
+                M <= u16::MAX as usize,
+                "the maximum message size must fit the `u16` length prefix"
+            );
+        }
+
+        Self {
+            encode,
+            ring: Mutex::new(RefCell::new(Ring::new())),
+        }
+    }
+
+    /// Removes the oldest stored message, copying it into `buffer`.
+    ///
+    /// Returns the copied message, or `None` if the sink is empty.
+    pub fn pop<'b>(&self, buffer: &'b mut [u8; M]) -> Option<&'b [u8]> {
+        let message_len = critical_section::with(|cs| self.ring.borrow_ref_mut(cs).pop(buffer))?;
+        Some(&buffer[..message_len])
+    }
+
+    /// Removes all stored messages, passing each to `f` from oldest to newest.
+    ///
+    /// Messages are removed one at a time, so `f` runs outside the critical section and messages exported while
+    /// draining are passed to `f` as well.
+    pub fn drain(&self, mut f: impl FnMut(&[u8])) {
+        let mut buffer = [0; M];
+        while let Some(message) = self.pop(&mut buffer) {
+            f(message);
+        }
+    }
+
+    /// Returns the number of messages dropped because they were evicted by newer messages or failed to serialize.
+    pub fn dropped(&self) -> usize {
+        critical_section::with(|cs| self.ring.borrow_ref(cs).dropped)
+    }
+}
+
+impl<const N: usize, const M: usize> fmt::Debug for StaticSink<N, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StaticSink")
+            .field("capacity", &N)
+            .field("max_message_size", &M)
+            .field("dropped", &self.dropped())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<const N: usize, const M: usize> Export for StaticSink<N, M> {
+    fn export(&self, message: InstanceMessage<'_>) {
+        let mut buffer = [0; M];
+        let record = (self.encode)(&message, &mut buffer).and_then(|written| buffer.get(..written));
+
+        critical_section::with(|cs| {
+            let mut ring = self.ring.borrow_ref_mut(cs);
+            match record {
+                Some(record) => ring.push(record),
+                None => ring.dropped += 1,
+            }
+        });
+    }
+}
+
+/// A byte ring buffer storing length-prefixed records.
+#[derive(Debug)]
+struct Ring<const N: usize> {
+    buffer: [u8; N],
+    /// Offset of the oldest record.
+    head: usize,
+    /// Number of bytes used.
+    len: usize,
+    dropped: usize,
+}
+
+impl<const N: usize> Ring<N> {
+    const fn new() -> Self {
+        Self {
+            buffer: [0; N],
+            head: 0,
+            len: 0,
+            dropped: 0,
+        }
+    }
+
+    /// Appends `record`, evicting the oldest records until it fits.
+    ///
+    /// `record` is dropped if it can never fit.
+    fn push(&mut self, record: &[u8]) {
+        let needed = PREFIX_LEN + record.len();
+        let Ok(prefix) = u16::try_from(record.len()) else {
+            self.dropped += 1;
+            return;
+        };
+        if needed > N {
+            self.dropped += 1;
+            return;
+        }
+
+        while N - self.len < needed {
+            self.evict();
+        }
+
+        let tail = (self.head + self.len) % N;
+        self.write_at(tail, &prefix.to_le_bytes());
+        self.write_at((tail + PREFIX_LEN) % N, record);
+        self.len += needed;
+    }
+
+    /// Removes the oldest record, copying it into `buffer`.
+    ///
+    /// Returns the length of the record, or `None` if the ring is empty.
+    fn pop(&mut self, buffer: &mut [u8]) -> Option<usize> {
+        let record_len = self.oldest_len()?;
+        self.read_at((self.head + PREFIX_LEN) % N, &mut buffer[..record_len]);
+        self.remove_oldest(record_len);
+        Some(record_len)
+    }
+
+    fn evict(&mut self) {
+        if let Some(record_len) = self.oldest_len() {
+            self.remove_oldest(record_len);
+            self.dropped += 1;
+        }
+    }
+
+    fn oldest_len(&self) -> Option<usize> {
+        if self.len == 0 {
+            return None;
+        }
+        let mut prefix = [0; PREFIX_LEN];
+        self.read_at(self.head, &mut prefix);
+        Some(usize::from(u16::from_le_bytes(prefix)))
+    }
+
+    fn remove_oldest(&mut self, record_len: usize) {
+        let total = PREFIX_LEN + record_len;
+        self.head = (self.head + total) % N;
+        self.len -= total;
+    }
+
+    /// Copies `data` into the buffer starting at `offset`, wrapping around at the end.
+    fn write_at(&mut self, offset: usize, data: &[u8]) {
+        let (first, second) = data.split_at(data.len().min(N - offset));
+        self.buffer[offset..][..first.len()].copy_from_slice(first);
+        self.buffer[..second.len()].copy_from_slice(second);
+    }
+
+    /// Fills `data` from the buffer starting at `offset`, wrapping around at the end.
+    fn read_at(&self, offset: usize, data: &mut [u8]) {
+        let split = data.len().min(N - offset);
+        let (first, second) = data.split_at_mut(split);
+        first.copy_from_slice(&self.buffer[offset..][..split]);
+        second.copy_from_slice(&self.buffer[..second.len()]);
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::vec::Vec;
+
+    use super::{Ring, StaticSink};
+    use crate::collector::Export;
+    use crate::protocol::base::{ProcessId, ThreadId};
+    use crate::protocol::transient::{InstanceMessage, LogMessage, Severity, TelemetryMessage};
+
+    fn pop_all<const N: usize>(ring: &mut Ring<N>) -> Vec<Vec<u8>> {
+        let mut records = Vec::new();
+        let mut buffer = [0; N];
+        while let Some(record_len) = ring.pop(&mut buffer) {
+            records.push(buffer[..record_len].to_vec());
+        }
+        records
+    }
+
+    #[test]
+    fn ring_push_pop() {
+        let mut ring = Ring::<16>::new();
+        ring.push(b"abc");
+        ring.push(b"de");
+
+        assert_eq!(pop_all(&mut ring), [b"abc".to_vec(), b"de".to_vec()]);
+        assert_eq!(ring.dropped, 0);
+    }
+
+    #[test]
+    fn ring_wraps_around() {
+        let mut ring = Ring::<10>::new();
+        for record in [b"abcd", b"efgh", b"ijkl", b"mnop"] {
+            ring.push(record);
+            assert_eq!(pop_all(&mut ring), [record.to_vec()]);
+        }
+        assert_eq!(ring.dropped, 0);
+    }
+
+    #[test]
+    fn ring_evicts_oldest() {
+        let mut ring = Ring::<12>::new();
+        ring.push(b"abc");
+        ring.push(b"def");
+        ring.push(b"ghi");
+
+        assert_eq!(pop_all(&mut ring), [b"def".to_vec(), b"ghi".to_vec()]);
+        assert_eq!(ring.dropped, 1);
+    }
+
+    #[test]
+    fn ring_drops_oversized() {
+        let mut ring = Ring::<8>::new();
+        ring.push(b"abc");
+        ring.push(b"0123456789");
+
+        assert_eq!(pop_all(&mut ring), [b"abc".to_vec()]);
+        assert_eq!(ring.dropped, 1);
+    }
+
+    fn log(body: &str) -> InstanceMessage<'_> {
+        InstanceMessage {
+            thread_id: ThreadId::from_raw(ProcessId::from_raw(1), core::num::NonZeroU64::MIN),
+            message: TelemetryMessage::Log(LogMessage {
+                time_unix_nano: 0,
+                severity: Severity::Info,
+                body,
+                attributes: &[],
+            }),
+        }
+    }
+
+    fn encode_log_body(message: &InstanceMessage<'_>, buffer: &mut [u8]) -> Option<usize> {
+        let TelemetryMessage::Log(log) = &message.message else {
+            return None;
+        };
+        let body = log.body.as_bytes();
+        buffer.get_mut(..body.len())?.copy_from_slice(body);
+        Some(body.len())
+    }
+
+    #[test]
+    fn sink_export_and_drain() {
+        let sink = StaticSink::<12, 8>::new(encode_log_body);
+        sink.export(log("first"));
+        sink.export(log("second"));
+        sink.export(log("too long to encode"));
+
+        let mut drained = Vec::new();
+        sink.drain(|message| drained.push(message.to_vec()));
+
+        assert_eq!(drained, [b"second".to_vec()]);
+        assert_eq!(sink.dropped(), 2);
+
+        let mut buffer = [0; 8];
+        assert_eq!(sink.pop(&mut buffer), None);
+    }
+}
@@ -1,20 +1,58 @@
-use std::sync::{Arc, Mutex};
+use std::string::String;
+use std::sync::{Arc, Mutex, OnceLock};
 use std::vec::Vec;
 
+use veecle_osal_api::thread::ThreadAbstraction;
+use veecle_osal_api::time::TimeAbstraction;
+
 use super::Export;
+use crate::ProcessId;
+use crate::protocol::owned::{
+    SpanAddEventMessage, SpanCreateMessage, TelemetryMessage, TracingMessage, Value,
+};
 use crate::protocol::{owned, transient};
 
 /// An exporter for testing that stores all telemetry messages in memory.
 ///
 /// This exporter is useful for unit tests and integration tests where you need
 /// to verify that specific telemetry messages were generated.
-#[derive(Debug)]
+///
+/// # Examples
+///
+/// ```rust
+/// use veecle_osal_std::{thread::Thread, time::Time};
+/// use veecle_telemetry::collector::TestExporter;
+/// use veecle_telemetry::{event, span};
+///
+/// let exporter = TestExporter::set_global::<Time, Thread>();
+///
+/// {
+///     let _guard = span!("request", user_id = 123).entered();
+///     event!("cache_miss", key = "user:123");
+/// }
+///
+/// assert_eq!(exporter.spans_named("request").len(), 1);
+/// assert_eq!(exporter.events_with_attr("key", "user:123").len(), 1);
+/// exporter.assert_span_tree(
+///     r#"
+///     request [user_id: 123]
+///         + event: cache_miss [key: "user:123"]
+///     "#,
+/// );
+/// ```
+#[derive(Clone, Debug)]
 pub struct TestExporter {
     /// Shared vector storing all exported telemetry messages
     pub spans: Arc<Mutex<Vec<owned::InstanceMessage>>>,
 }
 
 impl TestExporter {
+    /// The [`ProcessId`] of the global collector installed by [`TestExporter::set_global`].
+    ///
+    /// Together with [`deterministic_span_ids`](crate::id::deterministic_span_ids) this makes the ids in the captured
+    /// messages reproducible.
+    pub const PROCESS_ID: ProcessId = ProcessId::from_raw(1);
+
     /// Creates a new test exporter and returns both the exporter and a handle to the message storage.
     ///
     /// The returned tuple contains the exporter and a shared reference to the vector
@@ -38,6 +76,108 @@ impl TestExporter {
             spans,
         )
     }
+
+    /// Returns the process-wide test exporter, installing it as the global collector on first use.
+    ///
+    /// The global collector uses the fixed [`TestExporter::PROCESS_ID`] rather than a random one.
+    /// The global collector can only be set once per process, so all tests share the same exporter.
+    /// Messages captured by previous tests are kept, call [`TestExporter::clear`] at the start of a
+    /// test to scope the captured messages to that test.
+    /// Tests using this must not run concurrently (e.g. by using `serial_test`).
+    ///
+    /// # Panics
+    ///
+    /// If a different global collector has already been set.
+    pub fn set_global<T, Th>() -> &'static Self
+    where
+        T: TimeAbstraction,
+        Th: ThreadAbstraction,
+    {
+        static EXPORTER: OnceLock<TestExporter> = OnceLock::new();
+
+        let exporter = EXPORTER.get_or_init(|| {
+            let (exporter, _) = TestExporter::new();
+
+            super::build()
+                .process_id(Self::PROCESS_ID)
+                .leaked_exporter(exporter.clone())
+                .time::<T>()
+                .thread::<Th>()
+                .set_global()
+                .expect("a global collector has already been set");
+
+            exporter
+        });
+
+        exporter
+    }
+
+    /// Removes all messages captured so far.
+    pub fn clear(&self) {
+        self.spans.lock().unwrap().clear();
+    }
+
+    /// Returns a copy of all messages captured so far.
+    pub fn messages(&self) -> Vec<owned::InstanceMessage> {
+        self.spans.lock().unwrap().clone()
+    }
+
+    /// Removes and returns all messages captured so far.
+    pub fn take_messages(&self) -> Vec<owned::InstanceMessage> {
+        self.spans.lock().unwrap().drain(..).collect()
+    }
+
+    /// Returns the creation messages of all captured spans with the given name.
+    pub fn spans_named(&self, name: &str) -> Vec<SpanCreateMessage> {
+        self.tracing_messages()
+            .filter_map(|message| match message {
+                TracingMessage::CreateSpan(span) if span.name == name => Some(span),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns all captured span events that have an attribute with the given key and value.
+    pub fn events_with_attr(&self, key: &str, value: impl Into<Value>) -> Vec<SpanAddEventMessage> {
+        let value = value.into();
+        self.tracing_messages()
+            .filter_map(|message| match message {
+                TracingMessage::AddEvent(event)
+                    if event
+                        .attributes
+                        .iter()
+                        .any(|attribute| attribute.key == key && attribute.value == value) =>
+                {
+                    Some(event)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Asserts that the captured messages form the expected span tree.
+    ///
+    /// The tree is rendered with one span per line, children indented by four spaces, and the
+    /// span's attributes, links, events and logs listed below it.
+    /// Common leading indentation and surrounding blank lines in `expected` are ignored.
+    ///
+    /// # Panics
+    ///
+    /// If the rendered tree does not match `expected`.
+    #[track_caller]
+    pub fn assert_span_tree(&self, expected: &str) {
+        let actual = crate::test_helpers::format_telemetry_tree(self.messages());
+        assert_eq!(actual, unindent(expected), "span tree mismatch");
+    }
+
+    fn tracing_messages(&self) -> impl Iterator<Item = TracingMessage> {
+        self.messages()
+            .into_iter()
+            .filter_map(|message| match message.message {
+                TelemetryMessage::Tracing(message) => Some(message),
+                _ => None,
+            })
+    }
 }
 
 impl Export for TestExporter {
@@ -45,3 +185,30 @@ impl Export for TestExporter {
         self.spans.lock().unwrap().push(message.into());
     }
 }
+
+/// Strips surrounding blank lines and the common leading indentation from `text`.
+fn unindent(text: &str) -> String {
+    let lines: Vec<&str> = text
+        .lines()
+        .skip_while(|line| line.trim().is_empty())
+        .collect();
+    let end = lines
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .map_or(0, |index| index + 1);
+    let lines = &lines[..end];
+
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    let mut result = String::new();
+    for line in lines {
+        result.push_str(line.get(indent..).unwrap_or_default());
+        result.push('\n');
+    }
+    result
+}
@@ -12,11 +12,31 @@
 //!
 //! - [`SpanId`]: An identifier that uniquely identifies a span within a process.
 //! - [`SpanContext`]: A combination of process id and span id that uniquely identifies a span globally.
+//!
+//! # Generating ids without `rand`
+//!
+//! [`ProcessId::random`] requires the `rand` feature and a random number generator. Targets without one can create the
+//! [`ProcessId`] with [`ProcessId::from_u128`] from a platform entropy source (e.g. a hardware RNG peripheral), or with
+//! [`ProcessId::from_parts`] from a unique device identifier and a value that changes on every boot (e.g. a persisted
+//! boot counter). The uniqueness of all ids depends on this: if two boots use the same [`ProcessId`], their
+//! [`SpanContext`]s are only distinguishable if their [`SpanId`]s don't overlap.
+//!
+//! [`SpanId`]s are generated by a [`SpanIdGenerator`], a monotonic counter that is unique within a process until it
+//! wraps around after 2<sup>64</sup> spans. The global generator starts at `1`, use [`seed_span_ids`] to start it from
+//! an entropy-derived value instead. This reduces the chance of collisions when the [`ProcessId`] can't be guaranteed to
+//! change between boots, but can't rule them out: two boots with the same [`ProcessId`] collide as soon as the ranges of
+//! span ids they used overlap.
+//!
+//! # Reproducible ids in tests
+//!
+//! Tests asserting on the complete telemetry output can use [`deterministic_span_ids`] to restart the span ids from a
+//! known value for the duration of the test, together with a fixed [`ProcessId`] such as
+//! [`TestExporter::PROCESS_ID`](crate::collector::TestExporter::PROCESS_ID).
 
 use core::fmt;
 use core::num::NonZeroU64;
 use core::str::FromStr;
-use rand::RngExt;
+use core::sync::atomic::{AtomicU64, Ordering};
 
 /// A globally-unique id identifying a process.
 ///
@@ -29,10 +49,44 @@ pub struct ProcessId(u128);
 
 impl ProcessId {
     /// Uses a random number generator to generate the [`ProcessId`].
+    #[cfg(feature = "rand")]
     pub fn random(rng: &mut impl rand::Rng) -> Self {
+        use rand::RngExt;
+
         Self(rng.random())
     }
 
+    /// Creates a [`ProcessId`] from 128 bits of entropy, e.g. read from a hardware random number generator.
+    ///
+    /// The value must differ between every time the system is started, see the [module docs](self) for the
+    /// uniqueness trade-offs.
+    ///
+    /// ```
+    /// use veecle_telemetry::ProcessId;
+    ///
+    /// # let read_hardware_rng = || 0x0123_4567_89ab_cdef_0123_4567_89ab_cdef;
+    /// let process_id = ProcessId::from_u128(read_hardware_rng());
+    /// ```
+    pub const fn from_u128(entropy: u128) -> Self {
+        Self(entropy)
+    }
+
+    /// Creates a [`ProcessId`] from a device identifier and a per-boot value.
+    ///
+    /// `device` should uniquely identify the device (e.g. a MAC address or the chip's unique id) and `boot` should be
+    /// different on every start of the device (e.g. a boot counter persisted in flash, or a real-time clock timestamp).
+    /// The id is only unique as long as both hold, see the [module docs](self) for the uniqueness trade-offs.
+    ///
+    /// ```
+    /// use veecle_telemetry::ProcessId;
+    ///
+    /// let process_id = ProcessId::from_parts(0x0000_0242_ac11_0002, 17);
+    /// assert_eq!(process_id.to_raw(), 0x0000_0242_ac11_0002_0000_0000_0000_0011);
+    /// ```
+    pub const fn from_parts(device: u64, boot: u64) -> Self {
+        Self(((device as u128) << 64) | boot as u128)
+    }
+
     /// Creates a [`ProcessId`] from a raw value
     ///
     /// Extra care needs to be taken that this is not a constant value or re-used in any way.
@@ -234,15 +288,114 @@ impl<'de> serde::Deserialize<'de> for ThreadId {
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct SpanId(pub u64);
 
+/// The generator used for all spans created in this process.
+static SPAN_ID_GENERATOR: SpanIdGenerator = SpanIdGenerator::new();
+
 #[cfg(feature = "enable")]
 impl SpanId {
     #[inline]
     #[doc(hidden)]
     /// Creates a non-zero [`SpanId`].
     pub fn next_id() -> Self {
-        use core::sync::atomic;
-        static COUNTER: atomic::AtomicU64 = atomic::AtomicU64::new(1);
-        SpanId(COUNTER.fetch_add(1, atomic::Ordering::Relaxed))
+        SPAN_ID_GENERATOR.next_id()
+    }
+}
+
+/// Seeds the generator used for the [`SpanId`]s of all spans created in this process.
+///
+/// This should be called once at startup, before any span is created, with a value derived from a platform entropy
+/// source. See the [module docs](self) for the uniqueness trade-offs.
+pub fn seed_span_ids(seed: u64) {
+    SPAN_ID_GENERATOR.reseed(seed);
+}
+
+/// Restarts the generator used for the [`SpanId`]s of all spans created in this process from `seed` until the
+/// returned guard is dropped.
+///
+/// This makes the span ids created by a test reproducible, e.g. to assert on the complete telemetry output. Once the
+/// guard is dropped the generator continues from where it was before, so later spans don't reuse the ids of spans
+/// created before the guard. The generator is shared by the whole process, so tests using this must not run
+/// concurrently with other tests creating spans (e.g. by using `serial_test`).
+///
+/// This is meant for tests only, see [`seed_span_ids`] for seeding the generator in production.
+///
+/// ```
+/// use veecle_telemetry::id::deterministic_span_ids;
+/// use veecle_telemetry::{Span, SpanId};
+///
+/// let _ids = deterministic_span_ids(1);
+///
+/// let span = Span::new("first", &[]);
+/// assert_eq!(span.context().unwrap().span_id, SpanId(1));
+/// ```
+pub fn deterministic_span_ids(seed: u64) -> DeterministicSpanIds {
+    DeterministicSpanIds {
+        previous: SPAN_ID_GENERATOR.next.swap(seed, Ordering::Relaxed),
+    }
+}
+
+/// Restores the global [`SpanId`] generator when dropped, see [`deterministic_span_ids`].
+#[must_use]
+#[derive(Debug)]
+pub struct DeterministicSpanIds {
+    previous: u64,
+}
+
+impl Drop for DeterministicSpanIds {
+    fn drop(&mut self) {
+        SPAN_ID_GENERATOR.reseed(self.previous);
+    }
+}
+
+/// Generates non-zero [`SpanId`]s from a monotonic counter.
+///
+/// ```
+/// use veecle_telemetry::SpanId;
+/// use veecle_telemetry::id::SpanIdGenerator;
+///
+/// let generator = SpanIdGenerator::with_seed(u64::MAX);
+///
+/// assert_eq!(generator.next_id(), SpanId(u64::MAX));
+/// // Zero is skipped when wrapping around.
+/// assert_eq!(generator.next_id(), SpanId(1));
+/// ```
+#[derive(Debug)]
+pub struct SpanIdGenerator {
+    next: AtomicU64,
+}
+
+impl SpanIdGenerator {
+    /// Creates a generator counting up from `1`.
+    pub const fn new() -> Self {
+        Self::with_seed(1)
+    }
+
+    /// Creates a generator counting up from `seed`, e.g. read from a platform entropy source.
+    pub const fn with_seed(seed: u64) -> Self {
+        Self {
+            next: AtomicU64::new(seed),
+        }
+    }
+
+    /// Restarts the generator from `seed`.
+    pub fn reseed(&self, seed: u64) {
+        self.next.store(seed, Ordering::Relaxed);
+    }
+
+    /// Returns the next non-zero [`SpanId`].
+    pub fn next_id(&self) -> SpanId {
+        loop {
+            let id = self.next.fetch_add(1, Ordering::Relaxed);
+            if id != 0 {
+                return SpanId(id);
+            }
+        }
+    }
+}
+
+impl Default for SpanIdGenerator {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -18,6 +18,11 @@
 //! - `enable` - Enable collecting and exporting telemetry data, should only be set in binary crates
 //! - `std` - Enable standard library support (implies `alloc`)
 //! - `alloc` - Enable allocator support for dynamic data structures
+//! - `osal-log` - Provide [`log::TelemetryLog`] to forward `veecle-osal-api` log output as telemetry events
+//! - `static-sink` - Provide [`collector::StaticSink`], a fixed-capacity exporter for targets without an allocator
+//! - `max_level_debug`, `max_level_info`, `max_level_warn`, `max_level_error`, `max_level_fatal` - Statically set the
+//!   most verbose level for which `#[instrument(level = ...)]` creates spans and log messages are exported, see
+//!   [`MAX_LEVEL`]
 //!
 //! ## Basic Usage
 //!
@@ -102,6 +107,65 @@ mod span;
 #[doc(hidden)]
 pub mod test_helpers;
 
-pub use id::{ProcessId, SpanContext, SpanId};
+pub use id::{ProcessId, SpanContext, SpanId, seed_span_ids};
 pub use span::{CurrentSpan, Span, SpanGuard, SpanGuardRef};
 pub use veecle_telemetry_macros::instrument;
+
+use crate::protocol::base::Severity;
+
+/// The most verbose level for which `#[instrument(level = ...)]` creates spans and log messages are exported.
+///
+/// Instrumented functions with a `level` below this threshold run their body without creating a span.
+/// Log macros with a severity below this threshold don't evaluate their attributes.
+/// The threshold is selected through the `max_level_*` features and defaults to [`Severity::Trace`], enabling all levels.
+/// If multiple `max_level_*` features are enabled, the least verbose level is used.
+pub const MAX_LEVEL: Severity = if cfg!(feature = "max_level_fatal") {
+    Severity::Fatal
+} else if cfg!(feature = "max_level_error") {
+    Severity::Error
+} else if cfg!(feature = "max_level_warn") {
+    Severity::Warn
+} else if cfg!(feature = "max_level_info") {
+    Severity::Info
+} else if cfg!(feature = "max_level_debug") {
+    Severity::Debug
+} else {
+    Severity::Trace
+};
+
+/// Returns whether spans at `level` are enabled by [`MAX_LEVEL`].
+///
+/// # Examples
+///
+/// ```rust
+/// use veecle_telemetry::level_enabled;
+/// use veecle_telemetry::protocol::base::Severity;
+///
+/// assert!(level_enabled(Severity::Fatal));
+/// ```
+pub const fn level_enabled(level: Severity) -> bool {
+    level as u8 >= MAX_LEVEL as u8
+}
+
+/// Returns whether telemetry is exported, i.e. the `enable` feature is set and a global collector has been set.
+///
+/// Use this to skip work that is only needed to produce telemetry.
+///
+/// # Examples
+///
+/// ```rust
+/// // No global collector has been set.
+/// assert!(!veecle_telemetry::enabled());
+/// ```
+#[inline]
+pub fn enabled() -> bool {
+    #[cfg(not(feature = "enable"))]
+    {
+        false
+    }
+
+    #[cfg(feature = "enable")]
+    {
+        collector::is_collector_set()
+    }
+}
@@ -2,6 +2,16 @@
 use crate::collector::get_collector;
 use crate::protocol::transient;
 
+/// Returns whether log messages with `severity` are exported.
+///
+/// This requires the `enable` feature, `severity` to be at least [`MAX_LEVEL`](crate::MAX_LEVEL) and a global
+/// collector to be set.
+/// The log macros check this before evaluating their attributes.
+#[inline]
+pub fn enabled(severity: transient::Severity) -> bool {
+    crate::level_enabled(severity) && crate::enabled()
+}
+
 /// Logs a message with the specified severity level and attributes.
 ///
 /// Prefer using the macros.
@@ -43,6 +53,7 @@ use crate::protocol::transient;
 ///
 /// When the `enable` feature is disabled, this function compiles to a no-op
 /// and has zero runtime overhead.
+/// Messages with a severity below [`MAX_LEVEL`](crate::MAX_LEVEL) are discarded.
 pub fn log<'a>(
     severity: transient::Severity,
     body: &'a str,
@@ -54,7 +65,46 @@ pub fn log<'a>(
     }
 
     #[cfg(feature = "enable")]
-    {
+    if enabled(severity) {
         get_collector().log_message(severity, body, attributes);
     }
 }
+
+/// A [`LogTarget`](veecle_osal_api::log::LogTarget) forwarding every line to the wrapped log target `L` and as a
+/// `log` event with a `message` attribute to the current span.
+///
+/// This allows showing the output of code using the OSAL log in the telemetry UI, attached to the span it was logged
+/// within.
+///
+/// ```rust
+/// use veecle_osal_api::log::LogTarget;
+/// use veecle_osal_std::log::Log;
+/// use veecle_telemetry::log::TelemetryLog;
+/// use veecle_telemetry::span;
+///
+/// let _guard = span!("startup").entered();
+///
+/// // Printed to stdout and recorded as an event on the `startup` span.
+/// TelemetryLog::<Log>::println(format_args!("initialized {} sensors", 4));
+/// ```
+#[cfg(feature = "osal-log")]
+#[derive(Debug)]
+pub struct TelemetryLog<L>(core::marker::PhantomData<L>);
+
+#[cfg(feature = "osal-log")]
+impl<L> veecle_osal_api::log::LogTarget for TelemetryLog<L>
+where
+    L: veecle_osal_api::log::LogTarget,
+{
+    type Time = L::Time;
+
+    fn init() {
+        L::init();
+    }
+
+    fn println(args: core::fmt::Arguments<'_>) {
+        L::println(args);
+
+        crate::CurrentSpan::add_event("log", &[transient::KeyValue::new("message", args)]);
+    }
+}
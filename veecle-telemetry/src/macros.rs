@@ -27,6 +27,8 @@ macro_rules! __private_function_path {
 ///
 /// A span represents a unit of work or operation that has a beginning and end.
 /// It can contain attributes that provide additional context about the operation.
+/// The attributes are only evaluated if the span is exported, i.e. the `enable` feature is set, a global collector is
+/// set and the span is sampled.
 ///
 /// # Examples
 ///
@@ -44,10 +46,73 @@ macro_rules! __private_function_path {
 /// let span =
 ///     span!("database_query", user_id, table = table_name, "operation" = "select");
 /// ```
+///
+/// Create a span as a child of a [`SpanContext`](crate::SpanContext) received out-of-band instead of the current
+/// span:
+///
+/// ```rust
+/// use veecle_telemetry::{ProcessId, SpanContext, SpanId, span};
+///
+/// let parent = SpanContext::new(ProcessId::from_raw(1), SpanId(2));
+/// let span = span!(parent: parent, "handle_request", "operation" = "select");
+/// ```
 #[macro_export]
 macro_rules! span {
+    (parent: $parent:expr, $name:literal $(, $($attributes:tt)*)?) => {
+        $crate::Span::new_lazy(Some($parent), $name, |create| {
+            create($crate::attributes!($($($attributes)*)?))
+        })
+    };
     ($name:literal $(, $($attributes:tt)*)?) => {
-        $crate::Span::new($name, $crate::attributes!($($($attributes)*)?))
+        $crate::Span::new_lazy(None, $name, |create| create($crate::attributes!($($($attributes)*)?)))
+    };
+}
+
+/// Runs an expression or async block within a new span.
+///
+/// This instruments a part of a function, like [`#[instrument]`](crate::instrument) does for whole functions, e.g. to
+/// record several phases of a function as separate spans.
+/// Attributes are passed in braces, with the same syntax as for [`span!`].
+///
+/// - For an `async` block, the block is instrumented with [`FutureExt::with_span`](crate::future::FutureExt::with_span)
+///   and the instrumented future is returned, the span is created immediately.
+/// - For any other expression, the span is entered while the expression is evaluated and its value is returned.
+///
+/// Other futures can be instrumented with [`FutureExt::with_span`](crate::future::FutureExt::with_span) and
+/// [`span!`] directly.
+///
+/// # Examples
+///
+/// ```rust
+/// use veecle_telemetry::spanned;
+///
+/// async fn handle_request(id: i64) -> usize {
+///     let request = spanned!("parse", { id }, async { "request" }).await;
+///
+///     let response = spanned!("process", async move { request.len() }).await;
+///
+///     spanned!("encode", { "bytes" = response as i64 }, response * 2)
+/// }
+/// ```
+#[macro_export]
+macro_rules! spanned {
+    ($name:literal, { $($attributes:tt)* }, async $($body:tt)+) => {
+        $crate::future::FutureExt::with_span(async $($body)+, $crate::span!($name, $($attributes)*))
+    };
+    ($name:literal, async $($body:tt)+) => {
+        $crate::future::FutureExt::with_span(async $($body)+, $crate::span!($name))
+    };
+    ($name:literal, { $($attributes:tt)* }, $body:expr) => {
+        {
+            let __guard__ = $crate::span!($name, $($attributes)*).entered();
+            $body
+        }
+    };
+    ($name:literal, $body:expr) => {
+        {
+            let __guard__ = $crate::span!($name).entered();
+            $body
+        }
     };
 }
 
@@ -73,8 +138,24 @@ macro_rules! span {
 /// let cache_type = "redis";
 /// event!("cache_miss", key = key, cache_type = cache_type, "retry_count" = 3);
 /// ```
+///
+/// Events default to [`Severity::Info`](crate::protocol::base::Severity::Info), mark e.g. a recoverable error with a
+/// different severity:
+/// ```rust
+/// use veecle_telemetry::event;
+/// use veecle_telemetry::protocol::base::Severity;
+///
+/// event!(severity: Severity::Warn, "retry", "attempt" = 2);
+/// ```
 #[macro_export]
 macro_rules! event {
+    (severity: $severity:expr, $name:literal $(, $($attributes:tt)*)?) => {
+        $crate::CurrentSpan::add_event_with_severity(
+            $severity,
+            $name,
+            $crate::attributes!($($($attributes)*)?),
+        )
+    };
     ($name:literal $(, $($attributes:tt)*)?) => {
         $crate::CurrentSpan::add_event($name, $crate::attributes!($($($attributes)*)?))
     };
@@ -85,6 +166,10 @@ macro_rules! event {
 /// This is the base logging macro that other severity-specific macros build upon.
 /// It allows you to specify the severity level and optional attributes.
 ///
+/// The attributes are only evaluated if the message is enabled, i.e. the `enable` feature is set and the severity is
+/// at least [`MAX_LEVEL`](crate::MAX_LEVEL).
+/// Use `format_args!` for formatted attributes, they are only formatted when the message is exported.
+///
 /// # Examples
 ///
 /// Log a simple message:
@@ -107,7 +192,12 @@ macro_rules! event {
 #[macro_export]
 macro_rules! log {
     ($severity:expr, $body:literal $(, $($attributes:tt)*)?) => {
-        $crate::log::log($severity, $body, $crate::attributes!($($($attributes)*)?))
+        {
+            let severity = $severity;
+            if $crate::log::enabled(severity) {
+                $crate::log::log(severity, $body, $crate::attributes!($($($attributes)*)?));
+            }
+        }
     };
 }
 
@@ -316,6 +406,7 @@ macro_rules! fatal {
 /// - `"literal" = value` - Uses the literal string as the key name
 /// - `identifier` - Uses the identifier as both key and value
 /// - `field.subfield` - Simple dot notation for field access
+/// - `identifier = [a, b]` / `"literal" = [a, b]` - Array values, each element converted to a `Value`
 ///
 /// # Examples
 ///
@@ -353,6 +444,13 @@ macro_rules! fatal {
 /// );
 /// ```
 ///
+/// Array values:
+/// ```rust
+/// use veecle_telemetry::span;
+///
+/// let span = span!("decode", signals = ["speed", "rpm"], "counts" = [1, 2, 3]);
+/// ```
+///
 /// Empty attributes:
 /// ```rust
 /// use veecle_telemetry::attributes;
@@ -388,6 +486,18 @@ macro_rules! attributes_inner {
             { $($($rest)*)? }
         )
     };
+    (@ { $($out:expr,)* }, { $key:ident = [$($element:expr),* $(,)?] $(, $($rest:tt)*)? }) => {
+        $crate::attributes_inner!(
+            @ { $($out,)* $crate::attribute!($key = [$($element),*]), },
+            { $($($rest)*)? }
+        )
+    };
+    (@ { $($out:expr,)* }, { $key:literal = [$($element:expr),* $(,)?] $(, $($rest:tt)*)? }) => {
+        $crate::attributes_inner!(
+            @ { $($out,)* $crate::attribute!($key = [$($element),*]), },
+            { $($($rest)*)? }
+        )
+    };
     (@ { $($out:expr,)* }, { $key:ident = $value:expr $(, $($rest:tt)*)? }) => {
         $crate::attributes_inner!(
             @ { $($out,)* $crate::attribute!($key = $value), },
@@ -405,6 +515,22 @@ macro_rules! attributes_inner {
 /// Constructs a single attribute `KeyValue` pair.
 #[macro_export]
 macro_rules! attribute {
+    ($($key:ident)+ = [$($element:expr),* $(,)?]) => {
+        $crate::protocol::transient::KeyValue::new(
+            ::core::stringify!($($key).+),
+            $crate::protocol::transient::Value::Array(&[
+                $($crate::protocol::transient::Value::from(&$element)),*
+            ]),
+        )
+    };
+    ($key:literal = [$($element:expr),* $(,)?]) => {
+        $crate::protocol::transient::KeyValue::new(
+            $key,
+            $crate::protocol::transient::Value::Array(&[
+                $($crate::protocol::transient::Value::from(&$element)),*
+            ]),
+        )
+    };
     ($($key:ident)+ = $value:expr) => {
         $crate::protocol::transient::KeyValue::new(::core::stringify!($($key).+), &$value)
     };
@@ -77,7 +77,7 @@ pub trait StorageFamily: Clone + core::fmt::Debug + Sealed {
 /// ```
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(bound(
-    deserialize = "F::String<'a>: serde::de::DeserializeOwned, F::Value<'a>: serde::de::DeserializeOwned"
+    deserialize = "F::String<'a>: serde::Deserialize<'de>, F::Value<'a>: serde::Deserialize<'de>"
 ))]
 pub struct KeyValue<'a, F>
 where
@@ -135,7 +135,7 @@ where
 /// This structure wraps a telemetry message with its execution context,
 /// allowing messages from different executions to be properly correlated.
 #[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(bound(deserialize = "TelemetryMessage<'a, F>: serde::de::DeserializeOwned"))]
+#[serde(bound(deserialize = "TelemetryMessage<'a, F>: serde::Deserialize<'de>"))]
 pub struct InstanceMessage<'a, F>
 where
     F: StorageFamily + 'a,
@@ -153,7 +153,7 @@ where
 /// collected and exported by the system.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(bound(
-    deserialize = "LogMessage<'a, F>: serde::de::DeserializeOwned, TracingMessage<'a, F>: serde::de::DeserializeOwned"
+    deserialize = "LogMessage<'a, F>: serde::Deserialize<'de>, TracingMessage<'a, F>: serde::Deserialize<'de>"
 ))]
 pub enum TelemetryMessage<'a, F>
 where
@@ -173,7 +173,9 @@ where
 ///
 /// These levels follow standard logging conventions, ordered from most verbose
 /// to most critical.
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[derive(
+    Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize,
+)]
 pub enum Severity {
     /// The "trace" level.
     ///
@@ -188,6 +190,7 @@ pub enum Severity {
     /// The "info" level.
     ///
     /// Designates useful information.
+    #[default]
     Info,
 
     /// The "warn" level.
@@ -211,7 +214,7 @@ pub enum Severity {
 /// Log messages can be optionally correlated with traces by including trace and span IDs when available.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(bound(
-    deserialize = "F::String<'a>: serde::de::DeserializeOwned, F::List<'a, KeyValue<'a, F>>: serde::de::DeserializeOwned"
+    deserialize = "F::String<'a>: serde::Deserialize<'de>, F::List<'a, KeyValue<'a, F>>: serde::Deserialize<'de>"
 ))]
 pub struct LogMessage<'a, F>
 where
@@ -232,11 +235,12 @@ where
 
 /// A time synchronization message for coordinating clocks between systems.
 ///
-/// This message contains both local time and absolute time information,
-/// allowing downstream systems to correlate local timestamps with real-world time.
+/// This message pairs the monotonic time of a process with its system time taken at the same moment,
+/// allowing downstream systems to correlate local timestamps with real-world time and to correct the skew
+/// between the clocks of different processes.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TimeSyncMessage {
-    /// Local timestamp in system-specific units.
+    /// Monotonic time of the process in nanoseconds.
     pub local_timestamp: u64,
 
     /// Time since Unix epoch in nanoseconds.
@@ -249,7 +253,7 @@ pub struct TimeSyncMessage {
 /// generated during span lifecycle management and tracing operations.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(bound(
-    deserialize = "SpanCreateMessage<'a, F>: serde::de::DeserializeOwned, SpanAddEventMessage<'a, F>: serde::de::DeserializeOwned, SpanSetAttributeMessage<'a, F>: serde::de::DeserializeOwned"
+    deserialize = "SpanCreateMessage<'a, F>: serde::Deserialize<'de>, SpanAddEventMessage<'a, F>: serde::Deserialize<'de>, SpanSetAttributeMessage<'a, F>: serde::Deserialize<'de>"
 ))]
 pub enum TracingMessage<'a, F>
 where
@@ -283,7 +287,7 @@ where
 /// in the trace, including its identity, timing, and initial attributes.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(bound(
-    deserialize = "F::String<'a>: serde::de::DeserializeOwned, F::List<'a, KeyValue<'a, F>>: serde::de::DeserializeOwned"
+    deserialize = "F::String<'a>: serde::Deserialize<'de>, F::List<'a, KeyValue<'a, F>>: serde::Deserialize<'de>"
 ))]
 pub struct SpanCreateMessage<'a, F>
 where
@@ -300,6 +304,12 @@ where
 
     /// Initial attributes attached to the span.
     pub attributes: F::List<'a, KeyValue<'a, F>>,
+
+    /// Explicit parent of the span.
+    ///
+    /// If `None`, the parent is the span that is current on the creating thread (if any).
+    #[serde(default)]
+    pub parent: Option<SpanContext>,
 }
 
 /// Message indicating a span has been entered.
@@ -330,11 +340,18 @@ pub struct SpanCloseMessage {
 
     /// Timestamp when the span was closed.
     pub end_time_unix_nano: u64,
+
+    /// Time elapsed between the creation and closing of the span, in nanoseconds.
+    ///
+    /// Measured with the same clock as the timestamps, so it is monotonic unless the collector was
+    /// configured with [`system_time`][crate::collector::Builder::system_time].
+    #[serde(default)]
+    pub duration_nano: u64,
 }
 
 /// Message indicating an attribute has been set on a span.
 #[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(bound(deserialize = "KeyValue<'a, F>: serde::de::DeserializeOwned"))]
+#[serde(bound(deserialize = "KeyValue<'a, F>: serde::Deserialize<'de>"))]
 pub struct SpanSetAttributeMessage<'a, F>
 where
     F: StorageFamily + 'a,
@@ -350,7 +367,7 @@ where
 /// Message indicating an event has been added to a span.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(bound(
-    deserialize = "F::String<'a>: serde::de::DeserializeOwned, F::List<'a, KeyValue<'a, F>>: serde::de::DeserializeOwned"
+    deserialize = "F::String<'a>: serde::Deserialize<'de>, F::List<'a, KeyValue<'a, F>>: serde::Deserialize<'de>"
 ))]
 pub struct SpanAddEventMessage<'a, F>
 where
@@ -363,6 +380,12 @@ where
     /// The name of the event.
     pub name: F::String<'a>,
 
+    /// The severity of the event, e.g. to distinguish a recoverable error from a milestone.
+    ///
+    /// Defaults to [`Severity::Info`] when missing, e.g. for events recorded by older versions.
+    #[serde(default)]
+    pub severity: Severity,
+
     /// Timestamp when the event occurred.
     pub time_unix_nano: u64,
 
@@ -0,0 +1,223 @@
+//! Type aliases for deserializing without copying strings (decoding received telemetry).
+//!
+//! These aliases use [`Str`] and [`Value`] which borrow strings from the input being deserialized
+//! where possible, only allocating for strings that need unescaping.
+//! Use these to process received telemetry synchronously, e.g. when forwarding a high volume of it,
+//! and convert to the [`owned`](super::owned) types to keep messages beyond the input buffer.
+//!
+//! See the [main protocol module][super] docs for more details on the protocol modules provided.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use core::num::NonZeroU64;
+//!
+//! use veecle_telemetry::protocol::{borrowed, owned, transient};
+//!
+//! let attributes = [transient::KeyValue::new("speed", 42)];
+//! let message = transient::InstanceMessage {
+//!     thread_id: transient::ThreadId::from_raw(transient::ProcessId::from_raw(1), NonZeroU64::MIN),
+//!     message: transient::TelemetryMessage::Log(transient::LogMessage {
+//!         time_unix_nano: 0,
+//!         severity: transient::Severity::Info,
+//!         body: "started",
+//!         attributes: &attributes,
+//!     }),
+//! };
+//! let json = serde_json::to_string(&message)?;
+//!
+//! let message: borrowed::InstanceMessage<'_> = serde_json::from_str(&json)?;
+//! let borrowed::TelemetryMessage::Log(log) = &message.message else { panic!("unexpected variant") };
+//! assert_eq!(log.body, "started");
+//! assert!(log.body.is_borrowed());
+//!
+//! // Convert to keep the message after `json` is dropped.
+//! let message = owned::InstanceMessage::from(message);
+//! # Ok::<(), serde_json::Error>(())
+//! ```
+
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::protocol::base;
+
+/// Borrowed storage family referencing the deserialized input.
+///
+/// This family uses strings that borrow from the input where possible and owned lists, as lists
+/// have to be built during deserialization.
+#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Borrowed;
+
+impl base::Sealed for Borrowed {}
+impl base::StorageFamily for Borrowed {
+    type String<'a>
+        = Str<'a>
+    where
+        Self: 'a;
+
+    type List<'a, T: Clone + core::fmt::Debug + serde::Serialize + 'a>
+        = Vec<T>
+    where
+        Self: 'a;
+
+    type Value<'a>
+        = Value<'a>
+    where
+        Self: 'a;
+}
+
+// Re-export non-generic types for convenience.
+pub use base::{
+    ProcessId, Severity, SpanAddLinkMessage, SpanCloseMessage, SpanContext, SpanEnterMessage,
+    SpanExitMessage, SpanId, ThreadId, TimeSyncMessage,
+};
+
+/// Key-value pair with borrowed value (deserialized from a buffer).
+pub type KeyValue<'a> = base::KeyValue<'a, Borrowed>;
+/// Instance message with borrowed values (deserialized from a buffer).
+pub type InstanceMessage<'a> = base::InstanceMessage<'a, Borrowed>;
+/// Telemetry message with borrowed values (deserialized from a buffer).
+pub type TelemetryMessage<'a> = base::TelemetryMessage<'a, Borrowed>;
+/// Log message with borrowed values (deserialized from a buffer).
+pub type LogMessage<'a> = base::LogMessage<'a, Borrowed>;
+/// Tracing message with borrowed values (deserialized from a buffer).
+pub type TracingMessage<'a> = base::TracingMessage<'a, Borrowed>;
+/// Span create message with borrowed values (deserialized from a buffer).
+pub type SpanCreateMessage<'a> = base::SpanCreateMessage<'a, Borrowed>;
+/// Span set attribute message with borrowed values (deserialized from a buffer).
+pub type SpanSetAttributeMessage<'a> = base::SpanSetAttributeMessage<'a, Borrowed>;
+/// Span add event message with borrowed values (deserialized from a buffer).
+pub type SpanAddEventMessage<'a> = base::SpanAddEventMessage<'a, Borrowed>;
+
+/// A string borrowed from the deserialized input where possible.
+///
+/// Deserializing a [`Cow`] directly always allocates, this borrows the string unless the input
+/// format requires it to be unescaped first (e.g. a JSON string containing `\n`).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Str<'a>(#[serde(borrow)] pub Cow<'a, str>);
+
+impl Str<'_> {
+    /// Returns whether the string is borrowed from the input rather than allocated.
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self.0, Cow::Borrowed(_))
+    }
+}
+
+impl AsRef<str> for Str<'_> {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl core::ops::Deref for Str<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl core::fmt::Display for Str<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl PartialEq<str> for Str<'_> {
+    fn eq(&self, other: &str) -> bool {
+        *self.0 == *other
+    }
+}
+
+impl PartialEq<&str> for Str<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        *self.0 == **other
+    }
+}
+
+impl<'a> From<&'a str> for Str<'a> {
+    fn from(value: &'a str) -> Self {
+        Self(Cow::Borrowed(value))
+    }
+}
+
+impl From<alloc::string::String> for Str<'_> {
+    fn from(value: alloc::string::String) -> Self {
+        Self(Cow::Owned(value))
+    }
+}
+
+/// A value deserialized from a buffer, borrowing its strings where possible.
+///
+/// Cross-serialization compatible with [`transient::Value`](super::transient::Value) and
+/// [`owned::Value`](super::owned::Value).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Value<'a> {
+    /// A string value (borrowed where possible)
+    String(#[serde(borrow)] Cow<'a, str>),
+
+    /// A boolean value
+    Bool(bool),
+
+    /// A 64-bit signed integer
+    I64(i64),
+
+    /// A 64-bit floating-point number
+    F64(f64),
+
+    /// Raw bytes, serialized as a lowercase hex string
+    Bytes(
+        #[serde(
+            serialize_with = "super::hex::serialize",
+            deserialize_with = "super::hex::deserialize"
+        )]
+        Vec<u8>,
+    ),
+
+    /// An array of values
+    Array(Vec<Value<'a>>),
+
+    /// A map of key-value pairs, in insertion order
+    Map(Vec<KeyValue<'a>>),
+}
+
+impl core::fmt::Display for Value<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            // For strings, debug print so they will get delimiters, see `owned::Value`.
+            Self::String(value) => write!(f, "{value:?}"),
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::I64(value) => write!(f, "{value}"),
+            Self::F64(value) => write!(f, "{value}"),
+            Self::Bytes(value) => write!(f, "0x{}", super::hex::Hex(value)),
+            Self::Array(values) => {
+                f.write_str("[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                f.write_str("]")
+            }
+            Self::Map(entries) => {
+                f.write_str("{")?;
+                for (i, entry) in entries.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{entry}")?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+impl PartialEq for KeyValue<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key && self.value == other.value
+    }
+}
@@ -0,0 +1,51 @@
+//! Hex encoding of byte values, used to serialize [`transient::Value::Bytes`](super::transient::Value::Bytes) and
+//! [`owned::Value::Bytes`](super::owned::Value::Bytes) as strings.
+
+use core::fmt;
+
+/// Displays bytes as lowercase hex digits without separators.
+pub(crate) struct Hex<'a>(pub(crate) &'a [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: AsRef<[u8]> + ?Sized,
+    S: serde::Serializer,
+{
+    serializer.collect_str(&Hex(bytes.as_ref()))
+}
+
+#[cfg(feature = "alloc")]
+pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<alloc::vec::Vec<u8>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::Deserialize;
+    use serde::de::Error;
+
+    let string = <alloc::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+    if string.len() % 2 != 0 {
+        return Err(D::Error::custom("hex string has an odd number of digits"));
+    }
+
+    string
+        .as_bytes()
+        .chunks_exact(2)
+        .map(|pair| {
+            let digit = |digit: u8| {
+                char::from(digit)
+                    .to_digit(16)
+                    .ok_or_else(|| D::Error::custom("invalid hex digit"))
+            };
+            Ok((digit(pair[0])? << 4 | digit(pair[1])?) as u8)
+        })
+        .collect()
+}
@@ -7,7 +7,8 @@
 //! with specific data storage families. The [`transient`] data is created with references and
 //! thread-local data to avoid copying data when recording log events, this is what gets passed in
 //! to a [`crate::collector::Export`] implementor. The [`owned`] data uses heap allocation to allow
-//! passing the data around in-memory or across processes as needed.
+//! passing the data around in-memory or across processes as needed. The [`borrowed`] data is
+//! deserialized without copying strings out of the input, to process received data synchronously.
 //!
 //! # Serialization
 //!
@@ -15,9 +16,16 @@
 //! implement [`serde::Deserialize`]; the types from both modules are compatible, so you can
 //! directly serialize a [`transient::LogMessage`] without any allocations then deserialize that as
 //! an [`owned::LogMessage`].
+//!
+//! The [`borrowed`] types also implement [`serde::Deserialize`], borrowing strings from the input
+//! where possible, and convert into the [`owned`] types with [`From`] where a message has to
+//! outlive the input.
 
 pub mod base;
 #[cfg(feature = "alloc")]
+pub mod borrowed;
+mod hex;
+#[cfg(feature = "alloc")]
 pub mod owned;
 pub mod transient;
 
@@ -10,7 +10,7 @@ use alloc::string::ToString;
 use alloc::vec::Vec;
 use serde::{Deserialize, Serialize};
 
-use crate::protocol::{base, transient};
+use crate::protocol::{base, borrowed, transient};
 
 /// Owned storage family using owned data.
 ///
@@ -88,7 +88,7 @@ pub type SpanAddEventMessage = base::SpanAddEventMessage<'static, Owned>;
 /// assert_eq!(string, "count: 42");
 /// # Ok::<(), serde_json::Error>(())
 /// ```
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[cfg(feature = "alloc")]
 pub enum Value {
     /// A string value (owned)
@@ -102,6 +102,21 @@ pub enum Value {
 
     /// A 64-bit floating-point number
     F64(f64),
+
+    /// Raw bytes, serialized as a lowercase hex string
+    Bytes(
+        #[serde(
+            serialize_with = "super::hex::serialize",
+            deserialize_with = "super::hex::deserialize"
+        )]
+        Vec<u8>,
+    ),
+
+    /// An array of values
+    Array(Vec<Value>),
+
+    /// A map of key-value pairs, in insertion order
+    Map(Vec<KeyValue>),
 }
 
 #[cfg(feature = "alloc")]
@@ -115,10 +130,73 @@ impl core::fmt::Display for Value {
             Self::Bool(value) => write!(f, "{value}"),
             Self::I64(value) => write!(f, "{value}"),
             Self::F64(value) => write!(f, "{value}"),
+            Self::Bytes(value) => write!(f, "0x{}", super::hex::Hex(value)),
+            Self::Array(values) => {
+                f.write_str("[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                f.write_str("]")
+            }
+            Self::Map(entries) => {
+                f.write_str("{")?;
+                for (i, entry) in entries.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{entry}")?;
+                }
+                f.write_str("}")
+            }
         }
     }
 }
 
+impl PartialEq for KeyValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key && self.value == other.value
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::String(value.to_string())
+    }
+}
+
+impl From<alloc::string::String> for Value {
+    fn from(value: alloc::string::String) -> Self {
+        Value::String(value)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Value::I64(value)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::F64(value)
+    }
+}
+
+impl From<Vec<u8>> for Value {
+    fn from(value: Vec<u8>) -> Self {
+        Value::Bytes(value)
+    }
+}
+
 // Conversions from transient types to owned types
 
 impl From<transient::InstanceMessage<'_>> for InstanceMessage {
@@ -174,6 +252,7 @@ impl From<transient::SpanCreateMessage<'_>> for SpanCreateMessage {
             name: value.name.to_string(),
             start_time_unix_nano: value.start_time_unix_nano,
             attributes: Vec::from_iter(value.attributes.as_ref().iter().map(|kv| kv.into())),
+            parent: value.parent,
         }
     }
 }
@@ -192,6 +271,7 @@ impl From<transient::SpanAddEventMessage<'_>> for SpanAddEventMessage {
         SpanAddEventMessage {
             span_id: value.span_id,
             name: value.name.to_string(),
+            severity: value.severity,
             time_unix_nano: value.time_unix_nano,
             attributes: Vec::from_iter(value.attributes.as_ref().iter().map(|kv| kv.into())),
         }
@@ -224,6 +304,13 @@ impl From<transient::Value<'_>> for Value {
             transient::Value::Bool(b) => Value::Bool(b),
             transient::Value::I64(i) => Value::I64(i),
             transient::Value::F64(f) => Value::F64(f),
+            transient::Value::Bytes(bytes) => Value::Bytes(bytes.to_vec()),
+            transient::Value::Array(values) => {
+                Value::Array(values.iter().map(Value::from).collect())
+            }
+            transient::Value::Map(entries) => {
+                Value::Map(entries.iter().map(KeyValue::from).collect())
+            }
         }
     }
 }
@@ -236,6 +323,211 @@ impl From<&transient::Value<'_>> for Value {
             transient::Value::Bool(b) => Value::Bool(*b),
             transient::Value::I64(i) => Value::I64(*i),
             transient::Value::F64(f) => Value::F64(*f),
+            transient::Value::Bytes(bytes) => Value::Bytes(bytes.to_vec()),
+            transient::Value::Array(values) => {
+                Value::Array(values.iter().map(Value::from).collect())
+            }
+            transient::Value::Map(entries) => {
+                Value::Map(entries.iter().map(KeyValue::from).collect())
+            }
+        }
+    }
+}
+
+// Conversions from borrowed types to owned types
+
+impl From<borrowed::InstanceMessage<'_>> for InstanceMessage {
+    fn from(value: borrowed::InstanceMessage<'_>) -> Self {
+        InstanceMessage {
+            thread_id: value.thread_id,
+            message: value.message.into(),
+        }
+    }
+}
+
+impl From<borrowed::TelemetryMessage<'_>> for TelemetryMessage {
+    fn from(value: borrowed::TelemetryMessage<'_>) -> Self {
+        match value {
+            borrowed::TelemetryMessage::Log(msg) => TelemetryMessage::Log(msg.into()),
+            borrowed::TelemetryMessage::Tracing(msg) => TelemetryMessage::Tracing(msg.into()),
+            borrowed::TelemetryMessage::TimeSync(msg) => TelemetryMessage::TimeSync(msg),
+        }
+    }
+}
+
+impl From<borrowed::LogMessage<'_>> for LogMessage {
+    fn from(value: borrowed::LogMessage<'_>) -> Self {
+        LogMessage {
+            time_unix_nano: value.time_unix_nano,
+            severity: value.severity,
+            body: value.body.0.into_owned(),
+            attributes: Vec::from_iter(value.attributes.into_iter().map(KeyValue::from)),
+        }
+    }
+}
+
+impl From<borrowed::TracingMessage<'_>> for TracingMessage {
+    fn from(value: borrowed::TracingMessage<'_>) -> Self {
+        match value {
+            borrowed::TracingMessage::CreateSpan(msg) => TracingMessage::CreateSpan(msg.into()),
+            borrowed::TracingMessage::EnterSpan(msg) => TracingMessage::EnterSpan(msg),
+            borrowed::TracingMessage::ExitSpan(msg) => TracingMessage::ExitSpan(msg),
+            borrowed::TracingMessage::CloseSpan(msg) => TracingMessage::CloseSpan(msg),
+            borrowed::TracingMessage::AddEvent(msg) => TracingMessage::AddEvent(msg.into()),
+            borrowed::TracingMessage::AddLink(msg) => TracingMessage::AddLink(msg),
+            borrowed::TracingMessage::SetAttribute(msg) => TracingMessage::SetAttribute(msg.into()),
+        }
+    }
+}
+
+impl From<borrowed::SpanCreateMessage<'_>> for SpanCreateMessage {
+    fn from(value: borrowed::SpanCreateMessage<'_>) -> Self {
+        SpanCreateMessage {
+            span_id: value.span_id,
+            name: value.name.0.into_owned(),
+            start_time_unix_nano: value.start_time_unix_nano,
+            attributes: Vec::from_iter(value.attributes.into_iter().map(KeyValue::from)),
+            parent: value.parent,
+        }
+    }
+}
+
+impl From<borrowed::SpanSetAttributeMessage<'_>> for SpanSetAttributeMessage {
+    fn from(value: borrowed::SpanSetAttributeMessage<'_>) -> Self {
+        SpanSetAttributeMessage {
+            span_id: value.span_id,
+            attribute: value.attribute.into(),
+        }
+    }
+}
+
+impl From<borrowed::SpanAddEventMessage<'_>> for SpanAddEventMessage {
+    fn from(value: borrowed::SpanAddEventMessage<'_>) -> Self {
+        SpanAddEventMessage {
+            span_id: value.span_id,
+            name: value.name.0.into_owned(),
+            severity: value.severity,
+            time_unix_nano: value.time_unix_nano,
+            attributes: Vec::from_iter(value.attributes.into_iter().map(KeyValue::from)),
+        }
+    }
+}
+
+impl From<borrowed::KeyValue<'_>> for KeyValue {
+    fn from(value: borrowed::KeyValue<'_>) -> Self {
+        KeyValue {
+            key: value.key.0.into_owned(),
+            value: value.value.into(),
+        }
+    }
+}
+
+impl From<borrowed::Value<'_>> for Value {
+    fn from(value: borrowed::Value<'_>) -> Self {
+        match value {
+            borrowed::Value::String(s) => Value::String(s.into_owned()),
+            borrowed::Value::Bool(b) => Value::Bool(b),
+            borrowed::Value::I64(i) => Value::I64(i),
+            borrowed::Value::F64(f) => Value::F64(f),
+            borrowed::Value::Bytes(bytes) => Value::Bytes(bytes),
+            borrowed::Value::Array(values) => {
+                Value::Array(values.into_iter().map(Value::from).collect())
+            }
+            borrowed::Value::Map(entries) => {
+                Value::Map(entries.into_iter().map(KeyValue::from).collect())
+            }
+        }
+    }
+}
+
+// Conversions from owned types to transient types
+
+/// Calls `f` with `attributes` borrowed as transient key-values.
+///
+/// Nested arrays and maps have to outlive the transient values borrowing them, so each nested call collects the next
+/// nesting level and the levels are converted innermost first.
+/// The recursion is only as deep as the attributes are nested, not as long as they are.
+#[cfg(feature = "enable")]
+pub(crate) fn with_transient_key_values<R>(
+    attributes: &[KeyValue],
+    f: &mut dyn FnMut(&[transient::KeyValue<'_>]) -> R,
+) -> R {
+    let attributes: Vec<&KeyValue> = attributes.iter().collect();
+    with_transient_level(&[], &attributes, &mut |_, attributes| f(attributes))
+}
+
+/// Calls `f` with the array elements `values` and map entries `entries` of one nesting level converted to transient
+/// values, in the same order.
+#[cfg(feature = "enable")]
+fn with_transient_level<R>(
+    values: &[&Value],
+    entries: &[&KeyValue],
+    f: &mut dyn FnMut(&[transient::Value<'_>], &[transient::KeyValue<'_>]) -> R,
+) -> R {
+    if values.is_empty() && entries.is_empty() {
+        return f(&[], &[]);
+    }
+
+    // The contents of all arrays and maps on this level form the next level, in order.
+    let mut nested_values = Vec::new();
+    let mut nested_entries = Vec::new();
+    for value in values
+        .iter()
+        .copied()
+        .chain(entries.iter().map(|entry| &entry.value))
+    {
+        match value {
+            Value::Array(values) => nested_values.extend(values),
+            Value::Map(entries) => nested_entries.extend(entries),
+            _ => {}
+        }
+    }
+
+    with_transient_level(
+        &nested_values,
+        &nested_entries,
+        &mut |nested_values, nested_entries| {
+            // Shortens the lifetimes of both levels to the same one, for handing out their contents in order.
+            let (mut nested_values, mut nested_entries) = (nested_values, nested_entries);
+            let values: Vec<transient::Value<'_>> = values
+                .iter()
+                .map(|value| transient_value(value, &mut nested_values, &mut nested_entries))
+                .collect();
+            let entries: Vec<transient::KeyValue<'_>> = entries
+                .iter()
+                .map(|entry| transient::KeyValue {
+                    key: &entry.key,
+                    value: transient_value(&entry.value, &mut nested_values, &mut nested_entries),
+                })
+                .collect();
+            f(&values, &entries)
+        },
+    )
+}
+
+/// Converts `value`, taking the contents of an array or map from the front of the converted `nested_values` or
+/// `nested_entries`.
+#[cfg(feature = "enable")]
+fn transient_value<'a>(
+    value: &'a Value,
+    nested_values: &mut &'a [transient::Value<'a>],
+    nested_entries: &mut &'a [transient::KeyValue<'a>],
+) -> transient::Value<'a> {
+    match value {
+        Value::String(s) => transient::Value::String(s),
+        Value::Bool(b) => transient::Value::Bool(*b),
+        Value::I64(i) => transient::Value::I64(*i),
+        Value::F64(v) => transient::Value::F64(*v),
+        Value::Bytes(bytes) => transient::Value::Bytes(bytes),
+        Value::Array(values) => {
+            let (array, rest) = nested_values.split_at(values.len());
+            *nested_values = rest;
+            transient::Value::Array(array)
+        }
+        Value::Map(entries) => {
+            let (map, rest) = nested_entries.split_at(entries.len());
+            *nested_entries = rest;
+            transient::Value::Map(map)
         }
     }
 }
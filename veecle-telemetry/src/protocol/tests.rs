@@ -2,8 +2,8 @@
 
 use core::num::NonZeroU64;
 
-use crate::protocol::base::{ProcessId, SpanId, ThreadId};
-use crate::protocol::{owned, transient};
+use crate::protocol::base::{ProcessId, Severity, SpanId, ThreadId};
+use crate::protocol::{borrowed, owned, transient};
 
 #[cfg(feature = "alloc")]
 #[test]
@@ -24,6 +24,7 @@ fn transient_to_owned_conversion() {
     let span_event = transient::SpanAddEventMessage {
         span_id: Some(SpanId(0)),
         name: borrowed_name_str,
+        severity: Severity::Info,
         time_unix_nano: 0,
         attributes: &attributes[..],
     };
@@ -61,6 +62,7 @@ fn serde_roundtrip_owned_types() {
     let span_event = owned::SpanAddEventMessage {
         span_id: Some(SpanId(42)),
         name: String::from("test_event"),
+        severity: Severity::Info,
         time_unix_nano: 123456789,
         attributes: alloc::vec![attribute],
     };
@@ -112,6 +114,7 @@ fn serde_transient_serialize_owned_deserialize() {
     let span_event = transient::SpanAddEventMessage {
         span_id: Some(SpanId(42)),
         name: borrowed_name_str,
+        severity: Severity::Info,
         time_unix_nano: 123456789,
         attributes: &attributes[..],
     };
@@ -143,3 +146,130 @@ fn serde_transient_serialize_owned_deserialize() {
         panic!("Expected AddEvent message");
     }
 }
+
+#[cfg(feature = "alloc")]
+#[test]
+fn serde_transient_serialize_owned_deserialize_nested_values() {
+    let values = [transient::Value::I64(1), transient::Value::String("two")];
+    let entries = [transient::KeyValue::new(
+        "inner",
+        transient::Value::Bool(true),
+    )];
+    let attributes = [
+        transient::KeyValue::new("array", transient::Value::Array(&values)),
+        transient::KeyValue::new("map", transient::Value::Map(&entries)),
+    ];
+
+    let json = serde_json::to_string(&attributes[..]).expect("serialization failed");
+    let deserialized: alloc::vec::Vec<owned::KeyValue> =
+        serde_json::from_str(&json).expect("deserialization failed");
+
+    let converted: alloc::vec::Vec<owned::KeyValue> =
+        attributes.iter().map(owned::KeyValue::from).collect();
+
+    for attributes in [deserialized, converted] {
+        assert_eq!(
+            alloc::format!("{}, {}", attributes[0], attributes[1]),
+            r#"array: [1, "two"], map: {inner: true}"#
+        );
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn serde_transient_serialize_owned_deserialize_bytes() {
+    let payload = [0x00, 0x1f, 0xa0, 0xff];
+    let attribute = transient::KeyValue::new("payload", &payload);
+
+    let json = serde_json::to_string(&attribute).expect("serialization failed");
+    assert_eq!(json, r#"{"key":"payload","value":{"Bytes":"001fa0ff"}}"#);
+
+    let deserialized: owned::KeyValue =
+        serde_json::from_str(&json).expect("deserialization failed");
+    assert_eq!(deserialized, owned::KeyValue::from(&attribute));
+    assert_eq!(deserialized.value, owned::Value::Bytes(payload.to_vec()));
+    assert_eq!(alloc::format!("{deserialized}"), "payload: 0x001fa0ff");
+
+    let empty = transient::Value::Bytes(&[]);
+    assert_eq!(serde_json::to_string(&empty).unwrap(), r#"{"Bytes":""}"#);
+
+    for invalid in [r#"{"Bytes":"0"}"#, r#"{"Bytes":"0g"}"#, r#"{"Bytes":"+f"}"#] {
+        assert!(
+            serde_json::from_str::<owned::Value>(invalid).is_err(),
+            "{invalid}"
+        );
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn serde_borrowed_deserialize() {
+    let payload = [0x01, 0xff];
+    let values = [transient::Value::I64(1), transient::Value::String("two")];
+    let attributes = [
+        transient::KeyValue::new("vin", "WVWZZZ1JZXW000001"),
+        transient::KeyValue::new("note", "first\nsecond"),
+        transient::KeyValue::new("payload", &payload),
+        transient::KeyValue::new("array", transient::Value::Array(&values)),
+    ];
+    let instance_message = transient::InstanceMessage {
+        thread_id: ThreadId::from_raw(ProcessId::from_raw(999), NonZeroU64::new(111).unwrap()),
+        message: transient::TelemetryMessage::Tracing(transient::TracingMessage::AddEvent(
+            transient::SpanAddEventMessage {
+                span_id: Some(SpanId(42)),
+                name: "test_event",
+                severity: Severity::Warn,
+                time_unix_nano: 123456789,
+                attributes: &attributes[..],
+            },
+        )),
+    };
+
+    let json = serde_json::to_string(&instance_message).expect("serialization failed");
+    let deserialized: borrowed::InstanceMessage<'_> =
+        serde_json::from_str(&json).expect("deserialization failed");
+
+    let borrowed::TelemetryMessage::Tracing(borrowed::TracingMessage::AddEvent(event)) =
+        &deserialized.message
+    else {
+        panic!("Expected AddEvent message");
+    };
+    assert_eq!(event.name, "test_event");
+    assert!(event.name.is_borrowed());
+    assert_eq!(event.severity, Severity::Warn);
+    assert_eq!(event.attributes[0].key, "vin");
+    let borrowed::Value::String(vin) = &event.attributes[0].value else {
+        panic!("Expected String value");
+    };
+    assert!(matches!(
+        vin,
+        alloc::borrow::Cow::Borrowed("WVWZZZ1JZXW000001")
+    ));
+    // Strings with escapes can't be borrowed.
+    assert_eq!(
+        event.attributes[1].value,
+        borrowed::Value::String("first\nsecond".into())
+    );
+    assert_eq!(
+        alloc::format!("{}, {}", event.attributes[2], event.attributes[3]),
+        r#"payload: 0x01ff, array: [1, "two"]"#
+    );
+
+    // Converting to owned matches deserializing as owned.
+    let owned: owned::InstanceMessage = deserialized.into();
+    let expected: owned::InstanceMessage =
+        serde_json::from_str(&json).expect("deserialization failed");
+    assert_eq!(
+        serde_json::to_string(&owned).unwrap(),
+        serde_json::to_string(&expected).unwrap()
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn event_severity_defaults_to_info() {
+    let json = r#"{"span_id":null,"name":"milestone","time_unix_nano":0,"attributes":[]}"#;
+    let event: owned::SpanAddEventMessage =
+        serde_json::from_str(json).expect("deserialization failed");
+    assert_eq!(event.severity, Severity::Info);
+}
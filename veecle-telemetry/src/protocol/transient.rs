@@ -71,6 +71,10 @@ pub type SpanAddEventMessage<'a> = base::SpanAddEventMessage<'a, Transient>;
 /// let number = Value::I64(42);
 /// let flag = Value::Bool(true);
 /// let rating = Value::F64(4.5);
+///
+/// // Values can be nested in arrays and maps.
+/// let values = [Value::I64(1), Value::I64(2)];
+/// let list = Value::Array(&values);
 /// ```
 #[derive(Clone, Debug, Serialize)]
 pub enum Value<'a> {
@@ -89,6 +93,15 @@ pub enum Value<'a> {
 
     /// A 64-bit floating-point number
     F64(f64),
+
+    /// Raw bytes, e.g. a frame payload, serialized as a lowercase hex string
+    Bytes(#[serde(serialize_with = "super::hex::serialize")] &'a [u8]),
+
+    /// An array of values
+    Array(&'a [Value<'a>]),
+
+    /// A map of key-value pairs, in insertion order
+    Map(&'a [KeyValue<'a>]),
 }
 
 impl<'a> core::fmt::Display for Value<'a> {
@@ -102,6 +115,27 @@ impl<'a> core::fmt::Display for Value<'a> {
             Self::Bool(value) => write!(f, "{value}"),
             Self::I64(value) => write!(f, "{value}"),
             Self::F64(value) => write!(f, "{value}"),
+            Self::Bytes(value) => write!(f, "0x{}", super::hex::Hex(value)),
+            Self::Array(values) => {
+                f.write_str("[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                f.write_str("]")
+            }
+            Self::Map(entries) => {
+                f.write_str("{")?;
+                for (i, entry) in entries.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{entry}")?;
+                }
+                f.write_str("}")
+            }
         }
     }
 }
@@ -125,6 +159,37 @@ impl<'a> From<&'a alloc::string::String> for Value<'a> {
     }
 }
 
+impl<'a> From<&'a [u8]> for Value<'a> {
+    fn from(value: &'a [u8]) -> Self {
+        Value::Bytes(value)
+    }
+}
+
+impl<'a> From<&'a &[u8]> for Value<'a> {
+    fn from(value: &'a &[u8]) -> Self {
+        Value::Bytes(value)
+    }
+}
+
+impl<'a, const N: usize> From<&'a [u8; N]> for Value<'a> {
+    fn from(value: &'a [u8; N]) -> Self {
+        Value::Bytes(value)
+    }
+}
+
+impl<'a, const N: usize> From<&'a &[u8; N]> for Value<'a> {
+    fn from(value: &'a &[u8; N]) -> Self {
+        Value::Bytes(*value)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a alloc::vec::Vec<u8>> for Value<'a> {
+    fn from(value: &'a alloc::vec::Vec<u8>) -> Self {
+        Value::Bytes(value)
+    }
+}
+
 impl<'a> From<core::fmt::Arguments<'a>> for Value<'a> {
     fn from(value: core::fmt::Arguments<'a>) -> Self {
         Value::Formatted(value)
@@ -172,3 +237,27 @@ impl<'a> From<&f64> for Value<'a> {
         Value::F64(*value)
     }
 }
+
+impl<'a> From<&'a [Value<'a>]> for Value<'a> {
+    fn from(value: &'a [Value<'a>]) -> Self {
+        Value::Array(value)
+    }
+}
+
+impl<'a, const N: usize> From<&'a [Value<'a>; N]> for Value<'a> {
+    fn from(value: &'a [Value<'a>; N]) -> Self {
+        Value::Array(value)
+    }
+}
+
+impl<'a> From<&'a [KeyValue<'a>]> for Value<'a> {
+    fn from(value: &'a [KeyValue<'a>]) -> Self {
+        Value::Map(value)
+    }
+}
+
+impl<'a, const N: usize> From<&'a [KeyValue<'a>; N]> for Value<'a> {
+    fn from(value: &'a [KeyValue<'a>; N]) -> Self {
+        Value::Map(value)
+    }
+}
@@ -31,7 +31,7 @@
 //! 2. **Entry**: Spans are entered to make them the current active span
 //! 3. **Events**: Events and attributes can be added to active spans
 //! 4. **Exit**: Spans are exited when no longer active
-//! 5. **Close**: Spans are closed when their work is complete
+//! 5. **Close**: Spans are closed when their work is complete, reporting the elapsed duration since creation
 //!
 //! # Nesting
 //!
@@ -48,13 +48,16 @@
 //! let _child_guard = child.entered();
 //! ```
 
+use core::future::IntoFuture;
 use core::marker::PhantomData;
 
 use crate::SpanContext;
 #[cfg(feature = "enable")]
-use crate::collector::get_collector;
+use crate::collector::{get_collector, is_collector_set};
+use crate::future::{FutureExt, WithSpan};
 #[cfg(feature = "enable")]
 use crate::id::SpanId;
+use crate::protocol::base::Severity;
 use crate::protocol::transient::KeyValue;
 
 /// A distributed tracing span representing a unit of work.
@@ -89,6 +92,10 @@ use crate::protocol::transient::KeyValue;
 pub struct Span {
     #[cfg(feature = "enable")]
     pub(crate) span_id: Option<SpanId>,
+
+    /// Monotonic start time, used to report the span duration when it is closed.
+    #[cfg(feature = "enable")]
+    pub(crate) start_time_unix_nano: u64,
 }
 
 /// Utilities for working with the currently active span.
@@ -122,6 +129,8 @@ impl Span {
         Self {
             #[cfg(feature = "enable")]
             span_id: None,
+            #[cfg(feature = "enable")]
+            start_time_unix_nano: 0,
         }
     }
 
@@ -151,7 +160,104 @@ impl Span {
 
         #[cfg(feature = "enable")]
         {
-            Self::new_inner(name, attributes)
+            Self::new_inner(name, attributes, None)
+        }
+    }
+
+    /// Creates a new span as a child of the current span, with owned attributes.
+    ///
+    /// This is useful for attributes computed at runtime, e.g. strings built with `format!`, which [`Span::new`]
+    /// would require to outlive the call.
+    /// [`Span::new`] borrows its attributes and doesn't allocate, while this allocates the attributes and temporary
+    /// lists borrowing them for every exported span, so prefer [`Span::new`] where the attributes can be borrowed.
+    /// Like [`Span::new`], the attributes are only needed while the span is created and can be dropped afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the span
+    /// * `attributes` - Owned key-value attributes to attach to the span
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use veecle_telemetry::Span;
+    /// use veecle_telemetry::protocol::owned::KeyValue;
+    ///
+    /// let request = 7;
+    /// let span = Span::new_owned("operation", &[KeyValue::new("request", format!("request-{request}"))]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn new_owned(name: &str, attributes: &[crate::protocol::owned::KeyValue]) -> Self {
+        #[cfg(not(feature = "enable"))]
+        {
+            let _ = (name, attributes);
+            Self::noop()
+        }
+
+        #[cfg(feature = "enable")]
+        {
+            Self::new_inner_with(name, None, |create| {
+                crate::protocol::owned::with_transient_key_values(attributes, create)
+            })
+        }
+    }
+
+    /// Creates a new span as a child of `parent`, ignoring the current span.
+    ///
+    /// This is used to continue a trace whose context was received out-of-band, e.g. from another process.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent` - The context of the parent span
+    /// * `name` - The name of the span
+    /// * `attributes` - Key-value attributes to attach to the span
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use veecle_telemetry::{ProcessId, Span, SpanContext, SpanId};
+    ///
+    /// let parent = SpanContext::new(ProcessId::from_raw(1), SpanId(2));
+    /// let span = Span::new_with_parent(parent, "operation", &[]);
+    /// ```
+    pub fn new_with_parent<'a>(
+        parent: SpanContext,
+        name: &'a str,
+        attributes: &'a [KeyValue<'a>],
+    ) -> Self {
+        #[cfg(not(feature = "enable"))]
+        {
+            let _ = (parent, name, attributes);
+            Self::noop()
+        }
+
+        #[cfg(feature = "enable")]
+        {
+            Self::new_inner(name, attributes, Some(parent))
+        }
+    }
+
+    /// Creates a new span as a child of `parent`, or of the current span if it is `None`, evaluating its attributes
+    /// only if they are exported.
+    ///
+    /// `attributes` is called with a function to pass the attributes to. It is not called if the `enable` feature is
+    /// not set, no global collector is set or the span is not sampled.
+    /// This is what [`span!`](crate::span!) expands to.
+    #[doc(hidden)]
+    pub fn new_lazy(
+        parent: Option<SpanContext>,
+        name: &str,
+        attributes: impl FnOnce(&mut dyn FnMut(&[KeyValue<'_>])),
+    ) -> Self {
+        #[cfg(not(feature = "enable"))]
+        {
+            let _ = (parent, name, attributes);
+            Self::noop()
+        }
+
+        #[cfg(feature = "enable")]
+        {
+            Self::new_inner_with(name, parent, attributes)
         }
     }
 
@@ -235,6 +341,54 @@ impl Span {
         }
     }
 
+    /// Runs a closure with this span entered, returning the closure's result.
+    ///
+    /// The span is entered for exactly the duration of the closure and closed afterwards.
+    /// Unlike holding a guard from [`Span::entered`], there is no guard variable that could be dropped early.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use veecle_telemetry::Span;
+    ///
+    /// let value = Span::new("operation", &[]).in_scope(|| {
+    ///     // span is active here
+    ///     42
+    /// });
+    /// assert_eq!(value, 42);
+    /// ```
+    pub fn in_scope<R>(self, f: impl FnOnce() -> R) -> R {
+        let _guard = self.entered();
+        f()
+    }
+
+    /// Instruments a future with this span, see [`FutureExt::with_span`].
+    ///
+    /// The span is entered every time the returned future is polled and closed once it is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use veecle_telemetry::Span;
+    ///
+    /// async fn example() -> u32 {
+    ///     Span::new("operation", &[])
+    ///         .in_scope_async(async {
+    ///             // span is active whenever this future is polled
+    ///             42
+    ///         })
+    ///         .await
+    /// }
+    /// ```
+    ///
+    /// [`FutureExt::with_span`]: crate::future::FutureExt::with_span
+    pub fn in_scope_async<F>(self, future: F) -> WithSpan<F::IntoFuture>
+    where
+        F: IntoFuture,
+    {
+        future.into_future().with_span(self)
+    }
+
     /// Adds an event to this span.
     ///
     /// Events represent point-in-time occurrences within a span's lifetime.
@@ -256,15 +410,45 @@ impl Span {
     /// span.add_event("query_completed", &[KeyValue::new("rows_returned", Value::I64(42))]);
     /// ```
     pub fn add_event<'a>(&self, name: &'a str, attributes: &'a [KeyValue<'a>]) {
+        self.add_event_with_severity(Severity::Info, name, attributes);
+    }
+
+    /// Adds an event with the given severity to this span.
+    ///
+    /// [`Span::add_event`] records events with [`Severity::Info`], this allows marking e.g. a recoverable error
+    /// within the span without emitting a separate log message.
+    ///
+    /// # Arguments
+    ///
+    /// * `severity` - The severity of the event
+    /// * `name` - The name of the event
+    /// * `attributes` - Key-value attributes providing additional context
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use veecle_telemetry::Span;
+    /// use veecle_telemetry::protocol::base::Severity;
+    /// use veecle_telemetry::protocol::transient::KeyValue;
+    ///
+    /// let span = Span::new("database_query", &[]);
+    /// span.add_event_with_severity(Severity::Warn, "query_retried", &[KeyValue::new("attempt", 2)]);
+    /// ```
+    pub fn add_event_with_severity<'a>(
+        &self,
+        severity: Severity,
+        name: &'a str,
+        attributes: &'a [KeyValue<'a>],
+    ) {
         #[cfg(not(feature = "enable"))]
         {
-            let _ = (name, attributes);
+            let _ = (severity, name, attributes);
         }
 
         #[cfg(feature = "enable")]
         {
             if let Some(span_id) = self.span_id {
-                get_collector().span_event(Some(span_id), name, attributes);
+                get_collector().span_event(Some(span_id), severity, name, attributes);
             }
         }
     }
@@ -273,6 +457,8 @@ impl Span {
     ///
     /// Links connect spans across different traces, allowing you to represent
     /// relationships between spans that are not parent-child relationships.
+    /// A link records that this span follows from the linked span, e.g. processing a queued job that was
+    /// enqueued by the linked span, which may already have ended when this span started.
     ///
     /// # Examples
     ///
@@ -352,14 +538,41 @@ impl CurrentSpan {
     /// CurrentSpan::add_event("milestone", &[KeyValue::new("progress", 75)]);
     /// ```
     pub fn add_event<'a>(name: &'a str, attributes: &'a [KeyValue<'a>]) {
+        Self::add_event_with_severity(Severity::Info, name, attributes);
+    }
+
+    /// Adds an event with the given severity to the current span.
+    ///
+    /// See [`Span::add_event_with_severity`].
+    ///
+    /// # Arguments
+    ///
+    /// * `severity` - The severity of the event
+    /// * `name` - The name of the event
+    /// * `attributes` - Key-value attributes providing additional context
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use veecle_telemetry::protocol::base::Severity;
+    /// use veecle_telemetry::{CurrentSpan, span};
+    ///
+    /// let _guard = span!("operation").entered();
+    /// CurrentSpan::add_event_with_severity(Severity::Error, "sensor_timeout", &[]);
+    /// ```
+    pub fn add_event_with_severity<'a>(
+        severity: Severity,
+        name: &'a str,
+        attributes: &'a [KeyValue<'a>],
+    ) {
         #[cfg(not(feature = "enable"))]
         {
-            let _ = (name, attributes);
+            let _ = (severity, name, attributes);
         }
 
         #[cfg(feature = "enable")]
         {
-            get_collector().span_event(None, name, attributes);
+            get_collector().span_event(None, severity, name, attributes);
         }
     }
 
@@ -367,6 +580,8 @@ impl CurrentSpan {
     ///
     /// Links connect spans across different traces, allowing you to represent
     /// relationships between spans that are not parent-child relationships.
+    /// A link records that the current span follows from the linked span, e.g. processing a queued job that was
+    /// enqueued by the linked span, which may already have ended when the current span started.
     ///
     /// # Examples
     ///
@@ -424,13 +639,40 @@ impl CurrentSpan {
 
 #[cfg(feature = "enable")]
 impl Span {
-    fn new_inner<'a>(name: &'a str, attributes: &'a [KeyValue<'a>]) -> Self {
+    fn new_inner<'a>(
+        name: &'a str,
+        attributes: &'a [KeyValue<'a>],
+        parent: Option<SpanContext>,
+    ) -> Self {
+        Self::new_inner_with(name, parent, |create| create(attributes))
+    }
+
+    fn new_inner_with(
+        name: &str,
+        parent: Option<SpanContext>,
+        attributes: impl FnOnce(&mut dyn FnMut(&[KeyValue<'_>])),
+    ) -> Self {
         let span_id = SpanId::next_id();
 
-        get_collector().new_span(span_id, name, attributes);
+        let collector = get_collector();
+        if !collector.should_sample(span_id, name) {
+            return Self::noop();
+        }
+
+        let mut start_time_unix_nano = 0;
+        let mut create = |attributes: &[KeyValue<'_>]| {
+            start_time_unix_nano = collector.new_span(span_id, name, attributes, parent);
+        };
+        // Without a global collector nothing is exported, so the span is created without evaluating its attributes.
+        if is_collector_set() {
+            attributes(&mut create);
+        } else {
+            create(&[]);
+        }
 
         Self {
             span_id: Some(span_id),
+            start_time_unix_nano,
         }
     }
 
@@ -453,7 +695,7 @@ impl Drop for Span {
     fn drop(&mut self) {
         #[cfg(feature = "enable")]
         if let Some(span_id) = self.span_id.take() {
-            get_collector().close_span(span_id);
+            get_collector().close_span(span_id, self.start_time_unix_nano);
         }
     }
 }
@@ -5,7 +5,7 @@ use alloc::vec::Vec;
 use core::fmt::Write;
 
 use crate::protocol::owned::{
-    InstanceMessage, KeyValue, LogMessage, SpanAddEventMessage, SpanAddLinkMessage,
+    InstanceMessage, KeyValue, LogMessage, Severity, SpanAddEventMessage, SpanAddLinkMessage,
     SpanCreateMessage, SpanId, SpanSetAttributeMessage, TelemetryMessage, ThreadId, TracingMessage,
 };
 
@@ -46,7 +46,10 @@ pub fn format_telemetry_tree(messages: Vec<InstanceMessage>) -> String {
     for message in messages {
         match message.message {
             TelemetryMessage::Tracing(TracingMessage::CreateSpan(span_create)) => {
-                let parent = telemetry_data.current_span_for(message.thread_id);
+                let parent = span_create
+                    .parent
+                    .map(|parent| parent.span_id)
+                    .or_else(|| telemetry_data.current_span_for(message.thread_id));
                 telemetry_data.spans.push(CreateAndParent {
                     parent,
                     span_create,
@@ -165,6 +168,10 @@ fn build_tree_string(
                     result.push_str("    ");
                 }
                 result.push_str("+ event: ");
+                // Only events with a non-default severity show it, to keep the common case short.
+                if event.severity != Severity::Info {
+                    result.push_str(&format!("[{:?}] ", event.severity));
+                }
                 result.push_str(event.name.as_ref());
                 result.push_str(" [");
                 format_attributes(&event.attributes, result);
@@ -0,0 +1,62 @@
+#![expect(missing_docs, reason = "tests")]
+#![cfg(not(miri))]
+
+// Checks the behavior before and after installing the global collector, so this lives in its own test binary.
+
+use std::cell::Cell;
+
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+use veecle_osal_std::{thread::Thread, time::Time};
+use veecle_telemetry::collector::{RatioSampler, SpanNameAllowlist, TestExporter};
+use veecle_telemetry::test_helpers::format_telemetry_tree;
+use veecle_telemetry::{ProcessId, info, span};
+
+static NONE: RatioSampler = RatioSampler::new(0.0);
+static SAMPLER: SpanNameAllowlist = SpanNameAllowlist::new(&["request"], &NONE);
+
+#[test]
+fn attributes_are_only_evaluated_when_exported() {
+    let evaluated = Cell::new(Vec::new());
+    let evaluate = |name: &'static str| {
+        let mut names = evaluated.take();
+        names.push(name);
+        evaluated.set(names);
+        name
+    };
+
+    // Without a global collector nothing is exported.
+    {
+        let _request = span!("request", id = evaluate("disabled span")).entered();
+        info!("message", id = evaluate("disabled log"));
+    }
+    assert!(evaluated.take().is_empty());
+
+    let (exporter, messages) = TestExporter::new();
+    veecle_telemetry::collector::build()
+        .process_id(ProcessId::from_raw(1))
+        .leaked_exporter(exporter)
+        .sampler(&SAMPLER)
+        .time::<Time>()
+        .thread::<Thread>()
+        .set_global()
+        .unwrap();
+
+    {
+        let _request = span!("request", id = evaluate("sampled span")).entered();
+        let _poll = span!("poll", id = evaluate("unsampled span")).entered();
+        // Logs are not affected by sampling.
+        info!("message", id = evaluate("log"));
+    }
+    assert_eq!(evaluated.take(), ["sampled span", "log"]);
+
+    let graph = format_telemetry_tree(messages.lock().unwrap().clone());
+    assert_eq!(
+        graph,
+        indoc! {r#"
+            request [id: "sampled span"]
+                + log: [Info] message [id: "log"]
+        "#}
+    );
+}
@@ -13,48 +13,23 @@ use pretty_assertions::assert_eq;
 use serial_test::serial;
 use tokio::runtime::Builder;
 
+use veecle_osal_std::{thread::Thread, time::Time};
+use veecle_telemetry::collector::TestExporter;
 use veecle_telemetry::future::FutureExt;
-use veecle_telemetry::protocol::transient::{KeyValue, Severity};
+use veecle_telemetry::protocol::owned::TelemetryMessage;
+use veecle_telemetry::protocol::transient::{KeyValue, ProcessId, Severity};
 use veecle_telemetry::test_helpers::format_telemetry_tree;
 use veecle_telemetry::{CurrentSpan, Span, SpanContext, instrument, span};
 
 mod exporter {
-    use std::sync::{Arc, LazyLock, Mutex};
-
+    use veecle_osal_std::{thread::Thread, time::Time};
     use veecle_telemetry::collector::TestExporter;
-    use veecle_telemetry::protocol::owned::InstanceMessage;
-
-    /// Initializes the lazy lock which sets the exporter.
-    pub fn set_exporter() -> ExporterHandle {
-        static EXPORTER: LazyLock<Arc<Mutex<Vec<InstanceMessage>>>> = LazyLock::new(|| {
-            use veecle_osal_std::{thread::Thread, time::Time};
-
-            let (reporter, collected_spans) = TestExporter::new();
-
-            veecle_telemetry::collector::build()
-                .random_process_id()
-                .leaked_exporter(reporter)
-                .time::<Time>()
-                .thread::<Thread>()
-                .set_global()
-                .expect("exporter was not set yet");
-
-            collected_spans
-        });
-
-        ExporterHandle {
-            message_buffer: EXPORTER.clone(),
-        }
-    }
 
-    pub struct ExporterHandle {
-        message_buffer: Arc<Mutex<Vec<InstanceMessage>>>,
-    }
-
-    impl ExporterHandle {
-        pub fn take_messages(&self) -> Vec<InstanceMessage> {
-            self.message_buffer.lock().unwrap().drain(..).collect()
-        }
+    /// Installs the global test exporter and clears previously captured messages.
+    pub fn set_exporter() -> &'static TestExporter {
+        let exporter = TestExporter::set_global::<Time, Thread>();
+        exporter.clear();
+        exporter
     }
 }
 
@@ -204,6 +179,438 @@ fn trace_macro_example() {
     );
 }
 
+#[test]
+#[serial]
+fn trace_macro_level() {
+    #[instrument(short_name = true, level = "trace")]
+    fn trace_level() {}
+
+    #[instrument(name = "fatal_level", level = "fatal")]
+    async fn fatal_level() {}
+
+    assert!(veecle_telemetry::level_enabled(Severity::Trace));
+
+    let exporter = set_exporter();
+
+    {
+        let _root_guard = Span::new("root", &[]).entered();
+
+        let runtime = Builder::new_current_thread().enable_all().build().unwrap();
+
+        trace_level();
+        runtime.block_on(fatal_level());
+    }
+
+    let graph = format_telemetry_tree(exporter.take_messages());
+    assert_eq!(
+        graph,
+        indoc! {"
+            root []
+                trace_level []
+                fatal_level []
+        "}
+    );
+}
+
+#[test]
+#[serial]
+fn trace_macro_trait_futures() {
+    use std::future::Future;
+
+    trait Service {
+        #[instrument(short_name = true)]
+        async fn provided(&self) {
+            veecle_telemetry::info!("provided body");
+            tokio::task::yield_now().await;
+        }
+
+        async fn required(&self, value: i64);
+
+        fn desugared(&self) -> impl Future<Output = u64> + Send;
+    }
+
+    struct Impl;
+
+    impl Service for Impl {
+        #[instrument(short_name = true)]
+        async fn required(&self, value: i64) {
+            tokio::task::yield_now().await;
+            veecle_telemetry::info!("required body", value);
+        }
+
+        #[instrument(short_name = true)]
+        fn desugared(&self) -> impl Future<Output = u64> + Send {
+            veecle_telemetry::info!("desugared setup");
+            async move {
+                tokio::task::yield_now().await;
+                veecle_telemetry::info!("desugared body");
+                7
+            }
+        }
+    }
+
+    let exporter = set_exporter();
+
+    {
+        let root = Span::new("root", &[]);
+        let runtime = Builder::new_current_thread().enable_all().build().unwrap();
+
+        let value = runtime.block_on(
+            async {
+                Impl.provided().await;
+                Impl.required(3).await;
+                // The future is created outside of any poll, its body still runs within the span.
+                let future = Impl.desugared();
+                tokio::task::yield_now().await;
+                future.await
+            }
+            .with_span(root),
+        );
+        assert_eq!(value, 7);
+    }
+
+    let graph = format_telemetry_tree(exporter.take_messages());
+    assert_eq!(
+        graph,
+        indoc! {"
+            root []
+                provided []
+                    + log: [Info] provided body []
+                required []
+                    + log: [Info] required body [value: 3]
+                desugared []
+                    + log: [Info] desugared setup []
+                    + log: [Info] desugared body []
+        "}
+    );
+}
+
+#[test]
+#[serial]
+fn spanned_blocks() {
+    use veecle_telemetry::spanned;
+
+    async fn phases(id: i64) -> usize {
+        let request = spanned!("parse", { id }, async {
+            tokio::task::yield_now().await;
+            veecle_telemetry::info!("parsing");
+            "request"
+        })
+        .await;
+
+        let length = spanned!("process", async move { request.len() }).await;
+
+        spanned!("encode", { "length" = length as i64 }, {
+            veecle_telemetry::info!("encoding");
+            length * 2
+        }) + spanned!("check", 1)
+    }
+
+    let exporter = set_exporter();
+
+    {
+        let runtime = Builder::new_current_thread().enable_all().build().unwrap();
+        let value = runtime.block_on(phases(5).with_span(Span::new("root", &[])));
+        assert_eq!(value, 15);
+    }
+
+    let graph = format_telemetry_tree(exporter.take_messages());
+    assert_eq!(
+        graph,
+        indoc! {"
+            root []
+                parse [id: 5]
+                    + log: [Info] parsing []
+                process []
+                encode [length: 7]
+                    + log: [Info] encoding []
+                check []
+        "}
+    );
+}
+
+#[test]
+#[serial]
+fn array_properties() {
+    #[instrument(short_name = true, properties = { "signals": ["speed", "rpm"], "nested": [[1, 2], [3]] })]
+    fn decode() {}
+
+    let exporter = set_exporter();
+
+    {
+        let counts = [1, 2, 3];
+        let _root_guard = span!("root", "counts" = [counts[0], counts[1], counts[2]]).entered();
+
+        decode();
+    }
+
+    let graph = format_telemetry_tree(exporter.take_messages());
+    assert_eq!(
+        graph,
+        indoc! {r#"
+            root [counts: [1, 2, 3]]
+                decode [signals: ["speed", "rpm"], nested: [[1, 2], [3]]]
+        "#}
+    );
+}
+
+#[test]
+#[serial]
+fn explicit_parent() {
+    let exporter = set_exporter();
+
+    {
+        let remote = Span::new("remote", &[]);
+        let remote_context = remote.context().unwrap();
+
+        let _local_guard = Span::new("local", &[]).entered();
+        let _child_guard = span!(parent: remote_context, "child", "key" = 1).entered();
+        let _grandchild_guard = span!("grandchild").entered();
+    }
+
+    let graph = format_telemetry_tree(exporter.take_messages());
+    assert_eq!(
+        graph,
+        indoc! {"
+            remote []
+                child [key: 1]
+                    grandchild []
+            local []
+        "}
+    );
+}
+
+#[test]
+#[serial]
+fn owned_attributes() {
+    use veecle_telemetry::protocol::owned::{KeyValue, Value};
+
+    let exporter = set_exporter();
+
+    {
+        let id = 7;
+        let attributes = vec![
+            KeyValue::new("request", format!("request-{id}")),
+            KeyValue::new(
+                "ids",
+                Value::Array(vec![Value::I64(id), Value::I64(id + 1)]),
+            ),
+            KeyValue::new(
+                "peer",
+                Value::Map(vec![
+                    KeyValue::new("port", Value::I64(8080)),
+                    KeyValue::new("up", true),
+                ]),
+            ),
+        ];
+        let span = Span::new_owned("handle", &attributes);
+        drop(attributes);
+
+        let _guard = span.entered();
+    }
+
+    let graph = format_telemetry_tree(exporter.take_messages());
+    assert_eq!(
+        graph,
+        indoc! {r#"
+            handle [request: "request-7", ids: [7, 8], peer: {port: 8080, up: true}]
+        "#}
+    );
+}
+
+#[test]
+#[serial]
+fn owned_attributes_nested_and_many() {
+    use veecle_telemetry::protocol::owned::{KeyValue, Value};
+
+    let exporter = set_exporter();
+
+    {
+        let attributes = vec![
+            KeyValue::new(
+                "peers",
+                Value::Array(vec![
+                    Value::Map(vec![KeyValue::new("port", Value::I64(1))]),
+                    Value::Array(vec![Value::I64(2), Value::Array(vec![Value::I64(3)])]),
+                    Value::Map(vec![KeyValue::new(
+                        "ports",
+                        Value::Array(vec![Value::I64(4)]),
+                    )]),
+                ]),
+            ),
+            KeyValue::new("up", true),
+        ];
+        let _guard = Span::new_owned("nested", &attributes).entered();
+    }
+
+    let graph = format_telemetry_tree(exporter.take_messages());
+    assert_eq!(
+        graph,
+        indoc! {r#"
+            nested [peers: [{port: 1}, [2, [3]], {ports: [4]}], up: true]
+        "#}
+    );
+
+    // The attributes are converted without recursing per attribute.
+    let attributes: Vec<KeyValue> = (0..100_000)
+        .map(|i| KeyValue::new("i", Value::I64(i)))
+        .collect();
+    drop(Span::new_owned("many", &attributes));
+
+    let spans = exporter.spans_named("many");
+    assert_eq!(spans.len(), 1);
+    assert_eq!(spans[0].attributes.len(), attributes.len());
+}
+
+#[test]
+#[serial]
+fn test_exporter_queries() {
+    let exporter = set_exporter();
+
+    {
+        let _root_guard = span!("root", user_id = 123).entered();
+        let _child_guard = span!("child").entered();
+        veecle_telemetry::event!("cache_miss", key = "user:123");
+        veecle_telemetry::event!("cache_miss", key = "user:456");
+    }
+
+    assert_eq!(exporter.spans_named("root").len(), 1);
+    assert_eq!(exporter.spans_named("missing").len(), 0);
+    assert_eq!(exporter.events_with_attr("key", "user:123").len(), 1);
+    assert_eq!(exporter.events_with_attr("key", 123).len(), 0);
+
+    exporter.assert_span_tree(
+        r#"
+        root [user_id: 123]
+            child []
+                + event: cache_miss [key: "user:123"]
+                + event: cache_miss [key: "user:456"]
+        "#,
+    );
+}
+
+#[test]
+#[serial]
+fn event_severity() {
+    let exporter = set_exporter();
+
+    {
+        let span = span!("root");
+        span.add_event("started", &[]);
+        span.add_event_with_severity(Severity::Error, "failed", &[KeyValue::new("attempt", 1)]);
+
+        let _guard = span.entered();
+        veecle_telemetry::event!(severity: Severity::Warn, "retry", "attempt" = 2);
+        veecle_telemetry::event!("recovered");
+    }
+
+    exporter.assert_span_tree(
+        r#"
+        root []
+            + event: started []
+            + event: [Error] failed [attempt: 1]
+            + event: [Warn] retry [attempt: 2]
+            + event: recovered []
+        "#,
+    );
+}
+
+#[test]
+#[serial]
+fn deterministic_ids() {
+    use veecle_telemetry::SpanId;
+    use veecle_telemetry::id::deterministic_span_ids;
+    use veecle_telemetry::protocol::owned::TracingMessage;
+
+    let exporter = set_exporter();
+
+    let run = || {
+        let _ids = deterministic_span_ids(1);
+
+        let _root_guard = span!("root").entered();
+        let _child_guard = span!("child").entered();
+
+        exporter
+            .take_messages()
+            .into_iter()
+            .filter_map(|message| {
+                assert_eq!(message.thread_id.process, TestExporter::PROCESS_ID);
+                match message.message {
+                    TelemetryMessage::Tracing(TracingMessage::CreateSpan(span)) => {
+                        Some((span.name, span.span_id))
+                    }
+                    _ => None,
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let expected = vec![
+        ("root".to_string(), SpanId(1)),
+        ("child".to_string(), SpanId(2)),
+    ];
+    assert_eq!(run(), expected);
+    assert_eq!(run(), expected);
+}
+
+#[test]
+#[serial]
+fn span_in_scope() {
+    let exporter = set_exporter();
+
+    {
+        let value = span!("sync").in_scope(|| {
+            veecle_telemetry::event!("inside");
+            42
+        });
+        assert_eq!(value, 42);
+
+        let runtime = Builder::new_current_thread().enable_all().build().unwrap();
+        let value = runtime.block_on(span!("async").in_scope_async(async {
+            veecle_telemetry::event!("inside");
+            tokio::task::yield_now().await;
+            7
+        }));
+        assert_eq!(value, 7);
+    }
+
+    exporter.assert_span_tree(
+        r#"
+        sync []
+            + event: inside []
+        async []
+            + event: inside []
+        "#,
+    );
+}
+
+#[test]
+#[serial]
+fn span_close_duration() {
+    use veecle_telemetry::protocol::owned::{TelemetryMessage, TracingMessage};
+
+    let exporter = set_exporter();
+
+    {
+        let _guard = span!("sleep").entered();
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    let durations: Vec<u64> = exporter
+        .take_messages()
+        .into_iter()
+        .filter_map(|message| match message.message {
+            TelemetryMessage::Tracing(TracingMessage::CloseSpan(close)) => {
+                Some(close.duration_nano)
+            }
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(durations.len(), 1);
+    assert!(durations[0] >= Duration::from_millis(10).as_nanos() as u64);
+}
+
 #[test]
 #[serial]
 fn span_property() {
@@ -238,6 +645,32 @@ fn span_property() {
     );
 }
 
+#[test]
+#[serial]
+fn telemetry_log() {
+    use veecle_osal_api::log::LogTarget;
+    use veecle_telemetry::log::TelemetryLog;
+
+    type Log = TelemetryLog<veecle_osal_std::log::Log>;
+
+    let exporter = set_exporter();
+
+    {
+        let _guard = span!("root").entered();
+
+        Log::println(format_args!("value: {}", 42));
+    }
+
+    let graph = format_telemetry_tree(exporter.take_messages());
+    assert_eq!(
+        graph,
+        indoc! {r#"
+            root []
+                + event: log [message: "value: 42"]
+        "#}
+    );
+}
+
 #[test]
 #[serial]
 fn current_span_integration() {
@@ -419,6 +852,71 @@ fn formatted_attributes() {
     );
 }
 
+#[test]
+#[serial]
+fn bytes_attributes() {
+    let exporter = set_exporter();
+
+    let frame: &[u8] = &[0xde, 0xad];
+    let payload = vec![0x01, 0x02, 0xff];
+    {
+        let _guard = span!("receive", frame).entered();
+        veecle_telemetry::info!("message", payload, "fixed" = [0u8; 2]);
+    }
+
+    let graph = format_telemetry_tree(exporter.take_messages());
+    assert_eq!(
+        graph,
+        indoc! {"
+            receive [frame: 0xdead]
+                + log: [Info] message [payload: 0x0102ff, fixed: 0x0000]
+        "}
+    );
+}
+
+#[test]
+#[serial]
+fn log_formats_lazily() {
+    use std::cell::Cell;
+    use std::fmt;
+
+    struct CountingDisplay<'a>(&'a Cell<usize>);
+
+    impl fmt::Display for CountingDisplay<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.set(self.0.get() + 1);
+            f.write_str("counted")
+        }
+    }
+
+    let exporter = set_exporter();
+
+    let formatted = Cell::new(0);
+    let evaluated = Cell::new(0);
+    let evaluate = || {
+        evaluated.set(evaluated.get() + 1);
+        format_args!("{}", CountingDisplay(&formatted)).to_string()
+    };
+    veecle_telemetry::info!(
+        "message",
+        lazy = format_args!("{}", CountingDisplay(&formatted)),
+        eager = evaluate(),
+    );
+
+    // Each attribute is evaluated once and the `format_args!` one only formatted when exported.
+    assert_eq!(evaluated.get(), 1);
+    assert_eq!(formatted.get(), 2);
+
+    let graph = format_telemetry_tree(exporter.take_messages());
+    assert_eq!(
+        graph,
+        indoc! {r#"
+            + log: [Info] message [lazy: "counted", eager: "counted"]
+        "#}
+    );
+    assert!(veecle_telemetry::log::enabled(Severity::Trace));
+}
+
 #[test]
 #[serial]
 fn test_trailing_comma_support() {
@@ -472,3 +970,37 @@ fn test_trailing_comma_support() {
         "#}
     );
 }
+
+#[test]
+fn time_sync() {
+    let (exporter, messages) = TestExporter::new();
+    let collector = veecle_telemetry::collector::build()
+        .process_id(ProcessId::from_raw(1))
+        .leaked_exporter(exporter)
+        .system_time::<Time>()
+        .thread::<Thread>()
+        .build();
+    collector.time_sync();
+
+    let messages = messages.lock().unwrap();
+    let [message] = messages.as_slice() else {
+        panic!("expected a single message, got {messages:?}");
+    };
+    let TelemetryMessage::TimeSync(time_sync) = &message.message else {
+        panic!("expected a time sync message, got {message:?}");
+    };
+    assert_eq!(message.thread_id.process, ProcessId::from_raw(1));
+    // The system time is after 2020-01-01.
+    assert!(time_sync.since_epoch > 1_577_836_800_000_000_000);
+
+    let (exporter, messages) = TestExporter::new();
+    let collector = veecle_telemetry::collector::build()
+        .process_id(ProcessId::from_raw(1))
+        .leaked_exporter(exporter)
+        .time::<Time>()
+        .thread::<Thread>()
+        .build();
+    collector.time_sync();
+
+    assert!(messages.lock().unwrap().is_empty());
+}
@@ -0,0 +1,56 @@
+#![expect(missing_docs, reason = "tests")]
+#![cfg(not(miri))]
+
+// Installs a global collector with a sampler, so this can't share a test binary with the tests using
+// `TestExporter::set_global`.
+
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+use veecle_osal_std::{thread::Thread, time::Time};
+use veecle_telemetry::collector::{RatioSampler, SpanNameAllowlist, TestExporter};
+use veecle_telemetry::protocol::transient::KeyValue;
+use veecle_telemetry::test_helpers::format_telemetry_tree;
+use veecle_telemetry::{ProcessId, event, span};
+
+static NONE: RatioSampler = RatioSampler::new(0.0);
+static SAMPLER: SpanNameAllowlist = SpanNameAllowlist::new(&["request", "error::*"], &NONE);
+
+#[test]
+fn unsampled_spans_are_not_exported() {
+    let (exporter, messages) = TestExporter::new();
+    veecle_telemetry::collector::build()
+        .process_id(ProcessId::from_raw(1))
+        .leaked_exporter(exporter)
+        .sampler(&SAMPLER)
+        .time::<Time>()
+        .thread::<Thread>()
+        .set_global()
+        .unwrap();
+
+    {
+        let _request = span!("request", id = 1).entered();
+
+        let poll = span!("poll", attempt = 1);
+        poll.add_event("polled", &[]);
+        poll.set_attribute(KeyValue::new("ready", false));
+
+        let _poll = poll.entered();
+        // Belongs to the closest sampled ancestor.
+        event!("slow_poll");
+
+        let _retry = span!("error::retry").entered();
+        event!("retrying");
+    }
+
+    let graph = format_telemetry_tree(messages.lock().unwrap().clone());
+    assert_eq!(
+        graph,
+        indoc! {"
+            request [id: 1]
+                + event: slow_poll []
+                error::retry []
+                    + event: retrying []
+        "}
+    );
+}